@@ -0,0 +1,303 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::components::Position;
+use crate::registry::{BuildingRegistry, QuartierId, QuartierRegistry};
+
+/// Births per resident per day (Paris 1840s: ~32‰ a year).
+pub const BIRTHS_PER_DAY: f64 = 0.032 / 365.0;
+
+/// Deaths per resident per day in good health (~30‰ a year).
+pub const BASE_DEATHS_PER_DAY: f64 = 0.030 / 365.0;
+
+/// Extra deaths per resident per day at zero mean health.
+pub const SICK_DEATHS_PER_DAY: f64 = 0.01;
+
+/// Food (person-days) reaching a quartier's markets per resident per day.
+pub const FOOD_IMPORT_PER_DAY: f64 = 0.9;
+
+/// Food (person-days) each food-trade worker adds per day.
+pub const FOOD_OUTPUT_PER_WORKER_DAY: f64 = 2.0;
+
+/// Days of food a quartier's cellars and shops can hold.
+pub const FOOD_STOCK_DAYS: f64 = 7.0;
+
+/// NAICS prefixes of the food trades: bakers, grocers, eating houses.
+const FOOD_NAICS_PREFIXES: [&str; 3] = ["311", "445", "722"];
+
+/// Hunger scale shared with the `Hunger` component.
+pub const HUNGER_MAX: f64 = 100.0;
+
+/// Health scale shared with the `Health` component.
+pub const HEALTH_MAX: f64 = 100.0;
+
+/// Fraction of the gap to its target mean hunger closes per day.
+const HUNGER_RESPONSE_PER_DAY: f64 = 4.0;
+
+/// Mean hunger above which mean health starts to fall.
+const STARVING_HUNGER: f64 = 60.0;
+
+/// Health lost per day at full hunger.
+const HEALTH_LOSS_PER_DAY: f64 = 20.0;
+
+/// Health regained per day while fed.
+const HEALTH_REGEN_PER_DAY: f64 = 5.0;
+
+/// Occupation key for residents without a trade (births land here).
+pub const NO_OCCUPATION: &str = "";
+
+/// Minutes per game day.
+const MINUTES_PER_DAY: f64 = 1440.0;
+
+/// Aggregate state of one quartier while it is in `LodZone::Statistical`.
+/// Counts are fractional; difference equations move them by small amounts
+/// each step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuartierAggregate {
+    /// Residents by NAICS sector (first two digits), `NO_OCCUPATION` for
+    /// those without a trade.
+    pub population: BTreeMap<String, f64>,
+    /// Residents in food trades, a subset of `population`.
+    pub food_workers: f64,
+    pub mean_hunger: f64,
+    pub mean_health: f64,
+    /// Food on hand, in person-days.
+    pub food_stock: f64,
+    /// Share of the usual market supply getting through (1.0 normally,
+    /// 0.0 under blockade).
+    pub supply: f64,
+    /// Deaths and births since seeding.
+    pub deaths: f64,
+    pub births: f64,
+    /// Chance that a resident counted at seeding is still alive: the
+    /// product of every step's survival factor.
+    pub survival: f64,
+}
+
+/// An entity folded into its quartier's aggregate: still alive, with every
+/// component kept, but off the map until the quartier leaves Statistical.
+#[derive(Debug, Clone, Copy)]
+pub struct Folded {
+    pub quartier: QuartierId,
+    /// Where it stood when folded.
+    pub pos: Position,
+    /// Whether it is counted in the aggregate's population.
+    pub resident: bool,
+    /// `QuartierAggregate::survival` when folded.
+    pub survival: f64,
+}
+
+impl QuartierAggregate {
+    /// Seed from the directory occupants of `quartier`'s buildings for
+    /// `year` (nearest listing within `tolerance` years): one resident per
+    /// listed name, fed and healthy.
+    pub fn seed(buildings: &BuildingRegistry, quartier: &str, year: u16, tolerance: u16) -> Self {
+        let mut population: BTreeMap<String, f64> = BTreeMap::new();
+        let mut food_workers = 0.0;
+        for bdata in &buildings.buildings {
+            if bdata.quartier != quartier {
+                continue;
+            }
+            let Some((_, occupants)) = bdata.occupants_nearest(year, tolerance) else {
+                continue;
+            };
+            for occupant in occupants {
+                let people = occupant
+                    .name
+                    .split(',')
+                    .filter(|n| !n.trim().is_empty())
+                    .count() as f64;
+                *population.entry(sector(&occupant.naics)).or_default() += people;
+                if is_food_trade(&occupant.naics) {
+                    food_workers += people;
+                }
+            }
+        }
+        let total: f64 = population.values().sum();
+        Self {
+            population,
+            food_workers,
+            mean_hunger: 0.0,
+            mean_health: HEALTH_MAX,
+            food_stock: total * FOOD_STOCK_DAYS / 2.0,
+            supply: 1.0,
+            deaths: 0.0,
+            births: 0.0,
+            survival: 1.0,
+        }
+    }
+
+    /// Replace the resident counts with `year`'s listing, keeping hunger,
+    /// health, food and the running totals. Used when the directory year
+    /// changes mid-session.
+    pub fn reseed_population(
+        &mut self,
+        buildings: &BuildingRegistry,
+        quartier: &str,
+        year: u16,
+        tolerance: u16,
+    ) {
+        let fresh = Self::seed(buildings, quartier, year, tolerance);
+        self.population = fresh.population;
+        self.food_workers = fresh.food_workers;
+    }
+
+    /// Count one more resident of trade `naics`.
+    pub fn add_resident(&mut self, naics: &str) {
+        *self.population.entry(sector(naics)).or_default() += 1.0;
+        if is_food_trade(naics) {
+            self.food_workers += 1.0;
+        }
+    }
+
+    /// Count one resident of trade `naics` fewer, e.g. once materialized
+    /// as an entity. Never goes below zero.
+    pub fn remove_resident(&mut self, naics: &str) {
+        if let Some(n) = self.population.get_mut(&sector(naics)) {
+            *n = (*n - 1.0).max(0.0);
+        }
+        if is_food_trade(naics) {
+            self.food_workers = (self.food_workers - 1.0).max(0.0);
+        }
+    }
+
+    pub fn total(&self) -> f64 {
+        self.population.values().sum()
+    }
+
+    /// Advance the quartier by `minutes` of game time.
+    pub fn step(&mut self, minutes: f64) {
+        let dt = minutes / MINUTES_PER_DAY;
+        let pop = self.total();
+        if pop <= 0.0 {
+            return;
+        }
+
+        // Food: markets and food trades supply, residents eat.
+        let supply = (pop * FOOD_IMPORT_PER_DAY + self.food_workers * FOOD_OUTPUT_PER_WORKER_DAY)
+            * self.supply
+            * dt;
+        let demand = pop * dt;
+        self.food_stock += supply;
+        let eaten = self.food_stock.min(demand);
+        self.food_stock = (self.food_stock - eaten).min(pop * FOOD_STOCK_DAYS);
+        let fed = eaten / demand;
+
+        // Hunger eases toward what the food allows.
+        let target = (1.0 - fed) * HUNGER_MAX;
+        self.mean_hunger += (target - self.mean_hunger) * (dt * HUNGER_RESPONSE_PER_DAY).min(1.0);
+
+        // Health falls while starving and recovers while fed.
+        if self.mean_hunger > STARVING_HUNGER {
+            let severity = (self.mean_hunger - STARVING_HUNGER) / (HUNGER_MAX - STARVING_HUNGER);
+            self.mean_health -= HEALTH_LOSS_PER_DAY * severity * dt;
+        } else {
+            self.mean_health += HEALTH_REGEN_PER_DAY * dt;
+        }
+        self.mean_health = self.mean_health.clamp(0.0, HEALTH_MAX);
+
+        // Deaths fall on every trade alike; births join those without one.
+        let sickness = 1.0 - self.mean_health / HEALTH_MAX;
+        let death_rate = (BASE_DEATHS_PER_DAY + SICK_DEATHS_PER_DAY * sickness) * dt;
+        let deaths = pop * death_rate.min(1.0);
+        let survive = 1.0 - deaths / pop;
+        for n in self.population.values_mut() {
+            *n *= survive;
+        }
+        self.food_workers *= survive;
+        self.survival *= survive;
+        let births = pop * BIRTHS_PER_DAY * dt;
+        *self
+            .population
+            .entry(NO_OCCUPATION.to_string())
+            .or_default() += births;
+        self.deaths += deaths;
+        self.births += births;
+    }
+}
+
+/// Two-digit NAICS sector of `naics`, or `NO_OCCUPATION`.
+fn sector(naics: &str) -> String {
+    naics.get(..2).unwrap_or(NO_OCCUPATION).to_string()
+}
+
+fn is_food_trade(naics: &str) -> bool {
+    FOOD_NAICS_PREFIXES.iter().any(|p| naics.starts_with(p))
+}
+
+/// Seed an aggregate for every quartier.
+pub fn seed_all(
+    quartiers: &QuartierRegistry,
+    buildings: &BuildingRegistry,
+    year: u16,
+    tolerance: u16,
+) -> HashMap<QuartierId, QuartierAggregate> {
+    quartiers
+        .quartiers
+        .values()
+        .map(|q| {
+            let agg = QuartierAggregate::seed(buildings, &q.name, year, tolerance);
+            (q.id, agg)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aggregate(pop: f64, food_workers: f64) -> QuartierAggregate {
+        let mut population = BTreeMap::new();
+        population.insert("31".to_string(), food_workers);
+        population.insert("53".to_string(), pop - food_workers);
+        QuartierAggregate {
+            population,
+            food_workers,
+            mean_hunger: 0.0,
+            mean_health: HEALTH_MAX,
+            food_stock: pop,
+            supply: 1.0,
+            deaths: 0.0,
+            births: 0.0,
+            survival: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_fed_quartier_stays_healthy_for_a_year() {
+        let mut agg = aggregate(1000.0, 100.0);
+        for _ in 0..365 * 24 {
+            agg.step(60.0);
+        }
+        assert!(agg.mean_hunger < 1.0);
+        assert!(agg.mean_health > 99.0);
+        // Births and deaths roughly balance over a year.
+        assert!((agg.births - 32.0).abs() < 2.0, "births {}", agg.births);
+        assert!((agg.deaths - 30.0).abs() < 2.0, "deaths {}", agg.deaths);
+        assert!((agg.total() - 1002.0).abs() < 3.0);
+    }
+
+    #[test]
+    fn test_blockade_starves_and_kills() {
+        let mut agg = aggregate(1000.0, 100.0);
+        let mut starving = agg.clone();
+        starving.supply = 0.0;
+        for _ in 0..30 * 24 {
+            agg.step(60.0);
+            starving.step(60.0);
+        }
+        assert_eq!(starving.food_stock, 0.0);
+        assert!(starving.mean_hunger > agg.mean_hunger);
+        assert!(starving.mean_health < agg.mean_health);
+        assert!(starving.deaths > agg.deaths * 5.0);
+    }
+
+    #[test]
+    fn test_deaths_conserve_population() {
+        let mut agg = aggregate(500.0, 50.0);
+        for _ in 0..1000 {
+            agg.step(60.0);
+        }
+        let expected = 500.0 + agg.births - agg.deaths;
+        assert!((agg.total() - expected).abs() < 1e-6);
+    }
+}
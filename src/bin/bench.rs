@@ -11,9 +11,13 @@ use std::time::{Duration, Instant};
 use rand::RngExt;
 
 use wulfaz::components::*;
+use wulfaz::systems::unrest::update_riots;
 use wulfaz::systems::{
-    combat::run_combat, death::run_death, decisions::run_decisions, eating::run_eating,
-    fatigue::run_fatigue, hunger::run_hunger, temperature::run_temperature, wander::run_wander,
+    combat::run_combat, crime::run_crime, danger::run_danger, death::run_death,
+    decisions::run_decisions, eating::run_eating, fatigue::run_fatigue, fire::run_fire,
+    hunger::run_hunger, perception::run_perception, plans::run_plans, reactions::run_reactions,
+    rescue::run_rescue, social::run_social, statistical::run_statistical,
+    temperature::run_temperature, unrest::run_unrest, wander::run_wander, wounds::run_wounds,
 };
 use wulfaz::tile_map::Terrain;
 use wulfaz::world::World;
@@ -126,13 +130,24 @@ fn scatter_terrain(world: &mut World) {
 struct SystemTimings {
     spatial1: Duration,
     temperature: Duration,
+    fire: Duration,
+    statistical: Duration,
     hunger: Duration,
     fatigue: Duration,
+    wounds: Duration,
+    perception: Duration,
+    reactions: Duration,
+    social: Duration,
+    danger: Duration,
     decisions: Duration,
     wander: Duration,
+    plans: Duration,
+    rescue: Duration,
     spatial2: Duration,
     eating: Duration,
+    crime: Duration,
     combat: Duration,
+    unrest: Duration,
     death: Duration,
 }
 
@@ -140,13 +155,24 @@ impl SystemTimings {
     fn total(&self) -> Duration {
         self.spatial1
             + self.temperature
+            + self.fire
+            + self.statistical
             + self.hunger
             + self.fatigue
+            + self.wounds
+            + self.perception
+            + self.reactions
+            + self.social
+            + self.danger
             + self.decisions
             + self.wander
+            + self.plans
+            + self.rescue
             + self.spatial2
             + self.eating
+            + self.crime
             + self.combat
+            + self.unrest
             + self.death
     }
 }
@@ -163,6 +189,14 @@ fn timed_tick(world: &mut World) -> SystemTimings {
     run_temperature(world, tick);
     let temperature = t.elapsed();
 
+    let t = Instant::now();
+    run_fire(world, tick);
+    let fire = t.elapsed();
+
+    let t = Instant::now();
+    run_statistical(world, tick);
+    let statistical = t.elapsed();
+
     let t = Instant::now();
     run_hunger(world, tick);
     let hunger = t.elapsed();
@@ -171,6 +205,26 @@ fn timed_tick(world: &mut World) -> SystemTimings {
     run_fatigue(world, tick);
     let fatigue = t.elapsed();
 
+    let t = Instant::now();
+    run_wounds(world, tick);
+    let wounds = t.elapsed();
+
+    let t = Instant::now();
+    run_perception(world, tick);
+    let perception = t.elapsed();
+
+    let t = Instant::now();
+    run_reactions(world, tick);
+    let reactions = t.elapsed();
+
+    let t = Instant::now();
+    run_social(world, tick);
+    let social = t.elapsed();
+
+    let t = Instant::now();
+    run_danger(world, tick);
+    let danger = t.elapsed();
+
     let t = Instant::now();
     run_decisions(world, tick);
     let decisions = t.elapsed();
@@ -179,6 +233,14 @@ fn timed_tick(world: &mut World) -> SystemTimings {
     run_wander(world, tick);
     let wander = t.elapsed();
 
+    let t = Instant::now();
+    run_plans(world, tick);
+    let plans = t.elapsed();
+
+    let t = Instant::now();
+    run_rescue(world, tick);
+    let rescue = t.elapsed();
+
     let t = Instant::now();
     world.rebuild_spatial_index();
     let spatial2 = t.elapsed();
@@ -187,10 +249,19 @@ fn timed_tick(world: &mut World) -> SystemTimings {
     run_eating(world, tick);
     let eating = t.elapsed();
 
+    let t = Instant::now();
+    run_crime(world, tick);
+    let crime = t.elapsed();
+
     let t = Instant::now();
     run_combat(world, tick);
     let combat = t.elapsed();
 
+    let t = Instant::now();
+    run_unrest(world, tick);
+    update_riots(world, None);
+    let unrest = t.elapsed();
+
     let t = Instant::now();
     run_death(world, tick);
     let death = t.elapsed();
@@ -200,13 +271,24 @@ fn timed_tick(world: &mut World) -> SystemTimings {
     SystemTimings {
         spatial1,
         temperature,
+        fire,
+        statistical,
         hunger,
         fatigue,
+        wounds,
+        perception,
+        reactions,
+        social,
+        danger,
         decisions,
         wander,
+        plans,
+        rescue,
         spatial2,
         eating,
+        crime,
         combat,
+        unrest,
         death,
     }
 }
@@ -228,13 +310,25 @@ fn run_benchmark(entity_count: usize) {
         let tick = world.tick;
         world.rebuild_spatial_index();
         run_temperature(&mut world, tick);
+        run_fire(&mut world, tick);
+        run_statistical(&mut world, tick);
         run_hunger(&mut world, tick);
         run_fatigue(&mut world, tick);
+        run_wounds(&mut world, tick);
+        run_perception(&mut world, tick);
+        run_reactions(&mut world, tick);
+        run_social(&mut world, tick);
+        run_danger(&mut world, tick);
         run_decisions(&mut world, tick);
         run_wander(&mut world, tick);
+        run_plans(&mut world, tick);
+        run_rescue(&mut world, tick);
         world.rebuild_spatial_index();
         run_eating(&mut world, tick);
+        run_crime(&mut world, tick);
         run_combat(&mut world, tick);
+        run_unrest(&mut world, tick);
+        update_riots(&mut world, None);
         run_death(&mut world, tick);
         world.tick = Tick(tick.0 + 1);
     }
@@ -245,13 +339,24 @@ fn run_benchmark(entity_count: usize) {
     let mut totals = SystemTimings {
         spatial1: Duration::ZERO,
         temperature: Duration::ZERO,
+        fire: Duration::ZERO,
+        statistical: Duration::ZERO,
         hunger: Duration::ZERO,
         fatigue: Duration::ZERO,
+        wounds: Duration::ZERO,
+        perception: Duration::ZERO,
+        reactions: Duration::ZERO,
+        social: Duration::ZERO,
+        danger: Duration::ZERO,
         decisions: Duration::ZERO,
         wander: Duration::ZERO,
+        plans: Duration::ZERO,
+        rescue: Duration::ZERO,
         spatial2: Duration::ZERO,
         eating: Duration::ZERO,
+        crime: Duration::ZERO,
         combat: Duration::ZERO,
+        unrest: Duration::ZERO,
         death: Duration::ZERO,
     };
 
@@ -260,13 +365,24 @@ fn run_benchmark(entity_count: usize) {
         let t = timed_tick(&mut world);
         totals.spatial1 += t.spatial1;
         totals.temperature += t.temperature;
+        totals.fire += t.fire;
+        totals.statistical += t.statistical;
         totals.hunger += t.hunger;
         totals.fatigue += t.fatigue;
+        totals.wounds += t.wounds;
+        totals.perception += t.perception;
+        totals.reactions += t.reactions;
+        totals.social += t.social;
+        totals.danger += t.danger;
         totals.decisions += t.decisions;
         totals.wander += t.wander;
+        totals.plans += t.plans;
+        totals.rescue += t.rescue;
         totals.spatial2 += t.spatial2;
         totals.eating += t.eating;
+        totals.crime += t.crime;
         totals.combat += t.combat;
+        totals.unrest += t.unrest;
         totals.death += t.death;
     }
     let wall_elapsed = wall_start.elapsed();
@@ -301,6 +417,14 @@ fn run_benchmark(entity_count: usize) {
         "    temperature: {:>7.0}",
         totals.temperature.as_micros() as f64 / n as f64
     );
+    println!(
+        "    fire:        {:>7.0}",
+        totals.fire.as_micros() as f64 / n as f64
+    );
+    println!(
+        "    statistical: {:>7.0}",
+        totals.statistical.as_micros() as f64 / n as f64
+    );
     println!(
         "    hunger:      {:>7.0}",
         totals.hunger.as_micros() as f64 / n as f64
@@ -309,6 +433,26 @@ fn run_benchmark(entity_count: usize) {
         "    fatigue:     {:>7.0}",
         totals.fatigue.as_micros() as f64 / n as f64
     );
+    println!(
+        "    wounds:      {:>7.0}",
+        totals.wounds.as_micros() as f64 / n as f64
+    );
+    println!(
+        "    perception:  {:>7.0}",
+        totals.perception.as_micros() as f64 / n as f64
+    );
+    println!(
+        "    reactions:   {:>7.0}",
+        totals.reactions.as_micros() as f64 / n as f64
+    );
+    println!(
+        "    social:      {:>7.0}",
+        totals.social.as_micros() as f64 / n as f64
+    );
+    println!(
+        "    danger:      {:>7.0}",
+        totals.danger.as_micros() as f64 / n as f64
+    );
     println!(
         "    decisions:   {:>7.0}",
         totals.decisions.as_micros() as f64 / n as f64
@@ -317,6 +461,14 @@ fn run_benchmark(entity_count: usize) {
         "    wander:      {:>7.0}",
         totals.wander.as_micros() as f64 / n as f64
     );
+    println!(
+        "    plans:       {:>7.0}",
+        totals.plans.as_micros() as f64 / n as f64
+    );
+    println!(
+        "    rescue:      {:>7.0}",
+        totals.rescue.as_micros() as f64 / n as f64
+    );
     println!(
         "    spatial2:    {:>7.0}",
         totals.spatial2.as_micros() as f64 / n as f64
@@ -325,10 +477,18 @@ fn run_benchmark(entity_count: usize) {
         "    eating:      {:>7.0}",
         totals.eating.as_micros() as f64 / n as f64
     );
+    println!(
+        "    crime:       {:>7.0}",
+        totals.crime.as_micros() as f64 / n as f64
+    );
     println!(
         "    combat:      {:>7.0}",
         totals.combat.as_micros() as f64 / n as f64
     );
+    println!(
+        "    unrest:      {:>7.0}",
+        totals.unrest.as_micros() as f64 / n as f64
+    );
     println!(
         "    death:       {:>7.0}",
         totals.death.as_micros() as f64 / n as f64
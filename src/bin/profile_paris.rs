@@ -10,9 +10,13 @@ use std::time::Instant;
 use wulfaz::components::Tick;
 use wulfaz::loading;
 use wulfaz::loading_gis;
+use wulfaz::systems::unrest::update_riots;
 use wulfaz::systems::{
-    combat::run_combat, death::run_death, decisions::run_decisions, eating::run_eating,
-    fatigue::run_fatigue, hunger::run_hunger, temperature::run_temperature, wander::run_wander,
+    combat::run_combat, crime::run_crime, danger::run_danger, death::run_death,
+    decisions::run_decisions, eating::run_eating, fatigue::run_fatigue, fire::run_fire,
+    hunger::run_hunger, perception::run_perception, plans::run_plans, reactions::run_reactions,
+    rescue::run_rescue, social::run_social, statistical::run_statistical,
+    temperature::run_temperature, unrest::run_unrest, wander::run_wander, wounds::run_wounds,
 };
 use wulfaz::world::World;
 
@@ -22,13 +26,24 @@ const MEASURE_TICKS: u32 = 200;
 struct Timings {
     spatial1: u128,
     temperature: u128,
+    fire: u128,
+    statistical: u128,
     hunger: u128,
     fatigue: u128,
+    wounds: u128,
+    perception: u128,
+    reactions: u128,
+    social: u128,
+    danger: u128,
     decisions: u128,
     wander: u128,
+    plans: u128,
+    rescue: u128,
     spatial2: u128,
     eating: u128,
+    crime: u128,
     combat: u128,
+    unrest: u128,
     death: u128,
 }
 
@@ -37,31 +52,53 @@ impl Timings {
         Self {
             spatial1: 0,
             temperature: 0,
+            fire: 0,
+            statistical: 0,
             hunger: 0,
             fatigue: 0,
+            wounds: 0,
+            perception: 0,
+            reactions: 0,
+            social: 0,
+            danger: 0,
             decisions: 0,
             wander: 0,
+            plans: 0,
+            rescue: 0,
             spatial2: 0,
             eating: 0,
+            crime: 0,
             combat: 0,
+            unrest: 0,
             death: 0,
         }
     }
     fn total(&self) -> u128 {
         self.spatial1
             + self.temperature
+            + self.fire
+            + self.statistical
             + self.hunger
             + self.fatigue
+            + self.wounds
+            + self.perception
+            + self.reactions
+            + self.social
+            + self.danger
             + self.decisions
             + self.wander
+            + self.plans
+            + self.rescue
             + self.spatial2
             + self.eating
+            + self.crime
             + self.combat
+            + self.unrest
             + self.death
     }
 }
 
-fn timed_tick(world: &mut World) -> Timings {
+fn timed_tick(world: &mut World, person: &loading::Archetype) -> Timings {
     let tick = world.tick;
     let mut t = Timings::zero();
 
@@ -75,13 +112,27 @@ fn timed_tick(world: &mut World) -> Timings {
 
     measure!(spatial1, world.rebuild_spatial_index());
     measure!(temperature, run_temperature(world, tick));
+    measure!(fire, run_fire(world, tick));
+    measure!(statistical, run_statistical(world, tick));
     measure!(hunger, run_hunger(world, tick));
     measure!(fatigue, run_fatigue(world, tick));
+    measure!(wounds, run_wounds(world, tick));
+    measure!(perception, run_perception(world, tick));
+    measure!(reactions, run_reactions(world, tick));
+    measure!(social, run_social(world, tick));
+    measure!(danger, run_danger(world, tick));
     measure!(decisions, run_decisions(world, tick));
     measure!(wander, run_wander(world, tick));
+    measure!(plans, run_plans(world, tick));
+    measure!(rescue, run_rescue(world, tick));
     measure!(spatial2, world.rebuild_spatial_index());
     measure!(eating, run_eating(world, tick));
+    measure!(crime, run_crime(world, tick));
     measure!(combat, run_combat(world, tick));
+    measure!(unrest, {
+        run_unrest(world, tick);
+        update_riots(world, Some(person));
+    });
     measure!(death, run_death(world, tick));
 
     world.tick = Tick(tick.0 + 1);
@@ -112,11 +163,21 @@ fn main() {
 
     world.tiles.initialize_temperatures();
     loading::load_utility_config(&mut world, "data/utility.ron");
+    loading::load_factions(&mut world, "data/factions.kdl");
+    loading::load_terrain_costs(&mut world, "data/terrain.kdl");
     let archetypes = loading::load_archetypes("data/archetypes.kdl");
     let person = archetypes
         .get("person")
         .expect("data/archetypes.kdl must define a 'person' archetype");
-    loading_gis::spawn_gis_entities(&mut world, "Arcis", person);
+    let args: Vec<String> = std::env::args().collect();
+    let spawn_options = loading_gis::SpawnOptions::from_args(&args);
+    loading_gis::spawn_population(&mut world, &spawn_options, person);
+    if let Some(officer) = archetypes.get("sergent_de_ville") {
+        loading_gis::spawn_police(&mut world, &spawn_options, officer);
+    }
+    if let Some(sapeur) = archetypes.get("sapeur_pompier") {
+        loading_gis::spawn_firefighters(&mut world, &spawn_options, sapeur);
+    }
 
     let entity_count = world.alive.len();
     let map_w = world.tiles.width();
@@ -133,7 +194,7 @@ fn main() {
     // Warmup
     print!("Warming up ({} ticks)...", WARMUP_TICKS);
     for _ in 0..WARMUP_TICKS {
-        let _ = timed_tick(&mut world);
+        let _ = timed_tick(&mut world, person);
     }
     println!(" done");
 
@@ -147,7 +208,7 @@ fn main() {
     let mut wander_spikes = 0u32; // ticks where wander > 1000us
 
     for i in 0..MEASURE_TICKS {
-        let t = timed_tick(&mut world);
+        let t = timed_tick(&mut world, person);
         let tick_total = t.total();
 
         if tick_total > max_total {
@@ -168,13 +229,24 @@ fn main() {
 
         sum.spatial1 += t.spatial1;
         sum.temperature += t.temperature;
+        sum.fire += t.fire;
+        sum.statistical += t.statistical;
         sum.hunger += t.hunger;
         sum.fatigue += t.fatigue;
+        sum.wounds += t.wounds;
+        sum.perception += t.perception;
+        sum.reactions += t.reactions;
+        sum.social += t.social;
+        sum.danger += t.danger;
         sum.decisions += t.decisions;
         sum.wander += t.wander;
+        sum.plans += t.plans;
+        sum.rescue += t.rescue;
         sum.spatial2 += t.spatial2;
         sum.eating += t.eating;
+        sum.crime += t.crime;
         sum.combat += t.combat;
+        sum.unrest += t.unrest;
         sum.death += t.death;
     }
 
@@ -186,8 +258,15 @@ fn main() {
     );
     println!("  spatial1:    {:>8}us", sum.spatial1 / n);
     println!("  temperature: {:>8}us", sum.temperature / n);
+    println!("  fire:        {:>8}us", sum.fire / n);
+    println!("  statistical: {:>8}us", sum.statistical / n);
     println!("  hunger:      {:>8}us", sum.hunger / n);
     println!("  fatigue:     {:>8}us", sum.fatigue / n);
+    println!("  wounds:      {:>8}us", sum.wounds / n);
+    println!("  perception:  {:>8}us", sum.perception / n);
+    println!("  reactions:   {:>8}us", sum.reactions / n);
+    println!("  social:      {:>8}us", sum.social / n);
+    println!("  danger:      {:>8}us", sum.danger / n);
     println!("  decisions:   {:>8}us", sum.decisions / n);
     println!(
         "  wander:      {:>8}us  (max: {}us, spikes>1ms: {}/{})",
@@ -196,9 +275,13 @@ fn main() {
         wander_spikes,
         MEASURE_TICKS
     );
+    println!("  plans:       {:>8}us", sum.plans / n);
+    println!("  rescue:      {:>8}us", sum.rescue / n);
     println!("  spatial2:    {:>8}us", sum.spatial2 / n);
     println!("  eating:      {:>8}us", sum.eating / n);
+    println!("  crime:       {:>8}us", sum.crime / n);
     println!("  combat:      {:>8}us", sum.combat / n);
+    println!("  unrest:      {:>8}us", sum.unrest / n);
     println!("  death:       {:>8}us", sum.death / n);
     println!("  ─────────────────────");
     let avg_total = sum.total() / n;
@@ -254,7 +254,7 @@ fn main() {
 
     // --- A. Terrain Census ---
     println!("=== A. Terrain Census ===");
-    let mut counts = [0u64; 9];
+    let mut counts = [0u64; 11];
     for y in 0..grid_h {
         for x in 0..grid_w {
             if let Some(t) = tiles.get_terrain(x, y) {
@@ -272,6 +272,8 @@ fn main() {
         "Water",
         "Bridge",
         "Fixture",
+        "Barricade",
+        "Rubble",
     ];
     for (i, name) in names.iter().enumerate() {
         if counts[i] > 0 {
@@ -350,7 +352,7 @@ fn main() {
         });
     }
 
-    components.sort_by(|a, b| b.tiles.len().cmp(&a.tiles.len()));
+    components.sort_by_key(|c| std::cmp::Reverse(c.tiles.len()));
 
     println!(
         "  {} Bridge tiles in {} components (8-connected)\n",
@@ -3,7 +3,8 @@ use std::hash::{Hash, Hasher};
 
 use serde::{Deserialize, Serialize};
 
-use crate::registry::BuildingId;
+use crate::faction::FactionId;
+use crate::registry::{BuildingId, QuartierId};
 
 /// Unique entity identifier. Never use raw u64 where an Entity is meant.
 /// Never cast between Entity and Tick.
@@ -58,6 +59,157 @@ pub struct CombatStats {
     pub aggression: f32,
 }
 
+/// Hand weapon an entity wields (see `systems::combat`). Bare hands when
+/// the entity has none.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weapon {
+    /// Walking cane or cudgel: blunt, quick.
+    Cane,
+    /// Knife: edged, short reach.
+    Knife,
+    /// Cavalry or infantry sabre: edged, heavy.
+    Sabre,
+    /// Musket fired at close range: slow to aim, deep wounds.
+    Musket,
+}
+
+impl Weapon {
+    /// Damage added to the wielder's attack.
+    pub fn damage(self) -> f32 {
+        match self {
+            Weapon::Cane => 3.0,
+            Weapon::Knife => 5.0,
+            Weapon::Sabre => 10.0,
+            Weapon::Musket => 18.0,
+        }
+    }
+
+    /// Added to the wielder's hit chance.
+    pub fn accuracy(self) -> f32 {
+        match self {
+            Weapon::Cane => 0.05,
+            Weapon::Knife => 0.0,
+            Weapon::Sabre => -0.05,
+            Weapon::Musket => -0.15,
+        }
+    }
+
+    /// Lowercase display name.
+    pub fn label(self) -> &'static str {
+        match self {
+            Weapon::Cane => "cane",
+            Weapon::Knife => "knife",
+            Weapon::Sabre => "sabre",
+            Weapon::Musket => "musket",
+        }
+    }
+}
+
+/// Where a blow lands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyPart {
+    Head,
+    Torso,
+    LeftArm,
+    RightArm,
+    LeftLeg,
+    RightLeg,
+}
+
+impl BodyPart {
+    pub fn is_arm(self) -> bool {
+        matches!(self, BodyPart::LeftArm | BodyPart::RightArm)
+    }
+
+    pub fn is_leg(self) -> bool {
+        matches!(self, BodyPart::LeftLeg | BodyPart::RightLeg)
+    }
+
+    /// Lowercase display name.
+    pub fn label(self) -> &'static str {
+        match self {
+            BodyPart::Head => "head",
+            BodyPart::Torso => "torso",
+            BodyPart::LeftArm => "left arm",
+            BodyPart::RightArm => "right arm",
+            BodyPart::LeftLeg => "left leg",
+            BodyPart::RightLeg => "right leg",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WoundKind {
+    Bruise,
+    Cut,
+    Fracture,
+}
+
+impl WoundKind {
+    /// Past participle for display ("cut left arm").
+    pub fn label(self) -> &'static str {
+        match self {
+            WoundKind::Bruise => "bruised",
+            WoundKind::Cut => "cut",
+            WoundKind::Fracture => "fractured",
+        }
+    }
+}
+
+/// One wound on one body part.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wound {
+    pub part: BodyPart,
+    pub kind: WoundKind,
+    /// Health lost per tick; slows as the wound clots.
+    pub bleeding: f32,
+    /// Ticks until the wound has healed.
+    pub healing: u32,
+}
+
+/// All open wounds of an entity. Removed once every wound has healed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Wounds(pub Vec<Wound>);
+
+impl Wounds {
+    /// Movement cooldown multiplier: 3 with a fractured leg, 2 with a cut
+    /// one, 1 otherwise.
+    pub fn limp(&self) -> u32 {
+        self.0
+            .iter()
+            .filter(|w| w.part.is_leg())
+            .map(|w| match w.kind {
+                WoundKind::Fracture => 3,
+                WoundKind::Cut => 2,
+                WoundKind::Bruise => 1,
+            })
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Fraction of normal strength left for work and fighting: each
+    /// fractured arm, head or torso costs half, each cut arm a quarter, and
+    /// bleeding anywhere another quarter.
+    pub fn work_ability(&self) -> f32 {
+        let mut ability = 1.0;
+        for w in &self.0 {
+            match w.kind {
+                WoundKind::Fracture if !w.part.is_leg() => ability -= 0.5,
+                WoundKind::Cut if w.part.is_arm() => ability -= 0.25,
+                _ => {}
+            }
+        }
+        if self.is_bleeding() {
+            ability -= 0.25;
+        }
+        f32::max(ability, 0.0)
+    }
+
+    pub fn is_bleeding(&self) -> bool {
+        self.0.iter().any(|w| w.bleeding > 0.0)
+    }
+}
+
 /// Gait tier — determines movement speed. All creatures share the same
 /// slow gaits (Creep/Stroll/Walk); fast gaits differ by body plan.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -125,12 +277,30 @@ pub struct Nutrition {
 }
 
 /// Available actions for the utility scorer. Variant order determines tiebreaking priority.
+/// Variants without an entry in `UtilityConfig` are never scored; they are
+/// primitive actions issued only by the plan layer (`systems::plans`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ActionId {
     Idle,
     Wander,
     Eat,
     Attack,
+    /// Plan primitive: pick up the target item on the current tile.
+    PickUp,
+    /// Run from a feared entity, or from any enemy when badly hurt.
+    Flee,
+    /// Fight whoever attacked us or an ally (grudge target).
+    Defend,
+    /// Reach an unconscious ally and drag them out of danger.
+    Rescue,
+    /// Criminals only: stalk a victim and rob or assault them (see
+    /// `systems::crime`).
+    Rob,
+    /// Police only: walk the streets around one's commissariat.
+    Patrol,
+    /// Firefighters only: reach a burning building and douse it (see
+    /// `systems::fire`).
+    Extinguish,
 }
 
 /// What an entity intends to do this tick, written by the Phase 3 scorer.
@@ -156,6 +326,149 @@ pub struct CachedPath {
     pub goal: (i32, i32),
     /// Index of the next step to consume. Exhausted when `next_step >= steps.len()`.
     pub next_step: usize,
+    /// `TileMap::chunk_versions` of `steps` when the path was computed.
+    /// `run_wander` drops the path once any of them has moved on.
+    pub chunk_versions: Vec<(usize, u32)>,
+}
+
+/// Where and when another entity was last perceived.
+#[derive(Debug, Clone, Copy)]
+pub struct Sighting {
+    pub x: i32,
+    pub y: i32,
+    pub tick: Tick,
+}
+
+/// Short-term memory of perceived food and creatures, keyed by the seen
+/// entity. Refreshed by `run_perception`; entries expire after `MEMORY_TICKS`.
+#[derive(Debug, Clone, Default)]
+pub struct Memory {
+    pub sightings: HashMap<Entity, Sighting>,
+}
+
+/// Faction membership. `quartier` scopes `local` factions to neighbors.
+#[derive(Debug, Clone, Copy)]
+pub struct FactionMember {
+    pub faction: FactionId,
+    pub quartier: Option<QuartierId>,
+}
+
+/// Personal grudges and fears from recent attacks, keyed by the other
+/// entity, valued by the tick they expire. Grudges override faction stance.
+#[derive(Debug, Clone, Default)]
+pub struct Grievances {
+    pub grudges: HashMap<Entity, Tick>,
+    pub fears: HashMap<Entity, Tick>,
+}
+
+/// What a criminal does to the victims they reach.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Crime {
+    /// Pick a pocket; noticed only sometimes.
+    Theft,
+    /// Beat the victim (resolved as a combat blow).
+    Assault,
+}
+
+/// What a successful theft takes (see `systems::crime`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Loot {
+    /// Food the victim was carrying, now carried by the thief.
+    Food(Entity),
+    /// The victim's weapon, now the thief's.
+    Weapon(Weapon),
+}
+
+impl Loot {
+    /// Lowercase display name.
+    pub fn label(self) -> &'static str {
+        match self {
+            Loot::Food(_) => "food",
+            Loot::Weapon(w) => w.label(),
+        }
+    }
+}
+
+/// How a building fire started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FireCause {
+    /// A baker's, smith's or founder's fire got loose.
+    Oven,
+    /// A candle, lamp or hearth anywhere.
+    Accident,
+    /// Set by rioters.
+    Arson,
+    /// Caught from a burning neighbor through a party wall.
+    Spread,
+}
+
+impl FireCause {
+    /// Lowercase noun for display ("fire (arson)").
+    pub fn label(self) -> &'static str {
+        match self {
+            FireCause::Oven => "oven",
+            FireCause::Accident => "accident",
+            FireCause::Arson => "arson",
+            FireCause::Spread => "spread",
+        }
+    }
+}
+
+/// Criminal disposition, rolled at spawn from archetype and occupation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Criminal {
+    pub crime: Crime,
+    /// Tick of the last offence; the next waits `OFFENCE_INTERVAL`.
+    pub last_offence: Option<Tick>,
+}
+
+/// Serving a sentence in a prison building. Imprisoned entities are frozen
+/// in place until `release`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Imprisoned {
+    pub prison: BuildingId,
+    pub release: Tick,
+}
+
+/// Compound task that a plan was expanded from (HTN method name).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskId {
+    /// Walk to food and eat it where it lies.
+    EatNearby,
+    /// Fetch food, carry it home, then eat it there.
+    EatAtHome,
+}
+
+/// One primitive step of a plan. Steps execute in order, one at a time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlanStep {
+    /// Walk to a fixed tile.
+    MoveTo { x: i32, y: i32 },
+    /// Pick up an item on the current tile and carry it.
+    PickUp { item: Entity },
+    /// Eat a food item on the current tile (carried or lying there).
+    Consume { food: Entity },
+}
+
+/// Ordered multi-step plan produced by the HTN layer on top of the utility
+/// scorer. Re-validated every tick by `run_decisions`.
+#[derive(Debug, Clone)]
+pub struct Plan {
+    pub task: TaskId,
+    /// Utility action this plan serves. Abandoned when the scorer picks another.
+    pub action: ActionId,
+    pub steps: Vec<PlanStep>,
+    /// Index of the executing step. Complete when `current >= steps.len()`.
+    pub current: usize,
+    /// Times the plan was re-expanded after a step failed.
+    pub replans: u8,
+}
+
+impl Plan {
+    /// The step currently executing, or None if the plan is complete.
+    pub fn current_step(&self) -> Option<PlanStep> {
+        self.steps.get(self.current).copied()
+    }
 }
 
 /// The building where this entity lives.
@@ -164,6 +477,12 @@ pub struct HomeBuilding(pub BuildingId);
 /// The building where this entity works.
 pub struct Workplace(pub BuildingId);
 
+/// A level of a building: 0 is street level, up to `floor_count - 1`.
+/// Upper storeys share the ground floor's footprint and are reached by
+/// the building's stairs tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Storey(pub u8);
+
 /// Professional activity from SoDUCo directory data.
 pub struct Occupation {
     /// Free-text French activity string, e.g. "boulanger", "rentier".
@@ -0,0 +1,526 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::RngExt;
+
+use crate::aggregate::NO_OCCUPATION;
+use crate::components::{Entity, GameDate, Occupation};
+use crate::loading::Archetype;
+use crate::loading_gis::spawn_resident;
+use crate::lod::LodZone;
+use crate::registry::{BuildingId, DoorAccess, Occupant};
+use crate::tile_map::Terrain;
+use crate::world::World;
+
+/// What switching the SoDUCo year changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct YearChange {
+    pub from: u16,
+    pub to: u16,
+    /// Residents spawned because the new directory lists them.
+    pub arrivals: Vec<Entity>,
+    /// Names of residents despawned because the new directory doesn't.
+    pub departures: Vec<String>,
+    /// Residents whose listed trade changed: entity, old and new activity.
+    pub trade_changes: Vec<(Entity, String, String)>,
+    /// Buildings whose doors became shop doors (a business is now listed).
+    pub shops_opened: Vec<BuildingId>,
+    /// Buildings whose shop doors went private (the business vanished).
+    pub shops_closed: Vec<BuildingId>,
+}
+
+impl YearChange {
+    /// One-line summary for the log and status messages.
+    pub fn summary(&self) -> String {
+        format!(
+            "Directory {} → {}: {} arrivals, {} departures, {} changed trade, \
+             {} shops opened, {} closed",
+            self.from,
+            self.to,
+            self.arrivals.len(),
+            self.departures.len(),
+            self.trade_changes.len(),
+            self.shops_opened.len(),
+            self.shops_closed.len()
+        )
+    }
+}
+
+/// Nearest directory year before (`forward == false`) or after `year`.
+pub fn step_year(world: &World, year: u16, forward: bool) -> Option<u16> {
+    let years = world.gis.buildings.directory_years();
+    if forward {
+        years.into_iter().find(|&y| y > year)
+    } else {
+        years.into_iter().rev().find(|&y| y < year)
+    }
+}
+
+/// Directory year nearest to the game-date `year`, preferring the later one
+/// on a tie.
+pub fn nearest_year(world: &World, year: i32) -> Option<u16> {
+    world
+        .gis
+        .buildings
+        .directory_years()
+        .into_iter()
+        .min_by_key(|&y| ((i32::from(y) - year).abs(), std::cmp::Reverse(y)))
+}
+
+/// Keep `active_year` on the directory year nearest the game date. Checked
+/// once per game-year change (a manual switch holds until the next New
+/// Year); a newer nearest year goes through `set_active_year`, opening and
+/// closing shops and reconciling residents with `archetype`.
+pub fn follow_game_date(world: &mut World, archetype: Option<&Archetype>) -> Option<YearChange> {
+    let year = GameDate::from_tick(world.tick, &world.start_date).year;
+    if year == world.gis.calendar_year {
+        return None;
+    }
+    world.gis.calendar_year = year;
+    let nearest = nearest_year(world, year)?;
+    (nearest != world.gis.active_year).then(|| set_active_year(world, nearest, archetype))
+}
+
+/// Switch `world.gis.active_year` to `year` mid-session: recount quartier
+/// occupants and reclassify doors for the new listings (hover info reads
+/// the active year directly). With an `archetype`, also reconcile the
+/// living residents of every materialized quartier against the new
+/// directory: listed names not yet living there arrive, residents no longer
+/// listed leave, and those listed under a new trade change occupation.
+/// Quartier aggregates are then reseeded from the new listings, less the
+/// residents living as entities.
+pub fn set_active_year(world: &mut World, year: u16, archetype: Option<&Archetype>) -> YearChange {
+    let mut change = YearChange {
+        from: world.gis.active_year,
+        to: year,
+        ..YearChange::default()
+    };
+    world.gis.active_year = year;
+    let tolerance = world.gis.year_tolerance;
+    world
+        .gis
+        .quartiers
+        .recount_occupants(&world.gis.buildings, year, tolerance);
+    let is_shop = |access: &DoorAccess| matches!(access, DoorAccess::Shop { .. });
+    let shops_before: HashSet<BuildingId> = world
+        .gis
+        .buildings
+        .buildings
+        .iter()
+        .filter(|b| is_shop(&b.doors.access))
+        .map(|b| b.id)
+        .collect();
+    world
+        .gis
+        .buildings
+        .assign_doors(&world.tiles, year, tolerance);
+    for b in &world.gis.buildings.buildings {
+        match (shops_before.contains(&b.id), is_shop(&b.doors.access)) {
+            (false, true) => change.shops_opened.push(b.id),
+            (true, false) => change.shops_closed.push(b.id),
+            _ => {}
+        }
+    }
+
+    if let Some(archetype) = archetype {
+        reconcile(world, archetype, &mut change);
+    }
+    reseed_aggregates(world);
+    log::info!("{}", change.summary());
+    change
+}
+
+/// One building's listed residents, one entry per name.
+type Listing = Vec<(String, Occupant)>;
+
+/// Recount every quartier aggregate for the active year: the listed
+/// residents, less those materialized as entities. Folded residents stay
+/// counted, as `fold` counted them.
+fn reseed_aggregates(world: &mut World) {
+    let (year, tolerance) = (world.gis.active_year, world.gis.year_tolerance);
+    for (q, agg) in world.gis.aggregates.iter_mut() {
+        if let Some(quartier) = world.gis.quartiers.quartiers.get(q) {
+            agg.reseed_population(&world.gis.buildings, &quartier.name, year, tolerance);
+        }
+    }
+    for (e, listed) in world.gis.listed_residents() {
+        if world.pending_deaths.contains(&e) || world.gis.folded.contains_key(&e) {
+            continue;
+        }
+        let quartier = world
+            .gis
+            .buildings
+            .get(listed)
+            .and_then(|b| world.gis.quartiers.get_by_name(&b.quartier))
+            .map(|q| q.id);
+        let naics = world
+            .mind
+            .occupations
+            .get(&e)
+            .map_or(NO_OCCUPATION, |o| o.naics.as_str());
+        if let Some(agg) = quartier.and_then(|q| world.gis.aggregates.get_mut(&q)) {
+            agg.remove_resident(naics);
+        }
+    }
+}
+
+/// Compare each materialized building's residents with its listing for the
+/// active year, matching by name. A newly listed name arrives with
+/// probability `sample_ratio`, like at spawn; a name already listed for the
+/// previous year but not living there was left unseen (or died) and stays
+/// so.
+fn reconcile(world: &mut World, archetype: &Archetype, change: &mut YearChange) {
+    // Living residents by listed home, outside Statistical quartiers.
+    let mut residents: HashMap<BuildingId, Vec<Entity>> = HashMap::new();
+    for (e, listed) in world.gis.listed_residents() {
+        if world.pending_deaths.contains(&e) || world.gis.folded.contains_key(&e) {
+            continue;
+        }
+        residents.entry(listed).or_default().push(e);
+    }
+    let statistical = |world: &World, name: &str| {
+        world
+            .gis
+            .quartiers
+            .get_by_name(name)
+            .and_then(|q| world.gis.lod_zones.get(&q.id))
+            == Some(&LodZone::Statistical)
+    };
+    let quartiers: HashSet<String> = residents
+        .keys()
+        .filter_map(|&bid| world.gis.buildings.get(bid))
+        .map(|b| b.quartier.clone())
+        .filter(|q| !statistical(world, q))
+        .collect();
+
+    let (year, tolerance) = (world.gis.active_year, world.gis.year_tolerance);
+    let listed_names = |b: &crate::registry::BuildingData, year: u16| -> Listing {
+        b.occupants_nearest(year, tolerance)
+            .map(|(_, occupants)| occupants)
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|o| {
+                o.name
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|n| !n.is_empty())
+                    .map(|n| (n.to_string(), o.clone()))
+            })
+            .collect()
+    };
+    let listings: Vec<(BuildingId, Listing, HashSet<String>)> = world
+        .gis
+        .buildings
+        .buildings
+        .iter()
+        .filter(|b| quartiers.contains(&b.quartier))
+        .map(|b| {
+            let before = listed_names(b, change.from)
+                .into_iter()
+                .map(|(n, _)| n)
+                .collect();
+            (b.id, listed_names(b, year), before)
+        })
+        .collect();
+
+    let sample_ratio = world.gis.sample_ratio;
+    for (bid, listed, before) in listings {
+        let mut living = residents.remove(&bid).unwrap_or_default();
+        living.sort_by_key(|e| e.0);
+        let mut arriving: Vec<(String, Occupant)> = Vec::new();
+        for (name, occupant) in listed {
+            let found = living
+                .iter()
+                .position(|e| world.body.names.get(e).is_some_and(|n| n.value == name));
+            let Some(i) = found else {
+                arriving.push((name, occupant));
+                continue;
+            };
+            let e = living.remove(i);
+            let old = world.mind.occupations.get(&e);
+            if old.is_none_or(|o| o.naics != occupant.naics || o.activity != occupant.activity) {
+                let old_activity = old.map(|o| o.activity.clone()).unwrap_or_default();
+                change
+                    .trade_changes
+                    .push((e, old_activity, occupant.activity.clone()));
+                world.mind.occupations.insert(
+                    e,
+                    Occupation {
+                        activity: occupant.activity,
+                        naics: occupant.naics,
+                    },
+                );
+            }
+        }
+        for e in living {
+            let name = world
+                .body
+                .names
+                .get(&e)
+                .map(|n| n.value.clone())
+                .unwrap_or_default();
+            world.despawn(e);
+            change.departures.push(name);
+        }
+        for (name, occupant) in arriving {
+            if before.contains(&name)
+                || (sample_ratio < 1.0 && world.rng.random::<f64>() >= sample_ratio)
+            {
+                continue;
+            }
+            if let Some(e) = arrive(world, archetype, &name, &occupant, bid) {
+                change.arrivals.push(e);
+            }
+        }
+    }
+}
+
+/// Spawn a newly listed resident on a random Floor tile of `bid`, in a
+/// random apartment if the building has upper storeys.
+fn arrive(
+    world: &mut World,
+    archetype: &Archetype,
+    name: &str,
+    occupant: &Occupant,
+    bid: BuildingId,
+) -> Option<Entity> {
+    let bdata = world.gis.buildings.get(bid)?;
+    let floors: Vec<(i32, i32)> = bdata
+        .tiles
+        .iter()
+        .filter(|&&(x, y)| world.tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Floor))
+        .copied()
+        .collect();
+    if floors.is_empty() {
+        return None;
+    }
+    let upper = if bdata.stairs.is_some() {
+        bdata.floor_count.saturating_sub(1)
+    } else {
+        0
+    };
+    let pos = floors[world.rng.random_range(0..floors.len())];
+    let storey = if upper > 0 {
+        world.rng.random_range(1..=upper)
+    } else {
+        0
+    };
+    Some(spawn_resident(
+        world, archetype, name, occupant, bid, pos, storey,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Tick;
+    use crate::registry::{BuildingData, QuartierRegistry};
+    use crate::tile_map::TileMap;
+    use std::collections::HashMap;
+
+    fn occupant(name: &str, activity: &str, naics: &str) -> Occupant {
+        Occupant {
+            name: name.into(),
+            activity: activity.into(),
+            naics: naics.into(),
+        }
+    }
+
+    /// One building in quartier "TestQ" listed in 1845 and 1850.
+    fn directory_world() -> World {
+        let mut world = World::new_with_seed(42);
+        world.tiles = TileMap::new(10, 10);
+        let bid = BuildingId(1);
+        let mut tiles = Vec::new();
+        for x in 2..6 {
+            world.tiles.set_terrain(x, 5, Terrain::Floor);
+            world.tiles.set_building_id(x, 5, bid);
+            tiles.push((x as i32, 5));
+        }
+        let mut occupants_by_year = HashMap::new();
+        occupants_by_year.insert(
+            1845,
+            vec![
+                occupant("Dupont", "boulanger", "311811"),
+                occupant("Martin, Leroux", "rentier", "531"),
+            ],
+        );
+        occupants_by_year.insert(
+            1850,
+            vec![
+                occupant("Dupont", "épicier", "445110"),
+                occupant("Martin", "rentier", "531"),
+                occupant("Garnier", "tailleur", "315"),
+            ],
+        );
+        world.gis.buildings.insert(BuildingData {
+            quartier: "TestQ".into(),
+            occupants_by_year,
+            ..BuildingData::test_default(bid, tiles)
+        });
+        world.gis.quartier_names = vec!["TestQ".into()];
+        world.gis.quartiers = QuartierRegistry::build_from_registries(
+            &world.gis.quartier_names,
+            &world.gis.buildings,
+            &world.gis.blocks,
+            world.gis.active_year,
+        );
+        world
+    }
+
+    fn names(world: &World) -> Vec<String> {
+        let mut names: Vec<String> = world
+            .alive
+            .iter()
+            .filter_map(|e| world.body.names.get(e))
+            .map(|n| n.value.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_step_year_walks_directory_years() {
+        let world = directory_world();
+        assert_eq!(step_year(&world, 1845, true), Some(1850));
+        assert_eq!(step_year(&world, 1845, false), None);
+        assert_eq!(step_year(&world, 1850, false), Some(1845));
+        assert_eq!(step_year(&world, 1847, false), Some(1845));
+    }
+
+    /// First tick whose game date falls in `year`.
+    fn first_tick_of(world: &World, year: i32) -> Tick {
+        (0..)
+            .map(|day| Tick(day * 1440))
+            .find(|&t| GameDate::from_tick(t, &world.start_date).year == year)
+            .expect("reachable year")
+    }
+
+    #[test]
+    fn test_nearest_year_prefers_later_on_tie() {
+        let world = directory_world();
+        assert_eq!(nearest_year(&world, 1840), Some(1845));
+        assert_eq!(nearest_year(&world, 1847), Some(1845));
+        assert_eq!(nearest_year(&world, 1848), Some(1850));
+        assert_eq!(nearest_year(&world, 1860), Some(1850));
+    }
+
+    #[test]
+    fn test_game_date_advances_directory_year() {
+        let mut world = directory_world();
+        // By 1850 the bakery has given way to a rentier: the shop closes.
+        world.gis.buildings.buildings[0]
+            .occupants_by_year
+            .insert(1850, vec![occupant("Martin", "rentier", "531")]);
+        world
+            .gis
+            .buildings
+            .assign_doors(&world.tiles, 1845, world.gis.year_tolerance);
+        let archetype = Archetype::default();
+        crate::loading_gis::spawn_gis_entities(&mut world, "TestQ", &archetype, 1.0);
+
+        assert_eq!(follow_game_date(&mut world, Some(&archetype)), None);
+        world.tick = first_tick_of(&world, 1847);
+        assert_eq!(follow_game_date(&mut world, Some(&archetype)), None);
+        assert_eq!(world.gis.active_year, 1845);
+
+        world.tick = first_tick_of(&world, 1848);
+        let change = follow_game_date(&mut world, Some(&archetype)).expect("year switch");
+        assert_eq!((change.from, change.to), (1845, 1850));
+        assert_eq!(change.shops_closed, vec![BuildingId(1)]);
+        assert!(change.shops_opened.is_empty());
+        assert_eq!(
+            world.gis.buildings.buildings[0].doors.access,
+            DoorAccess::Private
+        );
+        assert_eq!(names(&world), vec!["Martin"]);
+        // Same game year again: nothing to do.
+        assert_eq!(follow_game_date(&mut world, Some(&archetype)), None);
+    }
+
+    #[test]
+    fn test_year_change_recounts_without_touching_population() {
+        let mut world = directory_world();
+        let archetype = Archetype::default();
+        crate::loading_gis::spawn_gis_entities(&mut world, "TestQ", &archetype, 1.0);
+        let q = world
+            .gis
+            .quartiers
+            .get_by_name("TestQ")
+            .expect("quartier")
+            .id;
+        assert_eq!(world.gis.quartiers.quartiers[&q].occupant_count, 2);
+
+        let change = set_active_year(&mut world, 1850, None);
+        assert_eq!(world.gis.active_year, 1850);
+        assert_eq!(world.gis.quartiers.quartiers[&q].occupant_count, 3);
+        assert_eq!(change.arrivals.len() + change.departures.len(), 0);
+        assert_eq!(names(&world), vec!["Dupont", "Leroux", "Martin"]);
+    }
+
+    #[test]
+    fn test_year_change_reconciles_residents() {
+        let mut world = directory_world();
+        let archetype = Archetype::default();
+        crate::loading_gis::spawn_gis_entities(&mut world, "TestQ", &archetype, 1.0);
+        let dupont = *world
+            .alive
+            .iter()
+            .find(|e| world.body.names[e].value == "Dupont")
+            .expect("Dupont");
+
+        let change = set_active_year(&mut world, 1850, Some(&archetype));
+        assert_eq!(change.departures, vec!["Leroux".to_string()]);
+        assert_eq!(change.arrivals.len(), 1);
+        assert_eq!(
+            change.trade_changes,
+            vec![(dupont, "boulanger".to_string(), "épicier".to_string())]
+        );
+        assert_eq!(names(&world), vec!["Dupont", "Garnier", "Martin"]);
+        assert_eq!(world.mind.occupations[&dupont].naics, "445110");
+        crate::world::validate_world(&world);
+
+        // And back again.
+        let change = set_active_year(&mut world, 1845, Some(&archetype));
+        assert_eq!(change.departures, vec!["Garnier".to_string()]);
+        assert_eq!(names(&world), vec!["Dupont", "Leroux", "Martin"]);
+    }
+
+    #[test]
+    fn test_year_change_reseeds_aggregate_and_samples_arrivals() {
+        let mut world = directory_world();
+        world.gis.aggregates = crate::aggregate::seed_all(
+            &world.gis.quartiers,
+            &world.gis.buildings,
+            world.gis.active_year,
+            world.gis.year_tolerance,
+        );
+        let archetype = Archetype::default();
+        crate::loading_gis::spawn_gis_entities(&mut world, "TestQ", &archetype, 1.0);
+        let q = world
+            .gis
+            .quartiers
+            .get_by_name("TestQ")
+            .expect("quartier")
+            .id;
+        assert_eq!(world.gis.aggregates[&q].total(), 0.0);
+
+        // No newcomer is sampled: Garnier stays one of the unseen, counted
+        // against the 1850 listing rather than the 1845 one.
+        world.gis.sample_ratio = 0.0;
+        let change = set_active_year(&mut world, 1850, Some(&archetype));
+        assert!(change.arrivals.is_empty());
+        assert_eq!(names(&world), vec!["Dupont", "Martin"]);
+        let agg = &world.gis.aggregates[&q];
+        assert_eq!(agg.total(), 1.0);
+        assert_eq!(agg.population.get("31"), Some(&1.0));
+        assert_eq!(agg.food_workers, 0.0);
+
+        // Back to 1845 with everyone sampled: Leroux returns, and the
+        // aggregate again counts nobody unseen.
+        world.gis.sample_ratio = 1.0;
+        let change = set_active_year(&mut world, 1845, Some(&archetype));
+        assert_eq!(change.arrivals.len(), 1);
+        assert_eq!(names(&world), vec!["Dupont", "Leroux", "Martin"]);
+        assert_eq!(world.gis.aggregates[&q].total(), 0.0);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::components::{Entity, GameDate};
+use crate::registry::{BuildingData, BuildingId, DoorAccess};
+use crate::tile_map::{Terrain, TileMap};
+use crate::world::{GisTables, World};
+
+/// What an entity brings to a door: the buildings it holds keys to, the
+/// building it is standing in, and the time of day.
+#[derive(Debug, Clone, Copy)]
+pub struct DoorKeys {
+    pub home: Option<BuildingId>,
+    pub work: Option<BuildingId>,
+    /// Building the entity is inside. It can always leave through that
+    /// building's doors.
+    pub inside: Option<BuildingId>,
+    pub hour: u32,
+}
+
+impl DoorKeys {
+    pub fn of(world: &World, e: Entity) -> Self {
+        let inside = world.body.positions.get(&e).and_then(|p| {
+            if p.x < 0 || p.y < 0 {
+                return None;
+            }
+            world.tiles.get_building_id(p.x as usize, p.y as usize)
+        });
+        Self {
+            home: world.gis.home_buildings.get(&e).map(|h| h.0),
+            work: world.gis.workplaces.get(&e).map(|w| w.0),
+            inside,
+            hour: GameDate::from_tick(world.tick, &world.start_date).hour,
+        }
+    }
+
+    /// Whether these keys open `bdata`'s doors right now.
+    pub fn may_enter(&self, bdata: &BuildingData) -> bool {
+        let bid = Some(bdata.id);
+        if self.home == bid || self.work == bid || self.inside == bid {
+            return true;
+        }
+        if bdata.doors.locked {
+            return false;
+        }
+        match bdata.doors.access {
+            DoorAccess::Private => false,
+            DoorAccess::Shop { opens, closes } => {
+                (u32::from(opens)..u32::from(closes)).contains(&self.hour)
+            }
+            DoorAccess::Public => true,
+        }
+    }
+
+    /// Whether stepping onto `(x, y)` is allowed. Only `Terrain::Door`
+    /// tiles of known buildings and the decks of closed bridges are ever
+    /// refused.
+    pub fn may_pass(&self, tiles: &TileMap, gis: &GisTables, x: i32, y: i32) -> bool {
+        if x < 0 || y < 0 {
+            return true;
+        }
+        let (ux, uy) = (x as usize, y as usize);
+        match tiles.get_terrain(ux, uy) {
+            Some(Terrain::Door) => tiles
+                .get_building_id(ux, uy)
+                .and_then(|bid| gis.buildings.get(bid))
+                .is_none_or(|bdata| self.may_enter(bdata)),
+            Some(Terrain::Bridge) => !gis.bridges.is_closed_at(x, y),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{HomeBuilding, Position, Tick};
+    use crate::registry::Occupant;
+    use std::collections::HashMap;
+
+    fn shop_world() -> (World, BuildingId) {
+        let mut world = World::new_with_seed(42);
+        world.tiles = TileMap::new(10, 10);
+        let bid = world.gis.buildings.next_id();
+        let tiles = vec![(5, 5), (6, 5)];
+        for &(x, y) in &tiles {
+            world.tiles.set_building_id(x as usize, y as usize, bid);
+        }
+        world.tiles.set_terrain(5, 5, Terrain::Door);
+        world.tiles.set_terrain(6, 5, Terrain::Floor);
+        let mut occupants_by_year = HashMap::new();
+        occupants_by_year.insert(
+            1845,
+            vec![Occupant {
+                name: "Dupont".into(),
+                activity: "boulanger".into(),
+                naics: "311811".into(),
+            }],
+        );
+        world.gis.buildings.insert(BuildingData {
+            occupants_by_year,
+            ..BuildingData::test_default(bid, tiles)
+        });
+        world
+            .gis
+            .buildings
+            .assign_doors(&world.tiles, 1845, world.gis.year_tolerance);
+        (world, bid)
+    }
+
+    #[test]
+    fn test_assign_doors_classifies_shop() {
+        let (world, bid) = shop_world();
+        let doors = &world.gis.buildings.get(bid).expect("building").doors;
+        assert_eq!(doors.tiles, vec![(5, 5)]);
+        assert!(matches!(doors.access, DoorAccess::Shop { .. }));
+    }
+
+    #[test]
+    fn test_assign_doors_uses_year_tolerance() {
+        let (mut world, bid) = shop_world();
+        let access = |world: &World| world.gis.buildings.get(bid).expect("building").doors.access;
+        world.gis.buildings.assign_doors(&world.tiles, 1860, 20);
+        assert!(matches!(access(&world), DoorAccess::Shop { .. }));
+        world.gis.buildings.assign_doors(&world.tiles, 1860, 10);
+        assert!(matches!(access(&world), DoorAccess::Private));
+    }
+
+    #[test]
+    fn test_shop_door_open_only_in_hours() {
+        let (mut world, _) = shop_world();
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x: 4, y: 5 });
+
+        world.tick = Tick(10 * 60);
+        let keys = DoorKeys::of(&world, e);
+        assert!(keys.may_pass(&world.tiles, &world.gis, 5, 5));
+
+        world.tick = Tick(22 * 60);
+        let keys = DoorKeys::of(&world, e);
+        assert!(!keys.may_pass(&world.tiles, &world.gis, 5, 5));
+        assert!(keys.may_pass(&world.tiles, &world.gis, 4, 4));
+    }
+
+    #[test]
+    fn test_residents_pass_locked_door() {
+        let (mut world, bid) = shop_world();
+        if let Some(b) = world.gis.buildings.get_mut(bid) {
+            b.doors.locked = true;
+        }
+        world.tick = Tick(10 * 60);
+        let stranger = world.spawn();
+        world
+            .body
+            .positions
+            .insert(stranger, Position { x: 4, y: 5 });
+        let resident = world.spawn();
+        world
+            .body
+            .positions
+            .insert(resident, Position { x: 4, y: 5 });
+        world.gis.home_buildings.insert(resident, HomeBuilding(bid));
+        let trapped = world.spawn();
+        world
+            .body
+            .positions
+            .insert(trapped, Position { x: 6, y: 5 });
+
+        let (t, g) = (&world.tiles, &world.gis);
+        assert!(!DoorKeys::of(&world, stranger).may_pass(t, g, 5, 5));
+        assert!(DoorKeys::of(&world, resident).may_pass(t, g, 5, 5));
+        assert!(
+            DoorKeys::of(&world, trapped).may_pass(t, g, 5, 5),
+            "can leave"
+        );
+    }
+}
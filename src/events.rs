@@ -1,4 +1,5 @@
-use crate::components::{Entity, Tick};
+use crate::components::{BodyPart, Entity, FireCause, Loot, Tick, WoundKind};
+use crate::registry::{BuildingId, QuartierId};
 
 /// All event types in the simulation. Every variant includes tick: Tick.
 /// Fields are structural data stored in the ring buffer — not all are read
@@ -37,6 +38,78 @@ pub enum Event {
         new_val: f32,
         tick: Tick,
     },
+    Wounded {
+        entity: Entity,
+        part: BodyPart,
+        kind: WoundKind,
+        tick: Tick,
+    },
+    WoundHealed {
+        entity: Entity,
+        part: BodyPart,
+        kind: WoundKind,
+        tick: Tick,
+    },
+    /// A pickpocketing attempt; `noticed` thefts draw the victim's and the
+    /// police's reaction like an attack. `loot` is what changed hands, if
+    /// the victim had anything to take.
+    Theft {
+        thief: Entity,
+        victim: Entity,
+        success: bool,
+        noticed: bool,
+        loot: Option<Loot>,
+        tick: Tick,
+    },
+    Arrested {
+        officer: Entity,
+        offender: Entity,
+        tick: Tick,
+    },
+    RiotBegan {
+        quartier: QuartierId,
+        tick: Tick,
+    },
+    RiotDispersed {
+        quartier: QuartierId,
+        casualties: u32,
+        tick: Tick,
+    },
+    FireStarted {
+        building: BuildingId,
+        cause: FireCause,
+        tick: Tick,
+    },
+    /// The last flames in `building` are out; a `destroyed` building has
+    /// collapsed to rubble and its residents moved elsewhere.
+    FireOut {
+        building: BuildingId,
+        destroyed: bool,
+        displaced: u32,
+        tick: Tick,
+    },
+}
+
+impl Event {
+    /// Tick at which the event happened.
+    pub fn tick(&self) -> Tick {
+        match self {
+            Event::Spawned { tick, .. }
+            | Event::Died { tick, .. }
+            | Event::Moved { tick, .. }
+            | Event::Ate { tick, .. }
+            | Event::Attacked { tick, .. }
+            | Event::HungerChanged { tick, .. }
+            | Event::Wounded { tick, .. }
+            | Event::WoundHealed { tick, .. }
+            | Event::Theft { tick, .. }
+            | Event::Arrested { tick, .. }
+            | Event::RiotBegan { tick, .. }
+            | Event::RiotDispersed { tick, .. }
+            | Event::FireStarted { tick, .. }
+            | Event::FireOut { tick, .. } => *tick,
+        }
+    }
 }
 
 /// Ring buffer for events. Fixed capacity, overwrites oldest entries.
@@ -105,6 +178,21 @@ impl EventLog {
             .collect()
     }
 
+    /// Events at or after `tick`, oldest first. Walks back from the newest
+    /// entry, so the cost is proportional to the result, not the log size.
+    pub fn since(&self, tick: Tick) -> Vec<&Event> {
+        let mut out: Vec<&Event> = Vec::new();
+        for i in 0..self.count {
+            let idx = (self.write_pos + self.capacity - 1 - i) % self.capacity;
+            match self.buffer[idx].as_ref() {
+                Some(event) if event.tick() >= tick => out.push(event),
+                _ => break,
+            }
+        }
+        out.reverse();
+        out
+    }
+
     /// Total number of events currently stored.
     #[allow(dead_code)] // Used via lib crate in integration tests
     pub fn len(&self) -> usize {
@@ -121,7 +209,7 @@ impl EventLog {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::components::{Entity, Tick};
+    use crate::components::{BodyPart, Entity, Tick, WoundKind};
 
     fn make_spawned(id: u64, tick: u64) -> Event {
         Event::Spawned {
@@ -138,9 +226,30 @@ mod tests {
             Event::Ate { tick, .. } => tick.0,
             Event::Attacked { tick, .. } => tick.0,
             Event::HungerChanged { tick, .. } => tick.0,
+            Event::Wounded { tick, .. } => tick.0,
+            Event::WoundHealed { tick, .. } => tick.0,
+            Event::Theft { tick, .. } => tick.0,
+            Event::Arrested { tick, .. } => tick.0,
+            Event::RiotBegan { tick, .. } => tick.0,
+            Event::RiotDispersed { tick, .. } => tick.0,
+            Event::FireStarted { tick, .. } => tick.0,
+            Event::FireOut { tick, .. } => tick.0,
         }
     }
 
+    #[test]
+    fn since_returns_tail_from_tick() {
+        let mut log = EventLog::new(4);
+        for t in 0..6 {
+            log.push(make_spawned(t, t));
+        }
+        let ticks: Vec<u64> = log.since(Tick(4)).iter().map(|e| e.tick().0).collect();
+        assert_eq!(ticks, vec![4, 5]);
+        assert!(log.since(Tick(9)).is_empty());
+        // Older than the buffer: everything still stored.
+        assert_eq!(log.since(Tick(0)).len(), 4);
+    }
+
     #[test]
     fn push_and_iter_returns_events_in_order() {
         let mut log = EventLog::new(10);
@@ -270,6 +379,51 @@ mod tests {
                 new_val: 5.0,
                 tick: Tick(5),
             },
+            Event::Wounded {
+                entity: Entity(1),
+                part: BodyPart::Head,
+                kind: WoundKind::Bruise,
+                tick: Tick(6),
+            },
+            Event::WoundHealed {
+                entity: Entity(1),
+                part: BodyPart::Head,
+                kind: WoundKind::Bruise,
+                tick: Tick(7),
+            },
+            Event::Theft {
+                thief: Entity(1),
+                victim: Entity(2),
+                success: true,
+                noticed: false,
+                loot: None,
+                tick: Tick(8),
+            },
+            Event::Arrested {
+                officer: Entity(2),
+                offender: Entity(1),
+                tick: Tick(9),
+            },
+            Event::RiotBegan {
+                quartier: QuartierId(1),
+                tick: Tick(10),
+            },
+            Event::RiotDispersed {
+                quartier: QuartierId(1),
+                casualties: 3,
+                tick: Tick(11),
+            },
+            Event::FireStarted {
+                building: BuildingId(1),
+                cause: FireCause::Oven,
+                tick: Tick(12),
+            },
+            Event::FireOut {
+                building: BuildingId(1),
+                destroyed: true,
+                displaced: 4,
+                tick: Tick(13),
+            },
         ];
 
         for (i, event) in events.iter().enumerate() {
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use crate::components::Entity;
+use crate::relationships::{FRIEND_OPINION, HOSTILE_OPINION};
+use crate::world::World;
+
+/// Faction identifier, 0-based index into `FactionRegistry.factions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FactionId(pub u16);
+
+/// How one party regards another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stance {
+    Allied,
+    Neutral,
+    Hostile,
+}
+
+/// What a member does when attacked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackResponse {
+    /// Hold a grudge against the attacker and fight back.
+    Fight,
+    /// Fear the attacker and run.
+    Flee,
+}
+
+/// Faction definition, loaded from `data/factions.kdl`.
+#[derive(Debug, Clone)]
+pub struct FactionDef {
+    /// Stable key used by data files and spawn code, e.g. "residents".
+    pub key: String,
+    pub name: String,
+    /// Same-faction members are allied only within one quartier (neighbors).
+    pub local: bool,
+    pub when_attacked: AttackResponse,
+    /// Victims alert allies within earshot.
+    pub calls_for_help: bool,
+    /// Members take up the fight when an ally is attacked nearby.
+    pub defends_allies: bool,
+}
+
+/// Faction definitions plus the hostility matrix between them.
+#[derive(Default)]
+pub struct FactionRegistry {
+    pub factions: Vec<FactionDef>,
+    /// Explicit stances, stored in both directions.
+    stances: HashMap<(FactionId, FactionId), Stance>,
+}
+
+impl FactionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a faction and return its id.
+    pub fn insert(&mut self, def: FactionDef) -> FactionId {
+        self.factions.push(def);
+        FactionId(self.factions.len() as u16 - 1)
+    }
+
+    pub fn get(&self, id: FactionId) -> Option<&FactionDef> {
+        self.factions.get(id.0 as usize)
+    }
+
+    pub fn get_by_key(&self, key: &str) -> Option<FactionId> {
+        self.factions
+            .iter()
+            .position(|f| f.key == key)
+            .map(|i| FactionId(i as u16))
+    }
+
+    /// Set the stance between two factions. The matrix is symmetric.
+    pub fn set_stance(&mut self, a: FactionId, b: FactionId, stance: Stance) {
+        self.stances.insert((a, b), stance);
+        self.stances.insert((b, a), stance);
+    }
+
+    /// Stance between two factions. Unlisted pairs default to Allied for
+    /// the same faction and Neutral otherwise.
+    pub fn stance(&self, a: FactionId, b: FactionId) -> Stance {
+        match self.stances.get(&(a, b)) {
+            Some(&s) => s,
+            None if a == b => Stance::Allied,
+            None => Stance::Neutral,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.factions.is_empty()
+    }
+}
+
+/// Stance of entity `a` toward entity `b`.
+///
+/// A live grudge held by `a` makes `b` hostile regardless of faction, as
+/// does an opinion at or below `HOSTILE_OPINION`; an opinion at or above
+/// `FRIEND_OPINION` makes them allies.
+/// Entities without a faction are hostile to everyone and everyone is
+/// hostile to them (wild creatures keep the pre-faction free-for-all).
+/// Members of a `local` faction are allied only with neighbors from the
+/// same quartier and neutral to the rest of their faction.
+pub fn stance_between(world: &World, a: Entity, b: Entity) -> Stance {
+    if world
+        .mind
+        .grievances
+        .get(&a)
+        .is_some_and(|g| g.grudges.contains_key(&b))
+    {
+        return Stance::Hostile;
+    }
+    let opinion = world.mind.relationships.opinion(a, b, world.tick);
+    if opinion <= HOSTILE_OPINION {
+        return Stance::Hostile;
+    }
+    if opinion >= FRIEND_OPINION {
+        return Stance::Allied;
+    }
+    let (Some(ma), Some(mb)) = (
+        world.mind.faction_members.get(&a),
+        world.mind.faction_members.get(&b),
+    ) else {
+        return Stance::Hostile;
+    };
+    let factions = &world.mind.factions;
+    let stance = factions.stance(ma.faction, mb.faction);
+    let local = factions.get(ma.faction).is_some_and(|f| f.local);
+    if stance == Stance::Allied && ma.faction == mb.faction && local && ma.quartier != mb.quartier {
+        Stance::Neutral
+    } else {
+        stance
+    }
+}
+
+/// Whether `a` treats `b` as an enemy.
+pub fn is_hostile(world: &World, a: Entity, b: Entity) -> bool {
+    stance_between(world, a, b) == Stance::Hostile
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{FactionMember, Grievances, Tick};
+    use crate::registry::QuartierId;
+
+    fn def(key: &str, local: bool) -> FactionDef {
+        FactionDef {
+            key: key.into(),
+            name: key.into(),
+            local,
+            when_attacked: AttackResponse::Fight,
+            calls_for_help: false,
+            defends_allies: false,
+        }
+    }
+
+    fn member(world: &mut World, faction: FactionId, quartier: u8) -> Entity {
+        let e = world.spawn();
+        world.mind.faction_members.insert(
+            e,
+            FactionMember {
+                faction,
+                quartier: Some(QuartierId(quartier)),
+            },
+        );
+        e
+    }
+
+    #[test]
+    fn test_registry_default_stances() {
+        let mut reg = FactionRegistry::new();
+        let a = reg.insert(def("a", false));
+        let b = reg.insert(def("b", false));
+        assert_eq!(reg.stance(a, a), Stance::Allied);
+        assert_eq!(reg.stance(a, b), Stance::Neutral);
+        reg.set_stance(a, b, Stance::Hostile);
+        assert_eq!(reg.stance(b, a), Stance::Hostile);
+        assert_eq!(reg.get_by_key("b"), Some(b));
+    }
+
+    #[test]
+    fn test_unaffiliated_is_hostile() {
+        let mut world = World::new_with_seed(42);
+        let a = world.spawn();
+        let b = world.spawn();
+        assert!(is_hostile(&world, a, b));
+    }
+
+    #[test]
+    fn test_local_faction_allied_only_with_neighbors() {
+        let mut world = World::new_with_seed(42);
+        let residents = world.mind.factions.insert(def("residents", true));
+        let a = member(&mut world, residents, 1);
+        let b = member(&mut world, residents, 1);
+        let c = member(&mut world, residents, 2);
+        assert_eq!(stance_between(&world, a, b), Stance::Allied);
+        assert_eq!(stance_between(&world, a, c), Stance::Neutral);
+    }
+
+    #[test]
+    fn test_matrix_hostility_and_grudge_override() {
+        let mut world = World::new_with_seed(42);
+        let police = world.mind.factions.insert(def("police", false));
+        let criminals = world.mind.factions.insert(def("criminals", false));
+        world
+            .mind
+            .factions
+            .set_stance(police, criminals, Stance::Hostile);
+        let cop = member(&mut world, police, 1);
+        let thief = member(&mut world, criminals, 1);
+        let cop2 = member(&mut world, police, 1);
+        assert!(is_hostile(&world, cop, thief));
+        assert!(!is_hostile(&world, cop, cop2));
+
+        let mut g = Grievances::default();
+        g.grudges.insert(cop2, Tick(100));
+        world.mind.grievances.insert(cop, g);
+        assert!(is_hostile(&world, cop, cop2));
+        assert!(!is_hostile(&world, cop2, cop));
+    }
+}
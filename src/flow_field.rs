@@ -0,0 +1,304 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::tile_map::{TERRAIN_COUNT, TileMap};
+
+/// Half-width of the square window a flow field covers around its goal.
+/// Movers farther out than this path with A* as usual.
+pub const FLOW_RADIUS: i32 = 96;
+
+/// Flow fields kept before the least recently used one is evicted.
+pub const FLOW_CACHE_SIZE: usize = 32;
+
+/// Requests for an uncached goal before a field is built for it.
+pub const FLOW_MIN_DEMAND: u32 = 4;
+
+/// Demand counters are forgotten once this many goals are being tracked.
+const MAX_TRACKED_DEMAND: usize = 4096;
+
+/// Neighbor order for picking the next step; ties go to the earliest.
+const DIRS: [(i32, i32); 8] = [
+    (0, -1),
+    (1, 0),
+    (0, 1),
+    (-1, 0),
+    (1, -1),
+    (1, 1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// Whether `(x, y)` lies in the window a field toward `goal` would cover.
+pub fn in_window(goal: (i32, i32), x: i32, y: i32) -> bool {
+    (x - goal.0).abs().max((y - goal.1).abs()) <= FLOW_RADIUS
+}
+
+/// Dijkstra map toward one goal over a window of the tile map: the cost of
+/// walking from each tile to the goal, in `find_path` cost units.
+#[derive(Debug, Clone)]
+pub struct FlowField {
+    pub goal: (i32, i32),
+    /// `TileMap::move_costs` the field was built with.
+    move_costs: [u32; TERRAIN_COUNT],
+    x0: i32,
+    y0: i32,
+    w: i32,
+    h: i32,
+    dist: Vec<u32>,
+}
+
+impl FlowField {
+    /// Build the field for `goal`, which need not be walkable itself.
+    pub fn build(map: &TileMap, goal: (i32, i32)) -> Self {
+        let x0 = (goal.0 - FLOW_RADIUS).max(0);
+        let y0 = (goal.1 - FLOW_RADIUS).max(0);
+        let x1 = (goal.0 + FLOW_RADIUS + 1).min(map.width() as i32);
+        let y1 = (goal.1 + FLOW_RADIUS + 1).min(map.height() as i32);
+        let mut field = Self {
+            goal,
+            move_costs: map.move_costs(),
+            x0,
+            y0,
+            w: (x1 - x0).max(0),
+            h: (y1 - y0).max(0),
+            dist: Vec::new(),
+        };
+        field.dist = vec![u32::MAX; (field.w * field.h) as usize];
+        let Some(gi) = field.index(goal.0, goal.1) else {
+            return field;
+        };
+        field.dist[gi] = 0;
+
+        let mut open = BinaryHeap::new();
+        open.push(Reverse((0u32, goal.0, goal.1)));
+        while let Some(Reverse((d, x, y))) = open.pop() {
+            if field.index(x, y).is_some_and(|i| d > field.dist[i]) {
+                continue;
+            }
+            // Entering (x, y) from a neighbor costs (x, y)'s step cost.
+            for (dx, dy) in DIRS {
+                let (nx, ny) = (x + dx, y + dy);
+                let Some(ni) = field.index(nx, ny) else {
+                    continue;
+                };
+                if !map.is_walkable(nx as usize, ny as usize) {
+                    continue;
+                }
+                let diagonal = dx != 0 && dy != 0;
+                if diagonal && !map.diagonal_clear(nx, ny, x, y) {
+                    continue;
+                }
+                let nd = d + map.step_cost(x, y, diagonal);
+                if nd < field.dist[ni] {
+                    field.dist[ni] = nd;
+                    open.push(Reverse((nd, nx, ny)));
+                }
+            }
+        }
+        field
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        let (lx, ly) = (x - self.x0, y - self.y0);
+        (lx >= 0 && ly >= 0 && lx < self.w && ly < self.h).then(|| (ly * self.w + lx) as usize)
+    }
+
+    /// Whether `(x, y)` lies inside this field's window.
+    pub fn covers(&self, x: i32, y: i32) -> bool {
+        self.index(x, y).is_some()
+    }
+
+    /// Cost from `(x, y)` to the goal, or None if outside the window or
+    /// cut off from the goal.
+    pub fn cost(&self, x: i32, y: i32) -> Option<u32> {
+        self.index(x, y)
+            .map(|i| self.dist[i])
+            .filter(|&d| d != u32::MAX)
+    }
+
+    /// The neighbor of `pos` that is strictly closer to the goal, or None
+    /// at the goal or where the field can't help.
+    pub fn next_step(&self, map: &TileMap, pos: (i32, i32)) -> Option<(i32, i32)> {
+        let here = self.cost(pos.0, pos.1)?;
+        DIRS.iter()
+            .map(|&(dx, dy)| (pos.0 + dx, pos.1 + dy))
+            .filter(|&(x, y)| {
+                let diagonal = x != pos.0 && y != pos.1;
+                !diagonal || map.diagonal_clear(pos.0, pos.1, x, y)
+            })
+            .filter_map(|(x, y)| self.cost(x, y).map(|d| (d, (x, y))))
+            .filter(|&(d, _)| d < here)
+            .enumerate()
+            .min_by_key(|&(i, (d, _))| (d, i))
+            .map(|(_, (_, p))| p)
+    }
+}
+
+/// LRU cache of flow fields for popular destinations.
+///
+/// A goal gets a field once `FLOW_MIN_DEMAND` movers have asked for it;
+/// until then `next_step` returns None and callers fall back to A*.
+#[derive(Debug, Default)]
+pub struct FlowFieldCache {
+    fields: HashMap<(i32, i32), FlowField>,
+    /// Goal → value of `clock` when last used.
+    last_used: HashMap<(i32, i32), u64>,
+    demand: HashMap<(i32, i32), u32>,
+    clock: u64,
+}
+
+impl FlowFieldCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn contains(&self, goal: (i32, i32)) -> bool {
+        self.fields.contains_key(&goal)
+    }
+
+    /// Next step from `pos` toward `goal` along a cached field, building
+    /// the field if `goal` has become popular. None if there is no field
+    /// (yet), `pos` is outside it, or `pos` is already at the goal.
+    pub fn next_step(
+        &mut self,
+        map: &TileMap,
+        goal: (i32, i32),
+        pos: (i32, i32),
+    ) -> Option<(i32, i32)> {
+        if !in_window(goal, pos.0, pos.1) {
+            return None;
+        }
+        self.clock += 1;
+        let stale = self
+            .fields
+            .get(&goal)
+            .is_some_and(|f| f.move_costs != map.move_costs());
+        if stale {
+            // Costs were retuned; the goal is known to be popular, so
+            // rebuild straight away.
+            self.insert(FlowField::build(map, goal));
+        } else if !self.fields.contains_key(&goal) {
+            if self.demand.len() >= MAX_TRACKED_DEMAND {
+                self.demand.clear();
+            }
+            let n = self.demand.entry(goal).or_default();
+            *n += 1;
+            if *n < FLOW_MIN_DEMAND {
+                return None;
+            }
+            self.demand.remove(&goal);
+            self.insert(FlowField::build(map, goal));
+        }
+        self.last_used.insert(goal, self.clock);
+        self.fields.get(&goal)?.next_step(map, pos)
+    }
+
+    /// Cache `field`, evicting the least recently used one if full.
+    pub fn insert(&mut self, field: FlowField) {
+        let goal = field.goal;
+        self.fields.insert(goal, field);
+        self.last_used.insert(goal, self.clock);
+        while self.fields.len() > FLOW_CACHE_SIZE {
+            let Some((&oldest, _)) = self.last_used.iter().min_by_key(|&(g, &t)| (t, *g)) else {
+                break;
+            };
+            self.fields.remove(&oldest);
+            self.last_used.remove(&oldest);
+        }
+    }
+
+    /// Drop every field whose window contains `(x, y)`.
+    pub fn invalidate_tile(&mut self, x: i32, y: i32) {
+        let stale: Vec<(i32, i32)> = self
+            .fields
+            .values()
+            .filter(|f| f.covers(x, y))
+            .map(|f| f.goal)
+            .collect();
+        for goal in stale {
+            self.fields.remove(&goal);
+            self.last_used.remove(&goal);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.fields.clear();
+        self.last_used.clear();
+        self.demand.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile_map::Terrain;
+
+    #[test]
+    fn test_field_descends_around_wall() {
+        let mut map = TileMap::new(20, 20);
+        for y in 0..15 {
+            map.set_terrain(10, y, Terrain::Wall);
+        }
+        let field = FlowField::build(&map, (15, 2));
+        let mut pos = (5, 2);
+        for _ in 0..40 {
+            if pos == (15, 2) {
+                break;
+            }
+            let next = field.next_step(&map, pos).expect("downhill step");
+            assert!(map.is_walkable(next.0 as usize, next.1 as usize));
+            assert!(field.cost(next.0, next.1) < field.cost(pos.0, pos.1));
+            pos = next;
+        }
+        assert_eq!(pos, (15, 2));
+        assert_eq!(field.next_step(&map, pos), None, "at goal");
+    }
+
+    #[test]
+    fn test_cache_builds_on_demand() {
+        let map = TileMap::new(20, 20);
+        let mut cache = FlowFieldCache::new();
+        for _ in 1..FLOW_MIN_DEMAND {
+            assert_eq!(cache.next_step(&map, (10, 10), (2, 2)), None);
+        }
+        assert_eq!(cache.next_step(&map, (10, 10), (2, 2)), Some((3, 3)));
+        assert!(cache.contains((10, 10)));
+    }
+
+    #[test]
+    fn test_lru_evicts_oldest() {
+        let map = TileMap::new(64, 64);
+        let mut cache = FlowFieldCache::new();
+        for i in 0..FLOW_CACHE_SIZE as i32 {
+            cache.insert(FlowField::build(&map, (i, 0)));
+        }
+        // Touch the first goal so the second becomes the oldest.
+        for _ in 0..FLOW_MIN_DEMAND {
+            cache.next_step(&map, (0, 0), (5, 5));
+        }
+        cache.insert(FlowField::build(&map, (0, 40)));
+        assert_eq!(cache.len(), FLOW_CACHE_SIZE);
+        assert!(cache.contains((0, 0)));
+        assert!(!cache.contains((1, 0)));
+        assert!(cache.contains((0, 40)));
+    }
+
+    #[test]
+    fn test_invalidate_drops_covering_fields() {
+        let map = TileMap::new(300, 20);
+        let mut cache = FlowFieldCache::new();
+        cache.insert(FlowField::build(&map, (10, 10)));
+        cache.insert(FlowField::build(&map, (280, 10)));
+        cache.invalidate_tile(20, 5);
+        assert!(!cache.contains((10, 10)));
+        assert!(cache.contains((280, 10)));
+    }
+}
@@ -1483,7 +1483,7 @@ fn rasterize_glyphs(
     }
 
     // Shelf-pack atlas: sort by height descending for better packing
-    raw_glyphs.sort_by(|a, b| b.height.cmp(&a.height));
+    raw_glyphs.sort_by_key(|g| std::cmp::Reverse(g.height));
 
     let atlas_width: u32 = 512;
     let atlas_height: u32 = 4096;
@@ -0,0 +1,596 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::tile_map::{
+    CHUNK_SIZE, IMPASSABLE, PathWorkspace, TERRAIN_COUNT, Terrain, TileMap, find_path_weighted,
+};
+
+/// Goals at least this far away (Chebyshev tiles) are routed through the
+/// chunk graph; anything closer goes straight to flat A*.
+pub const HPA_MIN_DISTANCE: i32 = CHUNK_SIZE as i32;
+
+const CARDINAL_COST: u32 = 100;
+const DIAGONAL_COST: u32 = 141;
+/// Abstract-node expansion cap. The whole of Paris has well under this many
+/// portals, so hitting it means the goal is unreachable.
+const MAX_ABSTRACT_EXPANDED: usize = 65_536;
+
+const START: u64 = u64::MAX - 1;
+const GOAL: u64 = u64::MAX;
+
+/// A chunk-border crossing: `pos` is inside this chunk, `exit` is the
+/// orthogonally adjacent tile in the neighboring chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Portal {
+    pub pos: (i32, i32),
+    pub exit: (i32, i32),
+}
+
+/// Portals of one chunk and the intra-chunk travel costs between them.
+#[derive(Debug, Clone)]
+struct ChunkGraph {
+    portals: Vec<Portal>,
+    /// `edges[i]` = (portal index, cost) reachable from portal `i` without
+    /// leaving the chunk.
+    edges: Vec<Vec<(u16, u32)>>,
+}
+
+/// HPA*-style abstraction over the 64×64 chunk grid.
+///
+/// Each chunk border is scanned for runs of tiles walkable on both sides;
+/// every run becomes one portal pair at its midpoint. Within a chunk,
+/// portals are linked by Dijkstra costs restricted to that chunk, with
+/// doors closed: who may use a door depends on the mover, so the cached
+/// graph only routes through public space. Chunk
+/// graphs are built on first use and cached until `invalidate_tile` marks
+/// them stale, so a terrain edit only rebuilds the chunks it touches.
+#[derive(Debug, Default)]
+pub struct HpaGraph {
+    width: usize,
+    height: usize,
+    chunks_x: usize,
+    chunks_y: usize,
+    /// `TileMap::move_costs` the cached chunk graphs were built with.
+    move_costs: [u32; TERRAIN_COUNT],
+    chunks: Vec<Option<ChunkGraph>>,
+}
+
+impl HpaGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop everything if `map` has different dimensions or terrain costs
+    /// than the cached graph (e.g. after loading a new map).
+    fn ensure_size(&mut self, map: &TileMap) {
+        if self.width != map.width()
+            || self.height != map.height()
+            || self.move_costs != map.move_costs()
+        {
+            self.width = map.width();
+            self.height = map.height();
+            self.move_costs = map.move_costs();
+            self.chunks_x = map.chunks_x();
+            self.chunks_y = map.chunks_y();
+            self.chunks = vec![None; self.chunks_x * self.chunks_y];
+        }
+    }
+
+    /// Mark the chunk containing `(x, y)` stale, plus any neighbor whose
+    /// shared border runs through that tile.
+    pub fn invalidate_tile(&mut self, x: i32, y: i32) {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return;
+        }
+        let (cx, cy) = (x as usize / CHUNK_SIZE, y as usize / CHUNK_SIZE);
+        let (lx, ly) = (x as usize % CHUNK_SIZE, y as usize % CHUNK_SIZE);
+        self.invalidate_chunk(cx, cy);
+        if lx == 0 && cx > 0 {
+            self.invalidate_chunk(cx - 1, cy);
+        }
+        if lx == CHUNK_SIZE - 1 {
+            self.invalidate_chunk(cx + 1, cy);
+        }
+        if ly == 0 && cy > 0 {
+            self.invalidate_chunk(cx, cy - 1);
+        }
+        if ly == CHUNK_SIZE - 1 {
+            self.invalidate_chunk(cx, cy + 1);
+        }
+    }
+
+    fn invalidate_chunk(&mut self, cx: usize, cy: usize) {
+        if cx < self.chunks_x && cy < self.chunks_y {
+            self.chunks[cy * self.chunks_x + cx] = None;
+        }
+    }
+
+    /// Number of chunk graphs currently built.
+    pub fn built_chunks(&self) -> usize {
+        self.chunks.iter().filter(|c| c.is_some()).count()
+    }
+
+    /// Portals of chunk `(cx, cy)`, building its graph if needed.
+    pub fn portals(&mut self, map: &TileMap, cx: usize, cy: usize) -> &[Portal] {
+        self.ensure_size(map);
+        &self.chunk(map, cy * self.chunks_x + cx).portals
+    }
+
+    fn chunk(&mut self, map: &TileMap, ci: usize) -> &ChunkGraph {
+        let (cx, cy) = (ci % self.chunks_x, ci / self.chunks_x);
+        self.chunks[ci].get_or_insert_with(|| build_chunk(map, cx, cy))
+    }
+
+    fn chunk_index(&self, (x, y): (i32, i32)) -> usize {
+        (y as usize / CHUNK_SIZE) * self.chunks_x + x as usize / CHUNK_SIZE
+    }
+}
+
+/// Tile bounds `(x0, y0, x1, y1)` of a chunk, exclusive at the far edge.
+fn chunk_bounds(map: &TileMap, cx: usize, cy: usize) -> (i32, i32, i32, i32) {
+    let x0 = cx * CHUNK_SIZE;
+    let y0 = cy * CHUNK_SIZE;
+    let x1 = (x0 + CHUNK_SIZE).min(map.width());
+    let y1 = (y0 + CHUNK_SIZE).min(map.height());
+    (x0 as i32, y0 as i32, x1 as i32, y1 as i32)
+}
+
+fn build_chunk(map: &TileMap, cx: usize, cy: usize) -> ChunkGraph {
+    let (x0, y0, x1, y1) = chunk_bounds(map, cx, cy);
+    let walkable = |x: i32, y: i32| x >= 0 && y >= 0 && map.is_walkable(x as usize, y as usize);
+
+    let mut portals = Vec::new();
+    // One portal pair per run of open border tiles, at the run's midpoint.
+    let mut scan = |cells: &mut dyn Iterator<Item = ((i32, i32), (i32, i32))>| {
+        let mut run: Vec<((i32, i32), (i32, i32))> = Vec::new();
+        for (pos, exit) in cells.chain(std::iter::once(((-1, -1), (-1, -1)))) {
+            if pos.0 >= 0 && walkable(pos.0, pos.1) && walkable(exit.0, exit.1) {
+                run.push((pos, exit));
+            } else if !run.is_empty() {
+                let (pos, exit) = run[run.len() / 2];
+                portals.push(Portal { pos, exit });
+                run.clear();
+            }
+        }
+    };
+    if x0 > 0 {
+        scan(&mut (y0..y1).map(|y| ((x0, y), (x0 - 1, y))));
+    }
+    if (x1 as usize) < map.width() {
+        scan(&mut (y0..y1).map(|y| ((x1 - 1, y), (x1, y))));
+    }
+    if y0 > 0 {
+        scan(&mut (x0..x1).map(|x| ((x, y0), (x, y0 - 1))));
+    }
+    if (y1 as usize) < map.height() {
+        scan(&mut (x0..x1).map(|x| ((x, y1 - 1), (x, y1))));
+    }
+
+    let edges = portals
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let dist = chunk_dijkstra(map, cx, cy, p.pos, &|x, y| closed_doors(map, x, y));
+            portals
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .filter_map(|(j, q)| {
+                    let d = dist[local_index(q.pos)];
+                    (d != u32::MAX).then_some((j as u16, d))
+                })
+                .collect()
+        })
+        .collect();
+
+    ChunkGraph { portals, edges }
+}
+
+/// Extra cost for the cached chunk graphs: doors are impassable.
+fn closed_doors(map: &TileMap, x: i32, y: i32) -> u32 {
+    if map.get_terrain(x as usize, y as usize) == Some(Terrain::Door) {
+        IMPASSABLE
+    } else {
+        0
+    }
+}
+
+fn local_index((x, y): (i32, i32)) -> usize {
+    (y as usize % CHUNK_SIZE) * CHUNK_SIZE + x as usize % CHUNK_SIZE
+}
+
+/// Dijkstra from `from` over the walkable tiles of one chunk, using the same
+/// terrain step costs, extra costs and diagonal rule as `find_path_weighted`.
+/// `from` itself need not be walkable. Returns costs indexed by
+/// `local_index`; `u32::MAX` = unreachable.
+fn chunk_dijkstra(
+    map: &TileMap,
+    cx: usize,
+    cy: usize,
+    from: (i32, i32),
+    extra_cost: &dyn Fn(i32, i32) -> u32,
+) -> Vec<u32> {
+    const DIRS: [(i32, i32); 8] = [
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ];
+    let (x0, y0, x1, y1) = chunk_bounds(map, cx, cy);
+    let mut dist = vec![u32::MAX; CHUNK_SIZE * CHUNK_SIZE];
+    let mut open: BinaryHeap<Reverse<(u32, i32, i32)>> = BinaryHeap::new();
+    dist[local_index(from)] = 0;
+    open.push(Reverse((0, from.0, from.1)));
+
+    while let Some(Reverse((d, x, y))) = open.pop() {
+        if d > dist[local_index((x, y))] {
+            continue;
+        }
+        for (dx, dy) in DIRS {
+            let (nx, ny) = (x + dx, y + dy);
+            if nx < x0 || nx >= x1 || ny < y0 || ny >= y1 {
+                continue;
+            }
+            if !map.is_walkable(nx as usize, ny as usize) {
+                continue;
+            }
+            let diagonal = dx != 0 && dy != 0;
+            if diagonal && !map.diagonal_clear(x, y, nx, ny) {
+                continue;
+            }
+            let extra = extra_cost(nx, ny);
+            if extra == IMPASSABLE {
+                continue;
+            }
+            let nd = d
+                .saturating_add(map.step_cost(nx, ny, diagonal))
+                .saturating_add(extra);
+            let ni = local_index((nx, ny));
+            if nd < dist[ni] {
+                dist[ni] = nd;
+                open.push(Reverse((nd, nx, ny)));
+            }
+        }
+    }
+    dist
+}
+
+/// Octile distance in A* fixed-point units.
+fn octile(a: (i32, i32), b: (i32, i32)) -> u32 {
+    let dx = (a.0 - b.0).unsigned_abs();
+    let dy = (a.1 - b.1).unsigned_abs();
+    let diag = dx.min(dy);
+    diag * DIAGONAL_COST + (dx.max(dy) - diag) * CARDINAL_COST
+}
+
+/// Portal-to-portal waypoints from `start` to `goal` (inclusive of `goal`,
+/// exclusive of `start`), or None if the chunk graph has no route.
+///
+/// `extra_cost` is charged in the start and goal chunks, on every border
+/// crossing, and across the `hot` chunks, whose portal edges are recomputed
+/// with it for this search; elsewhere the cached edges are used.
+fn abstract_route(
+    map: &TileMap,
+    graph: &mut HpaGraph,
+    start: (i32, i32),
+    goal: (i32, i32),
+    hot: &HashSet<usize>,
+    extra_cost: &dyn Fn(i32, i32) -> u32,
+) -> Option<Vec<(i32, i32)>> {
+    let start_chunk = graph.chunk_index(start);
+    let goal_chunk = graph.chunk_index(goal);
+    let cw = graph.chunks_x;
+    let from_start = chunk_dijkstra(map, start_chunk % cw, start_chunk / cw, start, extra_cost);
+    let to_goal = chunk_dijkstra(map, goal_chunk % cw, goal_chunk / cw, goal, extra_cost);
+
+    let key = |ci: usize, i: usize| ((ci as u64) << 16) | i as u64;
+    let pos_of = |graph: &mut HpaGraph, k: u64| -> (i32, i32) {
+        match k {
+            START => start,
+            GOAL => goal,
+            _ => graph.chunk(map, (k >> 16) as usize).portals[(k & 0xFFFF) as usize].pos,
+        }
+    };
+
+    let mut g: HashMap<u64, u32> = HashMap::new();
+    let mut came_from: HashMap<u64, u64> = HashMap::new();
+    let mut open: BinaryHeap<Reverse<(u32, u64)>> = BinaryHeap::new();
+    g.insert(START, 0);
+    open.push(Reverse((octile(start, goal), START)));
+    let mut expanded = 0;
+
+    while let Some(Reverse((f, k))) = open.pop() {
+        let gk = g[&k];
+        let here = pos_of(graph, k);
+        if f > gk + octile(here, goal) {
+            continue; // stale entry
+        }
+        if k == GOAL {
+            let mut waypoints = Vec::new();
+            let mut n = k;
+            while n != START {
+                waypoints.push(pos_of(graph, n));
+                n = came_from[&n];
+            }
+            waypoints.reverse();
+            return Some(waypoints);
+        }
+        expanded += 1;
+        if expanded > MAX_ABSTRACT_EXPANDED {
+            return None;
+        }
+
+        let mut next: Vec<(u64, u32)> = Vec::new();
+        if k == START {
+            let chunk = graph.chunk(map, start_chunk);
+            for (i, p) in chunk.portals.iter().enumerate() {
+                let d = from_start[local_index(p.pos)];
+                if d != u32::MAX {
+                    next.push((key(start_chunk, i), d));
+                }
+            }
+        } else {
+            let ci = (k >> 16) as usize;
+            let pi = (k & 0xFFFF) as usize;
+            let chunk = graph.chunk(map, ci);
+            let portal = chunk.portals[pi];
+            if hot.contains(&ci) {
+                let dist = chunk_dijkstra(map, ci % cw, ci / cw, portal.pos, extra_cost);
+                next.extend(
+                    chunk
+                        .portals
+                        .iter()
+                        .enumerate()
+                        .filter(|&(j, _)| j != pi)
+                        .map(|(j, q)| (key(ci, j), dist[local_index(q.pos)]))
+                        .filter(|&(_, d)| d != u32::MAX),
+                );
+            } else {
+                next.extend(
+                    chunk.edges[pi]
+                        .iter()
+                        .map(|&(j, d)| (key(ci, j as usize), d)),
+                );
+            }
+            if ci == goal_chunk {
+                let d = to_goal[local_index(portal.pos)];
+                if d != u32::MAX {
+                    next.push((GOAL, d));
+                }
+            }
+            let ni = graph.chunk_index(portal.exit);
+            let mate = graph
+                .chunk(map, ni)
+                .portals
+                .iter()
+                .position(|q| q.pos == portal.exit);
+            let extra = extra_cost(portal.exit.0, portal.exit.1);
+            if let Some(j) = mate
+                && extra != IMPASSABLE
+            {
+                let step = map.step_cost(portal.exit.0, portal.exit.1, false);
+                next.push((key(ni, j), step.saturating_add(extra)));
+            }
+        }
+
+        for (n, cost) in next {
+            let ng = gk.saturating_add(cost);
+            if ng < g.get(&n).copied().unwrap_or(u32::MAX) {
+                g.insert(n, ng);
+                came_from.insert(n, k);
+                let h = octile(pos_of(graph, n), goal);
+                open.push(Reverse((ng + h, n)));
+            }
+        }
+    }
+    None
+}
+
+/// Pathfinding for arbitrary distances. Short trips use `find_path_weighted`
+/// directly; goals at least `HPA_MIN_DISTANCE` away are first routed over
+/// the chunk graph, then each portal-to-portal leg is refined with
+/// `find_path_weighted`. Falls back to flat A* if the abstract route fails.
+///
+/// `hot_tiles` are where `extra_cost` may be large over a wide area (bridge
+/// tolls and closures, danger): the chunks holding them are costed with
+/// `extra_cost` in the abstract route too, so it detours around them the way
+/// flat A* would. Costs elsewhere (doors, congestion) only shape the start
+/// and goal chunks, border crossings and the refined legs.
+pub fn find_path_hpa(
+    map: &TileMap,
+    graph: &mut HpaGraph,
+    start: (i32, i32),
+    goal: (i32, i32),
+    ws: &mut PathWorkspace,
+    hot_tiles: &[(i32, i32)],
+    extra_cost: impl Fn(i32, i32) -> u32,
+) -> Option<Vec<(i32, i32)>> {
+    let far = (start.0 - goal.0).abs().max((start.1 - goal.1).abs()) >= HPA_MIN_DISTANCE;
+    let in_bounds = |(x, y): (i32, i32)| {
+        x >= 0 && y >= 0 && (x as usize) < map.width() && (y as usize) < map.height()
+    };
+    if !far || !in_bounds(start) || !in_bounds(goal) {
+        return find_path_weighted(map, start, goal, ws, extra_cost);
+    }
+    graph.ensure_size(map);
+    let hot: HashSet<usize> = hot_tiles
+        .iter()
+        .filter(|&&t| in_bounds(t))
+        .map(|&t| graph.chunk_index(t))
+        .collect();
+
+    if let Some(waypoints) = abstract_route(map, graph, start, goal, &hot, &extra_cost) {
+        let mut path = Vec::new();
+        let mut from = start;
+        let refined = waypoints.into_iter().all(|wp| {
+            match find_path_weighted(map, from, wp, ws, &extra_cost) {
+                Some(leg) => {
+                    path.extend(leg);
+                    from = wp;
+                    true
+                }
+                None => false,
+            }
+        });
+        if refined {
+            return Some(path);
+        }
+    }
+    find_path_weighted(map, start, goal, ws, extra_cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile_map::Terrain;
+
+    /// Wall column at `x` spanning the whole map height, except `gaps`.
+    fn wall_column(map: &mut TileMap, x: usize, gaps: &[usize]) {
+        for y in 0..map.height() {
+            if !gaps.contains(&y) {
+                map.set_terrain(x, y, Terrain::Wall);
+            }
+        }
+    }
+
+    fn assert_valid(map: &TileMap, start: (i32, i32), path: &[(i32, i32)]) {
+        let mut prev = start;
+        for &(x, y) in path {
+            assert!((x - prev.0).abs() <= 1 && (y - prev.1).abs() <= 1);
+            assert!(map.is_walkable(x as usize, y as usize), "{:?}", (x, y));
+            prev = (x, y);
+        }
+    }
+
+    fn cost(start: (i32, i32), path: &[(i32, i32)]) -> u32 {
+        let mut prev = start;
+        path.iter()
+            .map(|&p| {
+                let c = octile(prev, p);
+                prev = p;
+                c
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_portals_at_run_midpoints() {
+        let mut map = TileMap::new(128, 64);
+        wall_column(&mut map, 63, &[10, 11, 12, 40]);
+        let mut graph = HpaGraph::new();
+        let left = graph.portals(&map, 0, 0).to_vec();
+        let right = graph.portals(&map, 1, 0).to_vec();
+        assert_eq!(
+            left,
+            vec![
+                Portal {
+                    pos: (63, 11),
+                    exit: (64, 11)
+                },
+                Portal {
+                    pos: (63, 40),
+                    exit: (64, 40)
+                },
+            ]
+        );
+        assert_eq!(right.len(), 2);
+        assert!(right.iter().zip(&left).all(|(r, l)| r.exit == l.pos));
+    }
+
+    #[test]
+    fn test_long_route_is_near_optimal() {
+        let mut map = TileMap::new(256, 128);
+        wall_column(&mut map, 70, &[120]);
+        wall_column(&mut map, 150, &[5]);
+        let (start, goal) = ((2, 60), (250, 60));
+        let mut graph = HpaGraph::new();
+        let mut ws = PathWorkspace::new();
+
+        let hpa = find_path_hpa(&map, &mut graph, start, goal, &mut ws, &[], |_, _| 0)
+            .expect("route exists");
+        let flat = find_path_weighted(&map, start, goal, &mut ws, |_, _| 0).expect("flat");
+        assert_valid(&map, start, &hpa);
+        assert_eq!(hpa.last(), Some(&goal));
+        assert!(hpa.contains(&(70, 120)) && hpa.contains(&(150, 5)));
+        assert!(cost(start, &hpa) * 10 <= cost(start, &flat) * 12);
+    }
+
+    #[test]
+    fn test_route_beyond_flat_search_limit() {
+        // Serpentine: horizontal walls every 32 rows, gap alternating ends.
+        let mut map = TileMap::new(512, 512);
+        for (i, y) in (32..512).step_by(32).enumerate() {
+            let gap = if i % 2 == 0 { 510 } else { 1 };
+            for x in 0..512 {
+                if x != gap {
+                    map.set_terrain(x, y, Terrain::Wall);
+                }
+            }
+        }
+        let (start, goal) = ((5, 5), (5, 505));
+        let mut graph = HpaGraph::new();
+        let mut ws = PathWorkspace::new();
+        assert!(find_path_weighted(&map, start, goal, &mut ws, |_, _| 0).is_none());
+
+        let path = find_path_hpa(&map, &mut graph, start, goal, &mut ws, &[], |_, _| 0)
+            .expect("hierarchical route");
+        assert_valid(&map, start, &path);
+        assert_eq!(path.last(), Some(&goal));
+    }
+
+    #[test]
+    fn test_long_route_avoids_costly_crossing() {
+        // A river across the map with two bridges chunks apart: the one on
+        // the straight line carries a toll dearer than the detour.
+        let mut map = TileMap::new(256, 256);
+        wall_column(&mut map, 160, &[30, 220]);
+        let (start, goal) = ((10, 30), (246, 30));
+        let toll = |x, y| if (x, y) == (160, 30) { 30_000 } else { 0 };
+        let mut graph = HpaGraph::new();
+        let mut ws = PathWorkspace::new();
+
+        let path = find_path_hpa(&map, &mut graph, start, goal, &mut ws, &[(160, 30)], toll)
+            .expect("route");
+        assert_valid(&map, start, &path);
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.contains(&(160, 220)) && !path.contains(&(160, 30)));
+
+        let closed = |x, y| if (x, y) == (160, 220) { IMPASSABLE } else { 0 };
+        let path = find_path_hpa(
+            &map,
+            &mut graph,
+            start,
+            goal,
+            &mut ws,
+            &[(160, 220)],
+            closed,
+        )
+        .expect("route");
+        assert!(path.contains(&(160, 30)) && !path.contains(&(160, 220)));
+    }
+
+    #[test]
+    fn test_invalidate_rebuilds_touched_chunks() {
+        let mut map = TileMap::new(192, 64);
+        wall_column(&mut map, 100, &[10, 50]);
+        let (start, goal) = ((2, 30), (190, 30));
+        let mut graph = HpaGraph::new();
+        let mut ws = PathWorkspace::new();
+        let path =
+            find_path_hpa(&map, &mut graph, start, goal, &mut ws, &[], |_, _| 0).expect("route");
+        assert!(path.contains(&(100, 50)) || path.contains(&(100, 10)));
+        let built = graph.built_chunks();
+        assert!(built >= 2);
+
+        map.set_terrain(100, 10, Terrain::Wall);
+        map.set_terrain(100, 50, Terrain::Wall);
+        graph.invalidate_tile(100, 10);
+        graph.invalidate_tile(100, 50);
+        assert_eq!(graph.built_chunks(), built - 1);
+        assert!(find_path_hpa(&map, &mut graph, start, goal, &mut ws, &[], |_, _| 0).is_none());
+    }
+}
@@ -1,15 +1,23 @@
+pub mod aggregate;
 pub mod components;
+pub mod directory;
+pub mod doors;
 pub mod events;
+pub mod faction;
+pub mod flow_field;
 pub mod font;
+pub mod hpa;
 pub mod loading;
 pub mod loading_gis;
 pub mod lod;
 pub mod panel;
 pub mod registry;
+pub mod relationships;
 pub mod render;
 pub mod rng;
 pub mod settings;
 pub mod sprite_renderer;
+pub mod street_graph;
 pub mod systems;
 pub mod tile_map;
 pub mod ui;
@@ -3,8 +3,9 @@ use std::collections::HashMap;
 use rand::RngExt;
 
 use crate::components::*;
+use crate::faction::{AttackResponse, FactionDef, Stance};
 use crate::systems::decisions::UtilityConfig;
-use crate::tile_map::Terrain;
+use crate::tile_map::{TERRAIN_COUNT, Terrain};
 use crate::world::World;
 
 /// Parse a KDL file and return the document. Logs a warning and returns None on failure.
@@ -49,6 +50,11 @@ fn child_f64(children: &kdl::KdlDocument, key: &str) -> Option<f64> {
         .or_else(|| val.as_integer().map(|i| i as f64))
 }
 
+/// Helper to get a bool value from a child node's first argument.
+fn child_bool(children: &kdl::KdlDocument, key: &str) -> Option<bool> {
+    children.get_arg(key)?.as_bool()
+}
+
 fn require_str<'a>(children: &'a kdl::KdlDocument, key: &str, file: &str, node: &str) -> &'a str {
     child_str(children, key).unwrap_or_else(|| panic!("{file}: node '{node}': missing '{key}'"))
 }
@@ -57,6 +63,10 @@ fn require_f64(children: &kdl::KdlDocument, key: &str, file: &str, node: &str) -
     child_f64(children, key).unwrap_or_else(|| panic!("{file}: node '{node}': missing '{key}'"))
 }
 
+fn require_bool(children: &kdl::KdlDocument, key: &str, file: &str, node: &str) -> bool {
+    child_bool(children, key).unwrap_or_else(|| panic!("{file}: node '{node}': missing '{key}'"))
+}
+
 /// Default body/mind stats for spawning entities. Named archetypes live in
 /// `data/archetypes.kdl`; the GIS spawn path looks up the relevant one by name.
 pub struct Archetype {
@@ -67,6 +77,9 @@ pub struct Archetype {
     pub defense: f32,
     pub aggression: f32,
     pub gait_profile: GaitProfile,
+    /// Base chance a spawned resident is a criminal (see
+    /// `systems::crime::roll_disposition`).
+    pub criminal: f32,
 }
 
 impl Default for Archetype {
@@ -79,6 +92,7 @@ impl Default for Archetype {
             defense: 5.0,
             aggression: 0.0,
             gait_profile: GaitProfile::biped(),
+            criminal: 0.0,
         }
     }
 }
@@ -115,6 +129,7 @@ pub fn load_archetypes(path: &str) -> HashMap<String, Archetype> {
         let attack = require_f64(children, "attack", path, name) as f32;
         let defense = require_f64(children, "defense", path, name) as f32;
         let aggression = require_f64(children, "aggression", path, name) as f32;
+        let criminal = require_f64(children, "criminal", path, name) as f32;
         let gaits_str = require_str(children, "gaits", path, name);
         let gait_profile = match gaits_str {
             "quadruped" => GaitProfile::quadruped(),
@@ -131,6 +146,7 @@ pub fn load_archetypes(path: &str) -> HashMap<String, Archetype> {
                 defense,
                 aggression,
                 gait_profile,
+                criminal,
             },
         );
     }
@@ -138,6 +154,58 @@ pub fn load_archetypes(path: &str) -> HashMap<String, Archetype> {
     map
 }
 
+/// Load faction definitions and the stance matrix from a KDL file into
+/// `world.mind.factions`. Stances must name factions declared earlier.
+pub fn load_factions(world: &mut World, path: &str) {
+    let Some(doc) = parse_kdl_file(path) else {
+        return;
+    };
+
+    for node in doc.nodes() {
+        match node.name().to_string().as_str() {
+            "faction" => {
+                let Some(key) = node.get(0).and_then(|v| v.as_string()) else {
+                    continue;
+                };
+                let Some(children) = node.children() else {
+                    panic!("{path}: node '{key}': missing children block (all fields required)");
+                };
+                let when_attacked = match require_str(children, "when_attacked", path, key) {
+                    "fight" => AttackResponse::Fight,
+                    "flee" => AttackResponse::Flee,
+                    other => panic!("{path}: node '{key}': unknown when_attacked '{other}'"),
+                };
+                world.mind.factions.insert(FactionDef {
+                    key: key.to_string(),
+                    name: require_str(children, "name", path, key).to_string(),
+                    local: require_bool(children, "local", path, key),
+                    when_attacked,
+                    calls_for_help: require_bool(children, "calls_for_help", path, key),
+                    defends_allies: require_bool(children, "defends_allies", path, key),
+                });
+            }
+            "stance" => {
+                let arg = |i| node.get(i).and_then(|v: &kdl::KdlValue| v.as_string());
+                let (Some(a), Some(b), Some(s)) = (arg(0), arg(1), arg(2)) else {
+                    panic!("{path}: stance: expects three string arguments");
+                };
+                let stance = match s {
+                    "allied" => Stance::Allied,
+                    "neutral" => Stance::Neutral,
+                    "hostile" => Stance::Hostile,
+                    other => panic!("{path}: stance '{a}' '{b}': unknown stance '{other}'"),
+                };
+                let factions = &mut world.mind.factions;
+                let (Some(fa), Some(fb)) = (factions.get_by_key(a), factions.get_by_key(b)) else {
+                    panic!("{path}: stance '{a}' '{b}': unknown faction");
+                };
+                factions.set_stance(fa, fb, stance);
+            }
+            _ => {}
+        }
+    }
+}
+
 /// Load utility scorer config from a RON file.
 pub fn load_utility_config(world: &mut World, path: &str) {
     let content = match std::fs::read_to_string(path) {
@@ -155,6 +223,36 @@ pub fn load_utility_config(world: &mut World, path: &str) {
     }
 }
 
+/// Load per-terrain movement costs and tile capacities from a KDL file into
+/// `world.tiles`. Call after the map is loaded; terrains without a `cost` or
+/// `capacity` keep the default.
+pub fn load_terrain_costs(world: &mut World, path: &str) {
+    let Some(doc) = parse_kdl_file(path) else {
+        return;
+    };
+
+    for node in doc.nodes() {
+        if node.name().to_string() != "terrain" {
+            continue;
+        }
+        let Some(name) = node.get(0).and_then(|v| v.as_string()) else {
+            continue;
+        };
+        let Some(terrain) = (0..TERRAIN_COUNT as u8)
+            .filter_map(Terrain::from_u8)
+            .find(|t| format!("{t:?}") == name)
+        else {
+            panic!("{path}: unknown terrain '{name}'");
+        };
+        if let Some(cost) = node.children().and_then(|c| child_f64(c, "cost")) {
+            world.tiles.set_move_cost(terrain, cost as u32);
+        }
+        if let Some(cap) = node.children().and_then(|c| child_f64(c, "capacity")) {
+            world.tiles.set_capacity(terrain, cap as u16);
+        }
+    }
+}
+
 /// Load terrain definitions from a KDL file and apply them to the tile map.
 /// This maps terrain names to the Terrain enum and sets a default pattern.
 pub fn load_terrain(world: &mut World, path: &str) {
@@ -210,6 +308,11 @@ mod tests {
         assert_eq!(person.attack, 10.0);
         assert_eq!(person.defense, 5.0);
         assert_eq!(person.aggression, 0.0);
+        assert_eq!(person.criminal, 0.01);
+        assert!(
+            map.contains_key("sergent_de_ville"),
+            "missing police archetype"
+        );
     }
 
     #[test]
@@ -218,6 +321,35 @@ mod tests {
         assert!(map.is_empty());
     }
 
+    #[test]
+    fn test_load_factions_from_file() {
+        let mut world = World::new_with_seed(42);
+        load_factions(&mut world, "data/factions.kdl");
+        let factions = &world.mind.factions;
+        let residents = factions.get_by_key("residents").expect("residents");
+        let police = factions.get_by_key("police").expect("police");
+        let criminals = factions.get_by_key("criminals").expect("criminals");
+        let def = factions.get(residents).expect("residents def");
+        assert!(def.local);
+        assert_eq!(def.when_attacked, AttackResponse::Flee);
+        assert_eq!(factions.stance(police, criminals), Stance::Hostile);
+        assert_eq!(factions.stance(criminals, police), Stance::Hostile);
+        assert_eq!(factions.stance(residents, police), Stance::Allied);
+        assert_eq!(factions.stance(residents, criminals), Stance::Neutral);
+    }
+
+    #[test]
+    fn test_load_terrain_costs_from_file() {
+        let mut world = World::new_with_seed(42);
+        load_terrain_costs(&mut world, "data/terrain.kdl");
+        let tiles = &world.tiles;
+        assert_eq!(tiles.move_cost(Terrain::Road), 100);
+        assert!(tiles.move_cost(Terrain::Door) > tiles.move_cost(Terrain::Road));
+        assert!(tiles.move_cost(Terrain::Garden) > tiles.move_cost(Terrain::Courtyard));
+        assert_eq!(tiles.capacity(Terrain::Door), 1);
+        assert!(tiles.capacity(Terrain::Bridge) < tiles.capacity(Terrain::Road));
+    }
+
     #[test]
     fn test_load_terrain_scatters_variety() {
         let mut world = World::new_with_seed(42);
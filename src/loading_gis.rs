@@ -5,10 +5,14 @@ use std::time::Instant;
 use serde::{Deserialize, Serialize};
 use shapefile::dbase::FieldValue;
 
+use crate::components::Weapon;
+use crate::hpa::HpaGraph;
 use crate::registry::{
-    Address, BlockData, BlockId, BlockRegistry, BuildingData, BuildingId, BuildingRegistry,
-    Occupant, QuartierRegistry, StreetRegistry, estimate_floor_count,
+    Address, BlockData, BlockId, BlockRegistry, BridgeRegistry, BuildingData, BuildingDoors,
+    BuildingId, BuildingRegistry, Occupant, QuartierId, QuartierRegistry, StreetRegistry,
+    estimate_floor_count,
 };
+use crate::street_graph::StreetGraph;
 use crate::tile_map::{Terrain, TileMap};
 use crate::world::World;
 
@@ -858,6 +862,8 @@ pub fn rasterize_paris(
             date_coyec: bldg_ron.date_coyec.clone(),
             floor_count,
             tiles: tile_list,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -976,6 +982,8 @@ pub fn rasterize_paris(
                     date_coyec: bldg_ron.date_coyec.clone(),
                     floor_count,
                     tiles: tile_list,
+                    doors: BuildingDoors::default(),
+                    stairs: None,
                     addresses: Vec::new(),
                     occupants_by_year: HashMap::new(),
                 });
@@ -1038,6 +1046,8 @@ pub fn rasterize_paris(
                     date_coyec: bldg_ron.date_coyec.clone(),
                     floor_count: 0, // minor feature, no floors
                     tiles: tile_list,
+                    doors: BuildingDoors::default(),
+                    stairs: None,
                     addresses: Vec::new(),
                     occupants_by_year: HashMap::new(),
                 });
@@ -1096,7 +1106,16 @@ pub fn rasterize_paris(
 pub fn apply_paris_ron(world: &mut World, data: ParisMapRon) {
     let (tiles, buildings, blocks, quartier_names) = rasterize_paris(&data);
     world.tiles = tiles;
+    world.hpa = HpaGraph::new();
+    world.flow_fields.clear();
     world.gis.buildings = buildings;
+    world.gis.buildings.assign_doors(
+        &world.tiles,
+        world.gis.active_year,
+        world.gis.year_tolerance,
+    );
+    world.gis.buildings.assign_stairs(&world.tiles);
+    world.gis.bridges = BridgeRegistry::build_from_tiles(&world.tiles);
     world.gis.blocks = blocks;
     world.gis.quartier_names = quartier_names;
     world.gis.quartiers = QuartierRegistry::build_from_registries(
@@ -1105,6 +1124,12 @@ pub fn apply_paris_ron(world: &mut World, data: ParisMapRon) {
         &world.gis.blocks,
         world.gis.active_year,
     );
+    world.gis.aggregates = crate::aggregate::seed_all(
+        &world.gis.quartiers,
+        &world.gis.buildings,
+        world.gis.active_year,
+        world.gis.year_tolerance,
+    );
 }
 
 // --- Address + Occupant loading (A07) ---
@@ -1533,7 +1558,7 @@ pub fn load_occupants(gpkg_path: &str, buildings: &mut BuildingRegistry) {
 
     // Top-10 unmatched streets
     let mut unmatched_sorted: Vec<(String, usize)> = unmatched_streets.into_iter().collect();
-    unmatched_sorted.sort_by(|a, b| b.1.cmp(&a.1));
+    unmatched_sorted.sort_by_key(|s| std::cmp::Reverse(s.1));
     if !unmatched_sorted.is_empty() {
         log::info!("Top unmatched streets:");
         for (street, count) in unmatched_sorted.iter().take(10) {
@@ -1726,6 +1751,8 @@ pub fn load_paris_binary(world: &mut World, tiles_path: &str, meta_path: &str) {
     let (tilemap, tiles_uuid) = TileMap::read_binary(tiles_path)
         .unwrap_or_else(|e| panic!("Failed to read {tiles_path}: {e}"));
     world.tiles = tilemap;
+    world.hpa = HpaGraph::new();
+    world.flow_fields.clear();
     log::info!(
         "  Binary tiles loaded in {:.1}s ({}×{})",
         tile_start.elapsed().as_secs_f64(),
@@ -1796,6 +1823,30 @@ pub fn load_paris_binary(world: &mut World, tiles_path: &str, meta_path: &str) {
         world.gis.streets.streets.len()
     );
 
+    // Door ownership and access classes from Door tiles + occupants,
+    // stairs for upper storeys
+    world.gis.buildings.assign_doors(
+        &world.tiles,
+        world.gis.active_year,
+        world.gis.year_tolerance,
+    );
+    world.gis.buildings.assign_stairs(&world.tiles);
+
+    // Seine crossings from Bridge tiles
+    world.gis.bridges = BridgeRegistry::build_from_tiles(&world.tiles);
+    log::info!("  {} bridges", world.gis.bridges.len());
+
+    // Extract the street centerline graph from Road/Bridge tiles
+    let graph_start = Instant::now();
+    world.gis.street_graph =
+        StreetGraph::build(&world.tiles, &world.gis.buildings, &world.gis.streets);
+    log::info!(
+        "  Street graph: {} nodes, {} segments in {:.1}s",
+        world.gis.street_graph.nodes.len(),
+        world.gis.street_graph.segments.len(),
+        graph_start.elapsed().as_secs_f64()
+    );
+
     // Build quartier aggregates from building + block registries
     world.gis.quartiers = QuartierRegistry::build_from_registries(
         &world.gis.quartier_names,
@@ -1803,6 +1854,12 @@ pub fn load_paris_binary(world: &mut World, tiles_path: &str, meta_path: &str) {
         &world.gis.blocks,
         world.gis.active_year,
     );
+    world.gis.aggregates = crate::aggregate::seed_all(
+        &world.gis.quartiers,
+        &world.gis.buildings,
+        world.gis.active_year,
+        world.gis.year_tolerance,
+    );
     let with_buildings = world
         .gis
         .quartiers
@@ -1822,22 +1879,172 @@ pub fn load_paris_binary(world: &mut World, tiles_path: &str, meta_path: &str) {
     );
 }
 
+/// Which quartiers a new game populates, and how.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnOptions {
+    /// Quartiers to populate by name; `None` populates every quartier and
+    /// leaves the LOD zones to fold the ones away from the camera.
+    pub quartiers: Option<Vec<String>>,
+    /// Years from `active_year` a building's nearest directory listing may
+    /// be. Becomes `GisTables::year_tolerance`.
+    pub year_tolerance: u16,
+    /// Share of listed residents spawned (1.0 = all), for performance
+    /// testing. The rest stay counted in their quartier's aggregate.
+    pub sample_ratio: f64,
+}
+
+impl Default for SpawnOptions {
+    fn default() -> Self {
+        Self {
+            quartiers: Some(vec!["Arcis".to_string()]),
+            year_tolerance: crate::registry::DEFAULT_YEAR_TOLERANCE,
+            sample_ratio: 1.0,
+        }
+    }
+}
+
+impl SpawnOptions {
+    /// Read new-game options from command-line arguments:
+    /// `--quartiers all` or `--quartiers Arcis,Louvre`, `--year-tolerance N`
+    /// and `--sample R` (0.0–1.0). Unknown or malformed arguments are
+    /// ignored with a warning and leave the default.
+    pub fn from_args(args: &[String]) -> Self {
+        let mut opts = Self::default();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let flag = arg.as_str();
+            if !matches!(flag, "--quartiers" | "--year-tolerance" | "--sample") {
+                continue;
+            }
+            let Some(value) = iter.next() else {
+                log::warn!("{flag} needs a value");
+                break;
+            };
+            match flag {
+                "--quartiers" if value == "all" => opts.quartiers = None,
+                "--quartiers" => {
+                    opts.quartiers = Some(
+                        value
+                            .split(',')
+                            .map(|q| q.trim().to_string())
+                            .filter(|q| !q.is_empty())
+                            .collect(),
+                    );
+                }
+                "--year-tolerance" => match value.parse() {
+                    Ok(n) => opts.year_tolerance = n,
+                    Err(_) => log::warn!("--year-tolerance: bad value '{value}'"),
+                },
+                _ => match value.parse::<f64>() {
+                    Ok(r) if (0.0..=1.0).contains(&r) => opts.sample_ratio = r,
+                    _ => log::warn!("--sample: expected 0.0–1.0, got '{value}'"),
+                },
+            }
+        }
+        opts
+    }
+
+    /// Year tolerances the new-game menu cycles through.
+    pub const YEAR_TOLERANCES: [u16; 4] = [5, 10, 20, 40];
+    /// Sampling ratios the new-game menu cycles through.
+    pub const SAMPLE_RATIOS: [f64; 4] = [1.0, 0.5, 0.25, 0.1];
+
+    /// Menu label for the quartier choice: the names, or "All quartiers".
+    pub fn quartiers_label(&self) -> String {
+        match &self.quartiers {
+            Some(names) => names.join(", "),
+            None => "All quartiers".to_string(),
+        }
+    }
+
+    /// Step the quartier choice for the new-game menu: through each of
+    /// `names` alone, then all of them, then back to the first.
+    pub fn next_quartiers(&mut self, names: &[String]) {
+        self.quartiers = match &self.quartiers {
+            None => names.first().map(|n| vec![n.clone()]),
+            Some(current) => {
+                let next = match current.as_slice() {
+                    [one] => names.iter().position(|n| n == one).map_or(0, |i| i + 1),
+                    _ => 0,
+                };
+                names.get(next).map(|n| vec![n.clone()])
+            }
+        };
+    }
+
+    /// Step to the next of `YEAR_TOLERANCES`, wrapping.
+    pub fn next_year_tolerance(&mut self) {
+        let i = Self::YEAR_TOLERANCES
+            .iter()
+            .position(|&t| t == self.year_tolerance)
+            .map_or(0, |i| (i + 1) % Self::YEAR_TOLERANCES.len());
+        self.year_tolerance = Self::YEAR_TOLERANCES[i];
+    }
+
+    /// Step to the next of `SAMPLE_RATIOS`, wrapping.
+    pub fn next_sample_ratio(&mut self) {
+        let i = Self::SAMPLE_RATIOS
+            .iter()
+            .position(|&r| r == self.sample_ratio)
+            .map_or(0, |i| (i + 1) % Self::SAMPLE_RATIOS.len());
+        self.sample_ratio = Self::SAMPLE_RATIOS[i];
+    }
+}
+
+/// Populate a new game: apply `opts.year_tolerance` and `opts.sample_ratio`,
+/// reclassify doors and reseed the quartier aggregates, then spawn the chosen quartiers (in `QuartierId`
+/// order when spawning all).
+pub fn spawn_population(
+    world: &mut World,
+    opts: &SpawnOptions,
+    archetype: &crate::loading::Archetype,
+) {
+    world.gis.year_tolerance = opts.year_tolerance;
+    world.gis.sample_ratio = opts.sample_ratio;
+    world.gis.buildings.assign_doors(
+        &world.tiles,
+        world.gis.active_year,
+        world.gis.year_tolerance,
+    );
+    world.gis.aggregates = crate::aggregate::seed_all(
+        &world.gis.quartiers,
+        &world.gis.buildings,
+        world.gis.active_year,
+        world.gis.year_tolerance,
+    );
+    let names = match &opts.quartiers {
+        Some(names) => names.clone(),
+        None => world.gis.quartier_names.clone(),
+    };
+    for name in &names {
+        spawn_gis_entities(world, name, archetype, opts.sample_ratio);
+    }
+    log::info!(
+        "Population: {} entities in {} quartiers",
+        world.alive.len(),
+        names.len()
+    );
+}
+
 /// Spawn simulation entities from SoDUCo directory data.
 ///
 /// For each known occupant in the target quartier, creates an entity with full
-/// component set, positioned on a floor tile of their building.
+/// component set, positioned on a floor tile of their building. Each listed
+/// name is spawned with probability `sample_ratio`.
 pub fn spawn_gis_entities(
     world: &mut World,
     target_quartier: &str,
     archetype: &crate::loading::Archetype,
+    sample_ratio: f64,
 ) {
-    use crate::components::*;
     use rand::RngExt;
 
-    type SpawnEntry = (BuildingId, Vec<(i32, i32)>, Vec<Occupant>);
+    /// Building, its floor tiles, occupants, and storeys above ground.
+    type SpawnEntry = (BuildingId, Vec<(i32, i32)>, Vec<Occupant>, u8);
 
     let start = std::time::Instant::now();
     let active_year = world.gis.active_year;
+    let year_tolerance = world.gis.year_tolerance;
 
     // Phase 1 — Collect spawn data (immutable borrows only).
     let mut spawn_data: Vec<SpawnEntry> = Vec::new();
@@ -1852,7 +2059,8 @@ pub fn spawn_gis_entities(
         }
         buildings_in_quartier += 1;
 
-        let Some((_year, occupants)) = building.occupants_nearest(active_year, 20) else {
+        let Some((_year, occupants)) = building.occupants_nearest(active_year, year_tolerance)
+        else {
             continue;
         };
         buildings_with_occupants += 1;
@@ -1876,14 +2084,21 @@ pub fn spawn_gis_entities(
             continue;
         }
 
-        spawn_data.push((building.id, floor_tiles, occupants.to_vec()));
+        let upper_storeys = if building.stairs.is_some() {
+            building.floor_count.saturating_sub(1)
+        } else {
+            0
+        };
+        spawn_data.push((building.id, floor_tiles, occupants.to_vec(), upper_storeys));
     }
 
     // Phase 2 — Spawn entities (mutable world).
     let mut entities_spawned: u32 = 0;
     let mut empty_names_skipped: u32 = 0;
+    let mut unsampled: u32 = 0;
 
-    for (building_id, floor_tiles, occupants) in &spawn_data {
+    for (building_id, floor_tiles, occupants, upper_storeys) in &spawn_data {
+        let mut residents_placed: u32 = 0;
         for occupant in occupants {
             for raw_name in occupant.name.split(',') {
                 let name = raw_name.trim();
@@ -1891,84 +2106,33 @@ pub fn spawn_gis_entities(
                     empty_names_skipped += 1;
                     continue;
                 }
-
-                let e = world.spawn();
+                if sample_ratio < 1.0 && world.rng.random::<f64>() >= sample_ratio {
+                    unsampled += 1;
+                    continue;
+                }
 
                 // Position: random floor tile (deterministic via world.rng)
                 let idx = world.rng.random_range(0..floor_tiles.len());
                 let (x, y) = floor_tiles[idx];
 
-                // Body tables
-                world.body.names.insert(
-                    e,
-                    Name {
-                        value: name.to_string(),
-                    },
-                );
-                world.body.icons.insert(e, Icon { ch: archetype.icon });
-                world.body.positions.insert(e, Position { x, y });
-                world.body.healths.insert(
-                    e,
-                    Health {
-                        current: archetype.health,
-                        max: archetype.health,
-                    },
-                );
-                world.body.fatigues.insert(e, Fatigue { current: 0.0 });
-                world.body.combat_stats.insert(
-                    e,
-                    CombatStats {
-                        attack: archetype.attack,
-                        defense: archetype.defense,
-                        aggression: archetype.aggression,
-                    },
-                );
-                world
-                    .body
-                    .gait_profiles
-                    .insert(e, archetype.gait_profile.clone());
-                world.body.current_gaits.insert(e, Gait::Walk);
-                world
-                    .body
-                    .move_cooldowns
-                    .insert(e, MoveCooldown { remaining: 0 });
-
-                // Mind tables
-                world.mind.hungers.insert(
-                    e,
-                    Hunger {
-                        current: 0.0,
-                        max: archetype.max_hunger,
-                    },
-                );
-                world.mind.action_states.insert(
-                    e,
-                    ActionState {
-                        current_action: None,
-                        ticks_in_action: 0,
-                        cooldowns: HashMap::new(),
-                    },
-                );
-                world.mind.occupations.insert(
-                    e,
-                    Occupation {
-                        activity: occupant.activity.clone(),
-                        naics: occupant.naics.clone(),
-                    },
+                // Apartments fill the upper storeys in turn; the ground
+                // floor is left to shops and workshops.
+                let storey = if *upper_storeys > 0 {
+                    1 + (residents_placed % u32::from(*upper_storeys)) as u8
+                } else {
+                    0
+                };
+                residents_placed += 1;
+
+                spawn_resident(
+                    world,
+                    archetype,
+                    name,
+                    occupant,
+                    *building_id,
+                    (x, y),
+                    storey,
                 );
-
-                // GIS tables
-                world
-                    .gis
-                    .home_buildings
-                    .insert(e, HomeBuilding(*building_id));
-                world.gis.workplaces.insert(e, Workplace(*building_id));
-
-                // Event
-                world.events.push(crate::events::Event::Spawned {
-                    entity: e,
-                    tick: world.tick,
-                });
                 entities_spawned += 1;
             }
         }
@@ -1983,8 +2147,342 @@ pub fn spawn_gis_entities(
          {buildings_with_occupants} with occupants ({occupant_records} records), \
          {buildings_skipped_no_floors} skipped (no floors), \
          {entities_spawned} entities spawned \
-         ({empty_names_skipped} empty names skipped) [{ms}ms]"
+         ({empty_names_skipped} empty names, {unsampled} unsampled skipped) [{ms}ms]"
+    );
+}
+
+/// Spawn one directory resident of `building_id` at `pos` on `storey`:
+/// full archetype component set, home and workplace links, a criminal
+/// disposition by chance, and one fewer unseen resident in the quartier's
+/// aggregate.
+pub(crate) fn spawn_resident(
+    world: &mut World,
+    archetype: &crate::loading::Archetype,
+    name: &str,
+    occupant: &Occupant,
+    building_id: BuildingId,
+    pos: (i32, i32),
+    storey: u8,
+) -> crate::components::Entity {
+    use crate::components::*;
+
+    let residents = world.mind.factions.get_by_key("residents");
+    let quartier_id = world
+        .gis
+        .buildings
+        .get(building_id)
+        .and_then(|b| world.gis.quartiers.get_by_name(&b.quartier))
+        .map(|q| q.id);
+    let e = spawn_person(world, archetype, name, occupant, pos);
+
+    // Now an entity, no longer one of the aggregate's unseen.
+    if let Some(agg) = quartier_id.and_then(|q| world.gis.aggregates.get_mut(&q)) {
+        agg.remove_resident(&occupant.naics);
+    }
+    if let Some(faction) = residents {
+        world.mind.faction_members.insert(
+            e,
+            FactionMember {
+                faction,
+                quartier: quartier_id,
+            },
+        );
+    }
+    if let Some(crime) = crate::systems::crime::roll_disposition(world, archetype, occupant) {
+        world.mind.criminals.insert(
+            e,
+            Criminal {
+                crime,
+                last_offence: None,
+            },
+        );
+    }
+
+    // GIS tables
+    world
+        .gis
+        .home_buildings
+        .insert(e, HomeBuilding(building_id));
+    world.gis.workplaces.insert(e, Workplace(building_id));
+    if storey > 0 {
+        world.body.storeys.insert(e, Storey(storey));
+        world.gis.home_storeys.insert(e, Storey(storey));
+    }
+    e
+}
+
+/// Words in a listed activity that arm its trade, checked in order: the
+/// butcher's knife, the gunsmith's musket, the rentier's cane.
+const TRADE_WEAPONS: [(&str, Weapon); 10] = [
+    ("boucher", Weapon::Knife),
+    ("charcutier", Weapon::Knife),
+    ("coutelier", Weapon::Knife),
+    ("armurier", Weapon::Musket),
+    ("arquebusier", Weapon::Musket),
+    ("rentier", Weapon::Cane),
+    ("propriétaire", Weapon::Cane),
+    ("avocat", Weapon::Cane),
+    ("notaire", Weapon::Cane),
+    ("médecin", Weapon::Cane),
+];
+
+/// Weapon a member of `activity`'s trade carries, if any (`TRADE_WEAPONS`).
+pub fn trade_weapon(activity: &str) -> Option<Weapon> {
+    let activity = activity.to_lowercase();
+    TRADE_WEAPONS
+        .iter()
+        .find(|(word, _)| activity.contains(word))
+        .map(|&(_, weapon)| weapon)
+}
+
+/// Spawn a person at street level with the archetype's body and mind
+/// components, `occupant`'s trade and the weapon that trade carries.
+/// Callers add faction and GIS links.
+fn spawn_person(
+    world: &mut World,
+    archetype: &crate::loading::Archetype,
+    name: &str,
+    occupant: &Occupant,
+    (x, y): (i32, i32),
+) -> crate::components::Entity {
+    use crate::components::*;
+
+    let e = world.spawn();
+
+    // Body tables
+    world.body.names.insert(
+        e,
+        Name {
+            value: name.to_string(),
+        },
+    );
+    world.body.icons.insert(e, Icon { ch: archetype.icon });
+    world.body.positions.insert(e, Position { x, y });
+    world.body.healths.insert(
+        e,
+        Health {
+            current: archetype.health,
+            max: archetype.health,
+        },
+    );
+    world.body.fatigues.insert(e, Fatigue { current: 0.0 });
+    world.body.combat_stats.insert(
+        e,
+        CombatStats {
+            attack: archetype.attack,
+            defense: archetype.defense,
+            aggression: archetype.aggression,
+        },
+    );
+    world
+        .body
+        .gait_profiles
+        .insert(e, archetype.gait_profile.clone());
+    world.body.current_gaits.insert(e, Gait::Walk);
+    world
+        .body
+        .move_cooldowns
+        .insert(e, MoveCooldown { remaining: 0 });
+
+    // Mind tables
+    world.mind.hungers.insert(
+        e,
+        Hunger {
+            current: 0.0,
+            max: archetype.max_hunger,
+        },
+    );
+    world.mind.action_states.insert(
+        e,
+        ActionState {
+            current_action: None,
+            ticks_in_action: 0,
+            cooldowns: HashMap::new(),
+        },
+    );
+    world.mind.occupations.insert(
+        e,
+        Occupation {
+            activity: occupant.activity.clone(),
+            naics: occupant.naics.clone(),
+        },
+    );
+    if let Some(weapon) = trade_weapon(&occupant.activity) {
+        world.body.weapons.insert(e, weapon);
+    }
+
+    // Event
+    world.events.push(crate::events::Event::Spawned {
+        entity: e,
+        tick: world.tick,
+    });
+    e
+}
+
+/// Sergents de ville stationed at each police post.
+pub const POLICE_PER_POST: usize = 4;
+
+/// Sapeurs-pompiers stationed at each fire station.
+pub const FIREFIGHTERS_PER_STATION: usize = 6;
+
+/// Station `POLICE_PER_POST` sergents de ville at every commissariat (see
+/// `BuildingRegistry::commissariats`) in the quartiers `opts` spawns: police
+/// faction, armed with a sabre, the commissariat as workplace and no home.
+/// Returns how many were spawned.
+pub fn spawn_police(
+    world: &mut World,
+    opts: &SpawnOptions,
+    archetype: &crate::loading::Archetype,
+) -> usize {
+    let officer = Occupant {
+        name: "Sergent de ville".into(),
+        activity: "sergent de ville".into(),
+        naics: "922120".into(),
+    };
+    let posts = world.gis.buildings.commissariats();
+    let spawned = station(
+        world,
+        opts,
+        archetype,
+        posts,
+        "police",
+        &officer,
+        POLICE_PER_POST,
+        Some(Weapon::Sabre),
+    );
+    log::info!("Police: {spawned} sergents de ville stationed");
+    spawned
+}
+
+/// Station `FIREFIGHTERS_PER_STATION` sapeurs-pompiers at every fire
+/// station (see `BuildingRegistry::fire_stations`) in the quartiers `opts`
+/// spawns: pompiers faction, unarmed, the station as workplace and no home.
+/// Returns how many were spawned.
+pub fn spawn_firefighters(
+    world: &mut World,
+    opts: &SpawnOptions,
+    archetype: &crate::loading::Archetype,
+) -> usize {
+    let sapeur = Occupant {
+        name: "Sapeur-pompier".into(),
+        activity: "sapeur-pompier".into(),
+        naics: "922160".into(),
+    };
+    let stations = world.gis.buildings.fire_stations();
+    let spawned = station(
+        world,
+        opts,
+        archetype,
+        stations,
+        "pompiers",
+        &sapeur,
+        FIREFIGHTERS_PER_STATION,
+        None,
+    );
+    log::info!("Fire brigade: {spawned} sapeurs-pompiers stationed");
+    spawned
+}
+
+/// Spawn `per_post` `occupant`s on random Floor tiles of each of `posts` in
+/// the quartiers `opts` spawns, as members of faction `faction_key` (scoped
+/// to the post's quartier) working there. Returns how many were spawned.
+#[allow(clippy::too_many_arguments)]
+fn station(
+    world: &mut World,
+    opts: &SpawnOptions,
+    archetype: &crate::loading::Archetype,
+    posts: Vec<BuildingId>,
+    faction_key: &str,
+    occupant: &Occupant,
+    per_post: usize,
+    weapon: Option<Weapon>,
+) -> usize {
+    use crate::components::*;
+    use rand::RngExt;
+
+    let Some(faction) = world.mind.factions.get_by_key(faction_key) else {
+        log::warn!("station: no '{faction_key}' faction defined");
+        return 0;
+    };
+    let posts: Vec<(BuildingId, Vec<(i32, i32)>)> = posts
+        .into_iter()
+        .filter_map(|bid| world.gis.buildings.get(bid))
+        .filter(|b| {
+            opts.quartiers
+                .as_ref()
+                .is_none_or(|names| names.contains(&b.quartier))
+        })
+        .map(|b| {
+            let floors = b
+                .tiles
+                .iter()
+                .copied()
+                .filter(|&(x, y)| {
+                    world.tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Floor)
+                })
+                .collect();
+            (b.id, floors)
+        })
+        .collect();
+
+    let mut spawned = 0;
+    for (bid, floors) in posts {
+        if floors.is_empty() {
+            continue;
+        }
+        let quartier = world
+            .gis
+            .buildings
+            .get(bid)
+            .and_then(|b| world.gis.quartiers.get_by_name(&b.quartier))
+            .map(|q| q.id);
+        for _ in 0..per_post {
+            let pos = floors[world.rng.random_range(0..floors.len())];
+            let e = spawn_person(world, archetype, &occupant.name, occupant, pos);
+            world
+                .mind
+                .faction_members
+                .insert(e, FactionMember { faction, quartier });
+            if let Some(w) = weapon {
+                world.body.weapons.insert(e, w);
+            }
+            world.gis.workplaces.insert(e, Workplace(bid));
+            spawned += 1;
+        }
+    }
+    spawned
+}
+
+/// Spawn one insurgent of a riot in `quartier` at `pos`: insurgés faction
+/// (scoped to the quartier), `aggression` in place of the archetype's, no
+/// home or workplace. None without an "insurges" faction.
+pub(crate) fn spawn_rioter(
+    world: &mut World,
+    archetype: &crate::loading::Archetype,
+    quartier: QuartierId,
+    aggression: f32,
+    pos: (i32, i32),
+) -> Option<crate::components::Entity> {
+    use crate::components::*;
+
+    let faction = world.mind.factions.get_by_key("insurges")?;
+    let insurgent = Occupant {
+        name: "Insurgé".into(),
+        activity: "insurgé".into(),
+        naics: String::new(),
+    };
+    let e = spawn_person(world, archetype, &insurgent.name, &insurgent, pos);
+    world.mind.faction_members.insert(
+        e,
+        FactionMember {
+            faction,
+            quartier: Some(quartier),
+        },
     );
+    if let Some(cs) = world.body.combat_stats.get_mut(&e) {
+        cs.aggression = aggression;
+    }
+    Some(e)
 }
 
 /// Classify building tiles into Wall vs Floor.
@@ -2953,7 +3451,7 @@ pub fn place_doors(tiles: &mut TileMap, buildings: &BuildingRegistry) {
 
         // Group into facade runs, sort by length (longest = most significant).
         let mut runs = detect_facade_runs(&candidates, &candidate_facing);
-        runs.sort_by(|a, b| b.tiles.len().cmp(&a.tiles.len()));
+        runs.sort_by_key(|r| std::cmp::Reverse(r.tiles.len()));
 
         let has_road_runs = runs.iter().any(|r| r.road_facing);
         let has_courtyard_runs = runs.iter().any(|r| r.courtyard_facing);
@@ -3687,6 +4185,8 @@ mod tests {
             date_coyec: None,
             floor_count: 3,
             tiles: tile_list,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -3741,6 +4241,8 @@ mod tests {
             date_coyec: None,
             floor_count: 3,
             tiles: tile_list,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -3815,6 +4317,8 @@ mod tests {
             date_coyec: None,
             floor_count: 3,
             tiles: tile_list,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -3874,6 +4378,8 @@ mod tests {
             date_coyec: None,
             floor_count: 1,
             tiles: tile_list,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -3936,6 +4442,8 @@ mod tests {
             date_coyec: None,
             floor_count: 3,
             tiles: tile_list,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -4045,6 +4553,8 @@ mod tests {
             date_coyec: None,
             floor_count: 1,
             tiles: tiles1,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -4073,6 +4583,8 @@ mod tests {
             date_coyec: None,
             floor_count: 1,
             tiles: tiles2,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -4152,6 +4664,8 @@ mod tests {
             date_coyec: None,
             floor_count: 1,
             tiles: tiles2,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -4180,6 +4694,8 @@ mod tests {
             date_coyec: None,
             floor_count: 1,
             tiles: tiles1,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -4224,6 +4740,8 @@ mod tests {
             date_coyec: None,
             floor_count: 2,
             tiles: tiles2,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -4251,6 +4769,8 @@ mod tests {
             date_coyec: None,
             floor_count: 1,
             tiles: tiles1,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -4328,6 +4848,8 @@ mod tests {
             date_coyec: None,
             floor_count: 1,
             tiles: btiles,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -4388,6 +4910,8 @@ mod tests {
             date_coyec: None,
             floor_count: 1,
             tiles: btiles,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -4437,6 +4961,8 @@ mod tests {
             date_coyec: None,
             floor_count: 2,
             tiles: tile_list,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -5019,6 +5545,8 @@ mod tests {
             date_coyec: None,
             floor_count: 3,
             tiles: vec![(10, 20)],
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: vec![Address {
                 street_name: "Rue du Temple".into(),
                 house_number: "12".into(),
@@ -5335,6 +5863,8 @@ mod tests {
             date_coyec: None,
             floor_count: 1,
             tiles,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year,
         };
@@ -5345,7 +5875,7 @@ mod tests {
 
         let archetypes = crate::loading::load_archetypes("data/archetypes.kdl");
         let archetype = &archetypes["person"];
-        spawn_gis_entities(&mut world, "TestQ", archetype);
+        spawn_gis_entities(&mut world, "TestQ", archetype, 1.0);
 
         // "Dupont" → 1, "Lefèvre, Martin" → 2 (comma split), "" → 0 (skipped)
         assert_eq!(world.alive.len(), 3);
@@ -5427,12 +5957,14 @@ mod tests {
             date_coyec: None,
             floor_count: 1,
             tiles: tiles2,
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: occupants_by_year2,
         };
         world2.gis.buildings.insert(building2);
 
-        spawn_gis_entities(&mut world2, "TestQ", archetype);
+        spawn_gis_entities(&mut world2, "TestQ", archetype, 1.0);
 
         // Same entity positions as world1.
         let mut positions1: Vec<(i32, i32)> =
@@ -5444,6 +5976,62 @@ mod tests {
         assert_eq!(positions1, positions2, "deterministic replay failed");
     }
 
+    #[test]
+    fn test_spawn_gis_entities_fills_upper_storeys() {
+        let mut world = crate::world::World::new_with_seed(42);
+        world.tiles = TileMap::new(20, 20);
+        let bid = BuildingId(1);
+        let mut tiles = Vec::new();
+        for y in 5..8 {
+            for x in 5..8 {
+                world.tiles.set_terrain(x, y, Terrain::Floor);
+                world.tiles.set_building_id(x, y, bid);
+                tiles.push((x as i32, y as i32));
+            }
+        }
+        let mut occupants_by_year = HashMap::new();
+        occupants_by_year.insert(
+            1845,
+            vec![Occupant {
+                name: "Dupont, Martin, Leroy".to_string(),
+                activity: "rentier".to_string(),
+                naics: "531".to_string(),
+            }],
+        );
+        world.gis.buildings.insert(BuildingData {
+            identif: 100,
+            quartier: "TestQ".to_string(),
+            num_ilot: "001".to_string(),
+            floor_count: 3,
+            occupants_by_year,
+            ..BuildingData::test_default(bid, tiles)
+        });
+        world.gis.buildings.assign_stairs(&world.tiles);
+        assert!(
+            world
+                .gis
+                .buildings
+                .get(bid)
+                .expect("building")
+                .stairs
+                .is_some()
+        );
+
+        let archetypes = crate::loading::load_archetypes("data/archetypes.kdl");
+        spawn_gis_entities(&mut world, "TestQ", &archetypes["person"], 1.0);
+
+        let mut storeys: Vec<u8> = world.alive.iter().map(|&e| world.storey_of(e)).collect();
+        storeys.sort();
+        assert_eq!(storeys, vec![1, 1, 2], "apartments fill storeys in turn");
+        for e in &world.alive {
+            assert_eq!(
+                world.gis.home_storeys.get(e).map(|s| s.0),
+                Some(world.storey_of(*e))
+            );
+        }
+        crate::world::validate_world(&world);
+    }
+
     #[test]
     fn test_spawn_gis_entities_unknown_quartier() {
         let mut world = crate::world::World::new_with_seed(42);
@@ -5451,8 +6039,173 @@ mod tests {
 
         // No buildings at all — should produce 0 entities.
         let archetype = crate::loading::Archetype::default();
-        spawn_gis_entities(&mut world, "Nonexistent", &archetype);
+        spawn_gis_entities(&mut world, "Nonexistent", &archetype, 1.0);
+        assert!(world.alive.is_empty());
+        crate::world::validate_world(&world);
+    }
+
+    #[test]
+    fn test_trade_weapon_arms_civilians() {
+        use crate::components::Fatigue;
+
+        assert_eq!(trade_weapon("Boucher"), Some(Weapon::Knife));
+        assert_eq!(trade_weapon("arquebusier"), Some(Weapon::Musket));
+        assert_eq!(trade_weapon("rentier"), Some(Weapon::Cane));
+        assert_eq!(trade_weapon("tailleur"), None);
+
+        let mut world = crate::world::World::new_with_seed(42);
+        let archetypes = crate::loading::load_archetypes("data/archetypes.kdl");
+        let archetype = &archetypes["person"];
+        let resident = |world: &mut World, name: &str, activity: &str| {
+            let occupant = Occupant {
+                name: name.into(),
+                activity: activity.into(),
+                naics: String::new(),
+            };
+            spawn_resident(world, archetype, name, &occupant, BuildingId(1), (5, 5), 0)
+        };
+        let butcher = resident(&mut world, "Roux", "boucher");
+        let tailor = resident(&mut world, "Blanc", "tailleur");
+        let victim = resident(&mut world, "Noir", "tailleur");
+        assert_eq!(world.body.weapons.get(&butcher), Some(&Weapon::Knife));
+        assert!(!world.body.weapons.contains_key(&tailor));
+
+        // Unconscious victim: every blow lands, undefended.
+        world
+            .body
+            .fatigues
+            .insert(victim, Fatigue { current: 100.0 });
+        let armed = crate::systems::combat::roll_blow(&mut world, butcher, victim);
+        let unarmed = crate::systems::combat::roll_blow(&mut world, tailor, victim);
+        assert_eq!(armed, Some(archetype.attack + Weapon::Knife.damage()));
+        assert_eq!(unarmed, Some(archetype.attack));
+        crate::world::validate_world(&world);
+    }
+
+    #[test]
+    fn test_spawn_options_from_args() {
+        let args = |a: &[&str]| -> Vec<String> { a.iter().map(|s| s.to_string()).collect() };
+        assert_eq!(
+            SpawnOptions::from_args(&args(&["wulfaz"])),
+            SpawnOptions::default()
+        );
+
+        let opts = SpawnOptions::from_args(&args(&[
+            "wulfaz",
+            "--quartiers",
+            "Arcis, Louvre",
+            "--year-tolerance",
+            "5",
+            "--sample",
+            "0.25",
+        ]));
+        assert_eq!(
+            opts.quartiers,
+            Some(vec!["Arcis".to_string(), "Louvre".to_string()])
+        );
+        assert_eq!(opts.year_tolerance, 5);
+        assert_eq!(opts.sample_ratio, 0.25);
+
+        let opts = SpawnOptions::from_args(&args(&["--quartiers", "all", "--sample", "2"]));
+        assert_eq!(opts.quartiers, None);
+        assert_eq!(opts.sample_ratio, 1.0, "out of range is ignored");
+    }
+
+    #[test]
+    fn test_spawn_options_menu_cycling() {
+        let names = vec!["Arcis".to_string(), "Louvre".to_string()];
+        let mut opts = SpawnOptions::default();
+        opts.next_quartiers(&names);
+        assert_eq!(opts.quartiers_label(), "Louvre");
+        opts.next_quartiers(&names);
+        assert_eq!(opts.quartiers, None);
+        assert_eq!(opts.quartiers_label(), "All quartiers");
+        opts.next_quartiers(&names);
+        assert_eq!(opts.quartiers_label(), "Arcis");
+
+        assert_eq!(opts.year_tolerance, 20);
+        opts.next_year_tolerance();
+        assert_eq!(opts.year_tolerance, 40);
+        opts.next_year_tolerance();
+        assert_eq!(opts.year_tolerance, 5);
+
+        opts.next_sample_ratio();
+        assert_eq!(opts.sample_ratio, 0.5);
+        opts.sample_ratio = 0.3;
+        opts.next_sample_ratio();
+        assert_eq!(opts.sample_ratio, 1.0, "off-list values restart");
+    }
+
+    /// Two quartiers of one building each, ten residents listed in 1830.
+    fn two_quartier_world() -> crate::world::World {
+        let mut world = crate::world::World::new_with_seed(42);
+        world.tiles = TileMap::new(20, 10);
+        for (i, q) in ["TestQ", "OtherQ"].iter().enumerate() {
+            let bid = BuildingId(i as u32 + 1);
+            let mut tiles = Vec::new();
+            for x in (i * 10 + 2)..(i * 10 + 6) {
+                world.tiles.set_terrain(x, 5, Terrain::Floor);
+                world.tiles.set_building_id(x, 5, bid);
+                tiles.push((x as i32, 5));
+            }
+            let mut occupants_by_year = HashMap::new();
+            occupants_by_year.insert(
+                1830,
+                vec![Occupant {
+                    name: "A, B, C, D, E, F, G, H, I, J".to_string(),
+                    activity: "rentier".to_string(),
+                    naics: "531".to_string(),
+                }],
+            );
+            world.gis.buildings.insert(BuildingData {
+                identif: 100 + i as u32,
+                quartier: q.to_string(),
+                num_ilot: "001".to_string(),
+                occupants_by_year,
+                ..BuildingData::test_default(bid, tiles)
+            });
+            world.gis.quartier_names.push(q.to_string());
+        }
+        world.gis.quartiers = QuartierRegistry::build_from_registries(
+            &world.gis.quartier_names,
+            &world.gis.buildings,
+            &world.gis.blocks,
+            world.gis.active_year,
+        );
+        world
+    }
+
+    #[test]
+    fn test_spawn_population_options() {
+        let archetype = crate::loading::Archetype::default();
+        let total = |world: &crate::world::World| -> f64 {
+            world.gis.aggregates.values().map(|a| a.total()).sum()
+        };
+
+        // 1830 listings are 15 years from 1845.
+        let mut world = two_quartier_world();
+        let mut opts = SpawnOptions {
+            quartiers: None,
+            year_tolerance: 10,
+            sample_ratio: 1.0,
+        };
+        spawn_population(&mut world, &opts, &archetype);
         assert!(world.alive.is_empty());
+        assert_eq!(total(&world), 0.0);
+
+        let mut world = two_quartier_world();
+        opts.year_tolerance = 20;
+        spawn_population(&mut world, &opts, &archetype);
+        assert_eq!(world.alive.len(), 20, "both quartiers");
+        assert_eq!(total(&world), 0.0, "everyone materialized");
+
+        let mut world = two_quartier_world();
+        opts.quartiers = Some(vec!["OtherQ".to_string()]);
+        opts.sample_ratio = 0.5;
+        spawn_population(&mut world, &opts, &archetype);
+        let spawned = world.alive.len();
+        assert!(spawned > 0 && spawned < 10, "sampled {spawned}");
+        assert_eq!(total(&world), 20.0 - spawned as f64);
         crate::world::validate_world(&world);
     }
 }
@@ -36,6 +36,11 @@ pub const ACTIVE_RADIUS: i32 = 150; // ~150m
 /// (but outside ACTIVE_RADIUS) are Nearby; beyond are Statistical.
 pub const NEARBY_RADIUS: i32 = 500; // ~500m
 
+/// Ticks between updates of an entity in a Nearby quartier. Each update
+/// catches up on that many ticks: needs and cooldowns advance by the
+/// interval and movement skips along the cached path.
+pub const NEARBY_TICK_INTERVAL: u64 = 30;
+
 /// Speed configs for speeds 1-5. Index by `sim_speed - 1`.
 pub const SPEED_CONFIGS: [SpeedConfig; 5] = [
     SpeedConfig {
@@ -119,6 +124,8 @@ mod tests {
             total_building_area_m2: 100.0,
             occupant_count: 10,
             blocks: Vec::new(),
+            crime: Default::default(),
+            unrest: 0.0,
         }
     }
 
@@ -195,6 +202,8 @@ mod tests {
             total_building_area_m2: 0.0,
             occupant_count: 0,
             blocks: Vec::new(),
+            crime: Default::default(),
+            unrest: 0.0,
         };
         assert_eq!(classify_quartier(&q, 0, 0), LodZone::Statistical);
     }
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -10,6 +11,7 @@ use winit::window::{Window, WindowId};
 
 use wulfaz::components;
 use wulfaz::components::Tick;
+use wulfaz::directory;
 use wulfaz::font;
 use wulfaz::loading;
 use wulfaz::loading_gis;
@@ -19,13 +21,24 @@ use wulfaz::render;
 use wulfaz::settings::Settings;
 use wulfaz::sprite_renderer;
 use wulfaz::systems::combat::run_combat;
+use wulfaz::systems::crime::run_crime;
+use wulfaz::systems::danger::run_danger;
 use wulfaz::systems::death::run_death;
 use wulfaz::systems::decisions::run_decisions;
 use wulfaz::systems::eating::run_eating;
 use wulfaz::systems::fatigue::run_fatigue;
+use wulfaz::systems::fire::run_fire;
 use wulfaz::systems::hunger::run_hunger;
+use wulfaz::systems::perception::run_perception;
+use wulfaz::systems::plans::run_plans;
+use wulfaz::systems::reactions::run_reactions;
+use wulfaz::systems::rescue::run_rescue;
+use wulfaz::systems::social::run_social;
+use wulfaz::systems::statistical::{apply_lod_transitions, run_statistical};
 use wulfaz::systems::temperature::run_temperature;
+use wulfaz::systems::unrest::{self, run_unrest, update_riots};
 use wulfaz::systems::wander::run_wander;
+use wulfaz::systems::wounds::run_wounds;
 use wulfaz::ui;
 use wulfaz::world::World;
 
@@ -240,7 +253,12 @@ enum PlayerAction {
     Wait,
 }
 
-fn run_one_tick(world: &mut World) {
+/// Run every system for one tick, then start and end riots (crowds drawn
+/// from `person`). Returns the quartiers that rose this tick.
+fn run_one_tick(
+    world: &mut World,
+    person: Option<&loading::Archetype>,
+) -> Vec<wulfaz::registry::QuartierId> {
     macro_rules! timed {
         ($label:expr, $body:expr) => {{
             let _t = std::time::Instant::now();
@@ -257,19 +275,90 @@ fn run_one_tick(world: &mut World) {
     timed!("spatial1", world.rebuild_spatial_index());
     let tick = world.tick;
     timed!("temperature", run_temperature(world, tick));
+    timed!("fire", run_fire(world, tick));
+    timed!("statistical", run_statistical(world, tick));
     timed!("hunger", run_hunger(world, tick));
     timed!("fatigue", run_fatigue(world, tick));
+    timed!("wounds", run_wounds(world, tick));
+    timed!("perception", run_perception(world, tick));
+    timed!("reactions", run_reactions(world, tick));
+    timed!("social", run_social(world, tick));
+    timed!("danger", run_danger(world, tick));
     timed!("decisions", run_decisions(world, tick));
     timed!("wander", run_wander(world, tick));
-    // Spatial contract (rebuild 2 of 2): after wander mutates positions,
+    timed!("plans", run_plans(world, tick));
+    timed!("rescue", run_rescue(world, tick));
+    // Spatial contract (rebuild 2 of 2): after wander/plans mutate positions,
     // eating/combat need post-movement positions for same-tile checks.
     timed!("spatial2", world.rebuild_spatial_index());
     timed!("eating", run_eating(world, tick));
+    timed!("crime", run_crime(world, tick));
     timed!("combat", run_combat(world, tick));
+    timed!("unrest", run_unrest(world, tick));
+    let risen;
+    timed!("riots", risen = update_riots(world, person));
     timed!("death", run_death(world, tick));
     #[cfg(debug_assertions)]
     timed!("validate", wulfaz::world::validate_world(world));
     world.tick = Tick(tick.0 + 1);
+    risen
+}
+
+/// Narrative popup for a riot breaking out in `quartier`.
+fn riot_popup(world: &World, quartier: wulfaz::registry::QuartierId) -> ui::NarrativeEvent {
+    let name = world
+        .gis
+        .quartiers
+        .get(quartier)
+        .map_or("the quartier", |q| q.name.as_str());
+    let barricades = world
+        .gis
+        .riots
+        .get(&quartier)
+        .map_or(0, |r| r.barricades.len());
+    ui::NarrativeEvent {
+        title: format!("Barricades in {name}"),
+        body: format!(
+            "Hunger and bloodshed have worn out the patience of {name}. \
+             Paving stones are torn up and carts overturned: {barricades} \
+             barricades close its streets, and an armed crowd holds them \
+             against the sergents de ville."
+        ),
+        choices: vec![ui::EventChoice {
+            label: "Let it run its course".into(),
+            tooltip: Some("The riot disperses after two days or ten deaths.".into()),
+            callback: "riot".into(),
+        }],
+    }
+}
+
+/// Push the oldest queued narrative event as a modal popup, unless one is
+/// already up. Rebuilt into each frame's tree until a choice pops it.
+fn show_event_popup(
+    ui: &mut ui::UiContext,
+    tree: &mut ui::WidgetTree,
+    theme: &ui::Theme,
+    screen_w: f32,
+) {
+    if !ui.modals.is_empty() {
+        return;
+    }
+    let Some(event) = ui.event_popups.front() else {
+        return;
+    };
+    let popup = ui::build_event_popup(tree, theme, event, screen_w);
+    let choice = event
+        .choices
+        .first()
+        .map(|c| ui::UiAction::EventChoice(c.callback.clone()));
+    ui.modals.push(
+        tree,
+        popup,
+        ui::ModalOptions {
+            on_dismiss: choice.clone(),
+            on_confirm: choice,
+        },
+    );
 }
 
 /// Extract structured hover data from a map tile (UI-I01b).
@@ -299,7 +388,30 @@ fn collect_hover_info(world: &World, tile_x: i32, tile_y: i32) -> Option<ui::Hov
         && qid > 0
         && let Some(name) = world.gis.quartier_names.get((qid - 1) as usize)
     {
-        info.quartier = Some(name.clone());
+        let qid = wulfaz::registry::QuartierId(qid);
+        let data = world.gis.quartiers.get(qid);
+        let crime = data
+            .map(|q| (q.crime, q.occupant_count))
+            .filter(|(c, _)| *c != Default::default());
+        let mut label = match crime {
+            Some((c, population)) => format!(
+                "{name} (thefts {}, assaults {}, arrests {}; {:.1}/1000)",
+                c.thefts,
+                c.assaults,
+                c.arrests,
+                c.rate_per_1000(population)
+            ),
+            None => name.clone(),
+        };
+        if world.gis.riots.contains_key(&qid) {
+            label.push_str(" — in revolt");
+        } else if let Some(q) = data.filter(|q| q.unrest >= 0.01) {
+            label.push_str(&format!(
+                " — unrest {:.0}%",
+                100.0 * q.unrest / unrest::RIOT_THRESHOLD
+            ));
+        }
+        info.quartier = Some(label);
     }
 
     // Building
@@ -310,8 +422,14 @@ fn collect_hover_info(world: &World, tile_x: i32, tile_y: i32) -> Option<ui::Hov
             info.address = Some(format!("{} {}", a.house_number, a.street_name));
         }
         info.building_name = building.nom_bati.clone();
+        if world.gis.fires.contains_key(&bid) {
+            let name = info.building_name.take().unwrap_or_default();
+            info.building_name = Some(format!("{name} (on fire)").trim_start().to_string());
+        }
 
-        if let Some((year, occupants)) = building.occupants_nearest(world.gis.active_year, 20) {
+        if let Some((year, occupants)) =
+            building.occupants_nearest(world.gis.active_year, world.gis.year_tolerance)
+        {
             info.occupants = occupants
                 .iter()
                 .map(|o| (o.name.clone(), o.activity.clone()))
@@ -322,6 +440,27 @@ fn collect_hover_info(world: &World, tile_x: i32, tile_y: i32) -> Option<ui::Hov
         }
     }
 
+    // Bridge
+    if let Some(bridge) = world
+        .gis
+        .bridges
+        .bridge_at(tile_x, tile_y)
+        .and_then(|id| world.gis.bridges.get(id))
+    {
+        let mut name = bridge.name.clone();
+        if let Some(sous) = bridge.toll {
+            name.push_str(&format!(" (toll {sous} sous)"));
+        }
+        if bridge.closed {
+            name.push_str(" (closed)");
+        }
+        name.push_str(&format!(
+            " - {}/{} on deck, {} crossings",
+            bridge.load, bridge.capacity, bridge.crossings
+        ));
+        info.building_name = Some(name);
+    }
+
     // Entities on this tile
     for (&entity, pos) in &world.body.positions {
         if pos.x == tile_x && pos.y == tile_y && world.alive.contains(&entity) {
@@ -368,6 +507,12 @@ struct App {
     keybindings: ui::KeyBindings,
     paused: bool,
     sim_speed: u32, // 1 = normal, 2-5 = faster
+    /// Storey drawn on the map (0 = street level).
+    shown_storey: u8,
+    /// Pathfinding debug overlay for the selected entity (F3).
+    path_debug: bool,
+    /// Spawn archetypes by name, for residents arriving mid-session.
+    archetypes: HashMap<String, loading::Archetype>,
     inspector_close_id: Option<ui::WidgetId>,
     // Performance metrics (UI-505) — stores previous frame's metrics.
     ui_perf: ui::UiPerfMetrics,
@@ -384,6 +529,10 @@ struct App {
     map_drag_origin: Option<(f32, f32)>,
     /// Whether the current map press has exceeded the drag threshold.
     map_selecting: bool,
+    /// Main menu shown over the map (F10).
+    main_menu: bool,
+    /// Options the menu's New Game button starts with.
+    new_game: loading_gis::SpawnOptions,
 }
 
 impl App {
@@ -516,7 +665,27 @@ impl App {
             ui::UiAction::SelectTab(idx) => {
                 self.handle_tab_click(idx);
             }
-            ui::UiAction::MenuNewGame => {}
+            ui::UiAction::MenuNewGame => {
+                self.world = new_world(&self.new_game, &self.archetypes);
+                self.camera = population_camera(&self.world);
+                self.ui.selected_entity = None;
+                self.ui.last_selected_entity = None;
+                self.ui.event_popups.clear();
+                self.ui.modals = ui::ModalStack::new();
+                self.tick_accumulator = 0.0;
+                self.shown_storey = 0;
+                self.main_menu = false;
+                self.paused = false;
+            }
+            ui::UiAction::MenuQuartiers => {
+                self.new_game.next_quartiers(&self.world.gis.quartier_names);
+            }
+            ui::UiAction::MenuYearTolerance => {
+                self.new_game.next_year_tolerance();
+            }
+            ui::UiAction::MenuSample => {
+                self.new_game.next_sample_ratio();
+            }
             ui::UiAction::MenuContinue => {}
             ui::UiAction::MenuLoad => {}
             ui::UiAction::MenuSettings => {}
@@ -532,7 +701,21 @@ impl App {
             ui::UiAction::SaveLoadSelect(_name) => {}
             ui::UiAction::MapModeChange => {}
             ui::UiAction::MapModeSpeed => {}
-            ui::UiAction::EventChoice(_cb) => {}
+            ui::UiAction::EventChoice(_cb) => {
+                // Riots are the only narrative events so far and their
+                // single choice just lets the riot run its course. A button
+                // click leaves the popup up; Enter and Escape popped it.
+                if matches!(
+                    self.ui.modals.confirm_action(),
+                    Some(ui::UiAction::EventChoice(_))
+                ) {
+                    self.ui.modals.pop(&mut self.ui_tree);
+                    self.cleanup_after_modal_pop();
+                }
+                self.ui.event_popups.pop_front();
+                let screen_w = self.gpu.as_ref().map_or(0.0, |g| g.config.width as f32);
+                show_event_popup(&mut self.ui, &mut self.ui_tree, &self.ui_theme, screen_w);
+            }
             ui::UiAction::ContextAction(_action) => {}
         }
     }
@@ -718,6 +901,8 @@ impl ApplicationHandler for App {
                                     if self.map_drag_origin.is_some() {
                                         self.map_drag_origin = None;
                                         self.map_selecting = false;
+                                    } else if self.main_menu {
+                                        self.main_menu = false;
                                     } else {
                                         use ui::DismissResult;
                                         let now = Instant::now();
@@ -762,6 +947,40 @@ impl ApplicationHandler for App {
                                     self.ui_theme.ui_scale =
                                         (self.ui_theme.ui_scale - 0.1).max(0.5);
                                 }
+                                ui::Action::StoreyUp => {
+                                    let top = self
+                                        .world
+                                        .gis
+                                        .buildings
+                                        .buildings
+                                        .iter()
+                                        .map(|b| b.floor_count)
+                                        .max()
+                                        .unwrap_or(1)
+                                        .saturating_sub(1);
+                                    self.shown_storey = (self.shown_storey + 1).min(top);
+                                }
+                                ui::Action::StoreyDown => {
+                                    self.shown_storey = self.shown_storey.saturating_sub(1);
+                                }
+                                ui::Action::TogglePathDebug => {
+                                    self.path_debug = !self.path_debug;
+                                }
+                                ui::Action::ToggleMainMenu => {
+                                    self.main_menu = !self.main_menu;
+                                    if self.main_menu {
+                                        self.paused = true;
+                                    }
+                                }
+                                ui::Action::YearEarlier | ui::Action::YearLater => {
+                                    let later = action == ui::Action::YearLater;
+                                    let year = self.world.gis.active_year;
+                                    if let Some(to) = directory::step_year(&self.world, year, later)
+                                    {
+                                        let person = self.archetypes.get("person");
+                                        directory::set_active_year(&mut self.world, to, person);
+                                    }
+                                }
                             }
                             return;
                         }
@@ -1045,6 +1264,13 @@ impl ApplicationHandler for App {
                         // === Tick processing ===
                         let sim_start = Instant::now();
                         let mut sim_ticks_this_frame = 0u32;
+                        let person = self.archetypes.get("person");
+                        let mut risen = Vec::new();
+                        self.world.path_debug_entity =
+                            self.ui.selected_entity.filter(|_| self.path_debug);
+                        if self.world.path_debug_entity.is_none() {
+                            self.world.path_debug_trace.clear();
+                        }
                         if self.world.player.is_some() {
                             // Roguelike: advance on player action only
                             if let Some(action) = self.pending_player_action.take() {
@@ -1083,7 +1309,13 @@ impl ApplicationHandler for App {
                                                     .gait_profiles
                                                     .get(&player)
                                                     .map(|p| p.cooldown(gait))
-                                                    .unwrap_or(9);
+                                                    .unwrap_or(9)
+                                                    * self
+                                                        .world
+                                                        .body
+                                                        .wounds
+                                                        .get(&player)
+                                                        .map_or(1, |w| w.limp());
                                                 let cd = if is_diag {
                                                     base * 141 / 100 // √2 fixed-point
                                                 } else {
@@ -1116,7 +1348,7 @@ impl ApplicationHandler for App {
                                         if let Some(cd) = cooldown {
                                             // 1 action tick + cooldown wait ticks
                                             for _ in 0..1 + cd {
-                                                run_one_tick(&mut self.world);
+                                                risen.extend(run_one_tick(&mut self.world, person));
                                                 sim_ticks_this_frame += 1;
                                             }
                                         }
@@ -1141,7 +1373,7 @@ impl ApplicationHandler for App {
                                             components::MoveCooldown { remaining: base },
                                         );
                                         for _ in 0..base {
-                                            run_one_tick(&mut self.world);
+                                            risen.extend(run_one_tick(&mut self.world, person));
                                             sim_ticks_this_frame += 1;
                                         }
                                     }
@@ -1159,6 +1391,7 @@ impl ApplicationHandler for App {
                                 self.camera.y,
                                 speed_config.force_statistical,
                             );
+                            apply_lod_transitions(&mut self.world);
 
                             let now = Instant::now();
                             let dt = now.duration_since(self.last_frame_time).as_secs_f64();
@@ -1167,7 +1400,7 @@ impl ApplicationHandler for App {
                             if speed_config.time_mult == 0.0 {
                                 // Speed 5: bypass accumulator, run max ticks unconditionally.
                                 for _ in 0..max_ticks {
-                                    run_one_tick(&mut self.world);
+                                    risen.extend(run_one_tick(&mut self.world, person));
                                     sim_ticks_this_frame += 1;
                                 }
                                 self.tick_accumulator = 0.0;
@@ -1176,7 +1409,7 @@ impl ApplicationHandler for App {
                                 while self.tick_accumulator >= SIM_TICK_INTERVAL
                                     && sim_ticks_this_frame < max_ticks
                                 {
-                                    run_one_tick(&mut self.world);
+                                    risen.extend(run_one_tick(&mut self.world, person));
                                     self.tick_accumulator -= SIM_TICK_INTERVAL;
                                     sim_ticks_this_frame += 1;
                                 }
@@ -1185,7 +1418,23 @@ impl ApplicationHandler for App {
                             // Paused: keep frame time current to avoid tick burst on unpause.
                             self.last_frame_time = Instant::now();
                         }
+                        if sim_ticks_this_frame > 0 {
+                            directory::follow_game_date(&mut self.world, person);
+                        }
+                        if !risen.is_empty() {
+                            for q in risen {
+                                self.ui.event_popups.push_back(riot_popup(&self.world, q));
+                            }
+                            show_event_popup(
+                                &mut self.ui,
+                                &mut self.ui_tree,
+                                &self.ui_theme,
+                                gpu.config.width as f32,
+                            );
+                            self.paused = true;
+                        }
                         let sim_us = sim_start.elapsed().as_micros() as u64;
+                        let path_stats = self.world.path_workspace.take_stats();
 
                         // === Render ===
                         if let (Some(font), Some(panel)) = (self.font.as_mut(), self.panel.as_mut())
@@ -1257,6 +1506,7 @@ impl ApplicationHandler for App {
                             let event_entries = ui::collect_event_entries(
                                 &self.world.events,
                                 &self.world.body.names,
+                                &self.world.gis.buildings,
                             );
                             let event_log_id = ui::build_event_log(
                                 &mut self.ui_tree,
@@ -1465,6 +1715,36 @@ impl ApplicationHandler for App {
                                 );
                             }
 
+                            // Main menu (UI-415) over everything, with the
+                            // new-game options.
+                            if self.main_menu {
+                                let info = ui::MainMenuInfo {
+                                    has_saves: false,
+                                    screen_width: screen_w as f32,
+                                    screen_height: screen_h as f32,
+                                    quartiers: self.new_game.quartiers_label(),
+                                    year_tolerance: self.new_game.year_tolerance,
+                                    sample_ratio: self.new_game.sample_ratio,
+                                };
+                                let menu =
+                                    ui::build_main_menu(&mut self.ui_tree, &self.ui_theme, &info);
+                                self.ui_tree.set_z_tier(menu, ui::ZTier::Modal);
+                            }
+
+                            // Narrative event popup (UI-401): pushed once when
+                            // queued, re-inserted into the rebuilt tree until
+                            // a choice pops it.
+                            if let Some(event) = self.ui.event_popups.front() {
+                                self.ui.modals.rebuild(&mut self.ui_tree, |tree, _| {
+                                    ui::build_event_popup(
+                                        tree,
+                                        &self.ui_theme,
+                                        event,
+                                        screen_w as f32,
+                                    )
+                                });
+                            }
+
                             let build_us = build_start.elapsed().as_micros() as u64;
 
                             // Re-layout tree with all widgets included.
@@ -1609,8 +1889,9 @@ impl ApplicationHandler for App {
                             self.ui.animator.gc(now);
                             self.ui.panels.flush_closed(&mut self.ui_tree, now);
 
-                            let map_text = render::render_world_to_string(
+                            let map_text = render::render_storey_to_string(
                                 &self.world,
+                                self.shown_storey,
                                 self.camera.x,
                                 self.camera.y,
                                 viewport_cols,
@@ -1717,6 +1998,52 @@ impl ApplicationHandler for App {
                                 }
                             }
 
+                            // Map overlay: path debug (F3) — tiles the selected
+                            // entity's last A* search expanded, then its cached path.
+                            if self.path_debug
+                                && let Some(entity) = self.ui.selected_entity
+                            {
+                                let cached = self
+                                    .world
+                                    .mind
+                                    .cached_paths
+                                    .get(&entity)
+                                    .map(|cp| &cp.steps[cp.next_step.min(cp.steps.len())..])
+                                    .unwrap_or(&[]);
+                                let layers = [
+                                    (
+                                        &self.world.path_debug_trace[..],
+                                        self.ui_theme.overlay_search,
+                                    ),
+                                    (cached, self.ui_theme.overlay_path),
+                                ];
+                                for (tiles, color) in layers {
+                                    for &(x, y) in tiles {
+                                        let vx = x - self.camera.x;
+                                        let vy = y - self.camera.y;
+                                        if vx < 0
+                                            || vy < 0
+                                            || vx as usize >= viewport_cols
+                                            || vy as usize >= viewport_rows
+                                        {
+                                            continue;
+                                        }
+                                        panel.add_panel(
+                                            self.map_origin.0 + vx as f32 * mcw,
+                                            self.map_origin.1 + vy as f32 * mch,
+                                            mcw,
+                                            mch,
+                                            color,
+                                            no_border,
+                                            0.0,
+                                            0.0,
+                                            no_clip_min,
+                                            no_clip_max,
+                                        );
+                                    }
+                                }
+                            }
+
                             // Drag-select marquee box overlay.
                             if let Some(origin) = self.map_drag_origin
                                 && self.map_selecting
@@ -1889,6 +2216,9 @@ impl ApplicationHandler for App {
                                 panel_cmds: draw_list.panels.len(),
                                 text_cmds: draw_list.texts.len() + draw_list.rich_texts.len(),
                                 sprite_cmds: draw_list.sprites.len(),
+                                astar_calls: path_stats.calls,
+                                astar_failures: path_stats.failures,
+                                astar_expanded: path_stats.expanded,
                             };
 
                             // Warn when any phase exceeds 2ms (UI-505).
@@ -1921,9 +2251,11 @@ impl ApplicationHandler for App {
     }
 }
 
-fn main() {
-    env_logger::init();
-
+/// Load the map and data files and populate a new game per `opts`.
+fn new_world(
+    opts: &loading_gis::SpawnOptions,
+    archetypes: &HashMap<String, loading::Archetype>,
+) -> World {
     let mut world = World::new_with_seed(42);
 
     // Load map: prefer binary tiles+bincode meta → fallback RON → fallback default terrain.
@@ -1946,37 +2278,55 @@ fn main() {
     world.tiles.initialize_temperatures();
 
     loading::load_utility_config(&mut world, "data/utility.ron");
+    loading::load_factions(&mut world, "data/factions.kdl");
+    loading::load_terrain_costs(&mut world, "data/terrain.kdl");
 
-    let archetypes = loading::load_archetypes("data/archetypes.kdl");
     let person = archetypes
         .get("person")
         .expect("data/archetypes.kdl must define a 'person' archetype");
-    loading_gis::spawn_gis_entities(&mut world, "Arcis", person);
-
-    // Center camera on the Arcis quartier (centroid of spawned entity positions).
-    let start_camera = {
-        let (mut sum_x, mut sum_y, mut count) = (0i64, 0i64, 0u32);
-        for &e in &world.alive {
-            if let Some(pos) = world.body.positions.get(&e) {
-                sum_x += pos.x as i64;
-                sum_y += pos.y as i64;
-                count += 1;
-            }
-        }
-        let (cx, cy) = if count > 0 {
-            ((sum_x / count as i64) as i32, (sum_y / count as i64) as i32)
-        } else {
-            (3750, 3450) // fallback: Seine near Île de la Cité
-        };
-        Camera {
-            x: cx,
-            y: cy,
-            target_x: cx as f32,
-            target_y: cy as f32,
-            zoom: 1.0,
-            target_zoom: 1.0,
+    loading_gis::spawn_population(&mut world, opts, person);
+    if let Some(officer) = archetypes.get("sergent_de_ville") {
+        loading_gis::spawn_police(&mut world, opts, officer);
+    }
+    if let Some(sapeur) = archetypes.get("sapeur_pompier") {
+        loading_gis::spawn_firefighters(&mut world, opts, sapeur);
+    }
+    world
+}
+
+/// Camera centered on the spawned population (centroid of entity positions).
+fn population_camera(world: &World) -> Camera {
+    let (mut sum_x, mut sum_y, mut count) = (0i64, 0i64, 0u32);
+    for &e in &world.alive {
+        if let Some(pos) = world.body.positions.get(&e) {
+            sum_x += pos.x as i64;
+            sum_y += pos.y as i64;
+            count += 1;
         }
+    }
+    let (cx, cy) = if count > 0 {
+        ((sum_x / count as i64) as i32, (sum_y / count as i64) as i32)
+    } else {
+        (3750, 3450) // fallback: Seine near Île de la Cité
     };
+    Camera {
+        x: cx,
+        y: cy,
+        target_x: cx as f32,
+        target_y: cy as f32,
+        zoom: 1.0,
+        target_zoom: 1.0,
+    }
+}
+
+fn main() {
+    env_logger::init();
+
+    let archetypes = loading::load_archetypes("data/archetypes.kdl");
+    let args: Vec<String> = std::env::args().collect();
+    let new_game = loading_gis::SpawnOptions::from_args(&args);
+    let world = new_world(&new_game, &archetypes);
+    let start_camera = population_camera(&world);
 
     let settings = Settings::load();
 
@@ -2004,6 +2354,7 @@ fn main() {
             input: ui::UiState::new(),
             animator: ui::Animator::new(),
             modals: ui::ModalStack::new(),
+            event_popups: std::collections::VecDeque::new(),
             panels: ui::PanelManager::new(),
             scroll: std::collections::HashMap::new(),
             sidebar: ui::SidebarState {
@@ -2028,6 +2379,9 @@ fn main() {
         keybindings: ui::KeyBindings::defaults(),
         paused: false,
         sim_speed: 1,
+        shown_storey: 0,
+        path_debug: false,
+        archetypes,
         inspector_close_id: None,
         ui_perf: ui::UiPerfMetrics::default(),
         minimap_sprites: None, // created in resumed() when GPU is available
@@ -2039,6 +2393,8 @@ fn main() {
         viewport_rows: 0,
         map_drag_origin: None,
         map_selecting: false,
+        main_menu: false,
+        new_game,
     };
     event_loop.run_app(&mut app).expect("run event loop");
 }
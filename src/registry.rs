@@ -1,7 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
+use crate::tile_map::{Terrain, TileMap};
+
 /// Sequential building identifier, 1-based index into BuildingRegistry.buildings.
 /// 0 is reserved as the "no building" sentinel in tile arrays.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -61,8 +63,104 @@ pub struct BuildingData {
     pub addresses: Vec<Address>,
     /// Occupants by year (SoDUCo snapshot year → occupant list), populated by A07.
     pub occupants_by_year: HashMap<u16, Vec<Occupant>>,
+    /// Door tiles and who may use them. Derived from `tiles` at load time by
+    /// `BuildingRegistry::assign_doors`, so not serialized.
+    #[serde(skip)]
+    pub doors: BuildingDoors,
+    /// Floor tile linking the storeys of a multi-storey building, chosen by
+    /// `BuildingRegistry::assign_stairs` at load time. None for single-storey
+    /// buildings and those without floor space.
+    #[serde(skip)]
+    pub stairs: Option<(i32, i32)>,
+}
+
+/// Who may walk through a building's doors. Residents (`HomeBuilding`) and
+/// workers (`Workplace`) always may; see `doors::DoorKeys`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DoorAccess {
+    /// Residents and workers only.
+    #[default]
+    Private,
+    /// Open to anyone between `opens` and `closes` (hours, 0–23).
+    Shop { opens: u8, closes: u8 },
+    /// Open to anyone at any hour (market halls, churches).
+    Public,
+}
+
+/// Door ownership and lock state for one building.
+#[derive(Debug, Clone, Default)]
+pub struct BuildingDoors {
+    /// `Terrain::Door` tiles belonging to this building.
+    pub tiles: Vec<(i32, i32)>,
+    pub access: DoorAccess,
+    /// Locked doors admit only residents and workers, whatever `access` says.
+    pub locked: bool,
+}
+
+/// Default shop hours when a building is classed as a shop.
+pub const SHOP_OPENS: u8 = 7;
+pub const SHOP_CLOSES: u8 = 19;
+
+/// NAICS prefixes whose premises serve walk-in customers: food makers
+/// (bakers, butchers), retail, food and drink, personal services.
+const SHOP_NAICS_PREFIXES: [&str; 5] = ["311", "44", "45", "72", "812"];
+
+/// Building-name words that mark a place open to all.
+const PUBLIC_NAME_WORDS: [&str; 4] = ["marché", "halle", "église", "chapelle"];
+
+/// Building-name words that mark a police post (sergents de ville are
+/// stationed there).
+const POLICE_NAME_WORDS: [&str; 4] = [
+    "commissariat",
+    "poste de police",
+    "corps de garde",
+    "préfecture de police",
+];
+
+/// Building-name words that mark a fire station (sapeurs-pompiers are
+/// stationed there).
+const FIRE_STATION_NAME_WORDS: [&str; 2] = ["pompier", "pompe à incendie"];
+
+/// Building-name words that mark a prison or lock-up.
+const PRISON_NAME_WORDS: [&str; 5] = [
+    "prison",
+    "maison d'arrêt",
+    "conciergerie",
+    "pélagie",
+    "dépôt",
+];
+
+impl BuildingDoors {
+    /// Classify access from the building's name and its occupants in `year`
+    /// (nearest snapshot within `tolerance` years).
+    pub fn classify(bdata: &BuildingData, year: u16, tolerance: u16) -> DoorAccess {
+        if let Some(name) = &bdata.nom_bati {
+            let name = name.to_lowercase();
+            if PUBLIC_NAME_WORDS.iter().any(|w| name.contains(w)) {
+                return DoorAccess::Public;
+            }
+        }
+        let is_shop = bdata
+            .occupants_nearest(year, tolerance)
+            .is_some_and(|(_, occ)| {
+                occ.iter()
+                    .any(|o| SHOP_NAICS_PREFIXES.iter().any(|p| o.naics.starts_with(p)))
+            });
+        if is_shop {
+            DoorAccess::Shop {
+                opens: SHOP_OPENS,
+                closes: SHOP_CLOSES,
+            }
+        } else {
+            DoorAccess::Private
+        }
+    }
 }
 
+/// Default for `GisTables::year_tolerance`: how many years from the active
+/// year a building's nearest directory listing may be.
+pub const DEFAULT_YEAR_TOLERANCE: u16 = 20;
+
 impl BuildingData {
     /// Return occupants from the nearest available year within `max_distance` of `target`.
     /// Prefers exact match, then spirals outward (±1, ±2, …). Ties broken toward later year.
@@ -84,6 +182,31 @@ impl BuildingData {
         }
         None
     }
+
+    /// A one-storey BATI=1 building on `tiles` (one m² each) with no name,
+    /// address, occupants or doors, for tests to override as needed.
+    #[cfg(test)]
+    pub(crate) fn test_default(id: BuildingId, tiles: Vec<(i32, i32)>) -> Self {
+        Self {
+            id,
+            identif: id.0,
+            quartier: String::new(),
+            superficie: tiles.len() as f32,
+            bati: 1,
+            nom_bati: None,
+            num_ilot: String::new(),
+            perimetre: 0.0,
+            geox: 0.0,
+            geoy: 0.0,
+            date_coyec: None,
+            floor_count: 1,
+            tiles,
+            doors: BuildingDoors::default(),
+            stairs: None,
+            addresses: Vec::new(),
+            occupants_by_year: HashMap::new(),
+        }
+    }
 }
 
 #[allow(dead_code)] // Populated by GIS loading; read by C01 district definitions
@@ -156,6 +279,85 @@ impl BuildingRegistry {
             .unwrap_or(&[])
     }
 
+    /// Record each building's `Terrain::Door` tiles and classify its
+    /// `DoorAccess` for `year`, from listings within `tolerance` years.
+    /// Keeps existing lock state.
+    pub fn assign_doors(&mut self, tiles: &TileMap, year: u16, tolerance: u16) {
+        for bdata in &mut self.buildings {
+            bdata.doors.tiles = bdata
+                .tiles
+                .iter()
+                .copied()
+                .filter(|&(x, y)| tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Door))
+                .collect();
+            bdata.doors.access = BuildingDoors::classify(bdata, year, tolerance);
+        }
+    }
+
+    /// Pick a stairs tile for every multi-storey building: the `Floor`
+    /// tile closest (Manhattan) to its first door, or its first `Floor`
+    /// tile if it has no door. Call after `assign_doors`.
+    pub fn assign_stairs(&mut self, tiles: &TileMap) {
+        for bdata in &mut self.buildings {
+            if bdata.floor_count < 2 {
+                bdata.stairs = None;
+                continue;
+            }
+            let anchor = bdata.doors.tiles.first().copied();
+            bdata.stairs = bdata
+                .tiles
+                .iter()
+                .copied()
+                .filter(|&(x, y)| tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Floor))
+                .enumerate()
+                .min_by_key(|&(i, (x, y))| {
+                    let d = anchor.map_or(0, |(ax, ay)| (x - ax).abs() + (y - ay).abs());
+                    (d, i)
+                })
+                .map(|(_, p)| p);
+        }
+    }
+
+    /// Buildings whose `nom_bati` contains any of `words` (lowercased), in
+    /// `BuildingId` order.
+    fn named(&self, words: &[&str]) -> Vec<BuildingId> {
+        self.buildings
+            .iter()
+            .filter(|b| {
+                b.nom_bati.as_ref().is_some_and(|n| {
+                    let n = n.to_lowercase();
+                    words.iter().any(|w| n.contains(w))
+                })
+            })
+            .map(|b| b.id)
+            .collect()
+    }
+
+    /// Police posts, found by name.
+    pub fn commissariats(&self) -> Vec<BuildingId> {
+        self.named(&POLICE_NAME_WORDS)
+    }
+
+    /// Fire stations, found by name.
+    pub fn fire_stations(&self) -> Vec<BuildingId> {
+        self.named(&FIRE_STATION_NAME_WORDS)
+    }
+
+    /// Prisons and lock-ups, found by name.
+    pub fn prisons(&self) -> Vec<BuildingId> {
+        self.named(&PRISON_NAME_WORDS)
+    }
+
+    /// Every SoDUCo year some building has a listing for, ascending.
+    pub fn directory_years(&self) -> Vec<u16> {
+        let years: std::collections::BTreeSet<u16> = self
+            .buildings
+            .iter()
+            .flat_map(|b| b.occupants_by_year.keys().copied())
+            .collect();
+        years.into_iter().collect()
+    }
+
     pub fn len(&self) -> usize {
         self.buildings.len()
     }
@@ -203,10 +405,34 @@ pub struct QuartierData {
     pub building_count: u32,
     /// Sum of superficie (m²) from BATI=1 buildings.
     pub total_building_area_m2: f32,
-    /// Occupant count at active_year via `occupants_nearest(year, 20)`.
+    /// Occupant count at active_year within the year tolerance (see
+    /// `recount_occupants`).
     pub occupant_count: u32,
     /// Sub-district block grouping, sorted by `BlockId.0`.
     pub blocks: Vec<BlockId>,
+    /// Offences and arrests recorded here, written by `run_crime`.
+    pub crime: CrimeStats,
+    /// Residents' discontent, written by `run_unrest`; a riot breaks out at
+    /// `unrest::RIOT_THRESHOLD`.
+    pub unrest: f32,
+}
+
+/// Running crime counts for one quartier.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CrimeStats {
+    pub thefts: u32,
+    pub assaults: u32,
+    pub arrests: u32,
+}
+
+impl CrimeStats {
+    /// Offences (thefts + assaults) per 1,000 of `population`.
+    pub fn rate_per_1000(&self, population: u32) -> f32 {
+        if population == 0 {
+            return 0.0;
+        }
+        (self.thefts + self.assaults) as f32 * 1000.0 / population as f32
+    }
 }
 
 #[derive(Default)]
@@ -247,6 +473,8 @@ impl QuartierRegistry {
                     total_building_area_m2: 0.0,
                     occupant_count: 0,
                     blocks: Vec::new(),
+                    crime: CrimeStats::default(),
+                    unrest: 0.0,
                 },
             );
         }
@@ -267,7 +495,9 @@ impl QuartierRegistry {
             qdata.total_building_area_m2 += bdata.superficie;
 
             // Count occupants at active_year
-            if let Some((_year, occupants)) = bdata.occupants_nearest(active_year, 20) {
+            if let Some((_year, occupants)) =
+                bdata.occupants_nearest(active_year, DEFAULT_YEAR_TOLERANCE)
+            {
                 qdata.occupant_count += occupants.len() as u32;
             }
 
@@ -308,6 +538,29 @@ impl QuartierRegistry {
             .get(name)
             .and_then(|id| self.quartiers.get(id))
     }
+
+    /// Recount `occupant_count` for `year`, e.g. after the active year
+    /// changes.
+    pub fn recount_occupants(&mut self, buildings: &BuildingRegistry, year: u16, tolerance: u16) {
+        for qdata in self.quartiers.values_mut() {
+            qdata.occupant_count = 0;
+        }
+        for bdata in &buildings.buildings {
+            if bdata.bati != 1 {
+                continue;
+            }
+            let Some(qdata) = self
+                .name_to_id
+                .get(&bdata.quartier)
+                .and_then(|id| self.quartiers.get_mut(id))
+            else {
+                continue;
+            };
+            if let Some((_year, occupants)) = bdata.occupants_nearest(year, tolerance) {
+                qdata.occupant_count += occupants.len() as u32;
+            }
+        }
+    }
 }
 
 /// Sequential street identifier.
@@ -375,6 +628,250 @@ impl StreetRegistry {
     }
 }
 
+/// Sequential bridge identifier, 1-based index into BridgeRegistry.bridges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BridgeId(pub u16);
+
+/// Path cost added per sou of toll when crossing onto a bridge: one sou
+/// weighs like one extra tile of plain paving.
+pub const TOLL_COST_PER_SOU: u32 = 100;
+
+/// Path cost added for stepping onto a full bridge, scaled down linearly
+/// from there to nothing at half capacity, so crowds spread over crossings.
+pub const BRIDGE_CROWD_COST: u32 = 600;
+
+/// A river crossing: one 8-connected component of `Terrain::Bridge` tiles.
+#[derive(Debug, Clone)]
+pub struct BridgeData {
+    pub id: BridgeId,
+    pub name: String,
+    pub tiles: Vec<(i32, i32)>,
+    /// Bridge tiles next to walkable land, where crossing costs are charged.
+    pub abutments: Vec<(i32, i32)>,
+    /// The two abutments farthest apart, one per bank.
+    pub ends: [(i32, i32); 2],
+    /// People the deck holds at once (sum of tile capacities).
+    pub capacity: u32,
+    /// Toll in sous charged when stepping onto the bridge, if any.
+    pub toll: Option<u8>,
+    /// Closed bridges refuse everyone (`DoorKeys::may_pass`).
+    pub closed: bool,
+    /// People on the deck as of the last spatial rebuild.
+    pub load: u32,
+    /// People who have stepped onto the deck from a bank since load.
+    pub crossings: u32,
+}
+
+impl BridgeData {
+    /// Whether the deck is at least half full.
+    pub fn is_busy(&self) -> bool {
+        self.load > 0 && self.load * 2 >= self.capacity
+    }
+
+    /// Extra path cost for stepping onto one of its abutments: the toll,
+    /// plus crowding while busy.
+    fn crossing_cost(&self) -> u32 {
+        let toll = self
+            .toll
+            .map_or(0, |sous| u32::from(sous) * TOLL_COST_PER_SOU);
+        let crowd = if self.is_busy() {
+            let over = (self.load * 2 - self.capacity).min(self.capacity);
+            BRIDGE_CROWD_COST * over / self.capacity.max(1)
+        } else {
+            0
+        };
+        toll + crowd
+    }
+}
+
+/// Bridge registry, rebuilt from `Terrain::Bridge` tiles at load time.
+/// Bridges are numbered west to east.
+#[derive(Debug, Default)]
+pub struct BridgeRegistry {
+    pub bridges: Vec<BridgeData>,
+    /// Bridge tile → owning bridge, including barred abutments.
+    tile_index: HashMap<(i32, i32), BridgeId>,
+}
+
+impl BridgeRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Group the map's `Terrain::Bridge` tiles into bridges.
+    pub fn build_from_tiles(tiles: &TileMap) -> Self {
+        let (w, h) = (tiles.width() as i32, tiles.height() as i32);
+        let is_bridge = |x: i32, y: i32| {
+            x >= 0 && y >= 0 && tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Bridge)
+        };
+        let mut seen: HashSet<(i32, i32)> = HashSet::new();
+        let mut components: Vec<Vec<(i32, i32)>> = Vec::new();
+        for y in 0..h {
+            for x in 0..w {
+                if !is_bridge(x, y) || !seen.insert((x, y)) {
+                    continue;
+                }
+                let mut component = Vec::new();
+                let mut stack = vec![(x, y)];
+                while let Some((cx, cy)) = stack.pop() {
+                    component.push((cx, cy));
+                    for dy in -1..=1 {
+                        for dx in -1..=1 {
+                            let n = (cx + dx, cy + dy);
+                            if is_bridge(n.0, n.1) && seen.insert(n) {
+                                stack.push(n);
+                            }
+                        }
+                    }
+                }
+                component.sort();
+                components.push(component);
+            }
+        }
+        components
+            .sort_by_key(|c| c.iter().map(|&(x, _)| i64::from(x)).sum::<i64>() / c.len() as i64);
+
+        let mut registry = Self::new();
+        for (i, bridge_tiles) in components.into_iter().enumerate() {
+            let id = BridgeId(i as u16 + 1);
+            let abutments: Vec<(i32, i32)> = bridge_tiles
+                .iter()
+                .copied()
+                .filter(|&(x, y)| {
+                    [(0, -1), (1, 0), (0, 1), (-1, 0)].iter().any(|&(dx, dy)| {
+                        let (nx, ny) = (x + dx, y + dy);
+                        !is_bridge(nx, ny)
+                            && nx >= 0
+                            && ny >= 0
+                            && tiles.is_walkable(nx as usize, ny as usize)
+                    })
+                })
+                .collect();
+            let mut ends = [bridge_tiles[0]; 2];
+            let mut best = -1;
+            for (j, &a) in abutments.iter().enumerate() {
+                for &b in &abutments[j + 1..] {
+                    let d = (a.0 - b.0).pow(2) + (a.1 - b.1).pow(2);
+                    if d > best {
+                        best = d;
+                        ends = [a, b];
+                    }
+                }
+            }
+            let capacity = bridge_tiles
+                .iter()
+                .map(|&(x, y)| u32::from(tiles.capacity_at(x, y)))
+                .fold(0u32, u32::saturating_add);
+            for &t in &bridge_tiles {
+                registry.tile_index.insert(t, id);
+            }
+            registry.bridges.push(BridgeData {
+                id,
+                name: format!("Pont {}", id.0),
+                tiles: bridge_tiles,
+                abutments,
+                ends,
+                capacity,
+                toll: None,
+                closed: false,
+                load: 0,
+                crossings: 0,
+            });
+        }
+        registry
+    }
+
+    pub fn get(&self, id: BridgeId) -> Option<&BridgeData> {
+        if id.0 == 0 {
+            return None;
+        }
+        self.bridges.get(id.0 as usize - 1)
+    }
+
+    pub fn get_mut(&mut self, id: BridgeId) -> Option<&mut BridgeData> {
+        if id.0 == 0 {
+            return None;
+        }
+        self.bridges.get_mut(id.0 as usize - 1)
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<BridgeId> {
+        self.bridges.iter().find(|b| b.name == name).map(|b| b.id)
+    }
+
+    /// Bridge whose deck includes `(x, y)`.
+    pub fn bridge_at(&self, x: i32, y: i32) -> Option<BridgeId> {
+        self.tile_index.get(&(x, y)).copied()
+    }
+
+    /// Whether `(x, y)` is on the deck of a closed bridge.
+    pub fn is_closed_at(&self, x: i32, y: i32) -> bool {
+        self.bridge_at(x, y)
+            .and_then(|id| self.get(id))
+            .is_some_and(|b| b.closed)
+    }
+
+    /// Whether any bridge is currently tolled, busy or closed.
+    pub fn has_crossing_costs(&self) -> bool {
+        self.bridges
+            .iter()
+            .any(|b| b.toll.is_some() || b.closed || b.is_busy())
+    }
+
+    /// Abutments of tolled, busy or closed bridges: the tiles routing
+    /// charges for or refuses.
+    pub fn costly_tiles(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.bridges
+            .iter()
+            .filter(|b| b.toll.is_some() || b.closed || b.is_busy())
+            .flat_map(|b| b.abutments.iter().copied())
+    }
+
+    /// Extra path cost for stepping onto `(x, y)`: toll and crowding of
+    /// the bridge it is an abutment of, in path cost units; 0 elsewhere.
+    pub fn crossing_cost(&self, x: i32, y: i32) -> u32 {
+        self.bridge_at(x, y)
+            .and_then(|id| self.get(id))
+            .filter(|b| b.abutments.contains(&(x, y)))
+            .map_or(0, BridgeData::crossing_cost)
+    }
+
+    /// Zero every deck's load before a spatial rebuild recounts it.
+    pub fn clear_loads(&mut self) {
+        for b in &mut self.bridges {
+            b.load = 0;
+        }
+    }
+
+    /// Count one person standing on `(x, y)`, if it is a deck tile.
+    pub fn add_load(&mut self, x: i32, y: i32) {
+        if let Some(id) = self.bridge_at(x, y)
+            && let Some(b) = self.get_mut(id)
+        {
+            b.load += 1;
+        }
+    }
+
+    /// Record a street-level step from `from` to `to`: stepping onto a
+    /// deck from off it counts as a crossing.
+    pub fn record_step(&mut self, from: (i32, i32), to: (i32, i32)) {
+        if let Some(id) = self.bridge_at(to.0, to.1)
+            && self.bridge_at(from.0, from.1) != Some(id)
+            && let Some(b) = self.get_mut(id)
+        {
+            b.crossings += 1;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bridges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bridges.is_empty()
+    }
+}
+
 /// Estimate floor count from building footprint area (m²).
 /// <50m² → 2, 50-150m² → 3, 150-400m² → 4, >400m² → 5.
 pub fn estimate_floor_count(superficie: f32) -> u8 {
@@ -393,6 +890,73 @@ pub fn estimate_floor_count(superficie: f32) -> u8 {
 mod tests {
     use super::*;
 
+    /// A river across rows 4..8 of a 20×12 map, bridged at x = 3..5 and
+    /// x = 15.
+    fn river_tiles() -> TileMap {
+        let mut tiles = TileMap::new(20, 12);
+        for y in 4..8 {
+            for x in 0..20 {
+                let t = if (3..5).contains(&x) || x == 15 {
+                    Terrain::Bridge
+                } else {
+                    Terrain::Water
+                };
+                tiles.set_terrain(x, y, t);
+            }
+        }
+        tiles
+    }
+
+    #[test]
+    fn test_bridge_registry_from_tiles() {
+        let tiles = river_tiles();
+        let mut reg = BridgeRegistry::build_from_tiles(&tiles);
+        assert_eq!(reg.len(), 2);
+        let west = reg.get(BridgeId(1)).expect("west bridge");
+        assert_eq!(west.name, "Pont 1");
+        assert_eq!(west.tiles.len(), 8);
+        assert_eq!(west.abutments.len(), 4);
+        let mut ends_y: Vec<i32> = west.ends.iter().map(|&(_, y)| y).collect();
+        ends_y.sort();
+        assert_eq!(ends_y, vec![4, 7]);
+        assert!(west.capacity > 0);
+        assert_eq!(reg.bridge_at(15, 5), Some(BridgeId(2)));
+        assert_eq!(reg.bridge_at(10, 5), None);
+        assert_eq!(reg.find_by_name("Pont 2"), Some(BridgeId(2)));
+
+        assert!(!reg.has_crossing_costs());
+        if let Some(b) = reg.get_mut(BridgeId(2)) {
+            b.toll = Some(2);
+        }
+        assert!(reg.has_crossing_costs());
+        assert_eq!(reg.crossing_cost(15, 4), 2 * TOLL_COST_PER_SOU);
+        assert_eq!(reg.crossing_cost(15, 5), 0, "only charged on the ends");
+    }
+
+    #[test]
+    fn test_bridge_load_crowding_and_crossings() {
+        let tiles = river_tiles();
+        let mut reg = BridgeRegistry::build_from_tiles(&tiles);
+        if let Some(b) = reg.get_mut(BridgeId(2)) {
+            b.capacity = 4;
+        }
+        reg.add_load(15, 5);
+        assert_eq!(reg.crossing_cost(15, 4), 0, "a quarter full");
+        reg.add_load(15, 6);
+        assert!(reg.has_crossing_costs());
+        assert_eq!(reg.crossing_cost(15, 4), 0, "half full is free");
+        reg.add_load(15, 6);
+        reg.add_load(15, 7);
+        assert_eq!(reg.crossing_cost(15, 4), BRIDGE_CROWD_COST);
+        reg.clear_loads();
+        assert!(!reg.has_crossing_costs());
+
+        reg.record_step((15, 3), (15, 4));
+        reg.record_step((15, 4), (15, 5));
+        reg.record_step((14, 3), (15, 3));
+        assert_eq!(reg.get(BridgeId(2)).map(|b| b.crossings), Some(1));
+    }
+
     #[test]
     fn test_building_registry_insert_lookup() {
         let mut reg = BuildingRegistry::new();
@@ -412,6 +976,8 @@ mod tests {
             date_coyec: None,
             floor_count: 3,
             tiles: vec![(10, 20), (11, 20)],
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -444,6 +1010,8 @@ mod tests {
             date_coyec: None,
             floor_count: 3,
             tiles: vec![(1, 1), (2, 1)],
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -463,6 +1031,8 @@ mod tests {
             date_coyec: None,
             floor_count: 2,
             tiles: vec![(5, 5), (6, 5)],
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: Vec::new(),
             occupants_by_year: HashMap::new(),
         });
@@ -517,6 +1087,8 @@ mod tests {
             date_coyec: None,
             floor_count: 3,
             tiles: vec![(1, 1)],
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: vec![
                 Address {
                     street_name: "Rue du Temple".into(),
@@ -545,6 +1117,8 @@ mod tests {
             date_coyec: None,
             floor_count: 2,
             tiles: vec![(2, 2)],
+            doors: BuildingDoors::default(),
+            stairs: None,
             addresses: vec![Address {
                 street_name: "Rue du Temple".into(),
                 house_number: "14".into(),
@@ -599,21 +1173,10 @@ mod tests {
             );
         }
         BuildingData {
-            id: BuildingId(1),
-            identif: 1,
-            quartier: String::new(),
             superficie: 100.0,
-            bati: 1,
-            nom_bati: None,
-            num_ilot: String::new(),
-            perimetre: 0.0,
-            geox: 0.0,
-            geoy: 0.0,
-            date_coyec: None,
             floor_count: 3,
-            tiles: Vec::new(),
-            addresses: Vec::new(),
             occupants_by_year: occ,
+            ..BuildingData::test_default(BuildingId(1), Vec::new())
         }
     }
 
@@ -663,21 +1226,12 @@ mod tests {
         occupants_by_year: HashMap<u16, Vec<Occupant>>,
     ) -> BuildingData {
         BuildingData {
-            id,
-            identif: id.0,
             quartier: quartier.into(),
             superficie,
             bati,
-            nom_bati: None,
-            num_ilot: String::new(),
-            perimetre: 0.0,
-            geox: 0.0,
-            geoy: 0.0,
-            date_coyec: None,
             floor_count: estimate_floor_count(superficie),
-            tiles,
-            addresses: Vec::new(),
             occupants_by_year,
+            ..BuildingData::test_default(id, tiles)
         }
     }
 
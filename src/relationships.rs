@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use crate::components::{Entity, Tick};
+
+/// Opinion at or above which `a` regards `b` as a friend.
+pub const FRIEND_OPINION: i32 = 25;
+
+/// Opinion at or below which `a` regards `b` as a rival.
+pub const RIVAL_OPINION: i32 = -25;
+
+/// Opinion at or below which `a` treats `b` as an enemy.
+pub const HOSTILE_OPINION: i32 = -50;
+
+/// Why one entity thinks better or worse of another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModifierKind {
+    /// Lives in the same building.
+    Neighbor,
+    /// Works in the same building.
+    Coworker,
+    /// Was attacked by them. Stacks with repeated attacks.
+    AttackedMe,
+}
+
+impl ModifierKind {
+    /// Opinion contributed when freshly applied.
+    pub fn base_value(self) -> i32 {
+        match self {
+            ModifierKind::Neighbor => 10,
+            ModifierKind::Coworker => 15,
+            ModifierKind::AttackedMe => -40,
+        }
+    }
+
+    /// Ticks (game minutes) until a fresh modifier fades to nothing.
+    pub fn duration(self) -> u64 {
+        match self {
+            ModifierKind::Neighbor | ModifierKind::Coworker => 7 * 1440,
+            ModifierKind::AttackedMe => 30 * 1440,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ModifierKind::Neighbor => "Neighbor",
+            ModifierKind::Coworker => "Coworker",
+            ModifierKind::AttackedMe => "Attacked me",
+        }
+    }
+}
+
+/// One opinion modifier. Its value decays linearly from `value` at
+/// `applied` to zero at `expires`.
+#[derive(Debug, Clone, Copy)]
+pub struct Modifier {
+    pub kind: ModifierKind,
+    pub value: i32,
+    pub applied: Tick,
+    pub expires: Tick,
+}
+
+impl Modifier {
+    /// Decayed value at `tick`, rounded toward zero.
+    pub fn value_at(&self, tick: Tick) -> i32 {
+        if tick >= self.expires {
+            return 0;
+        }
+        let span = self.expires.0.saturating_sub(self.applied.0).max(1);
+        let left = self.expires.0 - tick.0.max(self.applied.0);
+        (self.value as i64 * left as i64 / span as i64) as i32
+    }
+}
+
+/// Sparse, directed opinion store: what `a` thinks of `b`.
+#[derive(Default)]
+pub struct Relationships {
+    edges: HashMap<(Entity, Entity), Vec<Modifier>>,
+}
+
+impl Relationships {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a modifier to `a`'s opinion of `b`. Refreshes an existing
+    /// modifier of the same kind; `AttackedMe` instead stacks onto what is
+    /// left of the old one, capped at -100.
+    pub fn apply(&mut self, a: Entity, b: Entity, kind: ModifierKind, tick: Tick) {
+        let mods = self.edges.entry((a, b)).or_default();
+        let mut value = kind.base_value();
+        if let Some(i) = mods.iter().position(|m| m.kind == kind) {
+            if kind == ModifierKind::AttackedMe {
+                value = (mods[i].value_at(tick) + value).clamp(-100, 100);
+            }
+            mods.swap_remove(i);
+        }
+        mods.push(Modifier {
+            kind,
+            value,
+            applied: tick,
+            expires: Tick(tick.0 + kind.duration()),
+        });
+    }
+
+    /// Live modifiers on `a`'s opinion of `b`, strongest first.
+    pub fn modifiers(&self, a: Entity, b: Entity, tick: Tick) -> Vec<Modifier> {
+        let mut out: Vec<Modifier> = self
+            .edges
+            .get(&(a, b))
+            .map(|m| {
+                m.iter()
+                    .filter(|m| m.value_at(tick) != 0)
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default();
+        out.sort_by_key(|m| (std::cmp::Reverse(m.value_at(tick).abs()), m.kind.label()));
+        out
+    }
+
+    /// `a`'s total opinion of `b`, clamped to -100..=100. 0 if unacquainted.
+    pub fn opinion(&self, a: Entity, b: Entity, tick: Tick) -> i32 {
+        self.edges
+            .get(&(a, b))
+            .map(|m| m.iter().map(|m| m.value_at(tick)).sum::<i32>())
+            .unwrap_or(0)
+            .clamp(-100, 100)
+    }
+
+    /// Everyone `a` has an opinion of, sorted by entity ID.
+    pub fn known_by(&self, a: Entity) -> Vec<Entity> {
+        let mut out: Vec<Entity> = self
+            .edges
+            .keys()
+            .filter(|(x, _)| *x == a)
+            .map(|&(_, b)| b)
+            .collect();
+        out.sort_by_key(|e| e.0);
+        out
+    }
+
+    /// Drop expired modifiers and empty edges.
+    pub fn prune(&mut self, tick: Tick) {
+        self.edges.retain(|_, mods| {
+            mods.retain(|m| m.expires > tick);
+            !mods.is_empty()
+        });
+    }
+
+    /// Forget every edge touching `entity`.
+    pub fn remove_entity(&mut self, entity: &Entity) {
+        self.edges.retain(|(a, b), _| a != entity && b != entity);
+    }
+
+    /// Every entity appearing on either side of an edge.
+    pub fn entities(&self) -> impl Iterator<Item = &Entity> {
+        self.edges.keys().flat_map(|(a, b)| [a, b])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modifier_decays_linearly() {
+        let mut rel = Relationships::new();
+        let (a, b) = (Entity(1), Entity(2));
+        rel.apply(a, b, ModifierKind::Coworker, Tick(0));
+        let d = ModifierKind::Coworker.duration();
+        assert_eq!(rel.opinion(a, b, Tick(0)), 15);
+        assert_eq!(rel.opinion(a, b, Tick(d / 3)), 10);
+        assert_eq!(rel.opinion(a, b, Tick(d)), 0);
+        assert_eq!(rel.opinion(b, a, Tick(0)), 0, "directed");
+
+        rel.prune(Tick(d));
+        assert!(rel.is_empty());
+    }
+
+    #[test]
+    fn test_refresh_and_stacking() {
+        let mut rel = Relationships::new();
+        let (a, b) = (Entity(1), Entity(2));
+        rel.apply(a, b, ModifierKind::Neighbor, Tick(0));
+        rel.apply(a, b, ModifierKind::Neighbor, Tick(100));
+        assert_eq!(rel.modifiers(a, b, Tick(100)).len(), 1);
+        assert_eq!(rel.opinion(a, b, Tick(100)), 10);
+
+        rel.apply(a, b, ModifierKind::AttackedMe, Tick(100));
+        rel.apply(a, b, ModifierKind::AttackedMe, Tick(100));
+        assert_eq!(rel.opinion(a, b, Tick(100)), 10 - 80);
+        assert_eq!(
+            rel.modifiers(a, b, Tick(100))[0].kind,
+            ModifierKind::AttackedMe
+        );
+    }
+
+    #[test]
+    fn test_remove_entity() {
+        let mut rel = Relationships::new();
+        rel.apply(Entity(1), Entity(2), ModifierKind::Neighbor, Tick(0));
+        rel.apply(Entity(3), Entity(1), ModifierKind::Neighbor, Tick(0));
+        rel.apply(Entity(3), Entity(2), ModifierKind::Neighbor, Tick(0));
+        rel.remove_entity(&Entity(1));
+        assert_eq!(rel.known_by(Entity(3)), vec![Entity(2)]);
+        assert!(rel.known_by(Entity(1)).is_empty());
+    }
+}
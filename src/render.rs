@@ -1,3 +1,4 @@
+use crate::systems::fire;
 use crate::tile_map::Terrain;
 use crate::world::World;
 
@@ -13,9 +14,17 @@ fn terrain_char(terrain: Terrain) -> char {
         Terrain::Water => '~',
         Terrain::Bridge => '=',
         Terrain::Fixture => 'o',
+        Terrain::Barricade => 'X',
+        Terrain::Rubble => '%',
     }
 }
 
+/// Glyph for a building's stairs tile.
+const STAIRS_CHAR: char = '<';
+
+/// Glyph for a burning tile (see `systems::fire`).
+const FIRE_CHAR: char = '^';
+
 /// Render a viewport of the simulation world as a text grid string.
 ///
 /// `cam_x` and `cam_y` are the world coordinates of the top-left visible tile.
@@ -31,6 +40,20 @@ pub fn render_world_to_string(
     cam_y: i32,
     viewport_cols: usize,
     viewport_rows: usize,
+) -> String {
+    render_storey_to_string(world, 0, cam_x, cam_y, viewport_cols, viewport_rows)
+}
+
+/// `render_world_to_string` for one storey. Above street level only
+/// buildings tall enough to have that storey are drawn (doors become wall),
+/// and only entities on that storey are shown.
+pub fn render_storey_to_string(
+    world: &World,
+    storey: u8,
+    cam_x: i32,
+    cam_y: i32,
+    viewport_cols: usize,
+    viewport_rows: usize,
 ) -> String {
     if viewport_cols == 0 || viewport_rows == 0 {
         return String::new();
@@ -50,11 +73,7 @@ pub fn render_world_to_string(
                 let ux = wx as usize;
                 let uy = wy as usize;
                 if ux < map_w && uy < map_h {
-                    if let Some(terrain) = world.tiles.get_terrain(ux, uy) {
-                        row.push(terrain_char(terrain));
-                    } else {
-                        row.push(' ');
-                    }
+                    row.push(storey_tile_char(world, storey, wx, wy));
                 } else {
                     row.push(' ');
                 }
@@ -73,6 +92,9 @@ pub fn render_world_to_string(
             if !world.alive.contains(&entity) {
                 continue;
             }
+            if world.storey_of(entity) != storey {
+                continue;
+            }
             let is_creature = world.body.combat_stats.contains_key(&entity);
             if (pass == 0) == is_creature {
                 continue; // pass 0: items only; pass 1: creatures only
@@ -105,6 +127,34 @@ pub fn render_world_to_string(
     result
 }
 
+/// Display character for tile `(x, y)` seen on `storey`.
+fn storey_tile_char(world: &World, storey: u8, x: i32, y: i32) -> char {
+    let (ux, uy) = (x as usize, y as usize);
+    let Some(terrain) = world.tiles.get_terrain(ux, uy) else {
+        return ' ';
+    };
+    let building = world
+        .tiles
+        .get_building_id(ux, uy)
+        .and_then(|b| world.gis.buildings.get(b));
+    if building.is_some_and(|b| b.stairs == Some((x, y))) {
+        return STAIRS_CHAR;
+    }
+    if fire::is_burning(world, x, y) {
+        return FIRE_CHAR;
+    }
+    if storey == 0 {
+        return terrain_char(terrain);
+    }
+    match building {
+        Some(b) if storey < b.floor_count && b.stairs.is_some() => match terrain {
+            Terrain::Door => terrain_char(Terrain::Wall),
+            t => terrain_char(t),
+        },
+        _ => ' ',
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -315,4 +365,38 @@ mod tests {
         assert_eq!(lines[2], "    ");
         assert_eq!(lines[3], "    ");
     }
+
+    #[test]
+    fn upper_storey_shows_only_its_building_and_occupants() {
+        use crate::registry::BuildingData;
+
+        let mut world = World::new_with_seed(42);
+        world.tiles = crate::tile_map::TileMap::new(5, 1);
+        let bid = world.gis.buildings.next_id();
+        let tiles = vec![(1, 0), (2, 0), (3, 0)];
+        for &(x, _) in &tiles {
+            world.tiles.set_building_id(x as usize, 0, bid);
+            world.tiles.set_terrain(x as usize, 0, Terrain::Floor);
+        }
+        world.tiles.set_terrain(1, 0, Terrain::Door);
+        world.gis.buildings.insert(BuildingData {
+            floor_count: 2,
+            ..BuildingData::test_default(bid, tiles)
+        });
+        world.gis.buildings.assign_stairs(&world.tiles);
+
+        let street = world.spawn();
+        world.body.positions.insert(street, Position { x: 0, y: 0 });
+        world.body.icons.insert(street, Icon { ch: 's' });
+        let upstairs = world.spawn();
+        world
+            .body
+            .positions
+            .insert(upstairs, Position { x: 3, y: 0 });
+        world.body.icons.insert(upstairs, Icon { ch: 'u' });
+        world.body.storeys.insert(upstairs, Storey(1));
+
+        assert_eq!(render_storey_to_string(&world, 0, 0, 0, 5, 1), "s+<_.");
+        assert_eq!(render_storey_to_string(&world, 1, 0, 0, 5, 1), " #<u ");
+    }
 }
@@ -0,0 +1,680 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::registry::{BuildingId, BuildingRegistry, StreetId, StreetRegistry};
+use crate::tile_map::{Terrain, TileMap};
+
+/// Step costs in the same fixed-point units as `find_path` (100 = 1 m).
+const CARDINAL_COST: u32 = 100;
+const DIAGONAL_COST: u32 = 141;
+
+/// Buildings within this many tiles of a segment vote on its street name.
+const LABEL_RADIUS: i32 = 6;
+/// Only every Nth segment tile casts votes, to keep labeling cheap.
+const LABEL_STRIDE: usize = 4;
+
+/// Bucket size for the node lookup grid.
+const NODE_BUCKET: i32 = 32;
+/// `nearest_node` gives up beyond this many buckets.
+const MAX_NODE_RINGS: i32 = 8;
+
+/// 8-neighbor offsets in Zhang-Suen order P2..P9 (N, NE, E, SE, S, SW, W, NW).
+const RING: [(i32, i32); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SegmentId(pub u32);
+
+/// An intersection or dead end on the street centerline.
+#[derive(Debug, Clone)]
+pub struct StreetNode {
+    pub pos: (i32, i32),
+    pub segments: Vec<SegmentId>,
+}
+
+/// A stretch of centerline between two nodes.
+#[derive(Debug, Clone)]
+pub struct StreetSegment {
+    pub a: NodeId,
+    pub b: NodeId,
+    /// Centerline tiles from `a` to `b`, both node tiles included.
+    pub tiles: Vec<(i32, i32)>,
+    /// Length in `find_path` cost units (100 per meter).
+    pub cost: u32,
+    /// Street name voted by nearby building addresses, if any.
+    pub street: Option<StreetId>,
+}
+
+impl StreetSegment {
+    pub fn length_m(&self) -> f32 {
+        self.cost as f32 / 100.0
+    }
+
+    /// The endpoint that isn't `from`.
+    pub fn other(&self, from: NodeId) -> NodeId {
+        if self.a == from { self.b } else { self.a }
+    }
+}
+
+/// A node-to-node route over the street graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreetRoute {
+    pub nodes: Vec<NodeId>,
+    pub segments: Vec<SegmentId>,
+    pub cost: u32,
+}
+
+/// One leg of walking directions: a run of consecutive segments on the
+/// same street.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteLeg {
+    pub street: Option<StreetId>,
+    pub length_m: f32,
+}
+
+/// Street centerline graph, extracted from `Road`/`Bridge` tiles.
+///
+/// Built once at load time: the street mask is thinned to a one-tile
+/// skeleton (Zhang-Suen), skeleton tiles whose crossing number isn't 2
+/// become nodes (adjacent ones merged into one intersection), and the runs
+/// between them become segments labeled from nearby building `Address`es.
+/// Runtime terrain changes don't rebuild it: `update_tile` closes segments
+/// whose centerline stops being street (barricades) and reopens them.
+#[derive(Debug, Default)]
+pub struct StreetGraph {
+    pub nodes: Vec<StreetNode>,
+    pub segments: Vec<StreetSegment>,
+    buckets: HashMap<(i32, i32), Vec<NodeId>>,
+    /// Segments through each centerline tile.
+    centerline: HashMap<(i32, i32), Vec<SegmentId>>,
+    /// Closed segments, with how many of their centerline tiles aren't
+    /// street any more.
+    closed: HashMap<SegmentId, u32>,
+}
+
+fn is_street(t: Terrain) -> bool {
+    matches!(t, Terrain::Road | Terrain::Bridge)
+}
+
+/// Binary grid with out-of-bounds reads as background.
+struct Mask {
+    w: i32,
+    h: i32,
+    bits: Vec<bool>,
+}
+
+impl Mask {
+    fn get(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && x < self.w && y < self.h && self.bits[(y * self.w + x) as usize]
+    }
+
+    fn neighbors(&self, x: i32, y: i32) -> [bool; 8] {
+        RING.map(|(dx, dy)| self.get(x + dx, y + dy))
+    }
+}
+
+/// Number of background→foreground transitions around the 8-ring.
+fn crossing_number(n: &[bool; 8]) -> usize {
+    (0..8).filter(|&i| !n[i] && n[(i + 1) % 8]).count()
+}
+
+/// Zhang-Suen thinning in place. Returns the surviving pixels, row-major.
+fn thin(mask: &mut Mask) -> Vec<(i32, i32)> {
+    let mut pixels: Vec<(i32, i32)> = (0..mask.h)
+        .flat_map(|y| (0..mask.w).map(move |x| (x, y)))
+        .filter(|&(x, y)| mask.get(x, y))
+        .collect();
+    loop {
+        let mut changed = false;
+        for step in 0..2 {
+            let doomed: Vec<(i32, i32)> = pixels
+                .iter()
+                .copied()
+                .filter(|&(x, y)| {
+                    let n = mask.neighbors(x, y);
+                    let b = n.iter().filter(|&&v| v).count();
+                    let [p2, _, p4, _, p6, _, p8, _] = n;
+                    let (c1, c2) = if step == 0 {
+                        (p2 && p4 && p6, p4 && p6 && p8)
+                    } else {
+                        (p2 && p4 && p8, p2 && p6 && p8)
+                    };
+                    (2..=6).contains(&b) && crossing_number(&n) == 1 && !c1 && !c2
+                })
+                .collect();
+            changed |= !doomed.is_empty();
+            for (x, y) in doomed {
+                mask.bits[(y * mask.w + x) as usize] = false;
+            }
+            pixels.retain(|&(x, y)| mask.get(x, y));
+        }
+        if !changed {
+            return pixels;
+        }
+    }
+}
+
+fn step_cost(a: (i32, i32), b: (i32, i32)) -> u32 {
+    if a.0 != b.0 && a.1 != b.1 {
+        DIAGONAL_COST
+    } else {
+        CARDINAL_COST
+    }
+}
+
+fn octile(a: (i32, i32), b: (i32, i32)) -> u32 {
+    let dx = (a.0 - b.0).unsigned_abs();
+    let dy = (a.1 - b.1).unsigned_abs();
+    let diag = dx.min(dy);
+    diag * DIAGONAL_COST + (dx.max(dy) - diag) * CARDINAL_COST
+}
+
+impl StreetGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Extract the street graph from `tiles` and label segments with
+    /// street names from `buildings`' addresses.
+    pub fn build(tiles: &TileMap, buildings: &BuildingRegistry, streets: &StreetRegistry) -> Self {
+        let (w, h) = (tiles.width() as i32, tiles.height() as i32);
+        let mut mask = Mask {
+            w,
+            h,
+            bits: (0..h)
+                .flat_map(|y| (0..w).map(move |x| (x as usize, y as usize)))
+                .map(|(x, y)| tiles.get_terrain(x, y).is_some_and(is_street))
+                .collect(),
+        };
+        let skeleton = thin(&mut mask);
+
+        // Node pixels: crossing number != 2 (dead ends and junctions).
+        let mut node_of: HashMap<(i32, i32), NodeId> = HashMap::new();
+        let mut graph = Self::new();
+        let is_node_px = |x: i32, y: i32| crossing_number(&mask.neighbors(x, y)) != 2;
+        for &(x, y) in &skeleton {
+            if node_of.contains_key(&(x, y)) || !is_node_px(x, y) {
+                continue;
+            }
+            // Flood-fill adjacent node pixels into one intersection.
+            let id = NodeId(graph.nodes.len() as u32);
+            let mut cluster = vec![(x, y)];
+            node_of.insert((x, y), id);
+            let mut i = 0;
+            while i < cluster.len() {
+                let (cx, cy) = cluster[i];
+                for (dx, dy) in RING {
+                    let p = (cx + dx, cy + dy);
+                    if mask.get(p.0, p.1) && !node_of.contains_key(&p) && is_node_px(p.0, p.1) {
+                        node_of.insert(p, id);
+                        cluster.push(p);
+                    }
+                }
+                i += 1;
+            }
+            graph.add_node(centermost(&cluster));
+        }
+
+        // Trace segments out of every node pixel; leftover pixels are
+        // node-less loops, which get a node of their own and are traced too.
+        let mut visited: HashSet<(i32, i32)> = HashSet::new();
+        for &p in &skeleton {
+            if node_of.contains_key(&p) {
+                graph.trace_from(&mask, &mut node_of, &mut visited, p);
+            }
+        }
+        for &p in &skeleton {
+            if !node_of.contains_key(&p) && !visited.contains(&p) {
+                let id = graph.add_node(p);
+                node_of.insert(p, id);
+                graph.trace_from(&mask, &mut node_of, &mut visited, p);
+            }
+        }
+
+        graph.label(tiles, buildings, streets);
+        graph
+    }
+
+    fn add_node(&mut self, pos: (i32, i32)) -> NodeId {
+        let id = NodeId(self.nodes.len() as u32);
+        self.nodes.push(StreetNode {
+            pos,
+            segments: Vec::new(),
+        });
+        self.buckets
+            .entry((pos.0.div_euclid(NODE_BUCKET), pos.1.div_euclid(NODE_BUCKET)))
+            .or_default()
+            .push(id);
+        id
+    }
+
+    /// Trace every untraced segment leaving node pixel `start`.
+    fn trace_from(
+        &mut self,
+        mask: &Mask,
+        node_of: &mut HashMap<(i32, i32), NodeId>,
+        visited: &mut HashSet<(i32, i32)>,
+        start: (i32, i32),
+    ) {
+        for (dx, dy) in RING {
+            let first = (start.0 + dx, start.1 + dy);
+            if mask.get(first.0, first.1)
+                && !node_of.contains_key(&first)
+                && !visited.contains(&first)
+            {
+                self.trace(mask, node_of, visited, start, first);
+            }
+        }
+    }
+
+    /// Walk from node pixel `start` through `first` until another node pixel.
+    fn trace(
+        &mut self,
+        mask: &Mask,
+        node_of: &mut HashMap<(i32, i32), NodeId>,
+        visited: &mut HashSet<(i32, i32)>,
+        start: (i32, i32),
+        first: (i32, i32),
+    ) {
+        let a = node_of[&start];
+        let mut tiles = vec![start, first];
+        visited.insert(first);
+        let (mut prev, mut cur) = (start, first);
+        let b = loop {
+            let around: Vec<(i32, i32)> = RING
+                .iter()
+                .map(|&(dx, dy)| (cur.0 + dx, cur.1 + dy))
+                .filter(|&p| p != prev && mask.get(p.0, p.1))
+                .collect();
+            // Reaching another intersection (or this one again, round a
+            // loop) ends the segment.
+            if let Some(&p) = around
+                .iter()
+                .find(|p| node_of.get(p).is_some_and(|&n| n != a || tiles.len() > 3))
+            {
+                tiles.push(p);
+                break node_of[&p];
+            }
+            // Otherwise continue along the skeleton, cardinal steps first.
+            let next = around
+                .iter()
+                .filter(|p| !node_of.contains_key(p) && !visited.contains(p))
+                .min_by_key(|&&p| step_cost(cur, p));
+            match next {
+                Some(&p) => {
+                    visited.insert(p);
+                    tiles.push(p);
+                    prev = cur;
+                    cur = p;
+                }
+                None => {
+                    // Dead end left by thinning: close it with a node.
+                    let id = self.add_node(cur);
+                    node_of.insert(cur, id);
+                    break id;
+                }
+            }
+        };
+        let cost = tiles.windows(2).map(|s| step_cost(s[0], s[1])).sum();
+        let id = SegmentId(self.segments.len() as u32);
+        for &t in &tiles {
+            self.centerline.entry(t).or_default().push(id);
+        }
+        self.nodes[a.0 as usize].segments.push(id);
+        if b != a {
+            self.nodes[b.0 as usize].segments.push(id);
+        }
+        self.segments.push(StreetSegment {
+            a,
+            b,
+            tiles,
+            cost,
+            street: None,
+        });
+    }
+
+    /// Label each segment with the street most often named by addresses of
+    /// buildings near it. Ties go to the lower `StreetId`.
+    fn label(&mut self, tiles: &TileMap, buildings: &BuildingRegistry, streets: &StreetRegistry) {
+        for seg in &mut self.segments {
+            let mut seen: Vec<BuildingId> = Vec::new();
+            for &(x, y) in seg.tiles.iter().step_by(LABEL_STRIDE) {
+                for dy in -LABEL_RADIUS..=LABEL_RADIUS {
+                    for dx in -LABEL_RADIUS..=LABEL_RADIUS {
+                        let (bx, by) = (x + dx, y + dy);
+                        if bx < 0 || by < 0 {
+                            continue;
+                        }
+                        if let Some(b) = tiles.get_building_id(bx as usize, by as usize)
+                            && !seen.contains(&b)
+                        {
+                            seen.push(b);
+                        }
+                    }
+                }
+            }
+            let mut votes: HashMap<StreetId, u32> = HashMap::new();
+            for b in seen {
+                let Some(data) = buildings.get(b) else {
+                    continue;
+                };
+                for addr in &data.addresses {
+                    if let Some(&sid) = streets.name_to_id.get(&addr.street_name) {
+                        *votes.entry(sid).or_default() += 1;
+                    }
+                }
+            }
+            seg.street = votes
+                .into_iter()
+                .max_by_key(|&(sid, n)| (n, Reverse(sid.0)))
+                .map(|(sid, _)| sid);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn node(&self, id: NodeId) -> &StreetNode {
+        &self.nodes[id.0 as usize]
+    }
+
+    pub fn segment(&self, id: SegmentId) -> &StreetSegment {
+        &self.segments[id.0 as usize]
+    }
+
+    /// Whether a tile of `id`'s centerline is no longer street.
+    pub fn is_closed(&self, id: SegmentId) -> bool {
+        self.closed.contains_key(&id)
+    }
+
+    /// Tile `pos` changed from `was` to `now`: close the segments through it
+    /// when it stops being street, reopen them when it is street again.
+    pub fn update_tile(&mut self, pos: (i32, i32), was: Terrain, now: Terrain) {
+        if is_street(was) == is_street(now) {
+            return;
+        }
+        for &sid in self.centerline.get(&pos).into_iter().flatten() {
+            if is_street(now) {
+                if let Some(n) = self.closed.get_mut(&sid) {
+                    *n -= 1;
+                    if *n == 0 {
+                        self.closed.remove(&sid);
+                    }
+                }
+            } else {
+                *self.closed.entry(sid).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Closest node to `pos` by octile distance, searching up to
+    /// `MAX_NODE_RINGS` buckets out.
+    pub fn nearest_node(&self, pos: (i32, i32)) -> Option<NodeId> {
+        let (bx, by) = (pos.0.div_euclid(NODE_BUCKET), pos.1.div_euclid(NODE_BUCKET));
+        let mut best: Option<(u32, NodeId)> = None;
+        for r in 0..=MAX_NODE_RINGS {
+            for cy in by - r..=by + r {
+                for cx in bx - r..=bx + r {
+                    if (cx - bx).abs().max((cy - by).abs()) != r {
+                        continue;
+                    }
+                    for &id in self.buckets.get(&(cx, cy)).into_iter().flatten() {
+                        let d = octile(pos, self.node(id).pos);
+                        if best.is_none_or(|b| (d, id) < b) {
+                            best = Some((d, id));
+                        }
+                    }
+                }
+            }
+            // Anything in the next ring is at least r * NODE_BUCKET away.
+            if best.is_some_and(|(d, _)| d <= (r * NODE_BUCKET) as u32 * CARDINAL_COST) {
+                break;
+            }
+        }
+        best.map(|(_, id)| id)
+    }
+
+    /// Shortest route between two nodes (Dijkstra over segment costs),
+    /// avoiding closed segments.
+    pub fn route(&self, from: NodeId, to: NodeId) -> Option<StreetRoute> {
+        let mut dist: HashMap<NodeId, u32> = HashMap::new();
+        let mut came: HashMap<NodeId, (NodeId, SegmentId)> = HashMap::new();
+        let mut open = BinaryHeap::new();
+        dist.insert(from, 0);
+        open.push(Reverse((0, from)));
+        while let Some(Reverse((d, n))) = open.pop() {
+            if n == to {
+                let (mut nodes, mut segments) = (vec![to], Vec::new());
+                let mut cur = to;
+                while let Some(&(prev, seg)) = came.get(&cur) {
+                    nodes.push(prev);
+                    segments.push(seg);
+                    cur = prev;
+                }
+                nodes.reverse();
+                segments.reverse();
+                return Some(StreetRoute {
+                    nodes,
+                    segments,
+                    cost: d,
+                });
+            }
+            if d > dist[&n] {
+                continue;
+            }
+            for &sid in &self.node(n).segments {
+                if self.is_closed(sid) {
+                    continue;
+                }
+                let seg = self.segment(sid);
+                let m = seg.other(n);
+                let nd = d + seg.cost;
+                if nd < dist.get(&m).copied().unwrap_or(u32::MAX) {
+                    dist.insert(m, nd);
+                    came.insert(m, (n, sid));
+                    open.push(Reverse((nd, m)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Walking distance in meters between two buildings along the street
+    /// graph, including the straight-line hop from each building to its
+    /// nearest node.
+    pub fn building_distance(
+        &self,
+        buildings: &BuildingRegistry,
+        a: BuildingId,
+        b: BuildingId,
+    ) -> Option<f32> {
+        let anchor = |id: BuildingId| buildings.get(id)?.tiles.first().copied();
+        let (pa, pb) = (anchor(a)?, anchor(b)?);
+        let (na, nb) = (self.nearest_node(pa)?, self.nearest_node(pb)?);
+        let route = self.route(na, nb)?;
+        let cost = octile(pa, self.node(na).pos) + route.cost + octile(pb, self.node(nb).pos);
+        Some(cost as f32 / 100.0)
+    }
+
+    /// Collapse a route into legs along the same street.
+    pub fn legs(&self, route: &StreetRoute) -> Vec<RouteLeg> {
+        let mut legs: Vec<RouteLeg> = Vec::new();
+        for &sid in &route.segments {
+            let seg = self.segment(sid);
+            match legs.last_mut() {
+                Some(leg) if leg.street == seg.street => leg.length_m += seg.length_m(),
+                _ => legs.push(RouteLeg {
+                    street: seg.street,
+                    length_m: seg.length_m(),
+                }),
+            }
+        }
+        legs
+    }
+
+    /// Human-readable directions, e.g. "Walk 120 m along Rue du Temple".
+    pub fn directions(&self, route: &StreetRoute, streets: &StreetRegistry) -> Vec<String> {
+        self.legs(route)
+            .iter()
+            .map(|leg| {
+                let m = leg.length_m.round() as u32;
+                match leg.street.and_then(|s| streets.streets.get(&s)) {
+                    Some(data) => format!("Walk {m} m along {}", data.name),
+                    None => format!("Walk {m} m"),
+                }
+            })
+            .collect()
+    }
+}
+
+/// The cluster pixel closest to the cluster's centroid.
+fn centermost(cluster: &[(i32, i32)]) -> (i32, i32) {
+    let n = cluster.len() as i32;
+    let cx = cluster.iter().map(|p| p.0).sum::<i32>() / n;
+    let cy = cluster.iter().map(|p| p.1).sum::<i32>() / n;
+    cluster
+        .iter()
+        .copied()
+        .min_by_key(|&p| (octile(p, (cx, cy)), p))
+        .unwrap_or((cx, cy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::{Address, BuildingData};
+
+    /// 41×41 walled map with a 3-wide horizontal street (rows 19..=21) and a
+    /// 3-wide vertical street (cols 19..=21) crossing in the middle.
+    fn crossroads() -> TileMap {
+        let mut map = TileMap::new(41, 41);
+        for y in 0..41 {
+            for x in 0..41 {
+                let street = (19..=21).contains(&y) || (19..=21).contains(&x);
+                if !street {
+                    map.set_terrain(x, y, Terrain::Wall);
+                }
+            }
+        }
+        map
+    }
+
+    fn building(reg: &mut BuildingRegistry, tiles: Vec<(i32, i32)>, street: &str) -> BuildingId {
+        let id = reg.next_id();
+        reg.insert(BuildingData {
+            quartier: "Arcis".into(),
+            num_ilot: "T1".into(),
+            floor_count: 3,
+            addresses: vec![Address {
+                street_name: street.into(),
+                house_number: "1".into(),
+            }],
+            ..BuildingData::test_default(id, tiles)
+        });
+        id
+    }
+
+    #[test]
+    fn test_crossroads_has_one_junction_and_four_arms() {
+        let map = crossroads();
+        let graph = StreetGraph::build(&map, &BuildingRegistry::new(), &StreetRegistry::new());
+        let junctions: Vec<&StreetNode> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.segments.len() == 4)
+            .collect();
+        assert_eq!(junctions.len(), 1, "{:?}", graph.nodes);
+        let (jx, jy) = junctions[0].pos;
+        assert!((19..=21).contains(&jx) && (19..=21).contains(&jy));
+        assert_eq!(graph.segments.len(), 4);
+        for seg in &graph.segments {
+            assert!(seg.tiles.len() > 10);
+            for w in seg.tiles.windows(2) {
+                assert!((w[0].0 - w[1].0).abs() <= 1 && (w[0].1 - w[1].1).abs() <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_segments_labeled_and_directions() {
+        let mut map = crossroads();
+        let mut reg = BuildingRegistry::new();
+        // Houses along the west arm (rue du Temple) and north arm (rue Rivoli).
+        let west = building(&mut reg, vec![(5, 17)], "Rue du Temple");
+        let _ = building(&mut reg, vec![(10, 23)], "Rue du Temple");
+        let north = building(&mut reg, vec![(17, 5)], "Rue de Rivoli");
+        for i in 1..=3 {
+            let id = BuildingId(i);
+            let (x, y) = reg.get(id).expect("inserted").tiles[0];
+            map.set_building_id(x as usize, y as usize, id);
+        }
+        let streets = StreetRegistry::build_from_buildings(&reg);
+        let graph = StreetGraph::build(&map, &reg, &streets);
+
+        let temple = streets.name_to_id["Rue du Temple"];
+        let rivoli = streets.name_to_id["Rue de Rivoli"];
+        let labeled: Vec<Option<StreetId>> = graph.segments.iter().map(|s| s.street).collect();
+        assert!(labeled.contains(&Some(temple)) && labeled.contains(&Some(rivoli)));
+
+        let from = graph.nearest_node((0, 20)).expect("west end");
+        let to = graph.nearest_node((20, 0)).expect("north end");
+        let route = graph.route(from, to).expect("connected");
+        assert_eq!(route.segments.len(), 2);
+        let dirs = graph.directions(&route, &streets);
+        assert_eq!(dirs.len(), 2);
+        assert!(dirs[0].ends_with("along Rue du Temple"), "{dirs:?}");
+        assert!(dirs[1].ends_with("along Rue de Rivoli"), "{dirs:?}");
+
+        let d = graph
+            .building_distance(&reg, west, north)
+            .expect("both reachable");
+        assert!((20.0..60.0).contains(&d), "{d}");
+    }
+
+    #[test]
+    fn test_disconnected_streets_have_no_route() {
+        let mut map = crossroads();
+        // Cut the horizontal street at x = 30.
+        for y in 19..=21 {
+            map.set_terrain(30, y, Terrain::Wall);
+        }
+        let graph = StreetGraph::build(&map, &BuildingRegistry::new(), &StreetRegistry::new());
+        let west = graph.nearest_node((0, 20)).expect("west end");
+        let east = graph.nearest_node((40, 20)).expect("east end");
+        assert!(graph.route(west, east).is_none());
+    }
+
+    #[test]
+    fn test_barricade_closes_segment_until_cleared() {
+        let map = crossroads();
+        let mut graph = StreetGraph::build(&map, &BuildingRegistry::new(), &StreetRegistry::new());
+        let west = graph.nearest_node((0, 20)).expect("west end");
+        let east = graph.nearest_node((40, 20)).expect("east end");
+        let open = graph.route(west, east).expect("connected");
+        let east_arm = *open.segments.last().expect("two segments");
+        let cut = graph.segment(east_arm).tiles[5];
+
+        graph.update_tile(cut, Terrain::Road, Terrain::Barricade);
+        assert!(graph.is_closed(east_arm));
+        assert!(graph.route(west, east).is_none());
+        // Still street to the router: no change.
+        graph.update_tile(cut, Terrain::Road, Terrain::Bridge);
+        assert!(graph.is_closed(east_arm));
+
+        graph.update_tile(cut, Terrain::Barricade, Terrain::Road);
+        assert!(!graph.is_closed(east_arm));
+        assert_eq!(graph.route(west, east), Some(open));
+    }
+}
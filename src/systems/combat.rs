@@ -1,21 +1,42 @@
 use crate::components::{ActionId, Entity, Tick};
 use crate::events::Event;
+use crate::faction;
 use crate::systems::fatigue::UNCONSCIOUS_THRESHOLD;
+use crate::systems::wounds;
 use crate::world::World;
 use rand::RngExt;
 
 /// Fatigue gained per attack (flat cost, standing in for encumbrance).
 const ATTACK_FATIGUE_COST: f32 = 1.0;
-
-/// Compute fatigue-modified damage: effective_attack - effective_defense, min 1.0.
-/// Fatigue degrades stats: -1 defense per 10 fatigue, -1 attack per 20 fatigue.
-/// Unconscious defenders (fatigue >= 100) have 0 effective defense.
-fn compute_fatigue_damage(world: &World, attacker: Entity, defender: Entity) -> f32 {
+/// Hit chance when effective attack equals effective defense.
+const BASE_HIT_CHANCE: f32 = 0.7;
+/// Hit chance gained per point of effective attack over defense.
+const HIT_CHANCE_PER_POINT: f32 = 0.02;
+/// Hit chance bounds for a conscious defender.
+const MIN_HIT_CHANCE: f32 = 0.1;
+const MAX_HIT_CHANCE: f32 = 0.95;
+
+/// Effective attack and defense after weapons, wounds and fatigue.
+/// The attacker's weapon adds its damage; the total scales with the
+/// attacker's wound `work_ability`. Fatigue degrades stats: -1 defense per
+/// 10 fatigue, -1 attack per 20 fatigue. Unconscious defenders (fatigue >=
+/// 100) have 0 effective defense.
+fn effective_stats(world: &World, attacker: Entity, defender: Entity) -> (f32, f32) {
+    let weapon = world
+        .body
+        .weapons
+        .get(&attacker)
+        .map_or(0.0, |w| w.damage());
+    let ability = world
+        .body
+        .wounds
+        .get(&attacker)
+        .map_or(1.0, |w| w.work_ability());
     let base_atk = world
         .body
         .combat_stats
         .get(&attacker)
-        .map(|cs| cs.attack)
+        .map(|cs| (cs.attack + weapon) * ability)
         .unwrap_or(0.0);
     let base_def = world
         .body
@@ -42,38 +63,83 @@ fn compute_fatigue_damage(world: &World, attacker: Entity, defender: Entity) ->
     } else {
         (base_def - fatigue_d / 10.0).max(0.0)
     };
+    (eff_atk, eff_def)
+}
+
+/// Damage of a landed blow: effective_attack - effective_defense, min 1.0.
+fn compute_damage(world: &World, attacker: Entity, defender: Entity) -> f32 {
+    let (eff_atk, eff_def) = effective_stats(world, attacker, defender);
     (eff_atk - eff_def).max(1.0)
 }
 
+/// Chance a blow lands: `BASE_HIT_CHANCE` shifted by the effective
+/// attack/defense difference and the attacker's weapon accuracy, clamped to
+/// [`MIN_HIT_CHANCE`, `MAX_HIT_CHANCE`]. Unconscious defenders are always hit.
+fn hit_chance(world: &World, attacker: Entity, defender: Entity) -> f32 {
+    let unconscious = world
+        .body
+        .fatigues
+        .get(&defender)
+        .is_some_and(|f| f.current >= UNCONSCIOUS_THRESHOLD);
+    if unconscious {
+        return 1.0;
+    }
+    let (eff_atk, eff_def) = effective_stats(world, attacker, defender);
+    let accuracy = world
+        .body
+        .weapons
+        .get(&attacker)
+        .map_or(0.0, |w| w.accuracy());
+    (BASE_HIT_CHANCE + HIT_CHANCE_PER_POINT * (eff_atk - eff_def) + accuracy)
+        .clamp(MIN_HIT_CHANCE, MAX_HIT_CHANCE)
+}
+
+/// Roll whether a blow lands; its damage if so.
+pub(crate) fn roll_blow(world: &mut World, attacker: Entity, defender: Entity) -> Option<f32> {
+    let chance = hit_chance(world, attacker, defender);
+    let roll: f32 = world.rng.random();
+    (roll < chance).then(|| compute_damage(world, attacker, defender))
+}
+
 /// Phase 4 (Actions): Combat resolution with fatigue.
 ///
 /// Finds entities with combat_stats, health, and position that share a tile
-/// with another combatant. Unconscious entities (fatigue >= 100) cannot attack.
-/// Fatigue degrades stats: -1 defense per 10, -1 attack per 20.
-/// Each attack costs the attacker ATTACK_FATIGUE_COST fatigue.
+/// with a hostile combatant (see `faction::is_hostile`). Unconscious entities
+/// (fatigue >= 100) cannot attack.
+/// Fatigue degrades stats: -1 defense per 10, -1 attack per 20; weapons
+/// add damage and wounds scale it (see `effective_stats`).
+/// Each attack costs the attacker ATTACK_FATIGUE_COST fatigue, hit or miss;
+/// whether it lands is rolled against `hit_chance`.
 /// Damage = effective_attack - effective_defense (min 1.0).
-/// Defender health is reduced; if it drops to 0 or below, a death event is
+/// Attackers strike on their `World::lod_step` ticks only: a Nearby
+/// attacker's one roll stands for the whole interval, so its fatigue cost
+/// and damage are scaled by the step.
+/// A landed blow reduces defender health and leaves a wound
+/// (`wounds::inflict`); if health drops to 0 or below, a death event is
 /// pushed and the defender is added to pending_deaths.
 pub fn run_combat(world: &mut World, tick: Tick) {
     // Collect combatants with position, health, and combat_stats, sorted for determinism
-    let mut combatants: Vec<(Entity, i32, i32, f32)> = world
+    let mut combatants: Vec<(Entity, i32, i32, f32, f32)> = world
         .body
         .combat_stats
         .iter()
         .filter(|&(&e, _)| !world.pending_deaths.contains(&e))
+        .filter(|&(&e, _)| !world.frozen.contains(&e))
         .filter(|&(&e, _)| world.player != Some(e))
         .filter_map(|(&e, cs)| {
             let pos = world.body.positions.get(&e)?;
             world.body.healths.get(&e)?;
-            Some((e, pos.x, pos.y, cs.aggression))
+            let step = world.lod_step(e, tick);
+            (step > 0).then_some((e, pos.x, pos.y, cs.aggression, step as f32))
         })
         .collect();
-    combatants.sort_by_key(|(e, _, _, _)| e.0);
+    combatants.sort_by_key(|(e, _, _, _, _)| e.0);
 
     // Find attack pairs: aggressive entity attacks another at same position
-    let mut combat_changes: Vec<(Entity, Entity, f32)> = Vec::new(); // (attacker, defender, damage)
+    // (attacker, defender, damage — None on a miss, step)
+    let mut combat_changes: Vec<(Entity, Entity, Option<f32>, f32)> = Vec::new();
 
-    for &(attacker, ax, ay, aggression) in &combatants {
+    for &(attacker, ax, ay, aggression, step) in &combatants {
         debug_assert!(
             world.body.combat_stats.contains_key(&attacker)
                 && world.body.healths.contains_key(&attacker)
@@ -81,17 +147,27 @@ pub fn run_combat(world: &mut World, tick: Tick) {
             "entity {:?}: in combatants but missing combat_stats, health, or position",
             attacker
         );
-        // Require Attack intention — no legacy fallback
-        let dominated = match world.mind.intentions.get(&attacker) {
+        // Require Attack or Defend intention — no legacy fallback.
+        // Defenders always strike; attackers strike with `aggression` odds.
+        let strike_chance = match world.mind.intentions.get(&attacker) {
             Some(intention) => match intention.action {
-                ActionId::Attack => false,
-                ActionId::Idle | ActionId::Wander | ActionId::Eat => true,
+                ActionId::Attack => Some(aggression),
+                ActionId::Defend => Some(1.0),
+                ActionId::Idle
+                | ActionId::Wander
+                | ActionId::Eat
+                | ActionId::PickUp
+                | ActionId::Flee
+                | ActionId::Rescue
+                | ActionId::Rob
+                | ActionId::Patrol
+                | ActionId::Extinguish => None,
             },
-            None => true,
+            None => None,
         };
-        if dominated {
+        let Some(strike_chance) = strike_chance else {
             continue;
-        }
+        };
 
         // Unconscious entities cannot attack
         let attacker_fatigue = world
@@ -104,9 +180,9 @@ pub fn run_combat(world: &mut World, tick: Tick) {
             continue;
         }
 
-        // RNG check: aggression is probability of attacking
+        // RNG check: strike chance is probability of attacking
         let roll: f32 = world.rng.random();
-        if roll > aggression {
+        if roll > strike_chance {
             continue;
         }
 
@@ -117,14 +193,16 @@ pub fn run_combat(world: &mut World, tick: Tick) {
         if let Some(target) = preferred_target
             && target != attacker
             && !world.pending_deaths.contains(&target)
+            && faction::is_hostile(world, attacker, target)
             && world.body.combat_stats.contains_key(&target)
             && world.body.healths.contains_key(&target)
             && let Some(tp) = world.body.positions.get(&target)
             && tp.x == ax
             && tp.y == ay
+            && world.storey_of(target) == world.storey_of(attacker)
         {
-            let damage = compute_fatigue_damage(world, attacker, target);
-            combat_changes.push((attacker, target, damage));
+            let damage = roll_blow(world, attacker, target);
+            combat_changes.push((attacker, target, damage, step));
             found_target = true;
         }
 
@@ -133,46 +211,67 @@ pub fn run_combat(world: &mut World, tick: Tick) {
             let mut candidates: Vec<Entity> = world
                 .entities_at(ax, ay)
                 .filter(|&e| e != attacker)
+                .filter(|&e| world.storey_of(e) == world.storey_of(attacker))
                 .filter(|e| !world.pending_deaths.contains(e))
+                .filter(|&e| faction::is_hostile(world, attacker, e))
                 .filter(|e| world.body.combat_stats.contains_key(e))
                 .filter(|e| world.body.healths.contains_key(e))
                 .collect();
             candidates.sort_unstable_by_key(|e| e.0); // determinism
 
             if let Some(&defender) = candidates.first() {
-                let damage = compute_fatigue_damage(world, attacker, defender);
-                combat_changes.push((attacker, defender, damage));
+                let damage = roll_blow(world, attacker, defender);
+                combat_changes.push((attacker, defender, damage, step));
             }
         }
     }
 
     // Apply combat_changes
-    for (attacker, defender, damage) in combat_changes {
+    for (attacker, defender, damage, step) in combat_changes {
         // Attacker gains fatigue from attacking
         if let Some(f) = world.body.fatigues.get_mut(&attacker) {
-            f.current += ATTACK_FATIGUE_COST;
+            f.current += ATTACK_FATIGUE_COST * step;
         }
+        let Some(damage) = damage else {
+            continue;
+        };
 
-        if let Some(health) = world.body.healths.get_mut(&defender) {
-            health.current -= damage;
-            health.current = health.current.clamp(0.0, health.max);
-
-            world.events.push(Event::Attacked {
-                attacker,
-                defender,
-                damage,
-                tick,
-            });
-
-            if health.current <= 0.0 {
-                // Lethal event: push AFTER the decision, BEFORE pending_deaths
-                world.events.push(Event::Died {
-                    entity: defender,
-                    tick,
-                });
-                world.pending_deaths.insert(defender);
-            }
-        }
+        land_blow(world, attacker, defender, damage * step, tick);
+    }
+}
+
+/// Apply a landed blow: take `damage` off the defender's health, push
+/// `Event::Attacked`, leave a wound, and kill the defender at 0 health.
+pub(crate) fn land_blow(
+    world: &mut World,
+    attacker: Entity,
+    defender: Entity,
+    damage: f32,
+    tick: Tick,
+) {
+    let Some(health) = world.body.healths.get_mut(&defender) else {
+        return;
+    };
+    health.current -= damage;
+    health.current = health.current.clamp(0.0, health.max);
+    let dead = health.current <= 0.0;
+
+    world.events.push(Event::Attacked {
+        attacker,
+        defender,
+        damage,
+        tick,
+    });
+    let weapon = world.body.weapons.get(&attacker).copied();
+    wounds::inflict(world, defender, weapon, damage, tick);
+
+    if dead {
+        // Lethal event: push AFTER the decision, BEFORE pending_deaths
+        world.events.push(Event::Died {
+            entity: defender,
+            tick,
+        });
+        world.pending_deaths.insert(defender);
     }
 }
 
@@ -193,9 +292,14 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_combat_damages_defender() {
-        let mut world = World::new_with_seed(42);
+    /// Seeds swept by the tests that drive `run_combat`'s hit roll, so each
+    /// sees both outcomes whatever the RNG's order of draws.
+    const SEEDS: std::ops::Range<u64> = 0..64;
+
+    /// An attacker (aggression 1.0, Attack intention) and a conscious
+    /// defender at (5, 5), in a world seeded with `seed`.
+    fn duel(seed: u64, attack: f32, defender_health: Health, defense: f32) -> (World, Entity) {
+        let mut world = World::new_with_seed(seed);
 
         let attacker = world.spawn();
         world
@@ -212,7 +316,7 @@ mod tests {
         world.body.combat_stats.insert(
             attacker,
             CombatStats {
-                attack: 15.0,
+                attack,
                 defense: 5.0,
                 aggression: 1.0,
             },
@@ -223,32 +327,82 @@ mod tests {
             .body
             .positions
             .insert(defender, Position { x: 5, y: 5 }); // same position
-        world.body.healths.insert(
-            defender,
-            Health {
-                current: 100.0,
-                max: 100.0,
-            },
-        );
+        world.body.healths.insert(defender, defender_health);
         world.body.combat_stats.insert(
             defender,
             CombatStats {
                 attack: 5.0,
-                defense: 3.0,
+                defense,
                 aggression: 0.0,
             },
         );
 
         set_attack_intention(&mut world, attacker, defender);
         world.rebuild_spatial_index();
-        run_combat(&mut world, Tick(0));
+        (world, defender)
+    }
 
-        // Attacker has aggression 1.0 so always attacks. Damage = 15-3 = 12
-        assert!(world.body.healths[&defender].current < 100.0);
+    #[test]
+    fn test_combat_damages_defender() {
+        let mut outcomes = Vec::new();
+        for seed in SEEDS {
+            let full = Health {
+                current: 100.0,
+                max: 100.0,
+            };
+            let (mut world, defender) = duel(seed, 15.0, full, 3.0);
+            run_combat(&mut world, Tick(0));
+            outcomes.push(world.body.healths[&defender].current);
+        }
+
+        // Aggression 1.0 always strikes; the blow lands at hit_chance 0.94.
+        // Damage = 15-3 = 12, or a miss leaves health untouched.
+        assert!(outcomes.iter().all(|&h| h == 88.0 || h == 100.0));
+        assert!(outcomes.contains(&88.0), "some seed hits");
+        assert!(outcomes.contains(&100.0), "some seed misses");
     }
 
     #[test]
     fn test_combat_kills_defender() {
+        let mut kills = 0;
+        for seed in SEEDS {
+            let wounded = Health {
+                current: 5.0,
+                max: 100.0,
+            };
+            let (mut world, defender) = duel(seed, 50.0, wounded, 3.0);
+            run_combat(&mut world, Tick(0));
+            if world.pending_deaths.contains(&defender) {
+                kills += 1;
+            } else {
+                assert_eq!(world.body.healths[&defender].current, 5.0, "missed");
+            }
+        }
+        assert!(kills > 0);
+    }
+
+    #[test]
+    fn test_combat_clamps_health_to_zero() {
+        let mut hits = 0;
+        for seed in SEEDS {
+            let wounded = Health {
+                current: 3.0,
+                max: 50.0,
+            };
+            let (mut world, defender) = duel(seed, 200.0, wounded, 1.0);
+            run_combat(&mut world, Tick(0));
+            // Health should be clamped to 0.0, not negative
+            let health = world.body.healths[&defender].current;
+            assert!(health == 0.0 || health == 3.0, "health {health}");
+            if health == 0.0 {
+                hits += 1;
+            }
+        }
+        assert!(hits > 0);
+    }
+
+    #[test]
+    fn test_combat_skips_pending_death() {
         let mut world = World::new_with_seed(42);
 
         let attacker = world.spawn();
@@ -266,7 +420,7 @@ mod tests {
         world.body.combat_stats.insert(
             attacker,
             CombatStats {
-                attack: 50.0,
+                attack: 15.0,
                 defense: 5.0,
                 aggression: 1.0,
             },
@@ -280,7 +434,7 @@ mod tests {
         world.body.healths.insert(
             defender,
             Health {
-                current: 5.0,
+                current: 100.0,
                 max: 100.0,
             },
         );
@@ -294,14 +448,15 @@ mod tests {
         );
 
         set_attack_intention(&mut world, attacker, defender);
-        world.rebuild_spatial_index();
+        world.pending_deaths.insert(attacker); // already dying
+
         run_combat(&mut world, Tick(0));
 
-        assert!(world.pending_deaths.contains(&defender));
+        assert_eq!(world.body.healths[&defender].current, 100.0); // undamaged
     }
 
     #[test]
-    fn test_combat_clamps_health_to_zero() {
+    fn test_no_attack_intention_no_fight() {
         let mut world = World::new_with_seed(42);
 
         let attacker = world.spawn();
@@ -319,7 +474,7 @@ mod tests {
         world.body.combat_stats.insert(
             attacker,
             CombatStats {
-                attack: 200.0,
+                attack: 15.0,
                 defense: 5.0,
                 aggression: 1.0,
             },
@@ -333,29 +488,28 @@ mod tests {
         world.body.healths.insert(
             defender,
             Health {
-                current: 3.0,
-                max: 50.0,
+                current: 100.0,
+                max: 100.0,
             },
         );
         world.body.combat_stats.insert(
             defender,
             CombatStats {
                 attack: 5.0,
-                defense: 1.0,
+                defense: 3.0,
                 aggression: 0.0,
             },
         );
 
-        set_attack_intention(&mut world, attacker, defender);
+        // No intention — attacker should not fight
         world.rebuild_spatial_index();
         run_combat(&mut world, Tick(0));
 
-        // Health should be clamped to 0.0, not negative
-        assert_eq!(world.body.healths[&defender].current, 0.0);
+        assert_eq!(world.body.healths[&defender].current, 100.0); // undamaged
     }
 
     #[test]
-    fn test_combat_skips_pending_death() {
+    fn test_combat_different_positions_no_fight() {
         let mut world = World::new_with_seed(42);
 
         let attacker = world.spawn();
@@ -383,7 +537,7 @@ mod tests {
         world
             .body
             .positions
-            .insert(defender, Position { x: 5, y: 5 });
+            .insert(defender, Position { x: 10, y: 10 }); // different position
         world.body.healths.insert(
             defender,
             Health {
@@ -401,115 +555,140 @@ mod tests {
         );
 
         set_attack_intention(&mut world, attacker, defender);
-        world.pending_deaths.insert(attacker); // already dying
-
         run_combat(&mut world, Tick(0));
 
         assert_eq!(world.body.healths[&defender].current, 100.0); // undamaged
     }
 
-    #[test]
-    fn test_no_attack_intention_no_fight() {
-        let mut world = World::new_with_seed(42);
-
-        let attacker = world.spawn();
-        world
-            .body
-            .positions
-            .insert(attacker, Position { x: 5, y: 5 });
+    /// Fighter at (5, 5) with full health and the given attack/defense.
+    fn spawn_fighter(world: &mut World, attack: f32, defense: f32) -> Entity {
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x: 5, y: 5 });
         world.body.healths.insert(
-            attacker,
+            e,
             Health {
                 current: 100.0,
                 max: 100.0,
             },
         );
         world.body.combat_stats.insert(
-            attacker,
+            e,
             CombatStats {
-                attack: 15.0,
-                defense: 5.0,
+                attack,
+                defense,
                 aggression: 1.0,
             },
         );
+        e
+    }
 
-        let defender = world.spawn();
-        world
-            .body
-            .positions
-            .insert(defender, Position { x: 5, y: 5 });
-        world.body.healths.insert(
-            defender,
-            Health {
-                current: 100.0,
-                max: 100.0,
-            },
-        );
-        world.body.combat_stats.insert(
-            defender,
-            CombatStats {
-                attack: 5.0,
-                defense: 3.0,
-                aggression: 0.0,
-            },
-        );
+    /// Knock `e` out: every blow lands on it, against no defense. Lets the
+    /// wound and step tests check damage without the hit roll.
+    fn knock_out(world: &mut World, e: Entity) {
+        world.body.fatigues.insert(e, Fatigue { current: 100.0 });
+    }
 
-        // No intention — attacker should not fight
+    #[test]
+    fn test_armed_hit_wounds_defender() {
+        let mut world = World::new_with_seed(42);
+        let attacker = spawn_fighter(&mut world, 15.0, 5.0);
+        let defender = spawn_fighter(&mut world, 5.0, 3.0);
+        world.body.weapons.insert(attacker, Weapon::Sabre);
+        knock_out(&mut world, defender);
+
+        set_attack_intention(&mut world, attacker, defender);
         world.rebuild_spatial_index();
         run_combat(&mut world, Tick(0));
 
-        assert_eq!(world.body.healths[&defender].current, 100.0); // undamaged
+        // Damage = 15 + 10 (sabre) - 0
+        assert_eq!(world.body.healths[&defender].current, 75.0);
+        let wounds = &world.body.wounds[&defender].0;
+        assert_eq!(wounds.len(), 1);
+        assert_eq!(wounds[0].kind, WoundKind::Cut);
+        assert!(wounds[0].bleeding > 0.0);
+        assert!(world.events.iter().any(|e| matches!(
+            e,
+            Event::Wounded { entity, .. } if *entity == defender
+        )));
     }
 
     #[test]
-    fn test_combat_different_positions_no_fight() {
+    fn test_hit_chance_from_stats_and_weapon() {
         let mut world = World::new_with_seed(42);
-
-        let attacker = world.spawn();
-        world
-            .body
-            .positions
-            .insert(attacker, Position { x: 5, y: 5 });
-        world.body.healths.insert(
-            attacker,
-            Health {
-                current: 100.0,
-                max: 100.0,
-            },
-        );
-        world.body.combat_stats.insert(
-            attacker,
-            CombatStats {
-                attack: 15.0,
-                defense: 5.0,
-                aggression: 1.0,
-            },
+        let a = spawn_fighter(&mut world, 5.0, 5.0);
+        let d = spawn_fighter(&mut world, 5.0, 5.0);
+        assert!((hit_chance(&world, a, d) - BASE_HIT_CHANCE).abs() < 0.001);
+
+        // +18 attack, -0.15 accuracy
+        world.body.weapons.insert(a, Weapon::Musket);
+        assert!((hit_chance(&world, a, d) - 0.91).abs() < 0.001);
+
+        world.body.combat_stats.get_mut(&a).expect("stats").attack = 100.0;
+        assert_eq!(hit_chance(&world, a, d), MAX_HIT_CHANCE);
+
+        // A broken sword arm halves the blow.
+        world.body.weapons.remove(&a);
+        world.body.wounds.insert(
+            a,
+            Wounds(vec![Wound {
+                part: BodyPart::RightArm,
+                kind: WoundKind::Fracture,
+                bleeding: 0.0,
+                healing: 100,
+            }]),
         );
+        assert_eq!(compute_damage(&world, a, d), 45.0);
+    }
 
+    #[test]
+    fn test_nearby_blow_scaled_by_step() {
+        use crate::lod::{LodZone, NEARBY_TICK_INTERVAL};
+        use crate::registry::QuartierId;
+
+        let mut world = World::new_with_seed(42);
+        world.tiles.set_quartier_id(5, 5, 1);
+        world.gis.lod_zones.insert(QuartierId(1), LodZone::Nearby);
+        let attacker = world.spawn();
         let defender = world.spawn();
+        for (e, aggression) in [(attacker, 1.0), (defender, 0.0)] {
+            world.body.positions.insert(e, Position { x: 5, y: 5 });
+            world.body.healths.insert(
+                e,
+                Health {
+                    current: 1000.0,
+                    max: 1000.0,
+                },
+            );
+            world.body.combat_stats.insert(
+                e,
+                CombatStats {
+                    attack: 12.0,
+                    defense: 10.0,
+                    aggression,
+                },
+            );
+        }
+        knock_out(&mut world, defender);
         world
             .body
-            .positions
-            .insert(defender, Position { x: 10, y: 10 }); // different position
-        world.body.healths.insert(
-            defender,
-            Health {
-                current: 100.0,
-                max: 100.0,
-            },
-        );
-        world.body.combat_stats.insert(
-            defender,
-            CombatStats {
-                attack: 5.0,
-                defense: 3.0,
-                aggression: 0.0,
-            },
-        );
-
+            .fatigues
+            .insert(attacker, Fatigue { current: 0.0 });
         set_attack_intention(&mut world, attacker, defender);
-        run_combat(&mut world, Tick(0));
+        world.rebuild_spatial_index();
 
-        assert_eq!(world.body.healths[&defender].current, 100.0); // undamaged
+        let step = NEARBY_TICK_INTERVAL - attacker.0 % NEARBY_TICK_INTERVAL;
+        run_combat(&mut world, Tick(step + 1));
+        assert_eq!(
+            world.body.healths[&defender].current, 1000.0,
+            "between steps"
+        );
+
+        run_combat(&mut world, Tick(step));
+        let n = NEARBY_TICK_INTERVAL as f32;
+        assert_eq!(world.body.healths[&defender].current, 1000.0 - 12.0 * n);
+        assert_eq!(
+            world.body.fatigues[&attacker].current,
+            ATTACK_FATIGUE_COST * n
+        );
     }
 }
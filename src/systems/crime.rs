@@ -0,0 +1,620 @@
+use crate::components::{ActionId, Crime, Entity, Imprisoned, Loot, Position, Tick};
+use crate::events::Event;
+use crate::loading::Archetype;
+use crate::registry::{BuildingId, CrimeStats, Occupant, QuartierId};
+use crate::systems::combat;
+use crate::systems::fatigue::UNCONSCIOUS_THRESHOLD;
+use crate::systems::perception::{SENSE_RANGE, remembered};
+use crate::tile_map::Terrain;
+use crate::world::World;
+use rand::RngExt;
+
+/// Ticks a criminal waits between offences (6 hours).
+pub const OFFENCE_INTERVAL: u64 = 360;
+
+/// Trades that fence stolen goods multiply the archetype's criminal chance
+/// by `CRIMINAL_TRADE_FACTOR`.
+const CRIMINAL_TRADE_WORDS: [&str; 5] = [
+    "chiffonnier",
+    "brocanteur",
+    "fripier",
+    "revendeu",
+    "marchand d'habits",
+];
+const CRIMINAL_TRADE_FACTOR: f32 = 5.0;
+
+/// Share of criminals who assault rather than pick pockets.
+const ASSAULT_SHARE: f32 = 0.25;
+
+/// Chance a pickpocket gets away with something.
+const THEFT_SUCCESS: f32 = 0.7;
+
+/// Chance a theft is noticed, drawing a reaction like an attack.
+const THEFT_NOTICED: f32 = 0.3;
+
+/// Chance per tick an officer on the offender's tile makes the arrest;
+/// unconscious offenders are always taken.
+const ARREST_CHANCE: f32 = 0.3;
+
+/// Sentences, in ticks (1 tick = 1 game minute).
+const THEFT_SENTENCE: u64 = 7 * 1440;
+const ASSAULT_SENTENCE: u64 = 30 * 1440;
+/// Held for anyone arrested without a criminal disposition (a brawler).
+const DEFAULT_SENTENCE: u64 = 2 * 1440;
+
+/// Chebyshev radius within which police respond to an attack or a noticed
+/// theft, whether or not they saw it.
+pub const POLICE_RESPONSE_RANGE: i32 = 40;
+
+/// How far (Chebyshev) from their commissariat police walk their beat.
+const PATROL_RANGE: i32 = 40;
+
+/// Roll a spawned resident's criminal disposition: the archetype's
+/// `criminal` chance, raised for trades that deal in second-hand goods.
+/// Consumes no randomness for archetypes that never turn to crime.
+pub fn roll_disposition(
+    world: &mut World,
+    archetype: &Archetype,
+    occupant: &Occupant,
+) -> Option<Crime> {
+    if archetype.criminal <= 0.0 {
+        return None;
+    }
+    let activity = occupant.activity.to_lowercase();
+    let chance = if CRIMINAL_TRADE_WORDS.iter().any(|w| activity.contains(w)) {
+        archetype.criminal * CRIMINAL_TRADE_FACTOR
+    } else {
+        archetype.criminal
+    };
+    if world.rng.random::<f32>() >= chance {
+        return None;
+    }
+    Some(if world.rng.random::<f32>() < ASSAULT_SHARE {
+        Crime::Assault
+    } else {
+        Crime::Theft
+    })
+}
+
+/// Whether `e` belongs to the "police" faction.
+pub fn is_police(world: &World, e: Entity) -> bool {
+    world
+        .mind
+        .faction_members
+        .get(&e)
+        .and_then(|m| world.mind.factions.get(m.faction))
+        .is_some_and(|f| f.key == "police")
+}
+
+/// Whether `e` is a criminal whose last offence is `OFFENCE_INTERVAL` behind.
+pub fn has_urge(world: &World, e: Entity) -> bool {
+    world.mind.criminals.get(&e).is_some_and(|c| {
+        c.last_offence
+            .is_none_or(|t| world.tick.0 >= t.0 + OFFENCE_INTERVAL)
+    })
+}
+
+/// Nearest remembered person within SENSE_RANGE worth robbing: not a
+/// criminal, not police, not in prison. None while any police officer is
+/// remembered within SENSE_RANGE. Ties to lowest entity ID.
+pub fn select_victim(world: &World, entity: Entity) -> Option<Entity> {
+    let pos = world.body.positions.get(&entity)?;
+    let near: Vec<(Entity, i32)> = remembered(world, entity)
+        .into_iter()
+        .filter(|&(e, _)| e != entity)
+        .map(|(e, seen)| (e, (seen.x - pos.x).abs().max((seen.y - pos.y).abs())))
+        .filter(|&(_, dist)| dist <= SENSE_RANGE)
+        .collect();
+    if near.iter().any(|&(e, _)| is_police(world, e)) {
+        return None;
+    }
+    near.into_iter()
+        .filter(|(e, _)| world.body.healths.contains_key(e))
+        .filter(|(e, _)| world.mind.faction_members.contains_key(e))
+        .filter(|(e, _)| !world.mind.criminals.contains_key(e))
+        .filter(|(e, _)| !world.mind.imprisoned.contains_key(e))
+        .filter(|&(e, _)| !is_police(world, e))
+        .min_by_key(|&(e, dist)| (dist, e.0))
+        .map(|(e, _)| e)
+}
+
+/// A random street tile within `PATROL_RANGE` of `e`'s workplace (their
+/// commissariat). None without a workplace or if no try lands on a road.
+pub fn patrol_goal(world: &mut World, e: Entity) -> Option<(i32, i32)> {
+    let bid = world.gis.workplaces.get(&e)?.0;
+    let b = world.gis.buildings.get(bid)?;
+    let &(bx, by) = b.doors.tiles.first().or(b.tiles.first())?;
+    for _ in 0..5 {
+        let gx = bx + world.rng.random_range(-PATROL_RANGE..=PATROL_RANGE);
+        let gy = by + world.rng.random_range(-PATROL_RANGE..=PATROL_RANGE);
+        if gx < 0 || gy < 0 {
+            continue;
+        }
+        if world.tiles.get_terrain(gx as usize, gy as usize) == Some(Terrain::Road) {
+            return Some((gx, gy));
+        }
+    }
+    None
+}
+
+/// Add to the crime counts of the quartier containing `pos`.
+fn record(world: &mut World, pos: Position, add: impl FnOnce(&mut CrimeStats)) {
+    if pos.x < 0 || pos.y < 0 {
+        return;
+    }
+    let Some(q) = world.tiles.get_quartier_id(pos.x as usize, pos.y as usize) else {
+        return;
+    };
+    if let Some(data) = world.gis.quartiers.quartiers.get_mut(&QuartierId(q)) {
+        add(&mut data.crime);
+    }
+}
+
+/// Where to lock up an offender arrested at `pos`: a floor tile of the
+/// nearest prison, else of the officer's own commissariat.
+fn cell_for(world: &mut World, officer: Entity, pos: Position) -> Option<(BuildingId, (i32, i32))> {
+    let floors = |world: &World, bid: BuildingId| -> Vec<(i32, i32)> {
+        world.gis.buildings.get(bid).map_or(Vec::new(), |b| {
+            b.tiles
+                .iter()
+                .copied()
+                .filter(|&(x, y)| {
+                    world.tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Floor)
+                })
+                .collect()
+        })
+    };
+    let prison = world
+        .gis
+        .buildings
+        .prisons()
+        .into_iter()
+        .filter_map(|bid| {
+            let &(x, y) = world.gis.buildings.get(bid)?.tiles.first()?;
+            Some((bid, (x - pos.x).abs().max((y - pos.y).abs())))
+        })
+        .min_by_key(|&(bid, dist)| (dist, bid.0))
+        .map(|(bid, _)| bid);
+    let post = world.gis.workplaces.get(&officer).map(|w| w.0);
+    let (bid, cells) = [prison, post]
+        .into_iter()
+        .flatten()
+        .map(|bid| (bid, floors(world, bid)))
+        .find(|(_, cells)| !cells.is_empty())?;
+    let cell = cells[world.rng.random_range(0..cells.len())];
+    Some((bid, cell))
+}
+
+/// Take something from `victim` for `thief`: food the victim carries
+/// (lowest id first), else the victim's weapon if the thief has none.
+fn steal(world: &mut World, thief: Entity, victim: Entity) -> Option<Loot> {
+    let food = world
+        .mind
+        .carried_by
+        .iter()
+        .filter(|&(item, &carrier)| carrier == victim && world.mind.nutritions.contains_key(item))
+        .map(|(&item, _)| item)
+        .min_by_key(|item| item.0);
+    if let Some(item) = food {
+        world.mind.carried_by.insert(item, thief);
+        return Some(Loot::Food(item));
+    }
+    if world.body.weapons.contains_key(&thief) {
+        return None;
+    }
+    let weapon = world.body.weapons.remove(&victim)?;
+    world.body.weapons.insert(thief, weapon);
+    Some(Loot::Weapon(weapon))
+}
+
+/// Phase 4 (Actions): offences, arrests and releases.
+///
+/// Runs before `run_combat`. In order:
+/// - prisoners whose sentence is up are released where they stand;
+/// - criminals whose `Rob` target shares their tile (and storey) offend, at
+///   most once per `OFFENCE_INTERVAL`: thieves roll `THEFT_SUCCESS` and
+///   `THEFT_NOTICED` (`Event::Theft`) and on success take the victim's
+///   `loot`, assailants land a combat blow;
+/// - police whose `Defend` target shares their tile arrest them with
+///   `ARREST_CHANCE` (always if unconscious): the offender is moved into
+///   the nearest prison (else the commissariat) and imprisoned for a
+///   sentence set by their crime (`Event::Arrested`).
+///
+/// Every offence and arrest is counted in its quartier's `CrimeStats`.
+pub fn run_crime(world: &mut World, tick: Tick) {
+    // Releases (frozen prisoners included: they are frozen for being there).
+    let mut released: Vec<Entity> = world
+        .mind
+        .imprisoned
+        .iter()
+        .filter(|(_, p)| p.release <= tick)
+        .map(|(&e, _)| e)
+        .collect();
+    released.sort_by_key(|e| e.0);
+    for e in released {
+        world.mind.imprisoned.remove(&e);
+        world.frozen.remove(&e);
+    }
+
+    let acting = |world: &World, e: Entity, action: ActionId| -> Option<(Entity, Position)> {
+        if world.pending_deaths.contains(&e)
+            || world.frozen.contains(&e)
+            || world.lod_step(e, tick) == 0
+        {
+            return None;
+        }
+        let intention = world.mind.intentions.get(&e)?;
+        if intention.action != action {
+            return None;
+        }
+        let target = intention.target?;
+        let pos = *world.body.positions.get(&e)?;
+        let tp = world.body.positions.get(&target)?;
+        let reachable = (tp.x, tp.y) == (pos.x, pos.y)
+            && world.storey_of(target) == world.storey_of(e)
+            && world.alive.contains(&target)
+            && !world.pending_deaths.contains(&target)
+            && !world.mind.imprisoned.contains_key(&target);
+        reachable.then_some((target, pos))
+    };
+
+    // Offences
+    let mut offences: Vec<(Entity, Entity, Position, Crime)> = world
+        .mind
+        .criminals
+        .iter()
+        .filter(|&(&e, _)| has_urge(world, e))
+        .filter_map(|(&e, c)| {
+            let (victim, pos) = acting(world, e, ActionId::Rob)?;
+            Some((e, victim, pos, c.crime))
+        })
+        .collect();
+    offences.sort_by_key(|(e, _, _, _)| e.0);
+
+    for (thief, victim, pos, crime) in offences {
+        match crime {
+            Crime::Theft => {
+                let success = world.rng.random::<f32>() < THEFT_SUCCESS;
+                let noticed = world.rng.random::<f32>() < THEFT_NOTICED;
+                let loot = if success {
+                    steal(world, thief, victim)
+                } else {
+                    None
+                };
+                world.events.push(Event::Theft {
+                    thief,
+                    victim,
+                    success,
+                    noticed,
+                    loot,
+                    tick,
+                });
+                record(world, pos, |s| s.thefts += 1);
+            }
+            Crime::Assault => {
+                if let Some(damage) = combat::roll_blow(world, thief, victim) {
+                    combat::land_blow(world, thief, victim, damage, tick);
+                }
+                record(world, pos, |s| s.assaults += 1);
+            }
+        }
+        if let Some(c) = world.mind.criminals.get_mut(&thief) {
+            c.last_offence = Some(tick);
+        }
+    }
+
+    // Arrests
+    let mut arrests: Vec<(Entity, Entity, Position)> = world
+        .mind
+        .faction_members
+        .keys()
+        .filter(|&&e| is_police(world, e))
+        .filter_map(|&e| {
+            let (offender, pos) = acting(world, e, ActionId::Defend)?;
+            let free =
+                !is_police(world, offender) && !world.mind.dragged_by.contains_key(&offender);
+            free.then_some((e, offender, pos))
+        })
+        .collect();
+    arrests.sort_by_key(|(e, _, _)| e.0);
+
+    for (officer, offender, pos) in arrests {
+        if world.mind.imprisoned.contains_key(&offender) {
+            continue; // another officer got there first
+        }
+        let out_cold = world
+            .body
+            .fatigues
+            .get(&offender)
+            .is_some_and(|f| f.current >= UNCONSCIOUS_THRESHOLD);
+        if !out_cold && world.rng.random::<f32>() >= ARREST_CHANCE {
+            continue;
+        }
+        let Some((prison, (x, y))) = cell_for(world, officer, pos) else {
+            continue;
+        };
+        let sentence = match world.mind.criminals.get(&offender).map(|c| c.crime) {
+            Some(Crime::Theft) => THEFT_SENTENCE,
+            Some(Crime::Assault) => ASSAULT_SENTENCE,
+            None => DEFAULT_SENTENCE,
+        };
+        world.body.positions.insert(offender, Position { x, y });
+        world.body.storeys.remove(&offender);
+        world.mind.cached_paths.remove(&offender);
+        world.mind.wander_targets.remove(&offender);
+        world.mind.dragged_by.retain(|_, r| *r != offender);
+        world.mind.imprisoned.insert(
+            offender,
+            Imprisoned {
+                prison,
+                release: Tick(tick.0 + sentence),
+            },
+        );
+        world.frozen.insert(offender);
+        world.events.push(Event::Arrested {
+            officer,
+            offender,
+            tick,
+        });
+        record(world, pos, |s| s.arrests += 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::*;
+    use crate::faction::{AttackResponse, FactionDef};
+    use crate::registry::BuildingData;
+
+    fn faction(world: &mut World, key: &str) -> crate::faction::FactionId {
+        world.mind.factions.insert(FactionDef {
+            key: key.into(),
+            name: key.into(),
+            local: false,
+            when_attacked: AttackResponse::Fight,
+            calls_for_help: true,
+            defends_allies: true,
+        })
+    }
+
+    fn person(world: &mut World, faction: crate::faction::FactionId, x: i32, y: i32) -> Entity {
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x, y });
+        world.body.healths.insert(
+            e,
+            Health {
+                current: 100.0,
+                max: 100.0,
+            },
+        );
+        world.body.combat_stats.insert(
+            e,
+            CombatStats {
+                attack: 10.0,
+                defense: 5.0,
+                aggression: 0.0,
+            },
+        );
+        world.mind.faction_members.insert(
+            e,
+            FactionMember {
+                faction,
+                quartier: None,
+            },
+        );
+        e
+    }
+
+    fn intend(world: &mut World, e: Entity, action: ActionId, target: Entity) {
+        world.mind.intentions.insert(
+            e,
+            Intention {
+                action,
+                target: Some(target),
+            },
+        );
+    }
+
+    fn prison(world: &mut World, tiles: Vec<(i32, i32)>) -> BuildingId {
+        let id = world.gis.buildings.next_id();
+        for &(x, y) in &tiles {
+            world
+                .tiles
+                .set_terrain(x as usize, y as usize, Terrain::Floor);
+        }
+        world.gis.buildings.insert(BuildingData {
+            quartier: "Arcis".into(),
+            nom_bati: Some("Prison de la Force".into()),
+            num_ilot: "T1".into(),
+            ..BuildingData::test_default(id, tiles)
+        });
+        id
+    }
+
+    #[test]
+    fn test_disposition_needs_archetype_chance_and_favours_trades() {
+        let mut world = World::new_with_seed(42);
+        let occupant = |activity: &str| Occupant {
+            name: "Dupont".into(),
+            activity: activity.into(),
+            naics: "".into(),
+        };
+        let honest = Archetype::default();
+        let rng_before = world.rng.random::<u64>();
+        let mut replay = World::new_with_seed(42);
+        assert_eq!(
+            roll_disposition(&mut replay, &honest, &occupant("chiffonnier")),
+            None
+        );
+        assert_eq!(replay.rng.random::<u64>(), rng_before, "no roll consumed");
+
+        let shady = Archetype {
+            criminal: 0.1,
+            ..Archetype::default()
+        };
+        let count = |world: &mut World, activity: &str| {
+            (0..1000)
+                .filter(|_| roll_disposition(world, &shady, &occupant(activity)).is_some())
+                .count()
+        };
+        let baker = count(&mut world, "boulanger");
+        let ragman = count(&mut world, "chiffonnier");
+        assert!(baker > 50 && baker < 150, "baker {baker}");
+        assert!(ragman > 400, "ragman {ragman}");
+    }
+
+    #[test]
+    fn test_theft_on_same_tile_counts_in_quartier() {
+        let mut world = World::new_with_seed(42);
+        let residents = faction(&mut world, "residents");
+        world.tiles.set_quartier_id(5, 5, 1);
+        world.gis.quartiers = crate::registry::QuartierRegistry::build_from_registries(
+            &["Arcis".to_string()],
+            &world.gis.buildings,
+            &crate::registry::BlockRegistry::new(),
+            1845,
+        );
+        let thief = person(&mut world, residents, 5, 5);
+        let victim = person(&mut world, residents, 5, 5);
+        world.mind.criminals.insert(
+            thief,
+            Criminal {
+                crime: Crime::Theft,
+                last_offence: None,
+            },
+        );
+        intend(&mut world, thief, ActionId::Rob, victim);
+
+        run_crime(&mut world, Tick(0));
+        assert!(world.events.iter().any(|ev| matches!(
+            ev,
+            Event::Theft { thief: t, victim: v, .. } if *t == thief && *v == victim
+        )));
+        assert_eq!(world.mind.criminals[&thief].last_offence, Some(Tick(0)));
+        let stats = world.gis.quartiers.get(QuartierId(1)).map(|q| q.crime);
+        assert_eq!(stats.map(|s| s.thefts), Some(1));
+
+        // No second offence until the interval passes.
+        world.tick = Tick(10);
+        run_crime(&mut world, Tick(10));
+        let thefts = world
+            .events
+            .iter()
+            .filter(|ev| matches!(ev, Event::Theft { .. }))
+            .count();
+        assert_eq!(thefts, 1);
+    }
+
+    #[test]
+    fn test_theft_takes_carried_food_then_weapon() {
+        let mut world = World::new_with_seed(42);
+        let residents = faction(&mut world, "residents");
+        let thief = person(&mut world, residents, 5, 5);
+        let victim = person(&mut world, residents, 5, 5);
+        world.mind.criminals.insert(
+            thief,
+            Criminal {
+                crime: Crime::Theft,
+                last_offence: None,
+            },
+        );
+        let bread = world.spawn();
+        world.body.positions.insert(bread, Position { x: 5, y: 5 });
+        world
+            .mind
+            .nutritions
+            .insert(bread, Nutrition { value: 30.0 });
+        world.mind.carried_by.insert(bread, victim);
+        world.body.weapons.insert(victim, Weapon::Cane);
+
+        assert_eq!(steal(&mut world, thief, victim), Some(Loot::Food(bread)));
+        assert_eq!(world.mind.carried_by.get(&bread), Some(&thief));
+        assert_eq!(
+            steal(&mut world, thief, victim),
+            Some(Loot::Weapon(Weapon::Cane))
+        );
+        assert_eq!(world.body.weapons.get(&thief), Some(&Weapon::Cane));
+        assert!(!world.body.weapons.contains_key(&victim));
+        assert_eq!(steal(&mut world, thief, victim), None);
+
+        // The thief keeps the loot without a plan for it.
+        crate::systems::plans::run_plans(&mut world, Tick(0));
+        assert_eq!(world.mind.carried_by.get(&bread), Some(&thief));
+    }
+
+    #[test]
+    fn test_victim_selection_avoids_police() {
+        let mut world = World::new_with_seed(42);
+        let residents = faction(&mut world, "residents");
+        let police = faction(&mut world, "police");
+        let thief = person(&mut world, residents, 5, 5);
+        let victim = person(&mut world, residents, 8, 5);
+        world.mind.criminals.insert(
+            thief,
+            Criminal {
+                crime: Crime::Theft,
+                last_offence: None,
+            },
+        );
+        let remember = |world: &mut World, seen: Entity, x: i32| {
+            world
+                .mind
+                .memories
+                .entry(thief)
+                .or_default()
+                .sightings
+                .insert(
+                    seen,
+                    Sighting {
+                        x,
+                        y: 5,
+                        tick: Tick(0),
+                    },
+                );
+        };
+        remember(&mut world, victim, 8);
+        assert_eq!(select_victim(&world, thief), Some(victim));
+
+        let officer = person(&mut world, police, 20, 5);
+        remember(&mut world, officer, 20);
+        assert_eq!(select_victim(&world, thief), None);
+    }
+
+    #[test]
+    fn test_arrest_imprisons_and_releases() {
+        let mut world = World::new_with_seed(42);
+        let residents = faction(&mut world, "residents");
+        let police = faction(&mut world, "police");
+        let jail = prison(&mut world, vec![(40, 40), (41, 40)]);
+        let offender = person(&mut world, residents, 5, 5);
+        world.mind.criminals.insert(
+            offender,
+            Criminal {
+                crime: Crime::Assault,
+                last_offence: Some(Tick(0)),
+            },
+        );
+        world
+            .body
+            .fatigues
+            .insert(offender, Fatigue { current: 100.0 });
+        let officer = person(&mut world, police, 5, 5);
+        intend(&mut world, officer, ActionId::Defend, offender);
+
+        run_crime(&mut world, Tick(1));
+        let held = world.mind.imprisoned[&offender];
+        assert_eq!(held.prison, jail);
+        assert_eq!(held.release, Tick(1 + ASSAULT_SENTENCE));
+        let pos = world.body.positions[&offender];
+        assert!(pos.x >= 40 && pos.y == 40);
+        assert!(world.frozen.contains(&offender));
+        assert!(world.events.iter().any(|ev| matches!(
+            ev,
+            Event::Arrested { officer: o, offender: x, .. } if *o == officer && *x == offender
+        )));
+
+        run_crime(&mut world, held.release);
+        assert!(!world.mind.imprisoned.contains_key(&offender));
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use crate::components::{ActionId, Tick};
+use crate::events::Event;
+use crate::world::World;
+
+/// Chebyshev radius around a danger source that raises path costs.
+pub const DANGER_RADIUS: i32 = 4;
+
+/// Ticks an attack site stays dangerous after the blow.
+pub const ATTACK_SITE_TICKS: u64 = 100;
+
+/// Extra cost for entering a source's own tile, in A* fixed-point units
+/// (100 = one cardinal step). Falls off linearly to the edge of the radius.
+const DANGER_PEAK: u32 = 400;
+
+/// Cap on stacked danger per tile, so a brawl doesn't wall off a street.
+const DANGER_CAP: u32 = 4 * DANGER_PEAK;
+
+/// A remembered attack location.
+#[derive(Debug, Clone, Copy)]
+struct DangerSite {
+    x: i32,
+    y: i32,
+    until: Tick,
+}
+
+/// Sparse per-tile extra path cost near fighting, recent attacks and fires.
+/// Rebuilt each tick by `run_danger`; read by movement and flee goals.
+#[derive(Default)]
+pub struct DangerMap {
+    sites: Vec<DangerSite>,
+    cost: HashMap<(i32, i32), u32>,
+}
+
+impl DangerMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Extra cost for entering tile (x, y); 0 when safe.
+    pub fn cost_at(&self, x: i32, y: i32) -> u32 {
+        self.cost.get(&(x, y)).copied().unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cost.is_empty()
+    }
+
+    /// Tiles with a nonzero cost, in no particular order.
+    pub fn tiles(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.cost.keys().copied()
+    }
+
+    fn stamp(&mut self, x: i32, y: i32) {
+        for dy in -DANGER_RADIUS..=DANGER_RADIUS {
+            for dx in -DANGER_RADIUS..=DANGER_RADIUS {
+                let falloff = (DANGER_RADIUS + 1 - dx.abs().max(dy.abs())) as u32;
+                let add = DANGER_PEAK * falloff / (DANGER_RADIUS + 1) as u32;
+                let c = self.cost.entry((x + dx, y + dy)).or_insert(0);
+                *c = (*c + add).min(DANGER_CAP);
+            }
+        }
+    }
+}
+
+/// Phase 3 (Decisions): rebuild the danger map.
+///
+/// Runs after `run_reactions`, before `run_decisions`. Sources are:
+/// - where last tick's `Event::Attacked` landed (the defender's tile), kept
+///   for `ATTACK_SITE_TICKS`;
+/// - every entity whose last intention was Attack or Defend, this tick only;
+/// - every burning tile (`gis.fires`), while it burns.
+pub fn run_danger(world: &mut World, tick: Tick) {
+    let mut new_sites: Vec<DangerSite> = Vec::new();
+    if tick.0 > 0 {
+        let last = Tick(tick.0 - 1);
+        for event in world.events.since(last) {
+            if let Event::Attacked {
+                defender, tick: t, ..
+            } = *event
+                && t == last
+                && let Some(p) = world.body.positions.get(&defender)
+            {
+                new_sites.push(DangerSite {
+                    x: p.x,
+                    y: p.y,
+                    until: Tick(tick.0 + ATTACK_SITE_TICKS),
+                });
+            }
+        }
+    }
+
+    let mut fighters: Vec<(i32, i32)> = world
+        .mind
+        .intentions
+        .iter()
+        .filter(|(_, i)| matches!(i.action, ActionId::Attack | ActionId::Defend))
+        .filter(|(e, _)| !world.pending_deaths.contains(e))
+        .filter_map(|(e, _)| world.body.positions.get(e).map(|p| (p.x, p.y)))
+        .collect();
+    fighters.sort_unstable();
+    let mut flames: Vec<(i32, i32)> = world
+        .gis
+        .fires
+        .values()
+        .flat_map(|f| f.burning.keys().copied())
+        .collect();
+    flames.sort_unstable();
+
+    let danger = &mut world.danger;
+    danger.sites.retain(|s| s.until > tick);
+    danger.sites.extend(new_sites);
+    danger.cost.clear();
+    let sites: Vec<(i32, i32)> = danger.sites.iter().map(|s| (s.x, s.y)).collect();
+    for (x, y) in sites.into_iter().chain(fighters).chain(flames) {
+        danger.stamp(x, y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::*;
+
+    fn entity_at(world: &mut World, x: i32, y: i32) -> Entity {
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x, y });
+        e
+    }
+
+    #[test]
+    fn test_attack_site_raises_cost_then_expires() {
+        let mut world = World::new_with_seed(42);
+        let a = entity_at(&mut world, 10, 10);
+        let d = entity_at(&mut world, 10, 10);
+        world.events.push(Event::Attacked {
+            attacker: a,
+            defender: d,
+            damage: 5.0,
+            tick: Tick(0),
+        });
+        run_danger(&mut world, Tick(1));
+        assert_eq!(world.danger.cost_at(10, 10), DANGER_PEAK);
+        assert!(world.danger.cost_at(10 + DANGER_RADIUS, 10) > 0);
+        assert_eq!(world.danger.cost_at(11 + DANGER_RADIUS, 10), 0);
+        assert!(world.danger.cost_at(12, 10) < world.danger.cost_at(11, 10));
+
+        run_danger(&mut world, Tick(1 + ATTACK_SITE_TICKS));
+        assert!(world.danger.is_empty());
+    }
+
+    #[test]
+    fn test_fighters_are_transient_sources() {
+        let mut world = World::new_with_seed(42);
+        let a = entity_at(&mut world, 20, 20);
+        world.mind.intentions.insert(
+            a,
+            Intention {
+                action: ActionId::Attack,
+                target: None,
+            },
+        );
+        run_danger(&mut world, Tick(0));
+        assert_eq!(world.danger.cost_at(20, 20), DANGER_PEAK);
+
+        world.mind.intentions.clear();
+        run_danger(&mut world, Tick(1));
+        assert!(world.danger.is_empty());
+    }
+}
@@ -2,12 +2,17 @@ use std::collections::BTreeMap;
 
 use serde::Deserialize;
 
-use crate::components::{ActionId, Entity, Intention, Tick};
+use crate::components::{ActionId, Entity, Gait, GameDate, Intention, Plan, Tick};
+use crate::faction::{self, Stance};
+use crate::lod::LodZone;
+use crate::registry::{BuildingId, SHOP_CLOSES, SHOP_OPENS};
+use crate::systems::crime;
+use crate::systems::fatigue::UNCONSCIOUS_THRESHOLD;
+use crate::systems::fire;
+use crate::systems::perception::{SENSE_RANGE, remembered};
+use crate::systems::plans;
 use crate::world::World;
 
-/// Chebyshev distance within which entities sense food/enemies (30 meters).
-const SENSE_RANGE: i32 = 30;
-
 // ---------------------------------------------------------------------------
 // Config types — scoring internals, not per-entity data
 // ---------------------------------------------------------------------------
@@ -35,6 +40,24 @@ pub enum InputAxis {
     FatigueRatio,
     FoodNearby,
     EnemyNearby,
+    /// 1.0 if a feared entity (see `run_reactions`) is remembered nearby;
+    /// otherwise `1 - HealthRatio` if any enemy is, else 0.0.
+    Threat,
+    /// 1.0 if an entity we hold a grudge against is remembered nearby.
+    Provoked,
+    /// 1.0 if an unconscious ally is remembered nearby and nobody else is
+    /// already dragging them.
+    AllyDown,
+    /// 1.0 for a criminal whose last offence is `OFFENCE_INTERVAL` behind.
+    CriminalUrge,
+    /// 1.0 if someone worth robbing is remembered nearby and no police are
+    /// (see `crime::select_victim`).
+    VictimNearby,
+    /// 1.0 for members of the police faction.
+    OnDuty,
+    /// 1.0 for a firefighter with a burning building within
+    /// `fire::FIRE_RESPONSE_RANGE`.
+    FireReported,
     Aggression,
     Constant(f32),
 }
@@ -112,14 +135,15 @@ fn read_input(axis: &InputAxis, world: &World, entity: Entity) -> f32 {
                 0.0 // no fatigue component = no fatigue
             }
         }
+        // Nearby = remembered (see `run_perception`) and last seen within SENSE_RANGE.
         InputAxis::FoodNearby => {
             let Some(pos) = world.body.positions.get(&entity) else {
                 return 0.0;
             };
-            let count = world
-                .entities_in_range(pos.x, pos.y, SENSE_RANGE)
-                .filter(|e| !world.pending_deaths.contains(e))
-                .filter(|e| world.mind.nutritions.contains_key(e))
+            let count = remembered(world, entity)
+                .into_iter()
+                .filter(|(_, s)| (s.x - pos.x).abs().max((s.y - pos.y).abs()) <= SENSE_RANGE)
+                .filter(|(e, _)| world.mind.nutritions.contains_key(e))
                 .count();
             (count.min(3) as f32) / 3.0
         }
@@ -127,14 +151,65 @@ fn read_input(axis: &InputAxis, world: &World, entity: Entity) -> f32 {
             let Some(pos) = world.body.positions.get(&entity) else {
                 return 0.0;
             };
-            let count = world
-                .entities_in_range(pos.x, pos.y, SENSE_RANGE)
-                .filter(|&e| e != entity)
-                .filter(|e| !world.pending_deaths.contains(e))
-                .filter(|e| world.body.combat_stats.contains_key(e))
+            let count = remembered(world, entity)
+                .into_iter()
+                .filter(|(_, s)| (s.x - pos.x).abs().max((s.y - pos.y).abs()) <= SENSE_RANGE)
+                .filter(|(e, _)| world.body.combat_stats.contains_key(e))
+                .filter(|&(e, _)| faction::is_hostile(world, entity, e))
                 .count();
             (count.min(3) as f32) / 3.0
         }
+        InputAxis::Threat => {
+            if nearest_grievance(world, entity, |g| &g.fears).is_some() {
+                1.0
+            } else if nearest_enemy(world, entity).is_some() {
+                1.0 - read_input(&InputAxis::HealthRatio, world, entity)
+            } else {
+                0.0
+            }
+        }
+        InputAxis::Provoked => {
+            if select_defend_target(world, entity).is_some() {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        InputAxis::AllyDown => {
+            if select_rescue_target(world, entity).is_some() {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        InputAxis::CriminalUrge => {
+            if crime::has_urge(world, entity) {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        InputAxis::VictimNearby => {
+            if crime::select_victim(world, entity).is_some() {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        InputAxis::OnDuty => {
+            if crime::is_police(world, entity) {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        InputAxis::FireReported => {
+            if fire::fire_reported(world, entity) {
+                1.0
+            } else {
+                0.0
+            }
+        }
         InputAxis::Aggression => {
             if let Some(cs) = world.body.combat_stats.get(&entity) {
                 cs.aggression
@@ -150,15 +225,22 @@ fn read_input(axis: &InputAxis, world: &World, entity: Entity) -> f32 {
 // Target selection
 // ---------------------------------------------------------------------------
 
-fn select_eat_target(world: &World, entity: Entity) -> Option<Entity> {
+// Targets come from perception memory, ranked by last-seen position.
+
+pub(crate) fn select_eat_target(world: &World, entity: Entity) -> Option<Entity> {
     let pos = world.body.positions.get(&entity)?;
-    world
-        .entities_in_range(pos.x, pos.y, SENSE_RANGE)
-        .filter(|e| !world.pending_deaths.contains(e))
-        .filter_map(|e| {
+    remembered(world, entity)
+        .into_iter()
+        .filter(|(e, _)| {
+            world
+                .mind
+                .carried_by
+                .get(e)
+                .is_none_or(|&carrier| carrier == entity)
+        })
+        .filter_map(|(e, seen)| {
             let n = world.mind.nutritions.get(&e)?;
-            let fp = world.body.positions.get(&e)?;
-            let dist = (fp.x - pos.x).abs().max((fp.y - pos.y).abs());
+            let dist = (seen.x - pos.x).abs().max((seen.y - pos.y).abs());
             Some((e, dist, n.value))
         })
         .min_by(|a, b| {
@@ -172,15 +254,14 @@ fn select_eat_target(world: &World, entity: Entity) -> Option<Entity> {
 
 fn select_attack_target(world: &World, entity: Entity) -> Option<Entity> {
     let pos = world.body.positions.get(&entity)?;
-    world
-        .entities_in_range(pos.x, pos.y, SENSE_RANGE)
-        .filter(|&e| e != entity)
-        .filter(|e| !world.pending_deaths.contains(e))
-        .filter_map(|e| {
+    remembered(world, entity)
+        .into_iter()
+        .filter(|&(e, _)| e != entity)
+        .filter(|&(e, _)| faction::is_hostile(world, entity, e))
+        .filter_map(|(e, seen)| {
             let _ = world.body.combat_stats.get(&e)?;
             let health = world.body.healths.get(&e)?;
-            let ep = world.body.positions.get(&e)?;
-            let dist = (ep.x - pos.x).abs().max((ep.y - pos.y).abs());
+            let dist = (seen.x - pos.x).abs().max((seen.y - pos.y).abs());
             Some((e, dist, health.current))
         })
         .min_by(|a, b| {
@@ -192,16 +273,142 @@ fn select_attack_target(world: &World, entity: Entity) -> Option<Entity> {
         .map(|(e, _, _)| e)
 }
 
+/// Nearest remembered entity within SENSE_RANGE whose grievance entry
+/// (fear or grudge, per `pick`) names it. Ties to lowest entity ID.
+fn nearest_grievance(
+    world: &World,
+    entity: Entity,
+    pick: impl Fn(&crate::components::Grievances) -> &std::collections::HashMap<Entity, Tick>,
+) -> Option<Entity> {
+    let pos = world.body.positions.get(&entity)?;
+    let listed = pick(world.mind.grievances.get(&entity)?);
+    remembered(world, entity)
+        .into_iter()
+        .filter(|(e, _)| listed.contains_key(e))
+        .map(|(e, seen)| (e, (seen.x - pos.x).abs().max((seen.y - pos.y).abs())))
+        .filter(|&(_, dist)| dist <= SENSE_RANGE)
+        .min_by_key(|&(e, dist)| (dist, e.0))
+        .map(|(e, _)| e)
+}
+
+/// Nearest remembered hostile combatant within SENSE_RANGE.
+fn nearest_enemy(world: &World, entity: Entity) -> Option<Entity> {
+    let pos = world.body.positions.get(&entity)?;
+    remembered(world, entity)
+        .into_iter()
+        .filter(|&(e, _)| e != entity && world.body.combat_stats.contains_key(&e))
+        .filter(|&(e, _)| faction::is_hostile(world, entity, e))
+        .map(|(e, seen)| (e, (seen.x - pos.x).abs().max((seen.y - pos.y).abs())))
+        .filter(|&(_, dist)| dist <= SENSE_RANGE)
+        .min_by_key(|&(e, dist)| (dist, e.0))
+        .map(|(e, _)| e)
+}
+
+/// Feared entities first, then the nearest enemy.
+fn select_flee_target(world: &World, entity: Entity) -> Option<Entity> {
+    nearest_grievance(world, entity, |g| &g.fears).or_else(|| nearest_enemy(world, entity))
+}
+
+fn select_defend_target(world: &World, entity: Entity) -> Option<Entity> {
+    nearest_grievance(world, entity, |g| &g.grudges)
+}
+
+/// The ally we are already dragging, else the nearest remembered unconscious
+/// ally within SENSE_RANGE that nobody else is dragging. Ties to lowest ID.
+fn select_rescue_target(world: &World, entity: Entity) -> Option<Entity> {
+    let dragging = world
+        .mind
+        .dragged_by
+        .iter()
+        .filter(|&(_, &r)| r == entity)
+        .map(|(&p, _)| p)
+        .min_by_key(|p| p.0);
+    if dragging.is_some() {
+        return dragging;
+    }
+    let pos = world.body.positions.get(&entity)?;
+    remembered(world, entity)
+        .into_iter()
+        .filter(|&(e, _)| e != entity && !world.mind.dragged_by.contains_key(&e))
+        .filter(|(e, _)| {
+            world
+                .body
+                .fatigues
+                .get(e)
+                .is_some_and(|f| f.current >= UNCONSCIOUS_THRESHOLD)
+        })
+        .filter(|&(e, _)| faction::stance_between(world, entity, e) == Stance::Allied)
+        .map(|(e, seen)| (e, (seen.x - pos.x).abs().max((seen.y - pos.y).abs())))
+        .filter(|&(_, dist)| dist <= SENSE_RANGE)
+        .min_by_key(|&(e, dist)| (dist, e.0))
+        .map(|(e, _)| e)
+}
+
+/// Gait an action calls for: run from danger, hurry to a fight or a
+/// casualty, otherwise walk.
+pub fn gait_for(action: ActionId) -> Gait {
+    match action {
+        ActionId::Flee => Gait::Run,
+        ActionId::Attack | ActionId::Defend | ActionId::Rescue | ActionId::Extinguish => {
+            Gait::Hustle
+        }
+        ActionId::Idle
+        | ActionId::Wander
+        | ActionId::Eat
+        | ActionId::PickUp
+        | ActionId::Rob
+        | ActionId::Patrol => Gait::Walk,
+    }
+}
+
+/// `e`'s workplace if it is working hours (`SHOP_OPENS` to `SHOP_CLOSES`).
+pub fn on_shift(world: &World, e: Entity) -> Option<BuildingId> {
+    let work = world.gis.workplaces.get(&e)?.0;
+    let hour = GameDate::from_tick(world.tick, &world.start_date).hour;
+    (u32::from(SHOP_OPENS)..u32::from(SHOP_CLOSES))
+        .contains(&hour)
+        .then_some(work)
+}
+
+/// Whether `e` should be at work by now but isn't standing in its
+/// workplace.
+pub fn late_for_work(world: &World, e: Entity) -> bool {
+    on_shift(world, e).is_some_and(|work| {
+        world.body.positions.get(&e).is_some_and(|p| {
+            p.x < 0
+                || p.y < 0
+                || world.tiles.get_building_id(p.x as usize, p.y as usize) != Some(work)
+        })
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Scorer system
 // ---------------------------------------------------------------------------
 
-// Single-writer invariant: only `run_decisions` writes `intentions` and
-// `action_states` per tick. Phase 4 systems read intentions but never modify
-// them. Spawn may initialize `ActionState`; no other system mutates it.
-pub fn run_decisions(world: &mut World, _tick: Tick) {
-    // Wipe stale intentions
-    world.mind.intentions.clear();
+// Single-writer invariant: only `run_decisions` writes `intentions`,
+// `action_states`, `plans` and (after spawn) `current_gaits` per tick.
+// Phase 4 systems read intentions but never modify them. Spawn may
+// initialize `ActionState`; no other system mutates it.
+pub fn run_decisions(world: &mut World, tick: Tick) {
+    // Wipe stale intentions. A Nearby entity between steps keeps its own:
+    // the action it chose carries on until it decides again.
+    let stale: Vec<Entity> = world
+        .mind
+        .intentions
+        .keys()
+        .filter(|&&e| {
+            world.lod_zone_of(e) != LodZone::Nearby
+                || world.lod_step(e, tick) > 0
+                || world.pending_deaths.contains(&e)
+                || world.frozen.contains(&e)
+                || world.player == Some(e)
+        })
+        .copied()
+        .collect();
+    for e in stale {
+        world.mind.intentions.remove(&e);
+    }
 
     if world.mind.utility_config.actions.is_empty() {
         return;
@@ -213,21 +420,25 @@ pub fn run_decisions(world: &mut World, _tick: Tick) {
         .action_states
         .keys()
         .filter(|e| !world.pending_deaths.contains(e))
+        .filter(|e| !world.frozen.contains(e))
         .filter(|e| world.player != Some(**e))
+        .filter(|e| world.lod_step(**e, tick) > 0)
         .copied()
         .collect();
     entities.sort_by_key(|e| e.0);
 
-    // Decrement all cooldowns first (collect-then-apply)
+    // Decrement all cooldowns first (collect-then-apply). Nearby entities
+    // decide once per interval and count down the whole interval.
     let cooldown_decrements: Vec<(Entity, Vec<(ActionId, u64)>)> = entities
         .iter()
         .filter_map(|&e| {
             let state = world.mind.action_states.get(&e)?;
+            let step = world.lod_step(e, tick);
             let updates: Vec<(ActionId, u64)> = state
                 .cooldowns
                 .iter()
                 .filter(|&(_, &cd)| cd > 0)
-                .map(|(&action, &cd)| (action, cd - 1))
+                .map(|(&action, &cd)| (action, cd.saturating_sub(step)))
                 .collect();
             if updates.is_empty() {
                 None
@@ -247,7 +458,7 @@ pub fn run_decisions(world: &mut World, _tick: Tick) {
 
     // Score and decide for each entity
     let config = world.mind.utility_config.clone();
-    let mut decision_changes: Vec<(Entity, ActionId, Option<Entity>)> = Vec::new();
+    let mut decision_changes: Vec<(Entity, ActionId, Intention, Option<Plan>)> = Vec::new();
 
     for &entity in &entities {
         let current_action = world
@@ -326,34 +537,60 @@ pub fn run_decisions(world: &mut World, _tick: Tick) {
             }
         }
 
-        // Select target
-        let target = match best_action {
-            ActionId::Eat => select_eat_target(world, entity),
-            ActionId::Attack => select_attack_target(world, entity),
-            _ => None,
+        // Multi-step actions follow their plan; others select a target directly
+        let plan = plans::update_plan(world, entity, best_action);
+        let (action, target) = match &plan {
+            Some(p) => plans::step_intention(p),
+            None => {
+                let target = match best_action {
+                    ActionId::Eat => select_eat_target(world, entity),
+                    ActionId::Attack => select_attack_target(world, entity),
+                    ActionId::Flee => select_flee_target(world, entity),
+                    ActionId::Defend => select_defend_target(world, entity),
+                    ActionId::Rescue => select_rescue_target(world, entity),
+                    ActionId::Rob => crime::select_victim(world, entity),
+                    _ => None,
+                };
+                (best_action, target)
+            }
         };
 
-        decision_changes.push((entity, best_action, target));
+        decision_changes.push((entity, best_action, Intention { action, target }, plan));
     }
 
     // Apply decision changes
-    for (entity, action, target) in decision_changes {
+    for (entity, action, intention, plan) in decision_changes {
         let old_action = world
             .mind
             .action_states
             .get(&entity)
             .and_then(|s| s.current_action);
 
-        // Write intention
-        world
-            .mind
-            .intentions
-            .insert(entity, Intention { action, target });
+        // Write intention, gait and plan
+        world.mind.intentions.insert(entity, intention);
+        if world.body.gait_profiles.contains_key(&entity) {
+            // Wandering workers hurry when they should be at work already.
+            let gait = if action == ActionId::Wander && late_for_work(world, entity) {
+                Gait::Hustle
+            } else {
+                gait_for(action)
+            };
+            world.body.current_gaits.insert(entity, gait);
+        }
+        match plan {
+            Some(p) => {
+                world.mind.plans.insert(entity, p);
+            }
+            None => {
+                world.mind.plans.remove(&entity);
+            }
+        }
 
         // Update action state
+        let step = world.lod_step(entity, tick);
         if let Some(state) = world.mind.action_states.get_mut(&entity) {
             if Some(action) == old_action {
-                state.ticks_in_action += 1;
+                state.ticks_in_action += step;
             } else {
                 // Action changed: set cooldown on old action
                 if let Some(old) = old_action
@@ -377,6 +614,7 @@ pub fn run_decisions(world: &mut World, _tick: Tick) {
 mod tests {
     use super::*;
     use crate::components::*;
+    use crate::systems::perception::run_perception;
     use crate::world::World;
     use std::collections::HashMap;
 
@@ -624,6 +862,7 @@ mod tests {
         world.mind.nutritions.insert(f2, Nutrition { value: 20.0 });
 
         world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
         let val = read_input(&InputAxis::FoodNearby, &world, e);
         assert!((val - 2.0 / 3.0).abs() < 0.001);
     }
@@ -689,6 +928,76 @@ mod tests {
         assert_eq!(intention.action, ActionId::Wander);
     }
 
+    #[test]
+    fn test_decision_sets_gait() {
+        let mut world = World::new_with_seed(42);
+        world.mind.utility_config = default_config();
+        let e = spawn_with_action_state(&mut world);
+        world.body.positions.insert(e, Position { x: 5, y: 5 });
+        world.body.gait_profiles.insert(e, GaitProfile::biped());
+        world.body.current_gaits.insert(e, Gait::Sprint);
+
+        run_decisions(&mut world, Tick(0));
+        assert_eq!(world.mind.intentions[&e].action, ActionId::Wander);
+        assert_eq!(world.body.current_gaits.get(&e), Some(&Gait::Walk));
+        assert_eq!(gait_for(ActionId::Flee), Gait::Run);
+        assert_eq!(gait_for(ActionId::Rescue), Gait::Hustle);
+    }
+
+    #[test]
+    fn test_late_worker_hustles() {
+        use crate::registry::BuildingId;
+
+        let mut world = World::new_with_seed(42);
+        world.mind.utility_config = default_config();
+        world.tiles.set_building_id(20, 20, BuildingId(1));
+        let e = spawn_with_action_state(&mut world);
+        world.body.positions.insert(e, Position { x: 5, y: 5 });
+        world.body.gait_profiles.insert(e, GaitProfile::biped());
+        world.gis.workplaces.insert(e, Workplace(BuildingId(1)));
+
+        // Before opening: no hurry.
+        world.tick = Tick(5 * 60);
+        run_decisions(&mut world, Tick(5 * 60));
+        assert_eq!(world.body.current_gaits.get(&e), Some(&Gait::Walk));
+
+        // Mid-morning, away from work: hurry.
+        world.tick = Tick(10 * 60);
+        run_decisions(&mut world, Tick(10 * 60));
+        assert_eq!(world.mind.intentions[&e].action, ActionId::Wander);
+        assert_eq!(world.body.current_gaits.get(&e), Some(&Gait::Hustle));
+
+        // At work: walk again.
+        world.body.positions.insert(e, Position { x: 20, y: 20 });
+        run_decisions(&mut world, Tick(10 * 60));
+        assert_eq!(world.body.current_gaits.get(&e), Some(&Gait::Walk));
+    }
+
+    #[test]
+    fn test_nearby_intention_kept_between_steps() {
+        use crate::lod::{LodZone, NEARBY_TICK_INTERVAL};
+        use crate::registry::QuartierId;
+
+        let mut world = World::new_with_seed(42);
+        world.mind.utility_config = default_config();
+        world.tiles.set_quartier_id(5, 5, 1);
+        world.gis.lod_zones.insert(QuartierId(1), LodZone::Nearby);
+        let e = spawn_with_action_state(&mut world);
+        world.body.positions.insert(e, Position { x: 5, y: 5 });
+
+        let step = NEARBY_TICK_INTERVAL - e.0 % NEARBY_TICK_INTERVAL;
+        run_decisions(&mut world, Tick(step));
+        let chosen = world.mind.intentions.get(&e).map(|i| i.action);
+        assert!(chosen.is_some());
+        run_decisions(&mut world, Tick(step + 1));
+        assert_eq!(world.mind.intentions.get(&e).map(|i| i.action), chosen);
+
+        // Frozen entities lose theirs even between steps.
+        world.frozen.insert(e);
+        run_decisions(&mut world, Tick(step + 2));
+        assert!(!world.mind.intentions.contains_key(&e));
+    }
+
     // --- Cooldown ---
 
     #[test]
@@ -727,6 +1036,7 @@ mod tests {
         }
 
         world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
         run_decisions(&mut world, Tick(0));
         let intention = world
             .mind
@@ -773,6 +1083,7 @@ mod tests {
 
         // Tick 0: decrement cooldown to 0, but Eat is still blocked (cd was 1 at start, decremented to 0)
         world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
         run_decisions(&mut world, Tick(0));
 
         // Tick 1: cooldown is now 0, Eat should be available
@@ -929,7 +1240,7 @@ mod tests {
     #[test]
     fn test_eat_selects_nearest_target() {
         let mut world = World::new_with_seed(42);
-        let e = world.spawn();
+        let e = spawn_with_action_state(&mut world);
         world.body.positions.insert(e, Position { x: 5, y: 5 });
 
         // Far food with high nutrition
@@ -944,14 +1255,43 @@ mod tests {
 
         // Nearest wins (distance 1 vs 10)
         world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
         let target = select_eat_target(&world, e);
         assert_eq!(target, Some(f2));
     }
 
+    #[test]
+    fn test_eat_ignores_food_behind_wall() {
+        let mut world = World::new_with_seed(42);
+        let e = spawn_with_action_state(&mut world);
+        world.body.positions.insert(e, Position { x: 5, y: 5 });
+
+        // Near food behind a wall, farther food in the open
+        let hidden = world.spawn();
+        world.body.positions.insert(hidden, Position { x: 8, y: 5 });
+        world
+            .mind
+            .nutritions
+            .insert(hidden, Nutrition { value: 10.0 });
+        let open = world.spawn();
+        world.body.positions.insert(open, Position { x: 5, y: 12 });
+        world
+            .mind
+            .nutritions
+            .insert(open, Nutrition { value: 10.0 });
+        world
+            .tiles
+            .set_terrain(7, 5, crate::tile_map::Terrain::Wall);
+
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+        assert_eq!(select_eat_target(&world, e), Some(open));
+    }
+
     #[test]
     fn test_eat_selects_highest_nutrition_at_same_distance() {
         let mut world = World::new_with_seed(42);
-        let e = world.spawn();
+        let e = spawn_with_action_state(&mut world);
         world.body.positions.insert(e, Position { x: 5, y: 5 });
 
         let f1 = world.spawn();
@@ -964,6 +1304,7 @@ mod tests {
 
         // Same distance → highest nutrition wins
         world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
         let target = select_eat_target(&world, e);
         assert_eq!(target, Some(f2));
     }
@@ -971,7 +1312,7 @@ mod tests {
     #[test]
     fn test_attack_selects_nearest_target() {
         let mut world = World::new_with_seed(42);
-        let e = world.spawn();
+        let e = spawn_with_action_state(&mut world);
         world.body.positions.insert(e, Position { x: 5, y: 5 });
         world.body.combat_stats.insert(
             e,
@@ -1022,6 +1363,7 @@ mod tests {
 
         // Nearest wins (distance 1 vs 10)
         world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
         let target = select_attack_target(&world, e);
         assert_eq!(target, Some(t1));
     }
@@ -1029,7 +1371,7 @@ mod tests {
     #[test]
     fn test_attack_selects_lowest_health_at_same_distance() {
         let mut world = World::new_with_seed(42);
-        let e = world.spawn();
+        let e = spawn_with_action_state(&mut world);
         world.body.positions.insert(e, Position { x: 5, y: 5 });
         world.body.combat_stats.insert(
             e,
@@ -1078,7 +1420,159 @@ mod tests {
 
         // Same distance → lowest health wins
         world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
         let target = select_attack_target(&world, e);
         assert_eq!(target, Some(t2));
     }
+
+    #[test]
+    fn test_attack_ignores_allied_faction() {
+        use crate::faction::{AttackResponse, FactionDef};
+        let mut world = World::new_with_seed(42);
+        let police = world.mind.factions.insert(FactionDef {
+            key: "police".into(),
+            name: "Police".into(),
+            local: false,
+            when_attacked: AttackResponse::Fight,
+            calls_for_help: true,
+            defends_allies: true,
+        });
+        let member = FactionMember {
+            faction: police,
+            quartier: None,
+        };
+        let e = spawn_with_action_state(&mut world);
+        let ally = world.spawn();
+        for (who, x) in [(e, 5), (ally, 6)] {
+            world.body.positions.insert(who, Position { x, y: 5 });
+            world.body.combat_stats.insert(
+                who,
+                CombatStats {
+                    attack: 10.0,
+                    defense: 5.0,
+                    aggression: 0.8,
+                },
+            );
+            world.body.healths.insert(
+                who,
+                Health {
+                    current: 100.0,
+                    max: 100.0,
+                },
+            );
+            world.mind.faction_members.insert(who, member);
+        }
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+        assert_eq!(select_attack_target(&world, e), None);
+        assert_eq!(read_input(&InputAxis::EnemyNearby, &world, e), 0.0);
+    }
+
+    #[test]
+    fn test_fear_selects_flee_target() {
+        let mut world = World::new_with_seed(42);
+        let e = spawn_with_action_state(&mut world);
+        world.body.positions.insert(e, Position { x: 5, y: 5 });
+        world.body.healths.insert(
+            e,
+            Health {
+                current: 100.0,
+                max: 100.0,
+            },
+        );
+        let thief = world.spawn();
+        world.body.positions.insert(thief, Position { x: 8, y: 5 });
+        world.body.combat_stats.insert(
+            thief,
+            CombatStats {
+                attack: 10.0,
+                defense: 5.0,
+                aggression: 0.5,
+            },
+        );
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+        assert_eq!(read_input(&InputAxis::Threat, &world, e), 0.0);
+
+        let mut g = Grievances::default();
+        g.fears.insert(thief, Tick(100));
+        world.mind.grievances.insert(e, g);
+        assert_eq!(select_flee_target(&world, e), Some(thief));
+        assert_eq!(read_input(&InputAxis::Threat, &world, e), 1.0);
+        assert_eq!(select_defend_target(&world, e), None);
+    }
+
+    #[test]
+    fn test_badly_hurt_flees_any_enemy() {
+        let mut world = World::new_with_seed(42);
+        let e = spawn_with_action_state(&mut world);
+        world.body.positions.insert(e, Position { x: 5, y: 5 });
+        world.body.healths.insert(
+            e,
+            Health {
+                current: 20.0,
+                max: 100.0,
+            },
+        );
+        let wolf = world.spawn();
+        world.body.positions.insert(wolf, Position { x: 7, y: 5 });
+        world.body.combat_stats.insert(
+            wolf,
+            CombatStats {
+                attack: 10.0,
+                defense: 5.0,
+                aggression: 0.9,
+            },
+        );
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+        assert!((read_input(&InputAxis::Threat, &world, e) - 0.8).abs() < 0.001);
+        assert_eq!(select_flee_target(&world, e), Some(wolf));
+    }
+
+    #[test]
+    fn test_rescue_targets_unconscious_ally() {
+        let mut world = World::new_with_seed(42);
+        let e = spawn_with_action_state(&mut world);
+        world.body.positions.insert(e, Position { x: 5, y: 5 });
+        let ally = world.spawn();
+        world.body.positions.insert(ally, Position { x: 9, y: 5 });
+        world.body.combat_stats.insert(
+            ally,
+            CombatStats {
+                attack: 10.0,
+                defense: 5.0,
+                aggression: 0.0,
+            },
+        );
+        world.body.fatigues.insert(ally, Fatigue { current: 50.0 });
+        let police = world.mind.factions.insert(crate::faction::FactionDef {
+            key: "police".into(),
+            name: "Police".into(),
+            local: false,
+            when_attacked: crate::faction::AttackResponse::Fight,
+            calls_for_help: true,
+            defends_allies: true,
+        });
+        for who in [e, ally] {
+            world.mind.faction_members.insert(
+                who,
+                FactionMember {
+                    faction: police,
+                    quartier: None,
+                },
+            );
+        }
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+        assert_eq!(select_rescue_target(&world, e), None, "ally is awake");
+
+        world.body.fatigues.insert(ally, Fatigue { current: 120.0 });
+        assert_eq!(select_rescue_target(&world, e), Some(ally));
+        assert_eq!(read_input(&InputAxis::AllyDown, &world, e), 1.0);
+
+        let other = world.spawn();
+        world.mind.dragged_by.insert(ally, other);
+        assert_eq!(select_rescue_target(&world, e), None, "already dragged");
+    }
 }
@@ -4,14 +4,20 @@ use crate::components::{ActionId, Entity, Tick};
 use crate::events::Event;
 use crate::world::World;
 
-/// Pickup range: same tile = within 1 meter.
+/// Phase 4 (Actions): entities with an Eat intention eat food on their
+/// tile. Pickup range: same tile = within 1 meter.
+///
+/// Runs on each eater's `World::lod_step` ticks only: a Nearby eater eats
+/// up to `NEARBY_TICK_INTERVAL` items at once, stopping when sated, as an
+/// Active one would over the interval.
 pub fn run_eating(world: &mut World, tick: Tick) {
-    // Collect hungry entities and their positions, sorted for determinism
-    let mut hungry: Vec<(Entity, i32, i32, f32)> = world
+    // Collect hungry entities, their positions and steps, sorted for determinism
+    let mut hungry: Vec<(Entity, i32, i32, u64)> = world
         .mind
         .hungers
         .iter()
         .filter(|&(&e, _)| !world.pending_deaths.contains(&e))
+        .filter(|&(&e, _)| !world.frozen.contains(&e))
         .filter(|&(&e, _)| world.player != Some(e))
         .filter(|&(&e, _)| {
             let Some(intention) = world.mind.intentions.get(&e) else {
@@ -19,12 +25,22 @@ pub fn run_eating(world: &mut World, tick: Tick) {
             };
             match intention.action {
                 ActionId::Eat => true,
-                ActionId::Idle | ActionId::Wander | ActionId::Attack => false,
+                ActionId::Idle
+                | ActionId::Wander
+                | ActionId::Attack
+                | ActionId::PickUp
+                | ActionId::Flee
+                | ActionId::Defend
+                | ActionId::Rescue
+                | ActionId::Rob
+                | ActionId::Patrol
+                | ActionId::Extinguish => false,
             }
         })
         .filter_map(|(&e, _)| {
             let pos = world.body.positions.get(&e)?;
-            Some((e, pos.x, pos.y, 0.0))
+            let step = world.lod_step(e, tick);
+            (step > 0).then_some((e, pos.x, pos.y, step))
         })
         .collect();
     hungry.sort_by_key(|(e, _, _, _)| e.0);
@@ -33,40 +49,18 @@ pub fn run_eating(world: &mut World, tick: Tick) {
     let mut eat_changes: Vec<(Entity, Entity, f32)> = Vec::new(); // (eater, food, nutrition)
     let mut consumed: HashSet<Entity> = HashSet::new();
 
-    for (eater, ex, ey, _) in &hungry {
-        // Prefer intention target if set and valid (same tile, has nutrition)
-        if let Some(target) = world.mind.intentions.get(eater).and_then(|i| i.target)
-            && !consumed.contains(&target)
-            && !world.pending_deaths.contains(&target)
-            && let Some(n) = world.mind.nutritions.get(&target)
-            && let Some(fp) = world.body.positions.get(&target)
-            && fp.x == *ex
-            && fp.y == *ey
-            && n.value > 0.0
-        {
-            eat_changes.push((*eater, target, n.value));
-            consumed.insert(target);
-            continue;
-        }
-        // Fallback: first food at same position via spatial index
-        let mut candidates: Vec<(Entity, f32)> = world
-            .entities_at(*ex, *ey)
-            .filter(|e| !consumed.contains(e))
-            .filter(|e| !world.pending_deaths.contains(e))
-            .filter_map(|e| {
-                let n = world.mind.nutritions.get(&e)?;
-                if n.value > 0.0 {
-                    Some((e, n.value))
-                } else {
-                    None
-                }
-            })
-            .collect();
-        candidates.sort_unstable_by_key(|(e, _)| e.0); // determinism
-
-        if let Some(&(food_entity, nutrition_value)) = candidates.first() {
-            eat_changes.push((*eater, food_entity, nutrition_value));
-            consumed.insert(food_entity);
+    for &(eater, ex, ey, step) in &hungry {
+        let mut left = world.mind.hungers.get(&eater).map_or(0.0, |h| h.current);
+        for _ in 0..step {
+            let Some((food, nutrition)) = pick_food(world, eater, ex, ey, &consumed) else {
+                break;
+            };
+            eat_changes.push((eater, food, nutrition));
+            consumed.insert(food);
+            left -= nutrition;
+            if left <= 0.0 {
+                break;
+            }
         }
     }
 
@@ -87,6 +81,60 @@ pub fn run_eating(world: &mut World, tick: Tick) {
     }
 }
 
+/// Food `eater` can eat on its tile `(ex, ey)`, skipping `consumed`: its
+/// intention target if valid, else the first food there. Food carried by
+/// someone else is theirs.
+fn pick_food(
+    world: &World,
+    eater: Entity,
+    ex: i32,
+    ey: i32,
+    consumed: &HashSet<Entity>,
+) -> Option<(Entity, f32)> {
+    // Prefer intention target if set and valid (same tile, has nutrition)
+    if let Some(target) = world.mind.intentions.get(&eater).and_then(|i| i.target)
+        && !consumed.contains(&target)
+        && !world.pending_deaths.contains(&target)
+        && world
+            .mind
+            .carried_by
+            .get(&target)
+            .is_none_or(|&carrier| carrier == eater)
+        && let Some(n) = world.mind.nutritions.get(&target)
+        && let Some(fp) = world.body.positions.get(&target)
+        && fp.x == ex
+        && fp.y == ey
+        && world.storey_of(target) == world.storey_of(eater)
+        && n.value > 0.0
+    {
+        return Some((target, n.value));
+    }
+    // Fallback: first food at same position via spatial index.
+    let mut candidates: Vec<(Entity, f32)> = world
+        .entities_at(ex, ey)
+        .filter(|e| !consumed.contains(e))
+        .filter(|&e| world.storey_of(e) == world.storey_of(eater))
+        .filter(|e| !world.pending_deaths.contains(e))
+        .filter(|e| {
+            world
+                .mind
+                .carried_by
+                .get(e)
+                .is_none_or(|&carrier| carrier == eater)
+        })
+        .filter_map(|e| {
+            let n = world.mind.nutritions.get(&e)?;
+            if n.value > 0.0 {
+                Some((e, n.value))
+            } else {
+                None
+            }
+        })
+        .collect();
+    candidates.sort_unstable_by_key(|(e, _)| e.0); // determinism
+    candidates.first().copied()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,4 +268,54 @@ mod tests {
 
         assert_eq!(world.mind.hungers[&eater].current, 80.0); // unchanged
     }
+
+    #[test]
+    fn test_nearby_eater_eats_until_sated() {
+        use crate::lod::{LodZone, NEARBY_TICK_INTERVAL};
+        use crate::registry::QuartierId;
+
+        let mut world = World::new_with_seed(42);
+        world.tiles.set_quartier_id(5, 5, 1);
+        world.gis.lod_zones.insert(QuartierId(1), LodZone::Nearby);
+        let eater = world.spawn();
+        world.body.positions.insert(eater, Position { x: 5, y: 5 });
+        world.mind.hungers.insert(
+            eater,
+            Hunger {
+                current: 50.0,
+                max: 100.0,
+            },
+        );
+        let foods: Vec<Entity> = (0..4)
+            .map(|_| {
+                let food = world.spawn();
+                world.body.positions.insert(food, Position { x: 5, y: 5 });
+                world
+                    .mind
+                    .nutritions
+                    .insert(food, Nutrition { value: 20.0 });
+                food
+            })
+            .collect();
+        world.mind.intentions.insert(
+            eater,
+            Intention {
+                action: ActionId::Eat,
+                target: None,
+            },
+        );
+        world.rebuild_spatial_index();
+
+        let step = NEARBY_TICK_INTERVAL - eater.0 % NEARBY_TICK_INTERVAL;
+        run_eating(&mut world, Tick(step + 1));
+        assert!(world.pending_deaths.is_empty(), "between steps");
+
+        run_eating(&mut world, Tick(step));
+        assert_eq!(world.mind.hungers[&eater].current, 0.0);
+        let eaten = foods
+            .iter()
+            .filter(|f| world.pending_deaths.contains(f))
+            .count();
+        assert_eq!(eaten, 3);
+    }
 }
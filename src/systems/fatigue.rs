@@ -7,6 +7,8 @@ use rand::RngExt;
 const RECOVERY_RATE: f32 = 0.2; // 20/sec at 100 tps
 /// Faster recovery per tick when at or above unconscious threshold.
 const FAST_RECOVERY_RATE: f32 = 1.0; // 100/sec at 100 tps
+/// Recovery per tick for an unconscious unit an ally is tending (see `run_rescue`).
+const TENDED_RECOVERY_RATE: f32 = 2.0; // 200/sec at 100 tps
 /// Fatigue threshold at which a unit falls unconscious.
 pub const UNCONSCIOUS_THRESHOLD: f32 = 100.0;
 /// Fatigue threshold above which excess converts to HP damage.
@@ -14,31 +16,42 @@ const HP_DAMAGE_THRESHOLD: f32 = 200.0;
 
 /// Phase 2 (Needs): Natural fatigue recovery and excess fatigue HP damage.
 ///
-/// Reduces fatigue by RECOVERY_RATE per tick (FAST_RECOVERY_RATE if >= 100).
+/// Reduces fatigue by RECOVERY_RATE per tick (FAST_RECOVERY_RATE if >= 100,
+/// TENDED_RECOVERY_RATE if also being dragged/tended by an ally).
 /// If fatigue exceeds 200, converts excess to HP damage: 1 per 50 excess,
 /// with remainder having a (remainder*2)% chance of +1 more. Skips pending deaths.
+/// Nearby entities update once per `NEARBY_TICK_INTERVAL`, recovering and
+/// taking damage for the whole interval.
 pub fn run_fatigue(world: &mut World, tick: Tick) {
     // Collect recovery fatigue_changes (can't borrow world.rng while iterating fatigues)
-    let mut fatigue_changes: Vec<(Entity, f32)> = world
+    let mut fatigue_changes: Vec<(Entity, f32, f32)> = world
         .body
         .fatigues
         .iter()
         .filter(|&(&e, _)| !world.pending_deaths.contains(&e))
-        .map(|(&e, f)| {
-            let recovery = if f.current >= UNCONSCIOUS_THRESHOLD {
-                FAST_RECOVERY_RATE
-            } else {
-                RECOVERY_RATE
-            };
-            let new_fatigue = (f.current - recovery).max(0.0);
-            (e, new_fatigue)
+        .filter(|&(&e, _)| !world.frozen.contains(&e))
+        .filter_map(|(&e, f)| {
+            let step = world.lod_step(e, tick) as f32;
+            if step == 0.0 {
+                return None;
+            }
+            let recovery =
+                if f.current >= UNCONSCIOUS_THRESHOLD && world.mind.dragged_by.contains_key(&e) {
+                    TENDED_RECOVERY_RATE
+                } else if f.current >= UNCONSCIOUS_THRESHOLD {
+                    FAST_RECOVERY_RATE
+                } else {
+                    RECOVERY_RATE
+                };
+            let new_fatigue = (f.current - recovery * step).max(0.0);
+            Some((e, new_fatigue, step))
         })
         .collect();
     // Sort by entity ID for deterministic RNG consumption in the apply loop
-    fatigue_changes.sort_by_key(|(e, _)| e.0);
+    fatigue_changes.sort_by_key(|(e, _, _)| e.0);
 
     // Apply recovery and check for HP damage from excess fatigue
-    for (e, new_fatigue) in fatigue_changes {
+    for (e, new_fatigue, step) in fatigue_changes {
         if let Some(f) = world.body.fatigues.get_mut(&e) {
             f.current = new_fatigue;
         }
@@ -51,7 +64,7 @@ pub fn run_fatigue(world: &mut World, tick: Tick) {
             let chance = remainder * 2.0 / 100.0;
             let roll: f32 = world.rng.random();
             let bonus = if roll < chance { 1.0 } else { 0.0 };
-            let total_damage = guaranteed + bonus;
+            let total_damage = (guaranteed + bonus) * step;
 
             if total_damage > 0.0
                 && let Some(health) = world.body.healths.get_mut(&e)
@@ -102,6 +115,18 @@ mod tests {
         assert!((world.body.fatigues[&e].current - 104.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_tended_recovery_when_dragged() {
+        let mut world = World::new_with_seed(42);
+        let e = world.spawn();
+        let rescuer = world.spawn();
+        world.body.fatigues.insert(e, Fatigue { current: 105.0 });
+        world.mind.dragged_by.insert(e, rescuer);
+
+        run_fatigue(&mut world, Tick(0));
+        assert!((world.body.fatigues[&e].current - 103.0).abs() < 0.001);
+    }
+
     #[test]
     fn test_excess_fatigue_damages_hp() {
         let mut world = World::new_with_seed(42);
@@ -0,0 +1,920 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::components::{ActionId, Entity, FireCause, HomeBuilding, Position, Storey, Tick};
+use crate::events::Event;
+use crate::lod::LodZone;
+use crate::registry::{BuildingData, BuildingId};
+use crate::tile_map::Terrain;
+use crate::world::World;
+use rand::RngExt;
+
+/// Ticks between ignition rolls (one game hour).
+const IGNITION_STEP: u64 = 60;
+/// Hourly chance per building of a stray candle, lamp or hearth fire.
+const ACCIDENT_CHANCE: f32 = 2e-6;
+/// Extra hourly chance where a trade keeps an oven or forge going.
+const OVEN_CHANCE: f32 = 2e-5;
+/// Hourly chance per building that rioters torch it (rioting quartiers).
+const ARSON_CHANCE: f32 = 2e-4;
+/// Activity words of the trades that keep an oven or forge going.
+const OVEN_TRADE_WORDS: [&str; 6] = [
+    "boulang",
+    "pâtiss",
+    "rôtiss",
+    "forgeron",
+    "fondeur",
+    "chaudronn",
+];
+
+/// Ticks between spread steps.
+const FIRE_STEP: u64 = 5;
+/// Temperature a burning tile is held at (°C).
+pub const FLAME_TEMPERATURE: f32 = 800.0;
+/// A building tile this hot catches fire.
+const IGNITION_TEMPERATURE: f32 = 300.0;
+/// Share of its gap to flame temperature a tile gains per step from each
+/// burning neighbor, through floors and doors...
+const CONDUCTION: f32 = 0.15;
+/// ...and through walls, party walls included.
+const WALL_CONDUCTION: f32 = 0.05;
+/// Ticks a tile burns per storey of its building before it falls in.
+const BURN_TICKS_PER_STOREY: u64 = 60;
+/// Temperature of freshly fallen rubble.
+const RUBBLE_TEMPERATURE: f32 = 60.0;
+/// Health lost per step by anyone on a burning tile.
+const BURN_DAMAGE: f32 = 8.0;
+/// A building whose fire burnt this share of its tiles collapses.
+const COLLAPSE_SHARE: f32 = 0.5;
+/// Chebyshev range within which firefighters answer a fire.
+pub const FIRE_RESPONSE_RANGE: i32 = 60;
+/// Chance per step that a firefighter beside a burning tile puts it out.
+const DOUSE_CHANCE: f32 = 0.5;
+/// Standing buildings nearest a collapsed one that take in its residents.
+const REHOUSING_CHOICES: usize = 8;
+
+/// A fire in one building (`gis.fires`).
+#[derive(Debug, Clone)]
+pub struct Fire {
+    pub started: Tick,
+    pub cause: FireCause,
+    /// Burning tiles and the tick each falls in, in tile order.
+    pub burning: BTreeMap<(i32, i32), Tick>,
+    /// Tiles burnt to rubble so far.
+    pub burnt: u32,
+}
+
+/// Building whose Floor, Wall or Door tile is at `(x, y)`; None for
+/// anything that doesn't burn.
+fn combustible(world: &World, x: i32, y: i32) -> Option<BuildingId> {
+    if x < 0 || y < 0 {
+        return None;
+    }
+    let (ux, uy) = (x as usize, y as usize);
+    match world.tiles.get_terrain(ux, uy)? {
+        Terrain::Floor | Terrain::Wall | Terrain::Door => world.tiles.get_building_id(ux, uy),
+        _ => None,
+    }
+}
+
+/// Whether the tile at `(x, y)` is on fire.
+pub fn is_burning(world: &World, x: i32, y: i32) -> bool {
+    x >= 0
+        && y >= 0
+        && world
+            .tiles
+            .get_building_id(x as usize, y as usize)
+            .and_then(|b| world.gis.fires.get(&b))
+            .is_some_and(|f| f.burning.contains_key(&(x, y)))
+}
+
+/// Whether `e` belongs to the "pompiers" faction.
+pub fn is_firefighter(world: &World, e: Entity) -> bool {
+    world
+        .mind
+        .faction_members
+        .get(&e)
+        .and_then(|m| world.mind.factions.get(m.faction))
+        .is_some_and(|f| f.key == "pompiers")
+}
+
+/// Whether `e` is a firefighter with a fire within `FIRE_RESPONSE_RANGE`.
+pub fn fire_reported(world: &World, e: Entity) -> bool {
+    is_firefighter(world, e)
+        && world
+            .body
+            .positions
+            .get(&e)
+            .is_some_and(|p| !burning_in_range(world, (p.x, p.y)).is_empty())
+}
+
+/// Burning tiles within `FIRE_RESPONSE_RANGE` of `from`, nearest first
+/// (ties by row, then column).
+fn burning_in_range(world: &World, from: (i32, i32)) -> Vec<(i32, i32)> {
+    let mut near: Vec<(i32, i32, i32)> = world
+        .gis
+        .fires
+        .values()
+        .flat_map(|f| f.burning.keys())
+        .map(|&(x, y)| ((x - from.0).abs().max((y - from.1).abs()), y, x))
+        .filter(|&(d, _, _)| d <= FIRE_RESPONSE_RANGE)
+        .collect();
+    near.sort_unstable();
+    near.into_iter().map(|(_, y, x)| (x, y)).collect()
+}
+
+/// Burning tiles around `pos` (its 3×3 block), in tile order.
+fn burning_beside(world: &World, pos: (i32, i32)) -> Vec<(i32, i32)> {
+    (-1..=1)
+        .flat_map(|dy| (-1..=1).map(move |dx| (pos.0 + dx, pos.1 + dy)))
+        .filter(|&(x, y)| is_burning(world, x, y))
+        .collect()
+}
+
+/// Whether a firefighter at `pos` can douse a burning tile from there.
+pub fn in_reach(world: &World, pos: (i32, i32)) -> bool {
+    !burning_beside(world, pos).is_empty()
+}
+
+/// Where a firefighter at `from` should stand: of the walkable tiles not on
+/// fire around the nearest burning tile in range that has any, the one
+/// closest to `from`. None without a fire in range.
+pub fn fire_goal(world: &World, from: (i32, i32)) -> Option<(i32, i32)> {
+    burning_in_range(world, from)
+        .into_iter()
+        .find_map(|(fx, fy)| {
+            (-1..=1)
+                .flat_map(|dy| (-1..=1).map(move |dx| (fx + dx, fy + dy)))
+                .filter(|&(x, y)| x >= 0 && y >= 0)
+                .filter(|&(x, y)| world.tiles.is_walkable(x as usize, y as usize))
+                .filter(|&(x, y)| !is_burning(world, x, y))
+                .min_by_key(|&(x, y)| ((x - from.0).abs().max((y - from.1).abs()), y, x))
+        })
+}
+
+/// Set building `bid` alight on a random Floor tile (else any tile that
+/// burns), with `FireCause` `cause`. False if it is already burning or has
+/// nothing left to burn.
+pub fn ignite(world: &mut World, bid: BuildingId, cause: FireCause) -> bool {
+    if world.gis.fires.contains_key(&bid) {
+        return false;
+    }
+    let Some(b) = world.gis.buildings.get(bid) else {
+        return false;
+    };
+    let tiles: Vec<(i32, i32)> = b
+        .tiles
+        .iter()
+        .copied()
+        .filter(|&(x, y)| combustible(world, x, y) == Some(bid))
+        .collect();
+    let floors: Vec<(i32, i32)> = tiles
+        .iter()
+        .copied()
+        .filter(|&(x, y)| world.tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Floor))
+        .collect();
+    let pool = if floors.is_empty() { tiles } else { floors };
+    if pool.is_empty() {
+        return false;
+    }
+    let tile = pool[world.rng.random_range(0..pool.len())];
+    let tick = world.tick;
+    set_alight(world, bid, tile, cause, tick);
+    true
+}
+
+/// Add `tile` of `bid` to the building's fire, starting one (and pushing
+/// `Event::FireStarted`) if it has none.
+fn set_alight(world: &mut World, bid: BuildingId, tile: (i32, i32), cause: FireCause, tick: Tick) {
+    let storeys = world
+        .gis
+        .buildings
+        .get(bid)
+        .map_or(1, |b| b.floor_count.max(1)) as u64;
+    let falls_in = Tick(tick.0 + BURN_TICKS_PER_STOREY * storeys);
+    world
+        .tiles
+        .set_temperature(tile.0 as usize, tile.1 as usize, FLAME_TEMPERATURE);
+    if let Some(fire) = world.gis.fires.get_mut(&bid) {
+        fire.burning.insert(tile, falls_in);
+        return;
+    }
+    world.gis.fires.insert(
+        bid,
+        Fire {
+            started: tick,
+            cause,
+            burning: BTreeMap::from([(tile, falls_in)]),
+            burnt: 0,
+        },
+    );
+    log::info!("Fire in building {} ({})", bid.0, cause.label());
+    world.events.push(Event::FireStarted {
+        building: bid,
+        cause,
+        tick,
+    });
+}
+
+/// Whether anyone listed in `b` works an oven or forge.
+fn has_oven(b: &BuildingData, year: u16, tolerance: u16) -> bool {
+    b.occupants_nearest(year, tolerance)
+        .is_some_and(|(_, occupants)| {
+            occupants.iter().any(|o| {
+                let activity = o.activity.to_lowercase();
+                OVEN_TRADE_WORDS.iter().any(|w| activity.contains(w))
+            })
+        })
+}
+
+/// Roll this hour's new fires: accidents and ovens in Active quartiers,
+/// arson in rioting ones. Buildings roll in id order.
+fn roll_ignitions(world: &mut World) {
+    let zone_of = |world: &World, name: &str| {
+        world
+            .gis
+            .quartiers
+            .get_by_name(name)
+            .and_then(|q| world.gis.lod_zones.get(&q.id))
+            .copied()
+    };
+    let rioting: HashSet<&str> = world
+        .gis
+        .riots
+        .keys()
+        .filter_map(|&q| world.gis.quartiers.get(q))
+        .map(|q| q.name.as_str())
+        .collect();
+    let (year, tolerance) = (world.gis.active_year, world.gis.year_tolerance);
+    let candidates: Vec<(BuildingId, f32, f32)> = world
+        .gis
+        .buildings
+        .buildings
+        .iter()
+        .filter(|b| b.bati == 1 && !world.gis.fires.contains_key(&b.id))
+        .filter_map(|b| {
+            let arson = if rioting.contains(b.quartier.as_str()) {
+                ARSON_CHANCE
+            } else {
+                0.0
+            };
+            if arson == 0.0 && zone_of(world, &b.quartier) != Some(LodZone::Active) {
+                return None;
+            }
+            let oven = if has_oven(b, year, tolerance) {
+                OVEN_CHANCE
+            } else {
+                0.0
+            };
+            Some((b.id, arson, oven))
+        })
+        .collect();
+    for (bid, arson, oven) in candidates {
+        let roll = world.rng.random::<f32>();
+        let cause = if roll < arson {
+            FireCause::Arson
+        } else if roll < arson + oven {
+            FireCause::Oven
+        } else if roll < arson + oven + ACCIDENT_CHANCE {
+            FireCause::Accident
+        } else {
+            continue;
+        };
+        ignite(world, bid, cause);
+    }
+}
+
+/// Phase 1 (Environment): building fires.
+///
+/// Runs after `run_temperature`. Every `IGNITION_STEP` ticks each building
+/// in an Active quartier may catch fire by accident, more likely where a
+/// trade keeps an oven; rioters (`gis.riots`) torch buildings in their
+/// quartier whatever its zone. Every `FIRE_STEP` ticks:
+/// - tiles burning `BURN_TICKS_PER_STOREY` per storey fall in to Rubble;
+/// - firefighters intent on `Extinguish` douse a burning tile beside them
+///   with `DOUSE_CHANCE`, cooling it and its neighbors;
+/// - burning tiles stay at `FLAME_TEMPERATURE` and heat neighboring
+///   Floor/Wall/Door tiles (walls, party walls included, conduct less); a
+///   tile reaching `IGNITION_TEMPERATURE` catches, starting a `Spread` fire
+///   if it belongs to another building;
+/// - anyone on a burning tile loses `BURN_DAMAGE` health;
+/// - fires with nothing left burning go out. A building that lost
+///   `COLLAPSE_SHARE` of its tiles collapses to Rubble and its residents
+///   are rehoused nearby (see `rehouse`).
+pub fn run_fire(world: &mut World, tick: Tick) {
+    if tick.0.is_multiple_of(IGNITION_STEP) {
+        roll_ignitions(world);
+    }
+    if world.gis.fires.is_empty() || !tick.0.is_multiple_of(FIRE_STEP) {
+        return;
+    }
+    let mut bids: Vec<BuildingId> = world.gis.fires.keys().copied().collect();
+    bids.sort_by_key(|b| b.0);
+
+    // Tiles that have burnt through fall in.
+    let fallen: Vec<(BuildingId, (i32, i32))> = bids
+        .iter()
+        .filter_map(|b| world.gis.fires.get(b).map(|f| (*b, f)))
+        .flat_map(|(b, f)| {
+            f.burning
+                .iter()
+                .filter(|(_, t)| t.0 <= tick.0)
+                .map(move |(&tile, _)| (b, tile))
+        })
+        .collect();
+    for (bid, (x, y)) in fallen {
+        if let Some(fire) = world.gis.fires.get_mut(&bid) {
+            fire.burning.remove(&(x, y));
+            fire.burnt += 1;
+        }
+        world.set_terrain(x, y, Terrain::Rubble);
+        world
+            .tiles
+            .set_temperature(x as usize, y as usize, RUBBLE_TEMPERATURE);
+    }
+
+    // Firefighters douse what burns beside them.
+    let mut crews: Vec<Entity> = world
+        .mind
+        .intentions
+        .iter()
+        .filter(|(_, i)| i.action == ActionId::Extinguish)
+        .map(|(&e, _)| e)
+        .filter(|e| !world.pending_deaths.contains(e))
+        .collect();
+    crews.sort_by_key(|e| e.0);
+    let mut doused: Vec<(i32, i32)> = Vec::new();
+    for e in crews {
+        let Some(&pos) = world.body.positions.get(&e) else {
+            continue;
+        };
+        let Some(&tile) = burning_beside(world, (pos.x, pos.y))
+            .iter()
+            .find(|t| !doused.contains(t))
+        else {
+            continue;
+        };
+        if world.rng.random::<f32>() < DOUSE_CHANCE {
+            doused.push(tile);
+        }
+    }
+    for (x, y) in doused {
+        if let Some(fire) = combustible(world, x, y).and_then(|b| world.gis.fires.get_mut(&b)) {
+            fire.burning.remove(&(x, y));
+        }
+        for (nx, ny) in (-1..=1).flat_map(|dy| (-1..=1).map(move |dx| (x + dx, y + dy))) {
+            if combustible(world, nx, ny).is_none() || is_burning(world, nx, ny) {
+                continue;
+            }
+            if let Some(t) = world.tiles.get_terrain(nx as usize, ny as usize) {
+                world
+                    .tiles
+                    .set_temperature(nx as usize, ny as usize, t.target_temperature());
+            }
+        }
+    }
+
+    // Burning tiles heat their neighbors.
+    let burning: Vec<(i32, i32)> = bids
+        .iter()
+        .filter_map(|b| world.gis.fires.get(b))
+        .flat_map(|f| f.burning.keys().copied())
+        .collect();
+    let mut heat: BTreeMap<(i32, i32), f32> = BTreeMap::new();
+    for &(x, y) in &burning {
+        world
+            .tiles
+            .set_temperature(x as usize, y as usize, FLAME_TEMPERATURE);
+        let source_wall = world.tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Wall);
+        for (nx, ny) in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            if combustible(world, nx, ny).is_none() || is_burning(world, nx, ny) {
+                continue;
+            }
+            let wall = source_wall
+                || world.tiles.get_terrain(nx as usize, ny as usize) == Some(Terrain::Wall);
+            let temp = world
+                .tiles
+                .get_temperature(nx as usize, ny as usize)
+                .unwrap_or(FLAME_TEMPERATURE);
+            let share = if wall { WALL_CONDUCTION } else { CONDUCTION };
+            *heat.entry((nx, ny)).or_insert(0.0) += (FLAME_TEMPERATURE - temp) * share;
+        }
+    }
+    let mut catching: Vec<(BuildingId, (i32, i32))> = Vec::new();
+    for ((x, y), gain) in heat {
+        let temp = world
+            .tiles
+            .get_temperature(x as usize, y as usize)
+            .unwrap_or(FLAME_TEMPERATURE);
+        let temp = (temp + gain).min(FLAME_TEMPERATURE);
+        world.tiles.set_temperature(x as usize, y as usize, temp);
+        if temp >= IGNITION_TEMPERATURE
+            && let Some(bid) = combustible(world, x, y)
+        {
+            catching.push((bid, (x, y)));
+        }
+    }
+
+    // Anyone caught in the flames burns.
+    let mut burnt: Vec<Entity> = burning
+        .iter()
+        .flat_map(|&(x, y)| world.entities_at(x, y).collect::<Vec<_>>())
+        .filter(|e| world.body.healths.contains_key(e))
+        .collect();
+    burnt.sort_by_key(|e| e.0);
+    burnt.dedup();
+    for e in burnt {
+        if world.pending_deaths.contains(&e) {
+            continue;
+        }
+        if let Some(health) = world.body.healths.get_mut(&e) {
+            health.current = (health.current - BURN_DAMAGE).max(0.0);
+            if health.current <= 0.0 {
+                world.events.push(Event::Died { entity: e, tick });
+                world.pending_deaths.insert(e);
+            }
+        }
+    }
+
+    for (bid, tile) in catching {
+        set_alight(world, bid, tile, FireCause::Spread, tick);
+    }
+
+    let mut out: Vec<BuildingId> = world
+        .gis
+        .fires
+        .iter()
+        .filter(|(_, f)| f.burning.is_empty())
+        .map(|(&b, _)| b)
+        .collect();
+    out.sort_by_key(|b| b.0);
+    for bid in out {
+        put_out(world, bid, tick);
+    }
+}
+
+/// End the fire in `bid`, collapsing the building if it burnt through
+/// `COLLAPSE_SHARE` of its tiles, and push `Event::FireOut`.
+fn put_out(world: &mut World, bid: BuildingId, tick: Tick) {
+    let Some(fire) = world.gis.fires.remove(&bid) else {
+        return;
+    };
+    let tiles = world
+        .gis
+        .buildings
+        .get(bid)
+        .map_or(Vec::new(), |b| b.tiles.clone());
+    let destroyed = fire.burnt as f32 >= COLLAPSE_SHARE * tiles.len().max(1) as f32;
+    let mut displaced = 0;
+    if destroyed {
+        for &(x, y) in &tiles {
+            if combustible(world, x, y) == Some(bid) {
+                world.set_terrain(x, y, Terrain::Rubble);
+            }
+        }
+        if let Some(b) = world.gis.buildings.get_mut(bid) {
+            b.stairs = None;
+        }
+        // Whoever was upstairs comes down with the floors.
+        let mut upstairs: Vec<Entity> = world
+            .body
+            .storeys
+            .keys()
+            .filter(|e| {
+                world.body.positions.get(e).is_some_and(|p| {
+                    world.tiles.get_building_id(p.x as usize, p.y as usize) == Some(bid)
+                })
+            })
+            .copied()
+            .collect();
+        upstairs.sort_by_key(|e| e.0);
+        for e in upstairs {
+            world.body.storeys.remove(&e);
+        }
+        displaced = rehouse(world, bid);
+    }
+    log::info!(
+        "Fire in building {} out: {} tiles burnt{}",
+        bid.0,
+        fire.burnt,
+        if destroyed { ", collapsed" } else { "" }
+    );
+    world.events.push(Event::FireOut {
+        building: bid,
+        destroyed,
+        displaced,
+        tick,
+    });
+}
+
+/// Move the residents of collapsed building `bid` into the
+/// `REHOUSING_CHOICES` nearest standing buildings of its quartier that have
+/// floor space, a random one each (upper storeys where there are stairs).
+/// Those who worked there lose their workplace. Residents with nowhere to
+/// go are left homeless. Either way the ruin stays their directory listing
+/// (`gis.listed_homes`), so a year switch still finds them there. Residents
+/// standing in the ruin move onto a Floor tile of their new home, or
+/// without one out to the nearest walkable tile beyond the rubble. Returns
+/// how many were displaced.
+fn rehouse(world: &mut World, bid: BuildingId) -> u32 {
+    let mut homeless: Vec<Entity> = world
+        .gis
+        .home_buildings
+        .iter()
+        .filter(|(_, h)| h.0 == bid)
+        .map(|(&e, _)| e)
+        .collect();
+    homeless.sort_by_key(|e| e.0);
+    world.gis.workplaces.retain(|_, w| w.0 != bid);
+    let Some(ruin) = world.gis.buildings.get(bid) else {
+        return 0;
+    };
+    let &(rx, ry) = ruin.tiles.first().unwrap_or(&(0, 0));
+    let mut homes: Vec<(i32, BuildingId, u8)> = world
+        .gis
+        .buildings
+        .buildings
+        .iter()
+        .filter(|b| b.id != bid && b.quartier == ruin.quartier)
+        .filter(|b| !world.gis.fires.contains_key(&b.id))
+        .filter(|b| {
+            b.tiles.iter().any(|&(x, y)| {
+                world.tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Floor)
+            })
+        })
+        .filter_map(|b| {
+            let &(x, y) = b.tiles.first()?;
+            let upper = if b.stairs.is_some() {
+                b.floor_count.saturating_sub(1)
+            } else {
+                0
+            };
+            Some(((x - rx).abs().max((y - ry).abs()), b.id, upper))
+        })
+        .collect();
+    homes.sort_by_key(|&(d, b, _)| (d, b.0));
+    homes.truncate(REHOUSING_CHOICES);
+
+    // Walkable tiles just outside the ruin, for those with nowhere to go.
+    let mut exits: Vec<(i32, i32)> = ruin
+        .tiles
+        .iter()
+        .flat_map(|&(x, y)| [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)])
+        .filter(|&(x, y)| {
+            x >= 0
+                && y >= 0
+                && world.tiles.is_walkable(x as usize, y as usize)
+                && world.tiles.get_terrain(x as usize, y as usize) != Some(Terrain::Rubble)
+        })
+        .collect();
+    exits.sort_unstable();
+    exits.dedup();
+
+    let displaced = homeless.len() as u32;
+    let mut moved = false;
+    for e in homeless {
+        world.gis.listed_homes.entry(e).or_insert(bid);
+        let home = (!homes.is_empty()).then(|| homes[world.rng.random_range(0..homes.len())]);
+        let storey = match home {
+            Some((_, home, upper)) => {
+                world.gis.home_buildings.insert(e, HomeBuilding(home));
+                let storey = if upper > 0 {
+                    world.rng.random_range(1..=upper)
+                } else {
+                    0
+                };
+                if storey > 0 {
+                    world.gis.home_storeys.insert(e, Storey(storey));
+                } else {
+                    world.gis.home_storeys.remove(&e);
+                }
+                storey
+            }
+            None => {
+                world.gis.home_buildings.remove(&e);
+                world.gis.home_storeys.remove(&e);
+                0
+            }
+        };
+
+        let Some(&pos) = world.body.positions.get(&e) else {
+            continue; // folded: unfolds at the new home
+        };
+        if world.tiles.get_building_id(pos.x as usize, pos.y as usize) != Some(bid) {
+            continue;
+        }
+        let floors: Vec<(i32, i32)> = home
+            .and_then(|(_, home, _)| world.gis.buildings.get(home))
+            .map_or(Vec::new(), |b| {
+                b.tiles
+                    .iter()
+                    .copied()
+                    .filter(|&(x, y)| {
+                        world.tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Floor)
+                    })
+                    .collect()
+            });
+        let to = if floors.is_empty() {
+            exits
+                .iter()
+                .copied()
+                .min_by_key(|&(x, y)| ((x - pos.x).pow(2) + (y - pos.y).pow(2), (x, y)))
+        } else {
+            Some(floors[world.rng.random_range(0..floors.len())])
+        };
+        let Some((x, y)) = to else {
+            continue;
+        };
+        world.body.positions.insert(e, Position { x, y });
+        if storey > 0 && !floors.is_empty() {
+            world.body.storeys.insert(e, Storey(storey));
+        }
+        world.mind.cached_paths.remove(&e);
+        world.mind.wander_targets.remove(&e);
+        world.events.push(Event::Moved {
+            entity: e,
+            x,
+            y,
+            tick: world.tick,
+        });
+        moved = true;
+    }
+    if moved {
+        world.rebuild_spatial_index();
+    }
+    displaced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{FactionMember, Health, Intention, Name, Position};
+    use crate::faction::{AttackResponse, FactionDef};
+    use crate::registry::Occupant;
+
+    /// Two 4×4 houses side by side sharing the party wall at x = 3/4 in
+    /// quartier "Arcis": walls round the edge, floors inside, street below.
+    fn setup() -> (World, BuildingId, BuildingId) {
+        let mut world = World::new_with_seed(42);
+        let mut ids = Vec::new();
+        for x0 in [0, 4] {
+            let id = world.gis.buildings.next_id();
+            let mut tiles = Vec::new();
+            for y in 0..4 {
+                for x in x0..x0 + 4 {
+                    let edge = x == x0 || x == x0 + 3 || y == 0 || y == 3;
+                    let t = if edge { Terrain::Wall } else { Terrain::Floor };
+                    world.tiles.set_terrain(x as usize, y as usize, t);
+                    world.tiles.set_building_id(x as usize, y as usize, id);
+                    tiles.push((x, y));
+                }
+            }
+            world.gis.buildings.insert(BuildingData {
+                quartier: "Arcis".into(),
+                num_ilot: "T1".into(),
+                ..BuildingData::test_default(id, tiles)
+            });
+            ids.push(id);
+        }
+        (world, ids[0], ids[1])
+    }
+
+    fn run_until_out(world: &mut World, from: u64) -> u64 {
+        for t in from..from + 5000 {
+            run_fire(world, Tick(t));
+            if world.gis.fires.is_empty() {
+                return t;
+            }
+        }
+        panic!("fire still burning after 5000 ticks");
+    }
+
+    #[test]
+    fn test_fire_spreads_through_party_wall_and_collapses() {
+        let (mut world, west, east) = setup();
+        let resident = world.spawn();
+        world
+            .gis
+            .home_buildings
+            .insert(resident, HomeBuilding(west));
+        let trapped = world.spawn();
+        world
+            .body
+            .positions
+            .insert(trapped, Position { x: 1, y: 1 });
+        world.body.healths.insert(
+            trapped,
+            Health {
+                current: 10.0,
+                max: 100.0,
+            },
+        );
+        world.rebuild_spatial_index();
+
+        assert!(ignite(&mut world, west, FireCause::Oven));
+        assert!(!ignite(&mut world, west, FireCause::Accident), "once");
+        // Light the whole interior so the spread is quick.
+        for tile in [(1, 1), (2, 1), (1, 2), (2, 2)] {
+            set_alight(&mut world, west, tile, FireCause::Oven, Tick(0));
+        }
+        run_until_out(&mut world, 0);
+
+        assert!(world.pending_deaths.contains(&trapped));
+        let started: Vec<(BuildingId, FireCause)> = world
+            .events
+            .iter()
+            .filter_map(|ev| match *ev {
+                Event::FireStarted {
+                    building, cause, ..
+                } => Some((building, cause)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            started,
+            vec![(west, FireCause::Oven), (east, FireCause::Spread)]
+        );
+        assert!(world.events.iter().any(|ev| matches!(
+            *ev,
+            Event::FireOut {
+                building,
+                destroyed: true,
+                displaced: 1,
+                ..
+            } if building == west
+        )));
+        for x in 0..4 {
+            for y in 0..4 {
+                assert_eq!(world.tiles.get_terrain(x, y), Some(Terrain::Rubble));
+            }
+        }
+        // The only other building burnt too: nowhere left to live.
+        assert!(!world.gis.home_buildings.contains_key(&resident));
+    }
+
+    #[test]
+    fn test_firefighters_douse_and_residents_rehoused() {
+        let (mut world, west, east) = setup();
+        world.mind.factions.insert(FactionDef {
+            key: "pompiers".into(),
+            name: "Sapeurs-pompiers".into(),
+            local: false,
+            when_attacked: AttackResponse::Flee,
+            calls_for_help: true,
+            defends_allies: false,
+        });
+        let pompiers = world
+            .mind
+            .factions
+            .get_by_key("pompiers")
+            .expect("pompiers faction");
+        let sapeur = world.spawn();
+        world.body.positions.insert(sapeur, Position { x: 5, y: 4 });
+        world.mind.faction_members.insert(
+            sapeur,
+            FactionMember {
+                faction: pompiers,
+                quartier: None,
+            },
+        );
+        assert!(!fire_reported(&world, sapeur));
+
+        set_alight(&mut world, east, (5, 2), FireCause::Accident, Tick(0));
+        assert!(fire_reported(&world, sapeur));
+        assert_eq!(fire_goal(&world, (20, 20)), Some((6, 2)));
+        assert!(!in_reach(&world, (5, 4)));
+        world.body.positions.insert(sapeur, Position { x: 6, y: 2 });
+        assert!(in_reach(&world, (6, 2)));
+
+        world.mind.intentions.insert(
+            sapeur,
+            Intention {
+                action: ActionId::Extinguish,
+                target: None,
+            },
+        );
+        let out = run_until_out(&mut world, 0);
+        assert!(out < 60, "doused before anything fell in, at tick {out}");
+        assert!(world.events.iter().any(|ev| matches!(
+            *ev,
+            Event::FireOut {
+                destroyed: false,
+                ..
+            }
+        )));
+        assert_eq!(world.tiles.get_terrain(5, 2), Some(Terrain::Floor));
+        assert_eq!(world.tiles.get_terrain(1, 1), Some(Terrain::Floor));
+
+        // A collapse rehouses the residents next door, and whoever was at
+        // home moves in there.
+        let resident = world.spawn();
+        world
+            .gis
+            .home_buildings
+            .insert(resident, HomeBuilding(west));
+        world
+            .body
+            .positions
+            .insert(resident, Position { x: 1, y: 1 });
+        world.gis.fires.insert(
+            west,
+            Fire {
+                started: Tick(0),
+                cause: FireCause::Arson,
+                burning: BTreeMap::new(),
+                burnt: 16,
+            },
+        );
+        put_out(&mut world, west, Tick(100));
+        assert_eq!(world.tiles.get_terrain(1, 1), Some(Terrain::Rubble));
+        assert_eq!(world.gis.home_buildings[&resident].0, east);
+        let p = world.body.positions[&resident];
+        assert_eq!(
+            world.tiles.get_building_id(p.x as usize, p.y as usize),
+            Some(east)
+        );
+        assert_eq!(
+            world.tiles.get_terrain(p.x as usize, p.y as usize),
+            Some(Terrain::Floor)
+        );
+    }
+
+    #[test]
+    fn test_collapse_with_no_home_moves_residents_out_of_the_rubble() {
+        let (mut world, west, east) = setup();
+        // The only other building is in another quartier.
+        if let Some(b) = world.gis.buildings.get_mut(east) {
+            b.quartier = "Marchés".into();
+        }
+        let resident = world.spawn();
+        world
+            .gis
+            .home_buildings
+            .insert(resident, HomeBuilding(west));
+        world
+            .body
+            .positions
+            .insert(resident, Position { x: 2, y: 2 });
+        world.rebuild_spatial_index();
+        world.gis.fires.insert(
+            west,
+            Fire {
+                started: Tick(0),
+                cause: FireCause::Accident,
+                burning: BTreeMap::new(),
+                burnt: 16,
+            },
+        );
+        put_out(&mut world, west, Tick(100));
+
+        assert!(!world.gis.home_buildings.contains_key(&resident));
+        // Out onto the street below the ruin, the nearest walkable tile.
+        let p = world.body.positions[&resident];
+        assert_eq!((p.x, p.y), (2, 4));
+        assert!(world.tiles.is_walkable(2, 4));
+        assert!(world.entities_at(2, 4).any(|e| e == resident));
+    }
+
+    #[test]
+    fn test_displaced_residents_survive_a_year_switch() {
+        let (mut world, west, east) = setup();
+        let dupont = Occupant {
+            name: "Dupont".into(),
+            activity: "boulanger".into(),
+            naics: "311811".into(),
+        };
+        if let Some(b) = world.gis.buildings.get_mut(west) {
+            b.occupants_by_year.insert(1845, vec![dupont.clone()]);
+            b.occupants_by_year.insert(1850, vec![dupont]);
+        }
+        let resident = world.spawn();
+        world.body.names.insert(
+            resident,
+            Name {
+                value: "Dupont".into(),
+            },
+        );
+        world
+            .gis
+            .home_buildings
+            .insert(resident, HomeBuilding(west));
+        world.gis.fires.insert(
+            west,
+            Fire {
+                started: Tick(0),
+                cause: FireCause::Accident,
+                burning: BTreeMap::new(),
+                burnt: 16,
+            },
+        );
+        put_out(&mut world, west, Tick(100));
+        assert_eq!(world.gis.home_buildings[&resident].0, east);
+        assert_eq!(world.gis.listed_homes.get(&resident), Some(&west));
+
+        // Still listed in the ruin, so the switch finds them there.
+        let change = crate::directory::set_active_year(&mut world, 1850, None);
+        assert!(change.departures.is_empty());
+        assert!(change.arrivals.is_empty());
+        assert!(world.alive.contains(&resident));
+        assert_eq!(world.gis.home_buildings[&resident].0, east);
+    }
+}
@@ -5,16 +5,22 @@ use crate::world::World;
 /// Phase 2 (Needs): Hunger increases over time.
 ///
 /// Every living entity with a Hunger component gets hungrier by 1.0 per tick,
-/// clamped to hunger.max. Entities in pending_deaths are skipped.
+/// clamped to hunger.max. Entities in pending_deaths are skipped. Nearby
+/// entities update once per `NEARBY_TICK_INTERVAL` by the whole interval.
 pub fn run_hunger(world: &mut World, tick: Tick) {
     let mut changes: Vec<(crate::components::Entity, f32, f32)> = world
         .mind
         .hungers
         .iter()
         .filter(|&(&e, _)| !world.pending_deaths.contains(&e))
-        .map(|(&e, h)| {
-            let new_val = (h.current + 1.0).min(h.max);
-            (e, h.current, new_val)
+        .filter(|&(&e, _)| !world.frozen.contains(&e))
+        .filter_map(|(&e, h)| {
+            let step = world.lod_step(e, tick);
+            if step == 0 {
+                return None;
+            }
+            let new_val = (h.current + step as f32).min(h.max);
+            Some((e, h.current, new_val))
         })
         .collect();
     changes.sort_by_key(|(e, _, _)| e.0);
@@ -83,4 +89,36 @@ mod tests {
         run_hunger(&mut world, Tick(0));
         assert_eq!(world.mind.hungers[&e].current, 50.0); // unchanged
     }
+
+    #[test]
+    fn test_nearby_hunger_catches_up_each_interval() {
+        use crate::components::Position;
+        use crate::lod::{LodZone, NEARBY_TICK_INTERVAL};
+        use crate::registry::QuartierId;
+        use crate::tile_map::TileMap;
+
+        let mut world = World::new_with_seed(42);
+        world.tiles = TileMap::new(4, 4);
+        world.tiles.set_quartier_id(1, 1, 1);
+        world.gis.lod_zones.insert(QuartierId(1), LodZone::Nearby);
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x: 1, y: 1 });
+        world.mind.hungers.insert(
+            e,
+            Hunger {
+                current: 0.0,
+                max: 100.0,
+            },
+        );
+        let mut updates = 0;
+        for t in 0..NEARBY_TICK_INTERVAL {
+            let before = world.mind.hungers[&e].current;
+            run_hunger(&mut world, Tick(t));
+            if world.mind.hungers[&e].current != before {
+                updates += 1;
+            }
+        }
+        assert_eq!(updates, 1);
+        assert_eq!(world.mind.hungers[&e].current, NEARBY_TICK_INTERVAL as f32);
+    }
 }
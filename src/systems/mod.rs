@@ -1,8 +1,19 @@
 pub mod combat;
+pub mod crime;
+pub mod danger;
 pub mod death;
 pub mod decisions;
 pub mod eating;
 pub mod fatigue;
+pub mod fire;
 pub mod hunger;
+pub mod perception;
+pub mod plans;
+pub mod reactions;
+pub mod rescue;
+pub mod social;
+pub mod statistical;
 pub mod temperature;
+pub mod unrest;
 pub mod wander;
+pub mod wounds;
@@ -0,0 +1,277 @@
+use crate::components::{Entity, Sighting, Tick};
+use crate::lod::LodZone;
+use crate::registry::BuildingId;
+use crate::tile_map::{Terrain, TileMap};
+use crate::world::World;
+
+/// Chebyshev distance within which entities can perceive (30 meters).
+pub const SENSE_RANGE: i32 = 30;
+
+/// Ticks a sighting stays in memory without being refreshed.
+pub const MEMORY_TICKS: u64 = 300;
+
+/// Which enclosed space a tile belongs to, for indoor/outdoor separation.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Space {
+    Outdoor,
+    Indoor(BuildingId),
+    /// Doors see both the street and the interior they open onto.
+    Threshold,
+}
+
+fn space_at(map: &TileMap, x: i32, y: i32) -> Space {
+    if x < 0 || y < 0 {
+        return Space::Outdoor;
+    }
+    let (ux, uy) = (x as usize, y as usize);
+    match map.get_terrain(ux, uy) {
+        Some(Terrain::Door) => Space::Threshold,
+        Some(Terrain::Floor) => match map.get_building_id(ux, uy) {
+            Some(id) => Space::Indoor(id),
+            None => Space::Outdoor,
+        },
+        _ => Space::Outdoor,
+    }
+}
+
+/// Whether an observer at `from` can see the tile `to`: both must share a
+/// space (same building interior, or both outdoors; doors join the two) and
+/// no wall may stand between them.
+pub fn can_see(map: &TileMap, from: (i32, i32), to: (i32, i32)) -> bool {
+    let connected = match (space_at(map, from.0, from.1), space_at(map, to.0, to.1)) {
+        (Space::Threshold, _) | (_, Space::Threshold) => true,
+        (a, b) => a == b,
+    };
+    connected && map.line_of_sight(from, to)
+}
+
+/// Remembered entities that still exist, with their last-seen sighting.
+/// Sorted by entity ID for deterministic consumers.
+pub fn remembered(world: &World, entity: Entity) -> Vec<(Entity, Sighting)> {
+    let Some(memory) = world.mind.memories.get(&entity) else {
+        return Vec::new();
+    };
+    let mut out: Vec<(Entity, Sighting)> = memory
+        .sightings
+        .iter()
+        .filter(|(e, _)| world.alive.contains(e) && !world.pending_deaths.contains(e))
+        .map(|(&e, &s)| (e, s))
+        .collect();
+    out.sort_by_key(|(e, _)| e.0);
+    out
+}
+
+/// Phase 3 (Decisions): refresh each AI entity's short-term memory.
+///
+/// Runs before `run_decisions` on pre-movement positions. For every entity
+/// with an `ActionState`, records food and creatures within `SENSE_RANGE`
+/// that pass `can_see`, then forgets sightings older than `MEMORY_TICKS`
+/// and sightings of entities that no longer exist. Entities outside Active
+/// zones don't look around; they act on what they last remembered.
+pub fn run_perception(world: &mut World, tick: Tick) {
+    let mut observers: Vec<Entity> = world
+        .mind
+        .action_states
+        .keys()
+        .filter(|e| !world.pending_deaths.contains(e))
+        .filter(|e| !world.frozen.contains(e))
+        .filter(|e| world.lod_zone_of(**e) == LodZone::Active)
+        .filter(|e| world.player != Some(**e))
+        .copied()
+        .collect();
+    observers.sort_by_key(|e| e.0);
+
+    let mut updates: Vec<(Entity, Vec<(Entity, Sighting)>)> = Vec::with_capacity(observers.len());
+    for observer in observers {
+        let Some(&pos) = world.body.positions.get(&observer) else {
+            continue;
+        };
+        let seen: Vec<(Entity, Sighting)> = world
+            .entities_in_range(pos.x, pos.y, SENSE_RANGE)
+            .filter(|&e| e != observer)
+            .filter(|e| !world.pending_deaths.contains(e))
+            .filter(|e| {
+                world.mind.nutritions.contains_key(e) || world.body.combat_stats.contains_key(e)
+            })
+            .filter_map(|e| {
+                let p = world.body.positions.get(&e)?;
+                if can_see(&world.tiles, (pos.x, pos.y), (p.x, p.y)) {
+                    Some((
+                        e,
+                        Sighting {
+                            x: p.x,
+                            y: p.y,
+                            tick,
+                        },
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        updates.push((observer, seen));
+    }
+
+    for (observer, seen) in updates {
+        let memory = world.mind.memories.entry(observer).or_default();
+        memory.sightings.extend(seen);
+        memory.sightings.retain(|e, s| {
+            world.alive.contains(e) && tick.0.saturating_sub(s.tick.0) <= MEMORY_TICKS
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::*;
+    use std::collections::HashMap;
+
+    fn observer(world: &mut World, x: i32, y: i32) -> Entity {
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x, y });
+        world.mind.action_states.insert(
+            e,
+            ActionState {
+                current_action: None,
+                ticks_in_action: 0,
+                cooldowns: HashMap::new(),
+            },
+        );
+        e
+    }
+
+    fn food_at(world: &mut World, x: i32, y: i32) -> Entity {
+        let f = world.spawn();
+        world.body.positions.insert(f, Position { x, y });
+        world.mind.nutritions.insert(f, Nutrition { value: 10.0 });
+        f
+    }
+
+    fn seen(world: &World, observer: Entity, target: Entity) -> bool {
+        world
+            .mind
+            .memories
+            .get(&observer)
+            .is_some_and(|m| m.sightings.contains_key(&target))
+    }
+
+    #[test]
+    fn test_line_of_sight_blocked_by_wall() {
+        let mut map = TileMap::new(16, 16);
+        assert!(map.line_of_sight((1, 5), (9, 5)));
+        map.set_terrain(5, 5, Terrain::Wall);
+        assert!(!map.line_of_sight((1, 5), (9, 5)));
+        // Walls at the endpoints themselves don't occlude.
+        assert!(map.line_of_sight((1, 5), (5, 5)));
+    }
+
+    #[test]
+    fn test_line_of_sight_blocked_by_diagonal_seam() {
+        let mut map = TileMap::new(16, 16);
+        map.set_terrain(2, 1, Terrain::Wall);
+        map.set_terrain(1, 2, Terrain::Wall);
+        assert!(!map.line_of_sight((1, 1), (2, 2)));
+    }
+
+    #[test]
+    fn test_indoor_outdoor_separation() {
+        let mut map = TileMap::new(16, 16);
+        let id = crate::registry::BuildingId(1);
+        for x in 5..8 {
+            map.set_terrain(x, 5, Terrain::Floor);
+            map.set_building_id(x, 5, id);
+        }
+        map.set_terrain(4, 5, Terrain::Door);
+        map.set_building_id(4, 5, id);
+
+        // Street sees the door, not the room behind it.
+        assert!(can_see(&map, (1, 5), (4, 5)));
+        assert!(!can_see(&map, (1, 5), (6, 5)));
+        // Inside sees inside and the door.
+        assert!(can_see(&map, (7, 5), (5, 5)));
+        assert!(can_see(&map, (7, 5), (4, 5)));
+    }
+
+    #[test]
+    fn test_perception_respects_walls() {
+        let mut world = World::new_with_seed(42);
+        let e = observer(&mut world, 1, 5);
+        let hidden = food_at(&mut world, 9, 5);
+        let visible = food_at(&mut world, 1, 9);
+        world.tiles.set_terrain(5, 5, Terrain::Wall);
+        world.rebuild_spatial_index();
+
+        run_perception(&mut world, Tick(0));
+        assert!(!seen(&world, e, hidden));
+        assert!(seen(&world, e, visible));
+    }
+
+    #[test]
+    fn test_memory_keeps_last_seen_position() {
+        let mut world = World::new_with_seed(42);
+        let e = observer(&mut world, 1, 5);
+        let f = food_at(&mut world, 4, 5);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+
+        // Food moves behind a wall: memory keeps where it was last seen.
+        world.body.positions.insert(f, Position { x: 9, y: 5 });
+        world.tiles.set_terrain(6, 5, Terrain::Wall);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(1));
+
+        let s = world.mind.memories[&e].sightings[&f];
+        assert_eq!((s.x, s.y, s.tick), (4, 5, Tick(0)));
+    }
+
+    #[test]
+    fn test_memory_expires() {
+        let mut world = World::new_with_seed(42);
+        let e = observer(&mut world, 1, 5);
+        let f = food_at(&mut world, 4, 5);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+
+        world.tiles.set_terrain(2, 5, Terrain::Wall);
+        run_perception(&mut world, Tick(MEMORY_TICKS));
+        assert!(seen(&world, e, f));
+        run_perception(&mut world, Tick(MEMORY_TICKS + 1));
+        assert!(!seen(&world, e, f));
+    }
+
+    #[test]
+    fn test_memory_forgets_dead_entities() {
+        let mut world = World::new_with_seed(42);
+        let e = observer(&mut world, 1, 5);
+        let f = food_at(&mut world, 4, 5);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+        assert!(seen(&world, e, f));
+
+        world.despawn(f);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(1));
+        assert!(remembered(&world, e).is_empty());
+        assert!(!seen(&world, e, f));
+    }
+
+    #[test]
+    fn test_nearby_observers_do_not_look() {
+        use crate::lod::LodZone;
+        use crate::registry::QuartierId;
+
+        let mut world = World::new_with_seed(42);
+        let e = observer(&mut world, 1, 5);
+        let f = food_at(&mut world, 4, 5);
+        world.tiles.set_quartier_id(1, 5, 1);
+        world.gis.lod_zones.insert(QuartierId(1), LodZone::Nearby);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+        assert!(!seen(&world, e, f));
+
+        world.gis.lod_zones.insert(QuartierId(1), LodZone::Active);
+        run_perception(&mut world, Tick(1));
+        assert!(seen(&world, e, f));
+    }
+}
@@ -0,0 +1,490 @@
+use crate::components::{ActionId, Entity, Plan, PlanStep, Position, TaskId, Tick};
+use crate::systems::decisions::select_eat_target;
+use crate::tile_map::Terrain;
+use crate::world::World;
+
+/// Replans allowed after step failures before a plan is abandoned.
+pub const MAX_REPLANS: u8 = 3;
+
+/// Hunger ratio below which an entity is patient enough to carry food home.
+const HOME_MEAL_HUNGER: f32 = 0.7;
+
+/// Result of checking the current step against the world.
+enum StepStatus {
+    Done,
+    Running,
+    Failed,
+}
+
+// ---------------------------------------------------------------------------
+// Planner — called by run_decisions (single writer of plans)
+// ---------------------------------------------------------------------------
+
+/// HTN layer on top of the utility scorer. The scorer picks *what* to do;
+/// this expands the chosen action into primitive steps and keeps the plan
+/// honest each tick.
+///
+/// Returns the plan the entity should follow this tick, or None when the
+/// action has no plan (single-step actions), the plan completed, or it was
+/// abandoned after `MAX_REPLANS` failures. A plan serving a different action
+/// than `action` is abandoned and a fresh one expanded.
+pub(crate) fn update_plan(world: &World, entity: Entity, action: ActionId) -> Option<Plan> {
+    let mut plan = match world.mind.plans.get(&entity) {
+        Some(p) if p.action == action => p.clone(),
+        _ => expand(world, entity, action)?,
+    };
+
+    loop {
+        let step = plan.current_step()?;
+        let status = if upcoming_subjects_valid(world, entity, &plan) {
+            step_status(world, entity, step)
+        } else {
+            StepStatus::Failed
+        };
+        match status {
+            StepStatus::Done => plan.current += 1,
+            StepStatus::Running => return Some(plan),
+            StepStatus::Failed => {
+                if plan.replans >= MAX_REPLANS {
+                    return None;
+                }
+                let replans = plan.replans + 1;
+                plan = expand(world, entity, action)?;
+                plan.replans = replans;
+            }
+        }
+    }
+}
+
+/// The intention for a plan's current step: which primitive to run and
+/// which entity it concerns. MoveTo steps report the plan's subject (the
+/// food being fetched) so the target stays visible while walking.
+pub(crate) fn step_intention(plan: &Plan) -> (ActionId, Option<Entity>) {
+    match plan.current_step() {
+        Some(PlanStep::PickUp { item }) => (ActionId::PickUp, Some(item)),
+        Some(PlanStep::Consume { food }) => (plan.action, Some(food)),
+        Some(PlanStep::MoveTo { .. }) | None => (plan.action, plan_subject(plan)),
+    }
+}
+
+/// First entity referenced by the remaining steps.
+fn plan_subject(plan: &Plan) -> Option<Entity> {
+    plan.steps[plan.current.min(plan.steps.len())..]
+        .iter()
+        .find_map(|step| match *step {
+            PlanStep::PickUp { item } => Some(item),
+            PlanStep::Consume { food } => Some(food),
+            PlanStep::MoveTo { .. } => None,
+        })
+}
+
+/// Domain methods per action, tried in order; first applicable wins.
+fn expand(world: &World, entity: Entity, action: ActionId) -> Option<Plan> {
+    match action {
+        ActionId::Eat => {
+            let food = select_eat_target(world, entity)?;
+            let fp = *world.body.positions.get(&food)?;
+            let (task, steps) = eat_at_home(world, entity, food, fp).unwrap_or((
+                TaskId::EatNearby,
+                vec![
+                    PlanStep::MoveTo { x: fp.x, y: fp.y },
+                    PlanStep::Consume { food },
+                ],
+            ));
+            Some(Plan {
+                task,
+                action,
+                steps,
+                current: 0,
+                replans: 0,
+            })
+        }
+        ActionId::Idle
+        | ActionId::Wander
+        | ActionId::Attack
+        | ActionId::PickUp
+        | ActionId::Flee
+        | ActionId::Defend
+        | ActionId::Rescue
+        | ActionId::Rob
+        | ActionId::Patrol
+        | ActionId::Extinguish => None,
+    }
+}
+
+/// Carry food home when not yet urgently hungry and home has a floor tile.
+fn eat_at_home(
+    world: &World,
+    entity: Entity,
+    food: Entity,
+    fp: Position,
+) -> Option<(TaskId, Vec<PlanStep>)> {
+    let hunger = world.mind.hungers.get(&entity)?;
+    if hunger.max <= 0.0 || hunger.current / hunger.max >= HOME_MEAL_HUNGER {
+        return None;
+    }
+    let home = world.gis.home_buildings.get(&entity)?.0;
+    if world.tiles.get_building_id(fp.x as usize, fp.y as usize) == Some(home) {
+        return None; // Food is already at home
+    }
+    // Nearest floor tile of home to the food; ties to lowest (y, x).
+    let &(hx, hy) = world
+        .gis
+        .buildings
+        .get(home)?
+        .tiles
+        .iter()
+        .filter(|&&(x, y)| world.tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Floor))
+        .min_by_key(|&&(x, y)| ((x - fp.x).abs().max((y - fp.y).abs()), y, x))?;
+    Some((
+        TaskId::EatAtHome,
+        vec![
+            PlanStep::MoveTo { x: fp.x, y: fp.y },
+            PlanStep::PickUp { item: food },
+            PlanStep::MoveTo { x: hx, y: hy },
+            PlanStep::Consume { food },
+        ],
+    ))
+}
+
+/// Entities referenced by steps after the current one must still be usable:
+/// alive, not dying, and not carried off by someone else.
+fn upcoming_subjects_valid(world: &World, entity: Entity, plan: &Plan) -> bool {
+    plan.steps[plan.current + 1..]
+        .iter()
+        .all(|step| match *step {
+            PlanStep::MoveTo { .. } => true,
+            PlanStep::PickUp { item: e } | PlanStep::Consume { food: e } => {
+                world.alive.contains(&e)
+                    && !world.pending_deaths.contains(&e)
+                    && world
+                        .mind
+                        .carried_by
+                        .get(&e)
+                        .is_none_or(|&carrier| carrier == entity)
+            }
+        })
+}
+
+fn step_status(world: &World, entity: Entity, step: PlanStep) -> StepStatus {
+    let Some(&pos) = world.body.positions.get(&entity) else {
+        return StepStatus::Failed;
+    };
+    let at_entity = |e: Entity| {
+        world
+            .body
+            .positions
+            .get(&e)
+            .is_some_and(|p| p.x == pos.x && p.y == pos.y)
+    };
+    match step {
+        PlanStep::MoveTo { x, y } => {
+            if pos.x == x && pos.y == y {
+                StepStatus::Done
+            } else if x < 0 || y < 0 || !world.tiles.is_walkable(x as usize, y as usize) {
+                StepStatus::Failed
+            } else {
+                StepStatus::Running
+            }
+        }
+        PlanStep::PickUp { item } => match world.mind.carried_by.get(&item) {
+            Some(&carrier) if carrier == entity => StepStatus::Done,
+            Some(_) => StepStatus::Failed,
+            None if world.alive.contains(&item)
+                && !world.pending_deaths.contains(&item)
+                && at_entity(item) =>
+            {
+                StepStatus::Running
+            }
+            None => StepStatus::Failed,
+        },
+        PlanStep::Consume { food } => {
+            if !world.alive.contains(&food) {
+                StepStatus::Done
+            } else if at_entity(food) {
+                StepStatus::Running
+            } else {
+                StepStatus::Failed
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Executor — Phase 4
+// ---------------------------------------------------------------------------
+
+/// Phase 4 (Actions): execute non-movement plan primitives.
+///
+/// Runs after `run_wander`. Drops items whose carrier's plan no longer
+/// references them (criminals keep what they carry: it may be stolen),
+/// picks up items for PickUp intentions on the same tile,
+/// then moves every carried item to its carrier's position.
+pub fn run_plans(world: &mut World, _tick: Tick) {
+    // Drop items no longer part of the carrier's plan (abandoned/completed).
+    let mut drops: Vec<Entity> = world
+        .mind
+        .carried_by
+        .iter()
+        .filter(|&(_, carrier)| !world.mind.criminals.contains_key(carrier))
+        .filter(|&(&item, carrier)| {
+            !world.mind.plans.get(carrier).is_some_and(|plan| {
+                plan.steps[plan.current.min(plan.steps.len())..]
+                    .contains(&PlanStep::Consume { food: item })
+            })
+        })
+        .map(|(&item, _)| item)
+        .collect();
+    drops.sort_by_key(|e| e.0);
+    for item in drops {
+        world.mind.carried_by.remove(&item);
+    }
+
+    // Pick up: PickUp intention, item on the same tile and not already held.
+    let mut pickers: Vec<(Entity, Entity)> = world
+        .mind
+        .intentions
+        .iter()
+        .filter(|(_, i)| i.action == ActionId::PickUp)
+        .filter_map(|(&e, i)| Some((e, i.target?)))
+        .filter(|(e, _)| !world.pending_deaths.contains(e))
+        .collect();
+    pickers.sort_by_key(|(e, _)| e.0);
+    for (carrier, item) in pickers {
+        if world.mind.carried_by.contains_key(&item)
+            || world.pending_deaths.contains(&item)
+            || !world.alive.contains(&item)
+        {
+            continue;
+        }
+        let (Some(cp), Some(ip)) = (
+            world.body.positions.get(&carrier),
+            world.body.positions.get(&item),
+        ) else {
+            continue;
+        };
+        if cp.x == ip.x && cp.y == ip.y {
+            world.mind.carried_by.insert(item, carrier);
+        }
+    }
+
+    // Carried items travel with their carrier.
+    let mut syncs: Vec<(Entity, Position)> = world
+        .mind
+        .carried_by
+        .iter()
+        .filter_map(|(&item, carrier)| Some((item, *world.body.positions.get(carrier)?)))
+        .collect();
+    syncs.sort_by_key(|(e, _)| e.0);
+    for (item, pos) in syncs {
+        world.body.positions.insert(item, pos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::*;
+    use crate::registry::BuildingData;
+    use crate::systems::perception::run_perception;
+    use std::collections::HashMap;
+
+    fn hungry_entity(world: &mut World, x: i32, y: i32, hunger: f32) -> Entity {
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x, y });
+        world.mind.hungers.insert(
+            e,
+            Hunger {
+                current: hunger,
+                max: 100.0,
+            },
+        );
+        world.mind.action_states.insert(
+            e,
+            ActionState {
+                current_action: None,
+                ticks_in_action: 0,
+                cooldowns: HashMap::new(),
+            },
+        );
+        e
+    }
+
+    fn food_at(world: &mut World, x: i32, y: i32) -> Entity {
+        let f = world.spawn();
+        world.body.positions.insert(f, Position { x, y });
+        world.mind.nutritions.insert(f, Nutrition { value: 30.0 });
+        f
+    }
+
+    /// Home building covering floor tiles (20..=22, 20).
+    fn add_home(world: &mut World, entity: Entity) {
+        let id = world.gis.buildings.next_id();
+        let tiles = vec![(20, 20), (21, 20), (22, 20)];
+        for &(x, y) in &tiles {
+            world
+                .tiles
+                .set_terrain(x as usize, y as usize, Terrain::Floor);
+            world.tiles.set_building_id(x as usize, y as usize, id);
+        }
+        world.gis.buildings.insert(BuildingData {
+            quartier: "Arcis".into(),
+            ..BuildingData::test_default(id, tiles)
+        });
+        world.gis.home_buildings.insert(entity, HomeBuilding(id));
+    }
+
+    #[test]
+    fn test_eat_expands_to_eat_nearby_without_home() {
+        let mut world = World::new_with_seed(42);
+        let e = hungry_entity(&mut world, 5, 5, 50.0);
+        let food = food_at(&mut world, 8, 5);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+
+        let plan = update_plan(&world, e, ActionId::Eat).expect("plan");
+        assert_eq!(plan.task, TaskId::EatNearby);
+        assert_eq!(
+            plan.steps,
+            vec![PlanStep::MoveTo { x: 8, y: 5 }, PlanStep::Consume { food }]
+        );
+        assert_eq!(step_intention(&plan), (ActionId::Eat, Some(food)));
+    }
+
+    #[test]
+    fn test_eat_expands_to_eat_at_home_when_patient() {
+        let mut world = World::new_with_seed(42);
+        let e = hungry_entity(&mut world, 5, 5, 50.0);
+        add_home(&mut world, e);
+        let food = food_at(&mut world, 8, 5);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+
+        let plan = update_plan(&world, e, ActionId::Eat).expect("plan");
+        assert_eq!(plan.task, TaskId::EatAtHome);
+        assert_eq!(plan.steps[1], PlanStep::PickUp { item: food });
+        assert_eq!(plan.steps[2], PlanStep::MoveTo { x: 20, y: 20 });
+    }
+
+    #[test]
+    fn test_urgent_hunger_eats_on_the_spot() {
+        let mut world = World::new_with_seed(42);
+        let e = hungry_entity(&mut world, 5, 5, 90.0);
+        add_home(&mut world, e);
+        food_at(&mut world, 8, 5);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+
+        let plan = update_plan(&world, e, ActionId::Eat).expect("plan");
+        assert_eq!(plan.task, TaskId::EatNearby);
+    }
+
+    #[test]
+    fn test_completed_steps_advance() {
+        let mut world = World::new_with_seed(42);
+        let e = hungry_entity(&mut world, 8, 5, 50.0);
+        let food = food_at(&mut world, 8, 5);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+
+        // Already standing on the food: MoveTo is done immediately.
+        let plan = update_plan(&world, e, ActionId::Eat).expect("plan");
+        assert_eq!(plan.current, 1);
+        assert_eq!(plan.current_step(), Some(PlanStep::Consume { food }));
+    }
+
+    #[test]
+    fn test_action_change_abandons_plan() {
+        let mut world = World::new_with_seed(42);
+        let e = hungry_entity(&mut world, 5, 5, 50.0);
+        food_at(&mut world, 8, 5);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+
+        let plan = update_plan(&world, e, ActionId::Eat).expect("plan");
+        world.mind.plans.insert(e, plan);
+        assert!(update_plan(&world, e, ActionId::Wander).is_none());
+    }
+
+    #[test]
+    fn test_failed_step_replans_to_other_food() {
+        let mut world = World::new_with_seed(42);
+        let e = hungry_entity(&mut world, 5, 5, 50.0);
+        let near = food_at(&mut world, 7, 5);
+        let far = food_at(&mut world, 12, 5);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+
+        let plan = update_plan(&world, e, ActionId::Eat).expect("plan");
+        assert_eq!(plan.steps[1], PlanStep::Consume { food: near });
+        world.mind.plans.insert(e, plan);
+
+        // Someone else eats the near food.
+        world.pending_deaths.insert(near);
+        let plan = update_plan(&world, e, ActionId::Eat).expect("replanned");
+        assert_eq!(plan.replans, 1);
+        assert_eq!(plan.steps[1], PlanStep::Consume { food: far });
+    }
+
+    #[test]
+    fn test_plan_abandoned_after_max_replans() {
+        let mut world = World::new_with_seed(42);
+        let e = hungry_entity(&mut world, 5, 5, 50.0);
+        let food = food_at(&mut world, 7, 5);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+
+        // Food on an unwalkable tile: every expansion fails its MoveTo.
+        world.tiles.set_terrain(7, 5, Terrain::Wall);
+        assert!(update_plan(&world, e, ActionId::Eat).is_none());
+
+        let plan = Plan {
+            task: TaskId::EatNearby,
+            action: ActionId::Eat,
+            steps: vec![PlanStep::MoveTo { x: 7, y: 5 }, PlanStep::Consume { food }],
+            current: 0,
+            replans: MAX_REPLANS,
+        };
+        world.mind.plans.insert(e, plan);
+        assert!(update_plan(&world, e, ActionId::Eat).is_none());
+    }
+
+    #[test]
+    fn test_run_plans_picks_up_and_carries() {
+        let mut world = World::new_with_seed(42);
+        let e = hungry_entity(&mut world, 8, 5, 50.0);
+        add_home(&mut world, e);
+        let food = food_at(&mut world, 8, 5);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(0));
+
+        let plan = update_plan(&world, e, ActionId::Eat).expect("plan");
+        assert_eq!(plan.current_step(), Some(PlanStep::PickUp { item: food }));
+        let (action, target) = step_intention(&plan);
+        world.mind.plans.insert(e, plan);
+        world
+            .mind
+            .intentions
+            .insert(e, Intention { action, target });
+
+        run_plans(&mut world, Tick(0));
+        assert_eq!(world.mind.carried_by.get(&food), Some(&e));
+
+        // Carrier moves; the item follows.
+        world.body.positions.insert(e, Position { x: 9, y: 6 });
+        run_plans(&mut world, Tick(1));
+        let fp = world.body.positions[&food];
+        assert_eq!((fp.x, fp.y), (9, 6));
+    }
+
+    #[test]
+    fn test_run_plans_drops_when_plan_abandoned() {
+        let mut world = World::new_with_seed(42);
+        let e = hungry_entity(&mut world, 8, 5, 50.0);
+        let food = food_at(&mut world, 8, 5);
+        world.mind.carried_by.insert(food, e);
+
+        run_plans(&mut world, Tick(0));
+        assert!(world.mind.carried_by.is_empty());
+    }
+}
@@ -0,0 +1,352 @@
+use crate::components::{Entity, Tick};
+use crate::events::Event;
+use crate::faction::{self, AttackResponse, Stance};
+use crate::systems::crime::{self, POLICE_RESPONSE_RANGE};
+use crate::systems::perception::SENSE_RANGE;
+use crate::world::World;
+
+/// Ticks a grudge against an attacker lasts.
+pub const GRUDGE_TICKS: u64 = 600;
+
+/// Ticks a victim keeps fleeing an attacker.
+pub const FEAR_TICKS: u64 = 300;
+
+/// Chebyshev radius within which a call for help is heard (no LOS needed).
+pub const HELP_RANGE: i32 = 20;
+
+/// Phase 3 (Decisions): faction reactions to last tick's attacks.
+///
+/// Runs after `run_perception`, before `run_decisions`. For every
+/// `Event::Attacked` and noticed `Event::Theft` from the previous tick:
+/// - the victim fights back (grudge) or flees (fear), per its faction;
+/// - allies who saw the attacker this tick, or heard the victim call for
+///   help within `HELP_RANGE`, take a grudge against the attacker if their
+///   faction defends allies;
+/// - police within `POLICE_RESPONSE_RANGE` take a grudge against the
+///   attacker whether or not they saw it, and against criminals even from
+///   an allied faction.
+///
+/// Then drops expired grievances and grievances against the dead or
+/// imprisoned.
+pub fn run_reactions(world: &mut World, tick: Tick) {
+    let mut grudges: Vec<(Entity, Entity)> = Vec::new(); // (holder, against)
+    let mut fears: Vec<(Entity, Entity)> = Vec::new();
+
+    if tick.0 > 0 {
+        let last = Tick(tick.0 - 1);
+        let attacks: Vec<(Entity, Entity)> = world
+            .events
+            .since(last)
+            .into_iter()
+            .filter_map(|e| match *e {
+                Event::Attacked {
+                    attacker,
+                    defender,
+                    tick: t,
+                    ..
+                } if t == last => Some((attacker, defender)),
+                Event::Theft {
+                    thief,
+                    victim,
+                    noticed: true,
+                    tick: t,
+                    ..
+                } if t == last => Some((thief, victim)),
+                _ => None,
+            })
+            .collect();
+
+        for (attacker, defender) in attacks {
+            if !world.alive.contains(&attacker) || world.pending_deaths.contains(&attacker) {
+                continue;
+            }
+            let victim_def = world
+                .mind
+                .faction_members
+                .get(&defender)
+                .and_then(|m| world.mind.factions.get(m.faction));
+            if world.alive.contains(&defender) {
+                match victim_def.map_or(AttackResponse::Fight, |f| f.when_attacked) {
+                    AttackResponse::Fight => grudges.push((defender, attacker)),
+                    AttackResponse::Flee => fears.push((defender, attacker)),
+                }
+            }
+
+            let Some(&vpos) = world.body.positions.get(&defender) else {
+                continue;
+            };
+            let calls = victim_def.is_some_and(|f| f.calls_for_help);
+            let criminal = world.mind.criminals.contains_key(&attacker);
+            let range = SENSE_RANGE.max(HELP_RANGE).max(POLICE_RESPONSE_RANGE);
+            let mut helpers: Vec<Entity> = world
+                .entities_in_range(vpos.x, vpos.y, range)
+                .filter(|&e| e != attacker && e != defender)
+                .filter(|e| !world.pending_deaths.contains(e))
+                .filter(|e| {
+                    world
+                        .mind
+                        .faction_members
+                        .get(e)
+                        .and_then(|m| world.mind.factions.get(m.faction))
+                        .is_some_and(|f| f.defends_allies)
+                })
+                .filter(|&e| {
+                    if crime::is_police(world, e) {
+                        return true; // within POLICE_RESPONSE_RANGE
+                    }
+                    let heard = calls
+                        && world.body.positions.get(&e).is_some_and(|p| {
+                            (p.x - vpos.x).abs().max((p.y - vpos.y).abs()) <= HELP_RANGE
+                        });
+                    let saw = world
+                        .mind
+                        .memories
+                        .get(&e)
+                        .and_then(|m| m.sightings.get(&attacker))
+                        .is_some_and(|s| s.tick == tick);
+                    heard || saw
+                })
+                .filter(|&e| faction::stance_between(world, e, defender) == Stance::Allied)
+                .filter(|&e| {
+                    (criminal && crime::is_police(world, e))
+                        || faction::stance_between(world, e, attacker) != Stance::Allied
+                })
+                .collect();
+            helpers.sort_by_key(|e| e.0);
+            grudges.extend(helpers.into_iter().map(|h| (h, attacker)));
+        }
+    }
+
+    for (holder, against) in grudges {
+        world
+            .mind
+            .grievances
+            .entry(holder)
+            .or_default()
+            .grudges
+            .insert(against, Tick(tick.0 + GRUDGE_TICKS));
+    }
+    for (holder, against) in fears {
+        world
+            .mind
+            .grievances
+            .entry(holder)
+            .or_default()
+            .fears
+            .insert(against, Tick(tick.0 + FEAR_TICKS));
+    }
+
+    // Expire grievances; drop holders with nothing left.
+    let alive = &world.alive;
+    let imprisoned = &world.mind.imprisoned;
+    let present = |e: &Entity| alive.contains(e) && !imprisoned.contains_key(e);
+    world.mind.grievances.retain(|_, g| {
+        g.grudges.retain(|e, until| present(e) && *until > tick);
+        g.fears.retain(|e, until| present(e) && *until > tick);
+        !g.grudges.is_empty() || !g.fears.is_empty()
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::*;
+    use crate::faction::{FactionDef, FactionId};
+    use crate::registry::QuartierId;
+    use crate::systems::perception::run_perception;
+    use std::collections::HashMap;
+
+    fn setup() -> (World, FactionId, FactionId) {
+        let mut world = World::new_with_seed(42);
+        let residents = world.mind.factions.insert(FactionDef {
+            key: "residents".into(),
+            name: "Résidents".into(),
+            local: true,
+            when_attacked: AttackResponse::Flee,
+            calls_for_help: true,
+            defends_allies: true,
+        });
+        let criminals = world.mind.factions.insert(FactionDef {
+            key: "criminals".into(),
+            name: "Pègre".into(),
+            local: false,
+            when_attacked: AttackResponse::Fight,
+            calls_for_help: false,
+            defends_allies: false,
+        });
+        (world, residents, criminals)
+    }
+
+    fn person(world: &mut World, faction: FactionId, quartier: u8, x: i32, y: i32) -> Entity {
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x, y });
+        world.body.combat_stats.insert(
+            e,
+            CombatStats {
+                attack: 10.0,
+                defense: 5.0,
+                aggression: 0.0,
+            },
+        );
+        world.mind.action_states.insert(
+            e,
+            ActionState {
+                current_action: None,
+                ticks_in_action: 0,
+                cooldowns: HashMap::new(),
+            },
+        );
+        world.mind.faction_members.insert(
+            e,
+            FactionMember {
+                faction,
+                quartier: Some(QuartierId(quartier)),
+            },
+        );
+        e
+    }
+
+    fn attack(world: &mut World, attacker: Entity, defender: Entity, tick: u64) {
+        world.events.push(Event::Attacked {
+            attacker,
+            defender,
+            damage: 5.0,
+            tick: Tick(tick),
+        });
+    }
+
+    fn has_grudge(world: &World, holder: Entity, against: Entity) -> bool {
+        world
+            .mind
+            .grievances
+            .get(&holder)
+            .is_some_and(|g| g.grudges.contains_key(&against))
+    }
+
+    #[test]
+    fn test_victim_flees_per_faction() {
+        let (mut world, residents, criminals) = setup();
+        let victim = person(&mut world, residents, 1, 5, 5);
+        let thief = person(&mut world, criminals, 1, 5, 5);
+        attack(&mut world, thief, victim, 0);
+        run_reactions(&mut world, Tick(1));
+
+        let g = &world.mind.grievances[&victim];
+        assert!(g.fears.contains_key(&thief));
+        assert!(g.grudges.is_empty());
+    }
+
+    #[test]
+    fn test_victim_fights_back_per_faction() {
+        let (mut world, residents, criminals) = setup();
+        let thief = person(&mut world, criminals, 1, 5, 5);
+        let resident = person(&mut world, residents, 1, 5, 5);
+        attack(&mut world, resident, thief, 0);
+        run_reactions(&mut world, Tick(1));
+        assert!(has_grudge(&world, thief, resident));
+    }
+
+    #[test]
+    fn test_neighbors_answer_call_for_help() {
+        let (mut world, residents, criminals) = setup();
+        let victim = person(&mut world, residents, 1, 5, 5);
+        let thief = person(&mut world, criminals, 1, 5, 5);
+        let neighbor = person(&mut world, residents, 1, 15, 5);
+        let stranger = person(&mut world, residents, 2, 15, 6);
+        let far = person(&mut world, residents, 1, 50, 50);
+        world.rebuild_spatial_index();
+        attack(&mut world, thief, victim, 0);
+        run_reactions(&mut world, Tick(1));
+
+        assert!(has_grudge(&world, neighbor, thief));
+        assert!(!has_grudge(&world, stranger, thief), "other quartier");
+        assert!(!has_grudge(&world, far, thief), "out of earshot");
+        assert!(faction::is_hostile(&world, neighbor, thief));
+    }
+
+    #[test]
+    fn test_witness_defends_without_call() {
+        let (mut world, residents, criminals) = setup();
+        if let Some(def) = world.mind.factions.factions.get_mut(residents.0 as usize) {
+            def.calls_for_help = false;
+        }
+        let victim = person(&mut world, residents, 1, 5, 5);
+        let thief = person(&mut world, criminals, 1, 5, 5);
+        let witness = person(&mut world, residents, 1, 28, 5);
+        let behind_wall = person(&mut world, residents, 1, 5, 28);
+        world
+            .tiles
+            .set_terrain(5, 20, crate::tile_map::Terrain::Wall);
+        world.rebuild_spatial_index();
+        run_perception(&mut world, Tick(1));
+        attack(&mut world, thief, victim, 0);
+        run_reactions(&mut world, Tick(1));
+
+        assert!(has_grudge(&world, witness, thief));
+        assert!(!has_grudge(&world, behind_wall, thief));
+    }
+
+    #[test]
+    fn test_police_respond_to_criminal_neighbor() {
+        let (mut world, residents, _) = setup();
+        let police = world.mind.factions.insert(FactionDef {
+            key: "police".into(),
+            name: "Police".into(),
+            local: false,
+            when_attacked: AttackResponse::Fight,
+            calls_for_help: true,
+            defends_allies: true,
+        });
+        world
+            .mind
+            .factions
+            .set_stance(residents, police, Stance::Allied);
+        let victim = person(&mut world, residents, 1, 5, 5);
+        let thief = person(&mut world, residents, 1, 5, 5);
+        world.mind.criminals.insert(
+            thief,
+            Criminal {
+                crime: Crime::Theft,
+                last_offence: None,
+            },
+        );
+        let officer = person(&mut world, police, 0, 40, 5);
+        world.rebuild_spatial_index();
+        world.events.push(Event::Theft {
+            thief,
+            victim,
+            success: true,
+            noticed: true,
+            loot: None,
+            tick: Tick(0),
+        });
+        run_reactions(&mut world, Tick(1));
+
+        assert!(world.mind.grievances[&victim].fears.contains_key(&thief));
+        assert!(has_grudge(&world, officer, thief));
+
+        // Locked up: nobody holds it against them any more.
+        world.mind.imprisoned.insert(
+            thief,
+            Imprisoned {
+                prison: crate::registry::BuildingId(1),
+                release: Tick(10_000),
+            },
+        );
+        run_reactions(&mut world, Tick(2));
+        assert!(!has_grudge(&world, officer, thief));
+    }
+
+    #[test]
+    fn test_grievances_expire() {
+        let (mut world, residents, criminals) = setup();
+        let thief = person(&mut world, criminals, 1, 5, 5);
+        let resident = person(&mut world, residents, 1, 5, 5);
+        attack(&mut world, resident, thief, 0);
+        run_reactions(&mut world, Tick(1));
+        assert!(has_grudge(&world, thief, resident));
+
+        run_reactions(&mut world, Tick(1 + GRUDGE_TICKS));
+        assert!(world.mind.grievances.is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::components::{ActionId, Entity, Position, Tick};
+use crate::systems::fatigue::UNCONSCIOUS_THRESHOLD;
+use crate::world::World;
+
+/// Move cooldown multiplier while dragging someone.
+pub const DRAG_SLOWDOWN: u32 = 2;
+
+/// Whether `entity` is currently dragging anyone.
+pub fn is_dragging(world: &World, entity: Entity) -> bool {
+    world.mind.dragged_by.values().any(|&r| r == entity)
+}
+
+/// Phase 4 (Actions): rescuers pick up, drag and release unconscious allies.
+///
+/// Runs after `run_wander`, so the patient follows the rescuer's new tile.
+/// - Releases patients who came to, or whose rescuer stopped rescuing them.
+/// - A rescuer with a Rescue intention within one tile of its target grabs
+///   it, unless someone else already has.
+/// - Every dragged patient is moved onto its rescuer's tile.
+pub fn run_rescue(world: &mut World, _tick: Tick) {
+    let unconscious = |e: &Entity| {
+        world
+            .body
+            .fatigues
+            .get(e)
+            .is_some_and(|f| f.current >= UNCONSCIOUS_THRESHOLD)
+    };
+    let rescuing = |rescuer: &Entity, patient: Entity| {
+        world
+            .mind
+            .intentions
+            .get(rescuer)
+            .is_some_and(|i| i.action == ActionId::Rescue && i.target == Some(patient))
+    };
+
+    let mut released: Vec<Entity> = world
+        .mind
+        .dragged_by
+        .iter()
+        .filter(|&(p, r)| {
+            !unconscious(p)
+                || !rescuing(r, *p)
+                || world.pending_deaths.contains(p)
+                || world.pending_deaths.contains(r)
+        })
+        .map(|(&p, _)| p)
+        .collect();
+    released.sort_by_key(|e| e.0);
+
+    let mut rescuers: Vec<(Entity, Entity)> = world
+        .mind
+        .intentions
+        .iter()
+        .filter(|(_, i)| i.action == ActionId::Rescue)
+        .filter(|(e, _)| !world.pending_deaths.contains(e))
+        .filter_map(|(&r, i)| i.target.map(|p| (r, p)))
+        .filter(|&(_, p)| unconscious(&p) && !world.pending_deaths.contains(&p))
+        .filter(
+            |&(r, p)| match (world.body.positions.get(&r), world.body.positions.get(&p)) {
+                (Some(a), Some(b)) => (a.x - b.x).abs().max((a.y - b.y).abs()) <= 1,
+                _ => false,
+            },
+        )
+        .collect();
+    rescuers.sort_by_key(|(r, _)| r.0);
+
+    for p in released {
+        world.mind.dragged_by.remove(&p);
+    }
+    for (r, p) in rescuers {
+        let held = world.mind.dragged_by.contains_key(&p) || is_dragging(world, r);
+        if !held {
+            world.mind.dragged_by.insert(p, r);
+        }
+    }
+
+    let mut moves: Vec<(Entity, Position)> = world
+        .mind
+        .dragged_by
+        .iter()
+        .filter_map(|(&p, r)| world.body.positions.get(r).map(|&pos| (p, pos)))
+        .collect();
+    moves.sort_by_key(|(e, _)| e.0);
+    for (p, pos) in moves {
+        world.body.positions.insert(p, pos);
+        world.mind.cached_paths.remove(&p);
+        world.mind.wander_targets.remove(&p);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::*;
+
+    fn at(world: &mut World, x: i32, y: i32, fatigue: f32) -> Entity {
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x, y });
+        world.body.fatigues.insert(e, Fatigue { current: fatigue });
+        e
+    }
+
+    fn intend_rescue(world: &mut World, rescuer: Entity, patient: Entity) {
+        world.mind.intentions.insert(
+            rescuer,
+            Intention {
+                action: ActionId::Rescue,
+                target: Some(patient),
+            },
+        );
+    }
+
+    #[test]
+    fn test_rescuer_grabs_and_drags_adjacent_patient() {
+        let mut world = World::new_with_seed(42);
+        let rescuer = at(&mut world, 5, 5, 0.0);
+        let patient = at(&mut world, 6, 5, 150.0);
+        intend_rescue(&mut world, rescuer, patient);
+        run_rescue(&mut world, Tick(0));
+        assert_eq!(world.mind.dragged_by.get(&patient), Some(&rescuer));
+        assert_eq!(world.body.positions[&patient].x, 5);
+
+        // Rescuer moves; patient follows on the next pass.
+        world
+            .body
+            .positions
+            .insert(rescuer, Position { x: 4, y: 4 });
+        run_rescue(&mut world, Tick(1));
+        let p = world.body.positions[&patient];
+        assert_eq!((p.x, p.y), (4, 4));
+    }
+
+    #[test]
+    fn test_out_of_reach_is_not_grabbed() {
+        let mut world = World::new_with_seed(42);
+        let rescuer = at(&mut world, 5, 5, 0.0);
+        let patient = at(&mut world, 8, 5, 150.0);
+        intend_rescue(&mut world, rescuer, patient);
+        run_rescue(&mut world, Tick(0));
+        assert!(world.mind.dragged_by.is_empty());
+    }
+
+    #[test]
+    fn test_released_when_patient_wakes_or_rescuer_stops() {
+        let mut world = World::new_with_seed(42);
+        let rescuer = at(&mut world, 5, 5, 0.0);
+        let patient = at(&mut world, 5, 5, 150.0);
+        intend_rescue(&mut world, rescuer, patient);
+        run_rescue(&mut world, Tick(0));
+        assert!(world.mind.dragged_by.contains_key(&patient));
+
+        world.mind.intentions.clear();
+        run_rescue(&mut world, Tick(1));
+        assert!(world.mind.dragged_by.is_empty());
+
+        intend_rescue(&mut world, rescuer, patient);
+        run_rescue(&mut world, Tick(2));
+        world
+            .body
+            .fatigues
+            .insert(patient, Fatigue { current: 50.0 });
+        run_rescue(&mut world, Tick(3));
+        assert!(world.mind.dragged_by.is_empty());
+    }
+
+    #[test]
+    fn test_one_rescuer_per_patient() {
+        let mut world = World::new_with_seed(42);
+        let a = at(&mut world, 5, 5, 0.0);
+        let b = at(&mut world, 5, 6, 0.0);
+        let patient = at(&mut world, 5, 5, 150.0);
+        intend_rescue(&mut world, a, patient);
+        intend_rescue(&mut world, b, patient);
+        run_rescue(&mut world, Tick(0));
+        assert_eq!(world.mind.dragged_by.get(&patient), Some(&a));
+    }
+}
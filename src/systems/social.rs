@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+
+use crate::components::{Entity, Tick};
+use crate::events::Event;
+use crate::registry::BuildingId;
+use crate::relationships::ModifierKind;
+use crate::world::World;
+
+/// Ticks between neighbor/coworker refreshes (one game hour).
+pub const SOCIAL_INTERVAL: u64 = 60;
+
+/// Each member of a household or workplace knows at most this many others
+/// on either side of it (by entity ID), so tenements stay O(n).
+const TIES_EACH_SIDE: usize = 4;
+
+/// Phase 3 (Decisions): update opinions from recent events and shared places.
+///
+/// Runs after `run_reactions`, before `run_decisions`.
+/// - Every `Event::Attacked` from the previous tick sours the defender's
+///   opinion of the attacker.
+/// - Every `SOCIAL_INTERVAL` ticks, refreshes `Neighbor` ties between
+///   entities sharing a `HomeBuilding` and `Coworker` ties between entities
+///   sharing a `Workplace`.
+/// - Drops expired modifiers.
+pub fn run_social(world: &mut World, tick: Tick) {
+    let mut applied: Vec<(Entity, Entity, ModifierKind)> = Vec::new();
+
+    if tick.0 > 0 {
+        let last = Tick(tick.0 - 1);
+        for event in world.events.since(last) {
+            if let Event::Attacked {
+                attacker,
+                defender,
+                tick: t,
+                ..
+            } = *event
+                && t == last
+                && attacker != defender
+                && world.alive.contains(&attacker)
+                && world.alive.contains(&defender)
+            {
+                applied.push((defender, attacker, ModifierKind::AttackedMe));
+            }
+        }
+    }
+
+    if tick.0.is_multiple_of(SOCIAL_INTERVAL) {
+        let homes = world.gis.home_buildings.iter().map(|(&e, h)| (e, h.0));
+        applied.extend(ties(world, homes, ModifierKind::Neighbor));
+        let works = world.gis.workplaces.iter().map(|(&e, w)| (e, w.0));
+        applied.extend(ties(world, works, ModifierKind::Coworker));
+    }
+
+    let rel = &mut world.mind.relationships;
+    for (a, b, kind) in applied {
+        rel.apply(a, b, kind, tick);
+    }
+    rel.prune(tick);
+}
+
+/// Directed ties between members of the same building, each member linked
+/// to up to `TIES_EACH_SIDE` ID-order neighbors on either side.
+fn ties(
+    world: &World,
+    members: impl Iterator<Item = (Entity, BuildingId)>,
+    kind: ModifierKind,
+) -> Vec<(Entity, Entity, ModifierKind)> {
+    let mut groups: BTreeMap<u32, Vec<Entity>> = BTreeMap::new();
+    for (e, b) in members {
+        if !world.pending_deaths.contains(&e) {
+            groups.entry(b.0).or_default().push(e);
+        }
+    }
+    let mut out = Vec::new();
+    for mut group in groups.into_values() {
+        group.sort_by_key(|e| e.0);
+        for (i, &a) in group.iter().enumerate() {
+            for &b in group.iter().skip(i + 1).take(TIES_EACH_SIDE) {
+                out.push((a, b, kind));
+                out.push((b, a, kind));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{HomeBuilding, Workplace};
+    use crate::faction::{self, Stance};
+    use crate::relationships::HOSTILE_OPINION;
+
+    #[test]
+    fn test_neighbors_and_coworkers_like_each_other() {
+        let mut world = World::new_with_seed(42);
+        let a = world.spawn();
+        let b = world.spawn();
+        let c = world.spawn();
+        for e in [a, b] {
+            world
+                .gis
+                .home_buildings
+                .insert(e, HomeBuilding(BuildingId(1)));
+            world.gis.workplaces.insert(e, Workplace(BuildingId(1)));
+        }
+        world
+            .gis
+            .home_buildings
+            .insert(c, HomeBuilding(BuildingId(2)));
+        run_social(&mut world, Tick(0));
+
+        let rel = &world.mind.relationships;
+        assert_eq!(rel.opinion(a, b, Tick(0)), 25);
+        assert_eq!(rel.opinion(b, a, Tick(0)), 25);
+        assert_eq!(rel.opinion(a, c, Tick(0)), 0);
+        assert_eq!(faction::stance_between(&world, a, b), Stance::Allied);
+    }
+
+    #[test]
+    fn test_ties_are_capped_per_member() {
+        let mut world = World::new_with_seed(42);
+        let members: Vec<Entity> = (0..20).map(|_| world.spawn()).collect();
+        for &e in &members {
+            world
+                .gis
+                .home_buildings
+                .insert(e, HomeBuilding(BuildingId(1)));
+        }
+        run_social(&mut world, Tick(0));
+        let known = world.mind.relationships.known_by(members[10]);
+        assert_eq!(known.len(), 2 * TIES_EACH_SIDE);
+    }
+
+    #[test]
+    fn test_attack_sours_opinion_until_hostile() {
+        let mut world = World::new_with_seed(42);
+        let a = world.spawn();
+        let d = world.spawn();
+        world
+            .gis
+            .home_buildings
+            .insert(a, HomeBuilding(BuildingId(1)));
+        world
+            .gis
+            .home_buildings
+            .insert(d, HomeBuilding(BuildingId(1)));
+        for t in 0..2 {
+            world.events.push(Event::Attacked {
+                attacker: a,
+                defender: d,
+                damage: 1.0,
+                tick: Tick(t),
+            });
+            run_social(&mut world, Tick(t + 1));
+        }
+        let rel = &world.mind.relationships;
+        assert!(rel.opinion(d, a, Tick(2)) <= HOSTILE_OPINION);
+        assert_eq!(rel.opinion(a, d, Tick(2)), 0, "only the victim minds");
+    }
+}
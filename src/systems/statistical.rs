@@ -0,0 +1,491 @@
+use rand::RngExt;
+
+use crate::aggregate::{Folded, HEALTH_MAX, HUNGER_MAX};
+use crate::components::{Entity, GameDate, Position, Storey, Tick};
+use crate::events::Event;
+use crate::lod::LodZone;
+use crate::registry::{BuildingId, QuartierId, SHOP_CLOSES, SHOP_OPENS};
+use crate::tile_map::Terrain;
+use crate::world::World;
+
+/// Game minutes between aggregate steps.
+pub const STATISTICAL_STEP_MINUTES: u64 = 60;
+/// Wound `work_ability` below which a resident stays home from work.
+const FIT_FOR_WORK: f32 = 0.5;
+
+/// Phase 1 (Environment): district aggregate model for Statistical zones.
+///
+/// Rebuilds `world.frozen` with the entities standing in Statistical
+/// quartiers and those in prison, which the per-entity systems then skip,
+/// and every
+/// `STATISTICAL_STEP_MINUTES` ticks advances each Statistical quartier's
+/// `QuartierAggregate`. Active and Nearby quartiers keep their aggregate
+/// as it was.
+pub fn run_statistical(world: &mut World, tick: Tick) {
+    let any_statistical = world
+        .gis
+        .lod_zones
+        .values()
+        .any(|&z| z == LodZone::Statistical);
+    let frozen: Vec<Entity> = if any_statistical {
+        world
+            .body
+            .positions
+            .iter()
+            .filter(|&(e, _)| world.alive.contains(e) && world.player != Some(*e))
+            .filter(|(_, p)| world.lod_zone_at(p.x, p.y) == LodZone::Statistical)
+            .map(|(&e, _)| e)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    world.frozen.clear();
+    world.frozen.extend(frozen);
+    world.frozen.extend(world.gis.folded.keys().copied());
+    world.frozen.extend(world.mind.imprisoned.keys().copied());
+
+    if !tick.0.is_multiple_of(STATISTICAL_STEP_MINUTES) {
+        return;
+    }
+    let mut quartiers: Vec<QuartierId> = world
+        .gis
+        .lod_zones
+        .iter()
+        .filter(|&(_, &z)| z == LodZone::Statistical)
+        .map(|(&q, _)| q)
+        .collect();
+    quartiers.sort_by_key(|q| q.0);
+    for q in quartiers {
+        if let Some(agg) = world.gis.aggregates.get_mut(&q) {
+            agg.step(STATISTICAL_STEP_MINUTES as f64);
+        }
+    }
+}
+
+/// Consume `world.gis.lod_transitions`: quartiers entering Statistical fold
+/// their entities into the aggregate, quartiers leaving it unfold them.
+/// Call once per frame, right after `recompute_lod_zones`.
+pub fn apply_lod_transitions(world: &mut World) {
+    let transitions = world.gis.lod_transitions.clone();
+    for t in transitions {
+        if t.to == LodZone::Statistical {
+            fold_quartier(world, t.quartier);
+        } else if t.from == LodZone::Statistical {
+            unfold_quartier(world, t.quartier);
+        }
+    }
+}
+
+/// Take every entity standing in `quartier` off the map, keeping all its
+/// components. Residents join the aggregate's population so it simulates
+/// them; visitors are only held. Prisoners stay in their cells. Folding
+/// twice is a no-op.
+pub fn fold_quartier(world: &mut World, quartier: QuartierId) {
+    let mut entities: Vec<(Entity, Position)> = world
+        .body
+        .positions
+        .iter()
+        .filter(|&(e, _)| {
+            world.alive.contains(e) && !world.pending_deaths.contains(e) && world.player != Some(*e)
+        })
+        .filter(|(e, _)| !world.mind.imprisoned.contains_key(e))
+        .filter(|(_, p)| {
+            p.x >= 0
+                && p.y >= 0
+                && world.tiles.get_quartier_id(p.x as usize, p.y as usize) == Some(quartier.0)
+        })
+        .map(|(&e, &p)| (e, p))
+        .collect();
+    entities.sort_by_key(|(e, _)| e.0);
+
+    for (e, pos) in entities {
+        let resident = world.gis.home_buildings.contains_key(&e);
+        let mut survival = 1.0;
+        if let Some(agg) = world.gis.aggregates.get_mut(&quartier) {
+            survival = agg.survival;
+            if resident {
+                let naics = world
+                    .mind
+                    .occupations
+                    .get(&e)
+                    .map_or("", |o| o.naics.as_str());
+                agg.add_resident(naics);
+            }
+        }
+        world.gis.folded.insert(
+            e,
+            Folded {
+                quartier,
+                pos,
+                resident,
+                survival,
+            },
+        );
+        world.body.positions.remove(&e);
+        world.mind.cached_paths.remove(&e);
+        world.mind.wander_targets.remove(&e);
+    }
+}
+
+/// Put the entities folded into `quartier` back on the map. Each resident
+/// survives with the aggregate's survival odds since it was folded; the
+/// survivors leave the aggregate's count, take on its mean hunger and
+/// health, and appear at work during shop hours (unless too badly wounded
+/// to work) or at home otherwise.
+pub fn unfold_quartier(world: &mut World, quartier: QuartierId) {
+    let mut entities: Vec<(Entity, Folded)> = world
+        .gis
+        .folded
+        .iter()
+        .filter(|(_, f)| f.quartier == quartier)
+        .map(|(&e, &f)| (e, f))
+        .collect();
+    entities.sort_by_key(|(e, _)| e.0);
+    let hour = GameDate::from_tick(world.tick, &world.start_date).hour;
+    let at_work = (u32::from(SHOP_OPENS)..u32::from(SHOP_CLOSES)).contains(&hour);
+
+    for (e, f) in entities {
+        world.gis.folded.remove(&e);
+        if !f.resident {
+            world.body.positions.insert(e, f.pos);
+            continue;
+        }
+        let Some(agg) = world.gis.aggregates.get_mut(&quartier) else {
+            world.body.positions.insert(e, f.pos);
+            continue;
+        };
+        let odds = if f.survival > 0.0 {
+            (agg.survival / f.survival).min(1.0)
+        } else {
+            0.0
+        };
+        if world.rng.random::<f64>() >= odds {
+            // Already counted among the aggregate's deaths.
+            world.body.positions.insert(e, f.pos);
+            world.events.push(Event::Died {
+                entity: e,
+                tick: world.tick,
+            });
+            world.pending_deaths.insert(e);
+            continue;
+        }
+
+        let naics = world
+            .mind
+            .occupations
+            .get(&e)
+            .map_or("", |o| o.naics.as_str());
+        agg.remove_resident(naics);
+        let (mean_hunger, mean_health) = (agg.mean_hunger, agg.mean_health);
+        if let Some(h) = world.mind.hungers.get_mut(&e) {
+            h.current = h.max * (mean_hunger / HUNGER_MAX) as f32;
+        }
+        if let Some(h) = world.body.healths.get_mut(&e) {
+            h.current = h.max * (mean_health / HEALTH_MAX) as f32;
+        }
+
+        let work = world.gis.workplaces.get(&e).map(|w| w.0);
+        let home = world.gis.home_buildings.get(&e).map(|h| h.0);
+        let fit = world
+            .body
+            .wounds
+            .get(&e)
+            .is_none_or(|w| w.work_ability() >= FIT_FOR_WORK);
+        let workplace = if at_work && fit { work } else { None };
+        let (pos, storey) = if let Some(pos) = workplace.and_then(|b| floor_tile(world, b)) {
+            (pos, None)
+        } else if let Some(pos) = home.and_then(|b| floor_tile(world, b)) {
+            (pos, world.gis.home_storeys.get(&e).copied())
+        } else {
+            (f.pos, None)
+        };
+        world.body.positions.insert(e, pos);
+        match storey {
+            Some(s) if s != Storey(0) => {
+                world.body.storeys.insert(e, s);
+            }
+            _ => {
+                world.body.storeys.remove(&e);
+            }
+        }
+    }
+}
+
+/// A random Floor tile of building `bid`.
+pub(crate) fn floor_tile(world: &mut World, bid: BuildingId) -> Option<Position> {
+    let floors: Vec<(i32, i32)> = world
+        .gis
+        .buildings
+        .get(bid)?
+        .tiles
+        .iter()
+        .filter(|&&(x, y)| world.tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Floor))
+        .copied()
+        .collect();
+    if floors.is_empty() {
+        return None;
+    }
+    let (x, y) = floors[world.rng.random_range(0..floors.len())];
+    Some(Position { x, y })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::QuartierAggregate;
+    use crate::components::{Health, HomeBuilding, Hunger, Name, Occupation, Workplace};
+    use crate::lod::LodTransition;
+    use crate::registry::BuildingData;
+    use crate::systems::hunger::run_hunger;
+    use crate::tile_map::TileMap;
+    use std::collections::BTreeMap;
+
+    /// Two quartiers side by side: 1 (x < 10) Active, 2 (x >= 10)
+    /// Statistical, each with an aggregate of 100 residents.
+    fn zoned_world() -> World {
+        let mut world = World::new_with_seed(42);
+        world.tiles = TileMap::new(20, 10);
+        for y in 0..10 {
+            for x in 0..20 {
+                world
+                    .tiles
+                    .set_quartier_id(x, y, if x < 10 { 1 } else { 2 });
+            }
+        }
+        world.gis.lod_zones.insert(QuartierId(1), LodZone::Active);
+        world
+            .gis
+            .lod_zones
+            .insert(QuartierId(2), LodZone::Statistical);
+        for q in [1, 2] {
+            let mut population = BTreeMap::new();
+            population.insert("53".to_string(), 100.0);
+            world.gis.aggregates.insert(
+                QuartierId(q),
+                QuartierAggregate {
+                    population,
+                    food_workers: 0.0,
+                    mean_hunger: 0.0,
+                    mean_health: 100.0,
+                    food_stock: 100.0,
+                    supply: 1.0,
+                    deaths: 0.0,
+                    births: 0.0,
+                    survival: 1.0,
+                },
+            );
+        }
+        world
+    }
+
+    /// Add a building on row `y` of quartier 2, all Floor, and return it.
+    fn add_building(world: &mut World, y: i32) -> BuildingId {
+        let bid = world.gis.buildings.next_id();
+        let tiles: Vec<(i32, i32)> = (12..16).map(|x| (x, y)).collect();
+        for &(x, y) in &tiles {
+            world.tiles.set_building_id(x as usize, y as usize, bid);
+            world
+                .tiles
+                .set_terrain(x as usize, y as usize, Terrain::Floor);
+        }
+        world
+            .gis
+            .buildings
+            .insert(BuildingData::test_default(bid, tiles));
+        bid
+    }
+
+    /// Quartier 2 made Active, with a home on row 2, a workshop on row 7,
+    /// three residents in the street between and one visitor.
+    fn town_world() -> (World, BuildingId, BuildingId, Vec<Entity>) {
+        let mut world = zoned_world();
+        world.gis.lod_zones.insert(QuartierId(2), LodZone::Active);
+        let home = add_building(&mut world, 2);
+        let work = add_building(&mut world, 7);
+        let mut people = Vec::new();
+        for (i, name) in ["Dupont", "Martin", "Leroux", "Visiteur"]
+            .iter()
+            .enumerate()
+        {
+            let e = world.spawn();
+            world.body.positions.insert(
+                e,
+                Position {
+                    x: 11 + i as i32,
+                    y: 5,
+                },
+            );
+            world.body.names.insert(
+                e,
+                Name {
+                    value: name.to_string(),
+                },
+            );
+            world.body.healths.insert(
+                e,
+                Health {
+                    current: 100.0,
+                    max: 100.0,
+                },
+            );
+            world.mind.hungers.insert(
+                e,
+                Hunger {
+                    current: 0.0,
+                    max: 100.0,
+                },
+            );
+            if *name != "Visiteur" {
+                world.gis.home_buildings.insert(e, HomeBuilding(home));
+                world.gis.workplaces.insert(e, Workplace(work));
+                world.mind.occupations.insert(
+                    e,
+                    Occupation {
+                        activity: "boulanger".into(),
+                        naics: "311811".into(),
+                    },
+                );
+            }
+            people.push(e);
+        }
+        (world, home, work, people)
+    }
+
+    fn building_at(world: &World, e: Entity) -> Option<BuildingId> {
+        let p = world.body.positions.get(&e)?;
+        world.tiles.get_building_id(p.x as usize, p.y as usize)
+    }
+
+    #[test]
+    fn test_fold_and_unfold_conserve_population() {
+        let (mut world, home, _, people) = town_world();
+        let before = world.gis.aggregates[&QuartierId(2)].total();
+
+        world.gis.lod_transitions = vec![LodTransition {
+            quartier: QuartierId(2),
+            from: LodZone::Active,
+            to: LodZone::Statistical,
+        }];
+        apply_lod_transitions(&mut world);
+        assert!(people.iter().all(|e| !world.body.positions.contains_key(e)));
+        assert_eq!(world.gis.folded.len(), 4);
+        assert_eq!(world.gis.aggregates[&QuartierId(2)].total(), before + 3.0);
+        assert_eq!(world.gis.aggregates[&QuartierId(2)].food_workers, 3.0);
+
+        // Folding again changes nothing.
+        fold_quartier(&mut world, QuartierId(2));
+        assert_eq!(world.gis.aggregates[&QuartierId(2)].total(), before + 3.0);
+
+        world.tick = Tick(22 * 60);
+        world.gis.lod_transitions = vec![LodTransition {
+            quartier: QuartierId(2),
+            from: LodZone::Statistical,
+            to: LodZone::Active,
+        }];
+        apply_lod_transitions(&mut world);
+        assert!(world.gis.folded.is_empty());
+        assert!(world.pending_deaths.is_empty());
+        assert_eq!(world.gis.aggregates[&QuartierId(2)].total(), before);
+        assert_eq!(world.gis.aggregates[&QuartierId(2)].food_workers, 0.0);
+        for &e in &people[..3] {
+            assert_eq!(
+                building_at(&world, e),
+                Some(home),
+                "residents sleep at home"
+            );
+        }
+        let p = world.body.positions[&people[3]];
+        assert_eq!((p.x, p.y), (14, 5));
+        assert_eq!(world.body.names[&people[0]].value, "Dupont");
+    }
+
+    #[test]
+    fn test_unfold_goes_to_work_by_day() {
+        let (mut world, _, work, people) = town_world();
+        fold_quartier(&mut world, QuartierId(2));
+        world.tick = Tick(10 * 60);
+        unfold_quartier(&mut world, QuartierId(2));
+        for &e in &people[..3] {
+            assert_eq!(building_at(&world, e), Some(work));
+        }
+    }
+
+    #[test]
+    fn test_unfold_after_deaths_kills_residents_and_takes_on_means() {
+        let (mut world, _, _, people) = town_world();
+        fold_quartier(&mut world, QuartierId(2));
+        let agg = world
+            .gis
+            .aggregates
+            .get_mut(&QuartierId(2))
+            .expect("aggregate");
+        agg.survival = 0.0;
+        agg.mean_hunger = 50.0;
+        let counted = agg.total();
+        unfold_quartier(&mut world, QuartierId(2));
+        for &e in &people[..3] {
+            assert!(world.pending_deaths.contains(&e));
+        }
+        assert!(!world.pending_deaths.contains(&people[3]));
+        // The dead were already taken off the aggregate by its own deaths.
+        assert_eq!(world.gis.aggregates[&QuartierId(2)].total(), counted);
+
+        let (mut world, _, _, people) = town_world();
+        fold_quartier(&mut world, QuartierId(2));
+        if let Some(agg) = world.gis.aggregates.get_mut(&QuartierId(2)) {
+            agg.mean_hunger = 50.0;
+            agg.mean_health = 80.0;
+        }
+        unfold_quartier(&mut world, QuartierId(2));
+        assert_eq!(world.mind.hungers[&people[0]].current, 50.0);
+        assert_eq!(world.body.healths[&people[0]].current, 80.0);
+        assert_eq!(world.mind.hungers[&people[3]].current, 0.0);
+    }
+
+    #[test]
+    fn test_folded_entities_stay_frozen() {
+        let (mut world, _, _, people) = town_world();
+        fold_quartier(&mut world, QuartierId(2));
+        run_statistical(&mut world, Tick(1));
+        assert!(people.iter().all(|e| world.frozen.contains(e)));
+    }
+
+    #[test]
+    fn test_entities_in_statistical_quartiers_freeze() {
+        let mut world = zoned_world();
+        let hungry = |world: &mut World, x| {
+            let e = world.spawn();
+            world.body.positions.insert(e, Position { x, y: 5 });
+            world.mind.hungers.insert(
+                e,
+                Hunger {
+                    current: 10.0,
+                    max: 100.0,
+                },
+            );
+            e
+        };
+        let active = hungry(&mut world, 2);
+        let frozen = hungry(&mut world, 15);
+
+        run_statistical(&mut world, Tick(1));
+        run_hunger(&mut world, Tick(1));
+        assert!(world.frozen.contains(&frozen));
+        assert!(!world.frozen.contains(&active));
+        assert_eq!(world.mind.hungers[&frozen].current, 10.0);
+        assert!(world.mind.hungers[&active].current > 10.0);
+    }
+
+    #[test]
+    fn test_only_statistical_aggregates_advance() {
+        let mut world = zoned_world();
+        for t in 0..(10 * 24 * STATISTICAL_STEP_MINUTES) {
+            run_statistical(&mut world, Tick(t));
+        }
+        let active = &world.gis.aggregates[&QuartierId(1)];
+        let statistical = &world.gis.aggregates[&QuartierId(2)];
+        assert_eq!(active.births, 0.0);
+        assert!(statistical.births > 0.0);
+        assert!(statistical.deaths > 0.0);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::components::Tick;
+use crate::lod::LodZone;
 use crate::tile_map::CHUNK_SIZE;
 use crate::world::World;
 
@@ -7,7 +8,10 @@ use crate::world::World;
 /// Each tile drifts toward a target temperature determined by its terrain type
 /// at a rate of 0.1 degrees per tick. Pure arithmetic — no RNG needed.
 ///
-/// Iterates by chunk and skips chunks already at equilibrium (O(1) steady state).
+/// Iterates by chunk and skips chunks already at equilibrium (O(1) steady state)
+/// and chunks centered outside the Active zone (Nearby or Statistical
+/// quartiers), which keep their temperatures until the camera brings them
+/// back into it.
 /// Uses collect-then-apply mutation pattern.
 pub fn run_temperature(world: &mut World, _tick: Tick) {
     let cx_count = world.tiles.chunks_x();
@@ -37,6 +41,11 @@ pub fn run_temperature(world: &mut World, _tick: Tick) {
             } else {
                 map_w % CHUNK_SIZE
             };
+            let center_x = (cx * CHUNK_SIZE + local_w / 2) as i32;
+            let center_y = (cy * CHUNK_SIZE + local_h / 2) as i32;
+            if world.lod_zone_at(center_x, center_y) != LodZone::Active {
+                continue;
+            }
 
             let mut chunk_has_changes = false;
 
@@ -82,6 +91,7 @@ pub fn run_temperature(world: &mut World, _tick: Tick) {
 mod tests {
     use super::*;
     use crate::components::Tick;
+    use crate::registry::QuartierId;
     use crate::tile_map::{Terrain, TileMap};
     use crate::world::World;
 
@@ -176,4 +186,21 @@ mod tests {
         // No changes needed → chunk should be marked equilibrium
         assert!(world.tiles.chunk_at(0, 0).at_equilibrium);
     }
+
+    #[test]
+    fn test_temperature_skips_chunks_outside_active_zone() {
+        for zone in [LodZone::Nearby, LodZone::Statistical] {
+            let mut world = World::new_with_seed(42);
+            world.tiles = TileMap::new(1, 1);
+            world.tiles.set_terrain(0, 0, Terrain::Water);
+            world.tiles.set_temperature(0, 0, 20.0);
+            world.tiles.set_quartier_id(0, 0, 1);
+            world.gis.lod_zones.insert(QuartierId(1), zone);
+
+            run_temperature(&mut world, Tick(0));
+
+            let temp = world.tiles.get_temperature(0, 0).expect("in bounds");
+            assert_eq!(temp, 20.0, "{zone:?} chunk should keep its temperature");
+        }
+    }
 }
@@ -0,0 +1,437 @@
+use std::collections::HashMap;
+
+use crate::components::{Entity, Tick};
+use crate::events::Event;
+use crate::loading::Archetype;
+use crate::loading_gis::spawn_rioter;
+use crate::registry::{BuildingId, QuartierId};
+use crate::tile_map::Terrain;
+use crate::world::World;
+use rand::RngExt;
+
+/// Ticks between hunger readings (one game hour).
+const UNREST_STEP: u64 = 60;
+/// Mean resident hunger ratio a quartier puts up with.
+const HUNGER_TOLERANCE: f32 = 0.5;
+/// Unrest gained per game hour per unit of mean hunger ratio over tolerance.
+const HUNGER_UNREST: f32 = 0.1;
+/// Fraction of unrest left after each game hour (half-life ~1.5 days).
+const UNREST_DECAY: f32 = 0.98;
+/// Unrest per resident attacked.
+const ATTACK_UNREST: f32 = 0.005;
+/// Unrest per resident killed.
+const DEATH_UNREST: f32 = 0.05;
+/// Unrest at which a quartier riots.
+pub const RIOT_THRESHOLD: f32 = 1.0;
+/// Insurgents a riot brings into the street.
+const RIOT_CROWD: usize = 40;
+/// Insurgents' aggression: they go for police and troops on sight.
+const RIOTER_AGGRESSION: f32 = 0.6;
+/// Chebyshev radius of the barricade ring around a riot's rallying point.
+const BARRICADE_RADIUS: i32 = 10;
+/// A riot disperses after this many ticks (two days)...
+const RIOT_TICKS: u64 = 2 * 1440;
+/// ...or once this many have died in its quartier.
+const RIOT_CASUALTIES: u32 = 10;
+
+/// A riot under way in one quartier (`gis.riots`).
+#[derive(Debug, Clone)]
+pub struct Riot {
+    pub started: Tick,
+    /// Tiles turned to `Terrain::Barricade`, back to Road on dispersal.
+    pub barricades: Vec<(i32, i32)>,
+    /// Insurgents spawned for the riot, sent home on dispersal.
+    pub crowd: Vec<Entity>,
+    /// Deaths in the quartier since the riot began.
+    pub casualties: u32,
+}
+
+/// Quartier of the tile at `(x, y)`; None outside every quartier.
+fn quartier_at(world: &World, x: i32, y: i32) -> Option<QuartierId> {
+    if x < 0 || y < 0 {
+        return None;
+    }
+    world
+        .tiles
+        .get_quartier_id(x as usize, y as usize)
+        .filter(|&q| q > 0)
+        .map(QuartierId)
+}
+
+/// Quartier of `e`'s home building, memoized per building in `cache`.
+fn home_quartier(
+    world: &World,
+    e: Entity,
+    cache: &mut HashMap<BuildingId, Option<QuartierId>>,
+) -> Option<QuartierId> {
+    let bid = world.gis.home_buildings.get(&e)?.0;
+    *cache.entry(bid).or_insert_with(|| {
+        world
+            .gis
+            .buildings
+            .get(bid)
+            .and_then(|b| world.gis.quartiers.get_by_name(&b.quartier))
+            .map(|q| q.id)
+    })
+}
+
+/// Phase 4 (Actions): quartier unrest.
+///
+/// Runs after `run_combat`, before `run_death`, so this tick's dead still
+/// have their homes. Each resident attacked this tick adds `ATTACK_UNREST`
+/// to their home quartier's `QuartierData::unrest`, each resident killed
+/// `DEATH_UNREST`; every death on a rioting quartier's tiles counts toward
+/// its riot's casualties. Every `UNREST_STEP` ticks unrest decays by
+/// `UNREST_DECAY` and grows with the residents' mean hunger ratio over
+/// `HUNGER_TOLERANCE`. Riots themselves start and end in `update_riots`.
+pub fn run_unrest(world: &mut World, tick: Tick) {
+    let mut cache: HashMap<BuildingId, Option<QuartierId>> = HashMap::new();
+    let mut gains: Vec<(QuartierId, f32)> = Vec::new();
+    let mut casualties: Vec<QuartierId> = Vec::new();
+    for ev in world.events.since(tick) {
+        match *ev {
+            Event::Attacked { defender, .. } => {
+                if let Some(q) = home_quartier(world, defender, &mut cache) {
+                    gains.push((q, ATTACK_UNREST));
+                }
+            }
+            Event::Died { entity, .. } => {
+                if let Some(q) = home_quartier(world, entity, &mut cache) {
+                    gains.push((q, DEATH_UNREST));
+                }
+                if let Some(q) = world
+                    .body
+                    .positions
+                    .get(&entity)
+                    .and_then(|p| quartier_at(world, p.x, p.y))
+                    && world.gis.riots.contains_key(&q)
+                {
+                    casualties.push(q);
+                }
+            }
+            _ => {}
+        }
+    }
+    for (q, gain) in gains {
+        if let Some(data) = world.gis.quartiers.quartiers.get_mut(&q) {
+            data.unrest += gain;
+        }
+    }
+    for q in casualties {
+        if let Some(riot) = world.gis.riots.get_mut(&q) {
+            riot.casualties += 1;
+        }
+    }
+
+    if !tick.0.is_multiple_of(UNREST_STEP) {
+        return;
+    }
+    let mut residents: Vec<Entity> = world
+        .gis
+        .home_buildings
+        .keys()
+        .filter(|e| !world.pending_deaths.contains(e))
+        .copied()
+        .collect();
+    residents.sort_by_key(|e| e.0);
+    let mut hunger: HashMap<QuartierId, (f32, u32)> = HashMap::new();
+    for e in residents {
+        let Some(h) = world.mind.hungers.get(&e).filter(|h| h.max > 0.0) else {
+            continue;
+        };
+        let ratio = h.current / h.max;
+        if let Some(q) = home_quartier(world, e, &mut cache) {
+            let entry = hunger.entry(q).or_default();
+            entry.0 += ratio;
+            entry.1 += 1;
+        }
+    }
+    for data in world.gis.quartiers.quartiers.values_mut() {
+        let pressure = hunger.get(&data.id).map_or(0.0, |&(sum, n)| {
+            (sum / n as f32 - HUNGER_TOLERANCE).max(0.0)
+        });
+        data.unrest = data.unrest * UNREST_DECAY + HUNGER_UNREST * pressure;
+    }
+}
+
+/// Start and end riots; call once per tick, after `run_unrest`.
+///
+/// Riots under way for `RIOT_TICKS`, or with `RIOT_CASUALTIES` dead, are
+/// dispersed: barricades revert to Road, the insurgents go home (despawn),
+/// the quartier's unrest resets and `Event::RiotDispersed` is pushed.
+/// Quartiers at `RIOT_THRESHOLD` unrest without a riot then rise (see
+/// `begin_riot`); no crowd is spawned without an `archetype`. Returns the
+/// quartiers that rose, for the narrative popup.
+pub fn update_riots(world: &mut World, archetype: Option<&Archetype>) -> Vec<QuartierId> {
+    let tick = world.tick;
+    let mut ending: Vec<QuartierId> = world
+        .gis
+        .riots
+        .iter()
+        .filter(|(_, r)| tick.0 >= r.started.0 + RIOT_TICKS || r.casualties >= RIOT_CASUALTIES)
+        .map(|(&q, _)| q)
+        .collect();
+    ending.sort_by_key(|q| q.0);
+    for q in ending {
+        disperse_riot(world, q);
+    }
+
+    let mut rising: Vec<QuartierId> = world
+        .gis
+        .quartiers
+        .quartiers
+        .values()
+        .filter(|d| d.unrest >= RIOT_THRESHOLD && !world.gis.riots.contains_key(&d.id))
+        .map(|d| d.id)
+        .collect();
+    rising.sort_by_key(|q| q.0);
+    rising
+        .into_iter()
+        .filter(|&q| begin_riot(world, q, archetype))
+        .collect()
+}
+
+/// Road tile of `quartier` nearest the centre of its bounds.
+fn rallying_point(world: &World, quartier: QuartierId) -> Option<(i32, i32)> {
+    let data = world.gis.quartiers.get(quartier)?;
+    if data.min_x > data.max_x || data.min_y > data.max_y {
+        return None;
+    }
+    let (cx, cy) = ((data.min_x + data.max_x) / 2, (data.min_y + data.max_y) / 2);
+    (data.min_y..=data.max_y)
+        .flat_map(|y| (data.min_x..=data.max_x).map(move |x| (x, y)))
+        .filter(|&(x, y)| quartier_at(world, x, y) == Some(quartier))
+        .filter(|&(x, y)| world.tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Road))
+        .min_by_key(|&(x, y)| ((x - cx).pow(2) + (y - cy).pow(2), y, x))
+}
+
+/// Raise a riot in `quartier`: barricade every free Road tile on the ring
+/// `BARRICADE_RADIUS` around its rallying point and fill the streets inside
+/// with `RIOT_CROWD` insurgents. A quartier without streets only vents its
+/// unrest. Returns whether a riot began.
+fn begin_riot(world: &mut World, quartier: QuartierId, archetype: Option<&Archetype>) -> bool {
+    let Some((sx, sy)) = rallying_point(world, quartier) else {
+        if let Some(data) = world.gis.quartiers.quartiers.get_mut(&quartier) {
+            data.unrest = 0.0;
+        }
+        return false;
+    };
+    let r = BARRICADE_RADIUS;
+    let mut ring: Vec<(i32, i32)> = Vec::new();
+    let mut inside: Vec<(i32, i32)> = Vec::new();
+    for y in sy - r..=sy + r {
+        for x in sx - r..=sx + r {
+            if x < 0
+                || y < 0
+                || world.tiles.get_terrain(x as usize, y as usize) != Some(Terrain::Road)
+            {
+                continue;
+            }
+            if (x - sx).abs().max((y - sy).abs()) == r {
+                ring.push((x, y));
+            } else {
+                inside.push((x, y));
+            }
+        }
+    }
+    let barricades: Vec<(i32, i32)> = ring
+        .into_iter()
+        .filter(|&(x, y)| world.entities_at(x, y).next().is_none())
+        .collect();
+    for &(x, y) in &barricades {
+        world.set_terrain(x, y, Terrain::Barricade);
+    }
+
+    let mut crowd = Vec::new();
+    if let Some(archetype) = archetype
+        && !inside.is_empty()
+    {
+        for _ in 0..RIOT_CROWD {
+            let pos = inside[world.rng.random_range(0..inside.len())];
+            match spawn_rioter(world, archetype, quartier, RIOTER_AGGRESSION, pos) {
+                Some(e) => crowd.push(e),
+                None => break,
+            }
+        }
+    }
+
+    let tick = world.tick;
+    log::info!(
+        "Riot in quartier {}: {} barricades, {} insurgents",
+        quartier.0,
+        barricades.len(),
+        crowd.len()
+    );
+    world.gis.riots.insert(
+        quartier,
+        Riot {
+            started: tick,
+            barricades,
+            crowd,
+            casualties: 0,
+        },
+    );
+    world.events.push(Event::RiotBegan { quartier, tick });
+    true
+}
+
+/// End the riot in `quartier` (see `update_riots`).
+fn disperse_riot(world: &mut World, quartier: QuartierId) {
+    let Some(riot) = world.gis.riots.remove(&quartier) else {
+        return;
+    };
+    for &(x, y) in &riot.barricades {
+        if world.tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Barricade) {
+            world.set_terrain(x, y, Terrain::Road);
+        }
+    }
+    for &e in &riot.crowd {
+        if world.alive.contains(&e) {
+            world.despawn(e);
+        }
+    }
+    if let Some(data) = world.gis.quartiers.quartiers.get_mut(&quartier) {
+        data.unrest = 0.0;
+    }
+    let tick = world.tick;
+    log::info!(
+        "Riot in quartier {} dispersed: {} casualties",
+        quartier.0,
+        riot.casualties
+    );
+    world.events.push(Event::RiotDispersed {
+        quartier,
+        casualties: riot.casualties,
+        tick,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{HomeBuilding, Hunger, Position};
+    use crate::faction::{AttackResponse, FactionDef};
+    use crate::registry::{BlockRegistry, BuildingData, QuartierRegistry};
+
+    /// A 64×64 street map, all of it quartier 1 ("Arcis"), with one house.
+    fn setup() -> (World, BuildingId) {
+        let mut world = World::new_with_seed(42);
+        for y in 0..64 {
+            for x in 0..64 {
+                world.tiles.set_quartier_id(x, y, 1);
+            }
+        }
+        let house = world.gis.buildings.next_id();
+        world.gis.buildings.insert(BuildingData {
+            quartier: "Arcis".into(),
+            num_ilot: "T1".into(),
+            ..BuildingData::test_default(house, vec![(0, 0), (63, 63)])
+        });
+        world.gis.quartiers = QuartierRegistry::build_from_registries(
+            &["Arcis".to_string()],
+            &world.gis.buildings,
+            &BlockRegistry::new(),
+            1845,
+        );
+        world.mind.factions.insert(FactionDef {
+            key: "insurges".into(),
+            name: "Insurgés".into(),
+            local: false,
+            when_attacked: AttackResponse::Fight,
+            calls_for_help: true,
+            defends_allies: true,
+        });
+        (world, house)
+    }
+
+    fn resident(world: &mut World, house: BuildingId, hunger: f32) -> Entity {
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x: 5, y: 5 });
+        world.gis.home_buildings.insert(e, HomeBuilding(house));
+        world.mind.hungers.insert(
+            e,
+            Hunger {
+                current: hunger,
+                max: 100.0,
+            },
+        );
+        e
+    }
+
+    fn unrest(world: &World) -> f32 {
+        world
+            .gis
+            .quartiers
+            .get(QuartierId(1))
+            .map_or(0.0, |q| q.unrest)
+    }
+
+    #[test]
+    fn test_hunger_and_deaths_raise_unrest() {
+        let (mut world, house) = setup();
+        let starving = resident(&mut world, house, 100.0);
+        resident(&mut world, house, 100.0);
+
+        run_unrest(&mut world, Tick(0));
+        assert!((unrest(&world) - HUNGER_UNREST * 0.5).abs() < 0.001);
+
+        world.events.push(Event::Died {
+            entity: starving,
+            tick: Tick(1),
+        });
+        run_unrest(&mut world, Tick(1));
+        assert!((unrest(&world) - (HUNGER_UNREST * 0.5 + DEATH_UNREST)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_riot_barricades_streets_then_disperses() {
+        let (mut world, _) = setup();
+        if let Some(q) = world.gis.quartiers.quartiers.get_mut(&QuartierId(1)) {
+            q.unrest = RIOT_THRESHOLD;
+        }
+        let person = Archetype::default();
+
+        let began = update_riots(&mut world, Some(&person));
+        assert_eq!(began, vec![QuartierId(1)]);
+        let riot = world.gis.riots[&QuartierId(1)].clone();
+        assert_eq!(riot.barricades.len(), 8 * BARRICADE_RADIUS as usize);
+        let &(bx, by) = riot.barricades.first().expect("barricade");
+        assert_eq!(
+            world.tiles.get_terrain(bx as usize, by as usize),
+            Some(Terrain::Barricade)
+        );
+        assert!(!world.tiles.is_walkable(bx as usize, by as usize));
+        assert_eq!(riot.crowd.len(), RIOT_CROWD);
+        assert!(
+            world
+                .events
+                .iter()
+                .any(|ev| matches!(ev, Event::RiotBegan { .. }))
+        );
+        assert!(update_riots(&mut world, Some(&person)).is_empty(), "once");
+
+        // A casualty on the riot's streets counts toward dispersal.
+        world.events.push(Event::Died {
+            entity: riot.crowd[0],
+            tick: Tick(5),
+        });
+        run_unrest(&mut world, Tick(5));
+        assert_eq!(world.gis.riots[&QuartierId(1)].casualties, 1);
+
+        world.tick = Tick(RIOT_TICKS);
+        update_riots(&mut world, Some(&person));
+        assert!(world.gis.riots.is_empty());
+        assert_eq!(
+            world.tiles.get_terrain(bx as usize, by as usize),
+            Some(Terrain::Road)
+        );
+        assert!(riot.crowd.iter().all(|e| !world.alive.contains(e)));
+        assert_eq!(unrest(&world), 0.0);
+        assert!(
+            world
+                .events
+                .iter()
+                .any(|ev| matches!(ev, Event::RiotDispersed { casualties: 1, .. }))
+        );
+    }
+}
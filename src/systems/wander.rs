@@ -1,8 +1,23 @@
+use std::collections::HashMap;
+
 use crate::components::{
-    ActionId, CachedPath, Entity, Gait, MoveCooldown, Position, Tick, WanderTarget,
+    ActionId, CachedPath, Entity, Gait, MoveCooldown, PlanStep, Position, Storey, Tick,
+    WanderTarget,
 };
+use crate::doors::DoorKeys;
 use crate::events::Event;
-use crate::tile_map::{find_path, is_diagonal_step};
+use crate::faction;
+use crate::flow_field;
+use crate::hpa::find_path_hpa;
+use crate::registry::BuildingRegistry;
+use crate::systems::fatigue::UNCONSCIOUS_THRESHOLD;
+use crate::systems::fire;
+use crate::systems::perception::{SENSE_RANGE, remembered};
+use crate::systems::rescue::{self, DRAG_SLOWDOWN};
+use crate::systems::{crime, decisions, statistical};
+use crate::tile_map::{
+    IMPASSABLE, PathWorkspace, Terrain, TileMap, find_path_weighted, is_diagonal_step,
+};
 use crate::world::World;
 use rand::RngExt;
 
@@ -12,15 +27,119 @@ const DIAGONAL_FACTOR: u32 = 141;
 /// How far (Chebyshev) a wandering entity picks random destinations (30 meters).
 const WANDER_RANGE: i32 = 30;
 
+/// Extra path cost per other entity standing on a tile (half a step).
+const CONGESTION_COST: u32 = 50;
+
+/// Crowds beyond this many entities on one tile cost no more.
+const CONGESTION_CAP: u32 = 4;
+
+/// Only crowds within this Chebyshev distance of the mover are avoided;
+/// farther ones will have moved on by the time it gets there.
+const CONGESTION_RADIUS: i32 = 8;
+
+/// Distances tried, longest first, when picking a tile away from threats.
+const FLEE_DISTANCES: [i32; 4] = [10, 5, 2, 1];
+
+/// Path steps a mover catching up on `step` ticks covers at
+/// `base_cooldown`: one per `base_cooldown + 1` ticks, at least one.
+fn coarse_hops(step: u64, base_cooldown: u32) -> usize {
+    ((step / (u64::from(base_cooldown) + 1)) as usize).max(1)
+}
+
+/// Cooldown left after `hops` steps taken in one `step`-tick update.
+fn coarse_cooldown(step: u64, hops: usize, base_cooldown: u32) -> u32 {
+    (hops as u64 * (u64::from(base_cooldown) + 1)).saturating_sub(step) as u32
+}
+
+/// Best tile to run to: among walkable tiles at `FLEE_DISTANCES` in the 8
+/// directions, the one farthest from every remembered enemy (and `threat`)
+/// with the least danger. None if nowhere beats staying put, which is also
+/// the answer when there are no enemies and the current tile is safe.
+fn flee_goal(
+    world: &World,
+    entity: Entity,
+    pos: (i32, i32),
+    threat: Option<(i32, i32)>,
+) -> Option<(i32, i32)> {
+    const DIRS: [(i32, i32); 8] = [
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ];
+    let chebyshev = |a: (i32, i32), b: (i32, i32)| (a.0 - b.0).abs().max((a.1 - b.1).abs());
+    let mut threats: Vec<(i32, i32)> = remembered(world, entity)
+        .into_iter()
+        .filter(|&(t, _)| world.body.combat_stats.contains_key(&t))
+        .filter(|&(t, _)| faction::is_hostile(world, entity, t))
+        .map(|(_, s)| (s.x, s.y))
+        .filter(|&p| chebyshev(p, pos) <= SENSE_RANGE)
+        .collect();
+    threats.extend(threat);
+    if threats.is_empty() && world.danger.cost_at(pos.0, pos.1) == 0 {
+        return None;
+    }
+
+    let score = |p: (i32, i32)| -> i64 {
+        let nearest = threats.iter().map(|&t| chebyshev(p, t)).min().unwrap_or(0);
+        nearest as i64 * 100 - world.danger.cost_at(p.0, p.1) as i64
+    };
+    let (w, h) = (world.tiles.width() as i32, world.tiles.height() as i32);
+    let mut best: Option<(i32, i32)> = None;
+    let mut best_score = score(pos);
+    for d in FLEE_DISTANCES {
+        for (dx, dy) in DIRS {
+            let (gx, gy) = (pos.0 + dx * d, pos.1 + dy * d);
+            if gx < 0 || gy < 0 || gx >= w || gy >= h {
+                continue;
+            }
+            if !world.tiles.is_walkable(gx as usize, gy as usize) {
+                continue;
+            }
+            let s = score((gx, gy));
+            if s > best_score {
+                best = Some((gx, gy));
+                best_score = s;
+            }
+        }
+    }
+    best
+}
+
 /// Phase 4 (Actions): Unified movement system.
 ///
 /// Handles all entity movement via A* pathfinding:
-/// - Eat/Attack intention with target: pathfind toward target position.
-/// - Wander intention (or no intention): pathfind to a random destination.
-/// - Idle intention: skip movement.
+/// - Plan whose current step is MoveTo: pathfind to that fixed tile.
+/// - Eat/Attack/Defend/Rob intention with target: pathfind toward target position.
+/// - Flee intention: pathfind to the tile farthest from enemies (`flee_goal`).
+/// - Rescue intention: reach the target; once dragging it, head out of
+///   danger, then stay put and tend it.
+/// - Wander intention (or no intention): pathfind to a random destination;
+///   on shift (`decisions::on_shift`), to a Floor tile of one's workplace.
+/// - Patrol intention: pathfind to a street near one's commissariat
+///   (`crime::patrol_goal`), else a random destination.
+/// - Extinguish intention: pathfind beside the nearest fire
+///   (`fire::fire_goal`), then stay put and douse it.
+/// - Idle/PickUp intention: skip movement.
 ///
+/// Everyone but fighters (Attack/Defend) and firefighters paths around the
+/// danger map, and
+/// everyone pays `CONGESTION_COST` per head for crowded tiles nearby on
+/// their own storey.
+/// Full tiles (see `TileMap::capacity_at`) can't be entered except onto
+/// one's own target; blocked movers sidestep or wait.
+/// Unconscious and dragged entities don't move on their own.
 /// Falls back to random 8-directional steps if no path is found.
-/// Cooldown timer gates movement speed (like DF).
+/// Cooldown timer gates movement speed (like DF) at the entity's current gait
+/// (chosen by `run_decisions`); dragging and leg wounds (`Wounds::limp`) slow it.
+///
+/// Nearby entities move once per `NEARBY_TICK_INTERVAL` (see
+/// `World::lod_step`), jumping along their wander path as many steps as
+/// the interval allows instead of walking it tile by tile.
 pub fn run_wander(world: &mut World, tick: Tick) {
     let map_w = world.tiles.width() as i32;
     let map_h = world.tiles.height() as i32;
@@ -34,6 +153,15 @@ pub fn run_wander(world: &mut World, tick: Tick) {
         .filter(|e| world.player != Some(**e))
         .filter(|e| world.body.gait_profiles.contains_key(e))
         .filter(|e| !world.pending_deaths.contains(e))
+        .filter(|e| !world.frozen.contains(e))
+        .filter(|e| !world.mind.dragged_by.contains_key(e))
+        .filter(|e| {
+            world
+                .body
+                .fatigues
+                .get(e)
+                .is_none_or(|f| f.current < UNCONSCIOUS_THRESHOLD)
+        })
         .copied()
         .collect();
     candidates.sort_by_key(|e| e.0);
@@ -45,12 +173,27 @@ pub fn run_wander(world: &mut World, tick: Tick) {
 
     enum PathUpdate {
         Remove,
-        Advance,             // bump next_step by 1
+        Advance(usize),      // bump next_step by this many
         Replace(CachedPath), // fresh path from A*
     }
     let mut cached_path_changes: Vec<(Entity, PathUpdate)> = Vec::new();
+    let mut storey_changes: Vec<(Entity, u8)> = Vec::new();
+    let crossing_costs = world.gis.bridges.has_crossing_costs();
+    // Flow fields can't see danger or bridge tolls, crowding and closures:
+    // tiles carrying any, and per goal whether its field's window holds
+    // one (memoized this tick). HPA* costs the chunks holding them per search.
+    let hazards: Vec<(i32, i32)> = world
+        .danger
+        .tiles()
+        .chain(world.gis.bridges.costly_tiles())
+        .collect();
+    let mut hazardous_goals: HashMap<(i32, i32), bool> = HashMap::new();
 
     for e in candidates {
+        let step = world.lod_step(e, tick);
+        if step == 0 {
+            continue;
+        }
         let remaining = world
             .body
             .move_cooldowns
@@ -60,11 +203,11 @@ pub fn run_wander(world: &mut World, tick: Tick) {
 
         if remaining > 0 {
             // Still cooling down — decrement
-            cooldown_changes.push((e, remaining - 1));
+            cooldown_changes.push((e, remaining.saturating_sub(step as u32)));
             continue;
         }
 
-        let Some(pos) = world.body.positions.get(&e) else {
+        let Some(&pos) = world.body.positions.get(&e) else {
             continue;
         };
         let Some(profile) = world.body.gait_profiles.get(&e) else {
@@ -81,40 +224,101 @@ pub fn run_wander(world: &mut World, tick: Tick) {
             "entity {:?}: has current_gait but no gait_profile",
             e
         );
-        let base_cooldown = profile.cooldown(gait);
+        let dragging = rescue::is_dragging(world, e);
+        let limp = world.body.wounds.get(&e).map_or(1, |w| w.limp());
+        let base_cooldown =
+            profile.cooldown(gait) * if dragging { DRAG_SLOWDOWN } else { 1 } * limp;
+        let keys = DoorKeys::of(world, e);
 
         let intention = world.mind.intentions.get(&e);
         let action = intention.map(|i| i.action);
+        let target = intention.and_then(|i| i.target);
+
+        // A plan's MoveTo step is a fixed destination: path like a wander
+        // goal (cacheable) instead of tracking the intention target.
+        let plan_goal = world
+            .mind
+            .plans
+            .get(&e)
+            .and_then(|p| p.current_step())
+            .and_then(|step| match step {
+                PlanStep::MoveTo { x, y } => Some((x, y)),
+                PlanStep::PickUp { .. } | PlanStep::Consume { .. } => None,
+            });
+
+        // Fleeing, dragging a patient to safety, or heading for a fire:
+        // re-pick every step.
+        let escape = match action {
+            Some(ActionId::Flee) => {
+                let threat = target
+                    .and_then(|t| world.body.positions.get(&t))
+                    .map(|p| (p.x, p.y));
+                flee_goal(world, e, (pos.x, pos.y), threat)
+            }
+            Some(ActionId::Rescue) if dragging => flee_goal(world, e, (pos.x, pos.y), None),
+            Some(ActionId::Extinguish) => fire::fire_goal(world, (pos.x, pos.y)),
+            _ => None,
+        };
 
         // Exhaustive match on ActionId to determine movement mode.
-        // Idle: stop and reassess — clear stale movement state.
-        // Eat/Attack: track target entity position.
-        // Wander/None: pathfind to random destination.
+        // Idle/PickUp: stop and reassess — clear stale movement state.
+        // Rescue while dragging somewhere safe: stop and tend the patient.
+        // Extinguish beside the fire: stop and douse it (`run_fire`).
+        // Eat/Attack/Defend/Rob: track target entity position (unless a plan says where).
+        // Flee/Rescue/Extinguish: track the escape tile, the patient or the fire.
+        // Wander/Patrol/None: pathfind to random destination (Patrol: a street
+        // near the commissariat).
         let is_tracking = match action {
-            Some(ActionId::Idle) => {
-                cooldown_changes.push((e, base_cooldown));
-                wander_target_changes.push((e, None));
-                cached_path_changes.push((e, PathUpdate::Remove));
-                continue;
+            Some(ActionId::Idle) | Some(ActionId::PickUp) => None,
+            Some(ActionId::Rescue) if dragging && escape.is_none() => None,
+            Some(ActionId::Extinguish) if fire::in_reach(world, (pos.x, pos.y)) => None,
+            Some(ActionId::Eat)
+            | Some(ActionId::Attack)
+            | Some(ActionId::Defend)
+            | Some(ActionId::Rob) => Some(plan_goal.is_none()),
+            Some(ActionId::Flee) | Some(ActionId::Rescue) | Some(ActionId::Extinguish) => {
+                Some(true)
             }
-            Some(ActionId::Eat) | Some(ActionId::Attack) => true,
-            Some(ActionId::Wander) | None => false,
+            Some(ActionId::Wander) | Some(ActionId::Patrol) | None => Some(false),
+        };
+        let Some(is_tracking) = is_tracking else {
+            cooldown_changes.push((e, base_cooldown));
+            wander_target_changes.push((e, None));
+            cached_path_changes.push((e, PathUpdate::Remove));
+            continue;
         };
 
+        let avoids_danger = !matches!(
+            action,
+            Some(ActionId::Attack) | Some(ActionId::Defend) | Some(ActionId::Extinguish)
+        );
+
         // Determine goal position
-        let goal: Option<(i32, i32)> = if is_tracking {
+        let goal: Option<(i32, i32)> = if let Some(g) = plan_goal {
+            Some(g)
+        } else if escape.is_some() || matches!(action, Some(ActionId::Flee | ActionId::Extinguish))
+        {
+            // Cornered fleers fall back to a random step.
+            escape
+        } else if is_tracking {
             // Pathfind to target entity's position (moving target)
-            intention
-                .and_then(|i| i.target)
+            target
                 .and_then(|t| world.body.positions.get(&t))
                 .map(|p| (p.x, p.y))
         } else {
-            // Wander or no intention: use cached wander target or pick new
-            let at_goal = world
-                .mind
-                .wander_targets
-                .get(&e)
-                .is_some_and(|wt| wt.goal_x == pos.x && wt.goal_y == pos.y);
+            // Wander or no intention: use cached wander target or pick new.
+            // On shift, goals are Floor tiles of the workplace: the worker
+            // commutes there and keeps to it until closing.
+            let shift = if action == Some(ActionId::Patrol) {
+                None
+            } else {
+                decisions::on_shift(world, e)
+            };
+            let at_goal = world.mind.wander_targets.get(&e).is_some_and(|wt| {
+                let goal = (wt.goal_x, wt.goal_y);
+                let storey = world.storey_of(e);
+                goal == (pos.x, pos.y) && goal_storey(world, e, goal, storey, goal, None) == storey
+            });
 
             if !at_goal {
                 world
@@ -125,6 +329,23 @@ pub fn run_wander(world: &mut World, tick: Tick) {
             } else {
                 None
             }
+            .filter(|&(x, y)| {
+                shift.is_none_or(|work| {
+                    world.tiles.get_building_id(x as usize, y as usize) == Some(work)
+                })
+            })
+            .or_else(|| {
+                if action == Some(ActionId::Patrol) {
+                    crime::patrol_goal(world, e)
+                } else {
+                    None
+                }
+            })
+            .or_else(|| {
+                shift
+                    .and_then(|work| statistical::floor_tile(world, work))
+                    .map(|p| (p.x, p.y))
+            })
             .or_else(|| {
                 // Pick new random walkable destination
                 for _ in 0..5 {
@@ -147,6 +368,11 @@ pub fn run_wander(world: &mut World, tick: Tick) {
         };
 
         let Some((gx, gy)) = goal else {
+            if world.storey_of(e) > 0 {
+                // Upstairs with nowhere to go: stay put.
+                cooldown_changes.push((e, base_cooldown));
+                continue;
+            }
             // No goal — fallback random step
             let direction = world.rng.random_range(0..8);
             let (dx, dy) = match direction {
@@ -161,7 +387,9 @@ pub fn run_wander(world: &mut World, tick: Tick) {
             };
             let x = (pos.x + dx).clamp(0, (map_w - 1).max(0));
             let y = (pos.y + dy).clamp(0, (map_h - 1).max(0));
-            if !world.tiles.is_walkable(x as usize, y as usize) {
+            if !world.tiles.is_walkable(x as usize, y as usize)
+                || !keys.may_pass(&world.tiles, &world.gis, x, y)
+            {
                 cooldown_changes.push((e, base_cooldown));
                 continue;
             }
@@ -182,7 +410,69 @@ pub fn run_wander(world: &mut World, tick: Tick) {
             continue;
         };
 
-        // For tracking intentions (Eat/Attack), always invalidate cached path
+        // Storeys: reaching a goal on another level means walking to the
+        // stairs first. `(gx, gy)` becomes the goal of this leg; the wander
+        // target stays `wander_goal` until the mover gets there.
+        let wander_goal = (gx, gy);
+        let storey = world.storey_of(e);
+        let target_entity = if is_tracking && plan_goal.is_none() {
+            target
+        } else {
+            None
+        };
+        let to_storey = goal_storey(world, e, (pos.x, pos.y), storey, (gx, gy), target_entity);
+        let (gx, gy) = match storey_move(
+            &world.tiles,
+            &world.gis.buildings,
+            &mut world.path_workspace,
+            (pos.x, pos.y),
+            storey,
+            (gx, gy),
+            to_storey,
+        ) {
+            StoreyMove::Level => (gx, gy),
+            StoreyMove::Via(stairs) => stairs,
+            StoreyMove::Climb(to) => {
+                storey_changes.push((e, to));
+                cooldown_changes.push((e, base_cooldown));
+                cached_path_changes.push((e, PathUpdate::Remove));
+                continue;
+            }
+            StoreyMove::Step(dest) => {
+                let is_diag = is_diagonal_step((pos.x, pos.y), dest);
+                let reset = if is_diag {
+                    base_cooldown * DIAGONAL_FACTOR / 100
+                } else {
+                    base_cooldown
+                };
+                position_changes.push((
+                    e,
+                    Position {
+                        x: dest.0,
+                        y: dest.1,
+                    },
+                ));
+                cooldown_changes.push((e, reset));
+                cached_path_changes.push((e, PathUpdate::Remove));
+                continue;
+            }
+            StoreyMove::Stay => {
+                cooldown_changes.push((e, base_cooldown));
+                if !is_tracking {
+                    wander_target_changes.push((e, None));
+                }
+                cached_path_changes.push((e, PathUpdate::Remove));
+                continue;
+            }
+        };
+        // Wander target once this leg's goal is reached: cleared on the
+        // final leg, kept while only the stairs have been reached.
+        let arrived_target = ((gx, gy) != wander_goal).then_some(WanderTarget {
+            goal_x: wander_goal.0,
+            goal_y: wander_goal.1,
+        });
+
+        // For tracking intentions (Eat/Attack/Defend/Flee/Rescue), always invalidate cached path
         // since the target may have moved. For wander, try to reuse cached path.
         let cached_step = if !is_tracking {
             world
@@ -190,11 +480,91 @@ pub fn run_wander(world: &mut World, tick: Tick) {
                 .cached_paths
                 .get(&e)
                 .filter(|cp| cp.goal == (gx, gy) && cp.next_step < cp.steps.len())
+                .filter(|cp| {
+                    // Terrain changed in a chunk the path crosses (a door
+                    // locked, a street barricaded), or the next door no
+                    // longer admits us: drop it and re-path.
+                    let (nx, ny) = cp.steps[cp.next_step];
+                    let current = world.tiles.versions_current(&cp.chunk_versions)
+                        && keys.may_pass(&world.tiles, &world.gis, nx, ny);
+                    if !current {
+                        cached_path_changes.push((e, PathUpdate::Remove));
+                    }
+                    current
+                })
                 .map(|cp| cp.steps[cp.next_step])
         } else {
             None
         };
 
+        // Shared destinations use a cached flow field instead of per-entity
+        // A*. Fields ignore danger, congestion and bridge costs, so skip
+        // those whose window holds a danger or costly bridge tile.
+        let flow_step = if cached_step.is_none() && !is_tracking {
+            let hazardous = *hazardous_goals.entry((gx, gy)).or_insert_with(|| {
+                hazards
+                    .iter()
+                    .any(|&(x, y)| flow_field::in_window((gx, gy), x, y))
+            });
+            if hazardous {
+                None
+            } else {
+                world
+                    .flow_fields
+                    .next_step(&world.tiles, (gx, gy), (pos.x, pos.y))
+                    .filter(|&(x, y)| keys.may_pass(&world.tiles, &world.gis, x, y))
+            }
+        } else {
+            None
+        };
+
+        // Per-entity A* (HPA* for long trips) when neither a cached path
+        // nor a flow field has a step. The path debug overlay shows what the
+        // selected entity's searches expanded.
+        let astar_path = if cached_step.is_none() && flow_step.is_none() {
+            let traced = world.path_debug_entity == Some(e);
+            if traced {
+                world.path_workspace.start_trace();
+            }
+            let path = find_path_hpa(
+                &world.tiles,
+                &mut world.hpa,
+                (pos.x, pos.y),
+                (gx, gy),
+                &mut world.path_workspace,
+                &hazards,
+                |x, y| {
+                    if !keys.may_pass(&world.tiles, &world.gis, x, y) {
+                        return IMPASSABLE;
+                    }
+                    let danger = if avoids_danger {
+                        world.danger.cost_at(x, y)
+                    } else {
+                        0
+                    };
+                    let near = (x - pos.x).abs().max((y - pos.y).abs()) <= CONGESTION_RADIUS;
+                    let congestion = if near {
+                        let n = world.occupancy.get(&(x, y, storey)).copied().unwrap_or(0);
+                        u32::from(n).min(CONGESTION_CAP) * CONGESTION_COST
+                    } else {
+                        0
+                    };
+                    let crossing = if crossing_costs {
+                        world.gis.bridges.crossing_cost(x, y)
+                    } else {
+                        0
+                    };
+                    danger + congestion + crossing
+                },
+            );
+            if traced {
+                world.path_debug_trace = world.path_workspace.finish_trace();
+            }
+            path
+        } else {
+            None
+        };
+
         if let Some(dest) = cached_step {
             // Validate cached step: reject if it crosses a diagonal wall seam.
             let is_diag = is_diagonal_step((pos.x, pos.y), dest);
@@ -204,8 +574,14 @@ pub fn run_wander(world: &mut World, tick: Tick) {
                 cached_path_changes.push((e, PathUpdate::Remove));
                 continue;
             }
-            // Use cached path: advance index
-            let reset = if is_diag {
+            // Use cached path: advance index. A Nearby mover jumps ahead
+            // by the steps its interval covers.
+            let cp = &world.mind.cached_paths[&e];
+            let hops = coarse_hops(step, base_cooldown).min(cp.steps.len() - cp.next_step);
+            let dest = cp.steps[cp.next_step + hops - 1];
+            let reset = if step > 1 {
+                coarse_cooldown(step, hops, base_cooldown)
+            } else if is_diag {
                 base_cooldown * DIAGONAL_FACTOR / 100
             } else {
                 base_cooldown
@@ -220,39 +596,62 @@ pub fn run_wander(world: &mut World, tick: Tick) {
             cooldown_changes.push((e, reset));
 
             // Check if path will be exhausted after this step
-            let cp = &world.mind.cached_paths[&e];
-            if cp.next_step + 1 >= cp.steps.len() {
+            if cp.next_step + hops >= cp.steps.len() {
                 // Path exhausted — clear target and cached path
-                wander_target_changes.push((e, None));
+                wander_target_changes.push((e, arrived_target));
                 cached_path_changes.push((e, PathUpdate::Remove));
             } else {
                 wander_target_changes.push((
                     e,
                     Some(WanderTarget {
-                        goal_x: gx,
-                        goal_y: gy,
+                        goal_x: wander_goal.0,
+                        goal_y: wander_goal.1,
                     }),
                 ));
-                cached_path_changes.push((e, PathUpdate::Advance));
+                cached_path_changes.push((e, PathUpdate::Advance(hops)));
             }
-        } else if let Some(path) = find_path(
-            &world.tiles,
-            (pos.x, pos.y),
-            (gx, gy),
-            &mut world.path_workspace,
-        ) {
+        } else if let Some(dest) = flow_step {
+            // Popular destination: follow the shared flow field downhill.
+            let is_diag = is_diagonal_step((pos.x, pos.y), dest);
+            let reset = if is_diag {
+                base_cooldown * DIAGONAL_FACTOR / 100
+            } else {
+                base_cooldown
+            };
+            position_changes.push((
+                e,
+                Position {
+                    x: dest.0,
+                    y: dest.1,
+                },
+            ));
+            cooldown_changes.push((e, reset));
+            let target = if dest == (gx, gy) {
+                arrived_target
+            } else {
+                Some(WanderTarget {
+                    goal_x: wander_goal.0,
+                    goal_y: wander_goal.1,
+                })
+            };
+            wander_target_changes.push((e, target));
+            cached_path_changes.push((e, PathUpdate::Remove));
+        } else if let Some(path) = astar_path {
             // Compute fresh A* path using pooled workspace
             if path.is_empty() {
                 // Already at goal
                 cooldown_changes.push((e, base_cooldown));
                 if !is_tracking {
-                    wander_target_changes.push((e, None));
+                    wander_target_changes.push((e, arrived_target));
                 }
                 cached_path_changes.push((e, PathUpdate::Remove));
             } else {
-                let dest = path[0];
+                let hops = coarse_hops(step, base_cooldown).min(path.len());
+                let dest = path[hops - 1];
                 let is_diag = is_diagonal_step((pos.x, pos.y), dest);
-                let reset = if is_diag {
+                let reset = if step > 1 {
+                    coarse_cooldown(step, hops, base_cooldown)
+                } else if is_diag {
                     base_cooldown * DIAGONAL_FACTOR / 100
                 } else {
                     base_cooldown
@@ -267,25 +666,26 @@ pub fn run_wander(world: &mut World, tick: Tick) {
                 cooldown_changes.push((e, reset));
 
                 if !is_tracking {
-                    if path.len() <= 1 {
+                    if path.len() <= hops {
                         // Will arrive this step — clear target
-                        wander_target_changes.push((e, None));
+                        wander_target_changes.push((e, arrived_target));
                         cached_path_changes.push((e, PathUpdate::Remove));
                     } else {
                         wander_target_changes.push((
                             e,
                             Some(WanderTarget {
-                                goal_x: gx,
-                                goal_y: gy,
+                                goal_x: wander_goal.0,
+                                goal_y: wander_goal.1,
                             }),
                         ));
-                        // Cache path with next_step=1 (step 0 already consumed)
+                        // Cache path past the steps already consumed
                         cached_path_changes.push((
                             e,
                             PathUpdate::Replace(CachedPath {
+                                chunk_versions: world.tiles.chunk_versions(&path),
                                 steps: path,
                                 goal: (gx, gy),
-                                next_step: 1,
+                                next_step: hops,
                             }),
                         ));
                     }
@@ -309,7 +709,9 @@ pub fn run_wander(world: &mut World, tick: Tick) {
             };
             let x = (pos.x + dx).clamp(0, (map_w - 1).max(0));
             let y = (pos.y + dy).clamp(0, (map_h - 1).max(0));
-            if !world.tiles.is_walkable(x as usize, y as usize) {
+            if !world.tiles.is_walkable(x as usize, y as usize)
+                || !keys.may_pass(&world.tiles, &world.gis, x, y)
+            {
                 cooldown_changes.push((e, base_cooldown));
                 wander_target_changes.push((e, None));
                 cached_path_changes.push((e, PathUpdate::Remove));
@@ -357,9 +759,9 @@ pub fn run_wander(world: &mut World, tick: Tick) {
             PathUpdate::Remove => {
                 world.mind.cached_paths.remove(&e);
             }
-            PathUpdate::Advance => {
+            PathUpdate::Advance(hops) => {
                 if let Some(cp) = world.mind.cached_paths.get_mut(&e) {
-                    cp.next_step += 1;
+                    cp.next_step += hops;
                 }
             }
             PathUpdate::Replace(cp) => {
@@ -368,8 +770,49 @@ pub fn run_wander(world: &mut World, tick: Tick) {
         }
     }
 
-    // Apply position_changes
-    for (e, new_pos) in position_changes {
+    // Apply position_changes in entity order, respecting tile capacity.
+    // A mover whose next tile is full sidesteps to a free tile next to both
+    // it and the blocked one, or else yields in place; either way it
+    // re-paths next time. Stepping onto one's own target is always allowed
+    // (combat and eating happen on a shared tile).
+    for (e, mut new_pos) in position_changes {
+        let Some(&old) = world.body.positions.get(&e) else {
+            continue;
+        };
+        let storey = world.storey_of(e);
+        let onto_target = world
+            .mind
+            .intentions
+            .get(&e)
+            .and_then(|i| i.target)
+            .and_then(|t| world.body.positions.get(&t))
+            .is_some_and(|p| (p.x, p.y) == (new_pos.x, new_pos.y));
+        if !onto_target && !world.has_room(new_pos.x, new_pos.y, storey) {
+            world.mind.cached_paths.remove(&e);
+            // Upstairs there is no street to step aside into.
+            let side = if storey == 0 {
+                sidestep(world, &DoorKeys::of(world, e), old, new_pos)
+            } else {
+                None
+            };
+            match side {
+                Some(side) => new_pos = side,
+                None => continue,
+            }
+        }
+        if let Some(n) = world.occupancy.get_mut(&(old.x, old.y, storey)) {
+            *n = n.saturating_sub(1);
+        }
+        *world
+            .occupancy
+            .entry((new_pos.x, new_pos.y, storey))
+            .or_default() += 1;
+        if storey == 0 {
+            world
+                .gis
+                .bridges
+                .record_step((old.x, old.y), (new_pos.x, new_pos.y));
+        }
         if let Some(pos) = world.body.positions.get_mut(&e) {
             *pos = new_pos;
             world.events.push(Event::Moved {
@@ -380,12 +823,177 @@ pub fn run_wander(world: &mut World, tick: Tick) {
             });
         }
     }
+
+    // Apply storey changes (climbing or descending at the stairs).
+    for (e, to) in storey_changes {
+        let Some(&pos) = world.body.positions.get(&e) else {
+            continue;
+        };
+        let from = world.storey_of(e);
+        if let Some(n) = world.occupancy.get_mut(&(pos.x, pos.y, from)) {
+            *n = n.saturating_sub(1);
+        }
+        *world.occupancy.entry((pos.x, pos.y, to)).or_default() += 1;
+        if to == 0 {
+            world.body.storeys.remove(&e);
+        } else {
+            world.body.storeys.insert(e, Storey(to));
+        }
+    }
+}
+
+/// What a mover does about storeys this tick; see `storey_move`.
+enum StoreyMove {
+    /// Street level, goal at street level: path as usual.
+    Level,
+    /// Street level, goal upstairs: path as usual to these stairs first.
+    Via((i32, i32)),
+    /// At the stairs: move to this storey.
+    Climb(u8),
+    /// Upstairs: this step toward the goal or the stairs, within the building.
+    Step((i32, i32)),
+    /// Upstairs at the goal, or cut off from it.
+    Stay,
+}
+
+/// Storey the mover should end up on to reach `goal`: a tracked entity's
+/// storey, the mover's apartment in its own home, its current storey when
+/// the goal is in the building it's already in, otherwise street level.
+fn goal_storey(
+    world: &World,
+    e: Entity,
+    pos: (i32, i32),
+    storey: u8,
+    goal: (i32, i32),
+    target: Option<Entity>,
+) -> u8 {
+    if let Some(t) = target {
+        return world.storey_of(t);
+    }
+    let building_at = |(x, y): (i32, i32)| {
+        if x < 0 || y < 0 {
+            return None;
+        }
+        world.tiles.get_building_id(x as usize, y as usize)
+    };
+    let Some(gb) = building_at(goal) else {
+        return 0;
+    };
+    if world.tiles.get_terrain(goal.0 as usize, goal.1 as usize) != Some(Terrain::Floor) {
+        return 0;
+    }
+    let wanted = if world.gis.home_buildings.get(&e).is_some_and(|h| h.0 == gb) {
+        world.gis.home_storeys.get(&e).map_or(0, |s| s.0)
+    } else if building_at(pos) == Some(gb) {
+        storey
+    } else {
+        0
+    };
+    // Only storeys the building has, and only if they can be reached.
+    world
+        .gis
+        .buildings
+        .get(gb)
+        .filter(|b| b.stairs.is_some() && wanted < b.floor_count)
+        .map_or(0, |_| wanted)
+}
+
+/// Route between storeys. Upstairs movement is confined to the `Floor`
+/// tiles of the building the mover is in and found with plain A*; changing
+/// storey takes one move at the building's stairs.
+fn storey_move(
+    tiles: &TileMap,
+    buildings: &BuildingRegistry,
+    ws: &mut PathWorkspace,
+    pos: (i32, i32),
+    storey: u8,
+    goal: (i32, i32),
+    goal_storey: u8,
+) -> StoreyMove {
+    let building_at = |(x, y): (i32, i32)| {
+        if x < 0 || y < 0 {
+            return None;
+        }
+        tiles.get_building_id(x as usize, y as usize)
+    };
+    let stairs_of = |b| buildings.get(b).and_then(|d| d.stairs);
+
+    if storey == 0 {
+        if goal_storey == 0 {
+            return StoreyMove::Level;
+        }
+        let Some(stairs) = building_at(goal).and_then(stairs_of) else {
+            return StoreyMove::Level;
+        };
+        return if pos == stairs {
+            StoreyMove::Climb(1)
+        } else {
+            StoreyMove::Via(stairs)
+        };
+    }
+
+    // Upstairs. Somehow outside any building with stairs: drop to street.
+    let Some(here) = building_at(pos) else {
+        return StoreyMove::Climb(0);
+    };
+    let Some(stairs) = stairs_of(here) else {
+        return StoreyMove::Climb(0);
+    };
+    let same_level = goal_storey == storey && building_at(goal) == Some(here);
+    let target = if same_level { goal } else { stairs };
+    if pos == target {
+        return if same_level {
+            StoreyMove::Stay
+        } else if goal_storey > storey {
+            StoreyMove::Climb(storey + 1)
+        } else {
+            StoreyMove::Climb(storey - 1)
+        };
+    }
+    let indoors = |x: i32, y: i32| {
+        let inside = building_at((x, y)) == Some(here)
+            && tiles.get_terrain(x as usize, y as usize) == Some(Terrain::Floor);
+        if inside { 0 } else { IMPASSABLE }
+    };
+    match find_path_weighted(tiles, pos, target, ws, indoors) {
+        Some(path) if !path.is_empty() => StoreyMove::Step(path[0]),
+        _ => StoreyMove::Stay,
+    }
+}
+
+/// A free, walkable tile adjacent to both `from` and the blocked `to`,
+/// preferring the emptiest (then the first in N, NE, E, … order).
+fn sidestep(world: &World, keys: &DoorKeys, from: Position, to: Position) -> Option<Position> {
+    const DIRS: [(i32, i32); 8] = [
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ];
+    DIRS.iter()
+        .map(|&(dx, dy)| (from.x + dx, from.y + dy))
+        .filter(|&(x, y)| (x, y) != (to.x, to.y))
+        .filter(|&(x, y)| (x - to.x).abs() <= 1 && (y - to.y).abs() <= 1)
+        .filter(|&(x, y)| x >= 0 && y >= 0 && world.tiles.is_walkable(x as usize, y as usize))
+        .filter(|&(x, y)| {
+            !is_diagonal_step((from.x, from.y), (x, y))
+                || world.tiles.diagonal_clear(from.x, from.y, x, y)
+        })
+        .filter(|&(x, y)| keys.may_pass(&world.tiles, &world.gis, x, y))
+        .filter(|&(x, y)| world.has_room(x, y, 0))
+        .enumerate()
+        .min_by_key(|&(i, (x, y))| (world.occupants(x, y, 0), i))
+        .map(|(_, (x, y))| Position { x, y })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::components::{Gait, GaitProfile, MoveCooldown, Position, Tick};
+    use crate::components::{Gait, GaitProfile, MoveCooldown, Position, Tick, WanderTarget};
     use crate::world::World;
 
     #[test]
@@ -675,6 +1283,7 @@ mod tests {
                 steps: vec![(6, 5), (7, 5)],
                 goal: (10, 10),
                 next_step: 0,
+                chunk_versions: Vec::new(),
             },
         );
 
@@ -860,6 +1469,7 @@ mod tests {
                 steps: vec![(4, 3), (5, 3)],
                 goal: (5, 3),
                 next_step: 0,
+                chunk_versions: Vec::new(),
             },
         );
 
@@ -878,4 +1488,696 @@ mod tests {
             "stale cached path should have been removed"
         );
     }
+
+    #[test]
+    fn test_flee_moves_away_from_threat() {
+        use crate::components::{CombatStats, Intention};
+
+        let mut world = World::new_with_seed(42);
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x: 30, y: 30 });
+        world.body.gait_profiles.insert(e, GaitProfile::biped());
+        let wolf = world.spawn();
+        world.body.positions.insert(wolf, Position { x: 31, y: 30 });
+        world.body.combat_stats.insert(
+            wolf,
+            CombatStats {
+                attack: 10.0,
+                defense: 5.0,
+                aggression: 0.9,
+            },
+        );
+        world.mind.intentions.insert(
+            e,
+            Intention {
+                action: ActionId::Flee,
+                target: Some(wolf),
+            },
+        );
+
+        run_wander(&mut world, Tick(0));
+        let pos = world.body.positions[&e];
+        assert!(pos.x < 30, "should run west, away from x=31, got {}", pos.x);
+    }
+
+    #[test]
+    fn test_eat_paths_around_danger() {
+        use crate::components::{Intention, Nutrition};
+        use crate::systems::danger::run_danger;
+
+        let mut world = World::new_with_seed(42);
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x: 5, y: 10 });
+        world.body.gait_profiles.insert(e, GaitProfile::biped());
+        let food = world.spawn();
+        world.body.positions.insert(food, Position { x: 15, y: 10 });
+        world
+            .mind
+            .nutritions
+            .insert(food, Nutrition { value: 30.0 });
+        world.mind.intentions.insert(
+            e,
+            Intention {
+                action: ActionId::Eat,
+                target: Some(food),
+            },
+        );
+        // A fight just happened halfway along the straight route.
+        let victim = world.spawn();
+        world
+            .body
+            .positions
+            .insert(victim, Position { x: 10, y: 10 });
+        world.events.push(Event::Attacked {
+            attacker: victim,
+            defender: victim,
+            damage: 1.0,
+            tick: Tick(0),
+        });
+        run_danger(&mut world, Tick(1));
+
+        let mut route = Vec::new();
+        for _ in 0..20 {
+            world
+                .body
+                .move_cooldowns
+                .insert(e, MoveCooldown { remaining: 0 });
+            run_wander(&mut world, Tick(1));
+            let pos = world.body.positions[&e];
+            route.push((pos.x, pos.y));
+        }
+        assert!(route.contains(&(15, 10)), "should still reach the food");
+        assert!(
+            !route.iter().any(|&(x, y)| (9..=11).contains(&x) && y == 10),
+            "route {route:?} crossed the fight"
+        );
+    }
+
+    #[test]
+    fn test_worker_on_shift_heads_to_work() {
+        use crate::components::Workplace;
+        use crate::registry::BuildingData;
+
+        let mut world = World::new_with_seed(42);
+        world.tiles = crate::tile_map::TileMap::new(30, 10);
+        let bid = world.gis.buildings.next_id();
+        let tiles = vec![(25, 5), (26, 5)];
+        for &(x, y) in &tiles {
+            world.tiles.set_building_id(x as usize, y as usize, bid);
+            world
+                .tiles
+                .set_terrain(x as usize, y as usize, Terrain::Floor);
+        }
+        world
+            .gis
+            .buildings
+            .insert(BuildingData::test_default(bid, tiles));
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x: 2, y: 5 });
+        world.body.gait_profiles.insert(e, GaitProfile::biped());
+        world.gis.workplaces.insert(e, Workplace(bid));
+        // A random wander goal from before opening is dropped.
+        world.mind.wander_targets.insert(
+            e,
+            WanderTarget {
+                goal_x: 2,
+                goal_y: 9,
+            },
+        );
+
+        world.tick = Tick(10 * 60);
+        run_wander(&mut world, Tick(10 * 60));
+        let goal = world.mind.wander_targets[&e];
+        assert!(matches!((goal.goal_x, goal.goal_y), (25, 5) | (26, 5)));
+        assert_eq!(world.body.positions[&e].x, 3, "should step toward work");
+    }
+
+    #[test]
+    fn test_path_steps_around_crowd() {
+        use crate::components::Intention;
+
+        let mut world = World::new_with_seed(42);
+        world.tiles = crate::tile_map::TileMap::new(20, 20);
+        let walker = world.spawn();
+        world.body.positions.insert(walker, Position { x: 4, y: 5 });
+        world
+            .body
+            .gait_profiles
+            .insert(walker, GaitProfile::biped());
+        let target = world.spawn();
+        world.body.positions.insert(target, Position { x: 9, y: 5 });
+        world.mind.intentions.insert(
+            walker,
+            Intention {
+                action: ActionId::Attack,
+                target: Some(target),
+            },
+        );
+        for _ in 0..CONGESTION_CAP {
+            let bystander = world.spawn();
+            world
+                .body
+                .positions
+                .insert(bystander, Position { x: 5, y: 5 });
+            world.mind.action_states.insert(
+                bystander,
+                crate::components::ActionState {
+                    current_action: Some(ActionId::Idle),
+                    ticks_in_action: 0,
+                    cooldowns: HashMap::new(),
+                },
+            );
+        }
+        world.rebuild_spatial_index();
+
+        run_wander(&mut world, Tick(0));
+        let pos = world.body.positions[&walker];
+        assert_eq!(pos.x, 5);
+        assert_ne!(pos.y, 5, "should sidestep the crowded tile");
+    }
+
+    #[test]
+    fn test_cached_path_dropped_after_terrain_change() {
+        use crate::components::WanderTarget;
+        use crate::tile_map::Terrain;
+
+        let mut world = World::new_with_seed(42);
+        world.tiles = crate::tile_map::TileMap::new(20, 20);
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x: 2, y: 5 });
+        world.body.gait_profiles.insert(e, GaitProfile::biped());
+        world.mind.wander_targets.insert(
+            e,
+            WanderTarget {
+                goal_x: 8,
+                goal_y: 5,
+            },
+        );
+        let steps: Vec<(i32, i32)> = (3..=8).map(|x| (x, 5)).collect();
+        world.mind.cached_paths.insert(
+            e,
+            CachedPath {
+                chunk_versions: world.tiles.chunk_versions(&steps),
+                steps,
+                goal: (8, 5),
+                next_step: 0,
+            },
+        );
+        // Wall off the next step after the path was cached.
+        world.set_terrain(3, 5, Terrain::Wall);
+        world.rebuild_spatial_index();
+
+        run_wander(&mut world, Tick(0));
+        let pos = world.body.positions[&e];
+        assert_ne!((pos.x, pos.y), (3, 5), "must not walk into the new wall");
+        assert_ne!((pos.x, pos.y), (2, 5), "should re-path, not stall");
+        let cp = world.mind.cached_paths.get(&e).expect("fresh path cached");
+        assert!(world.tiles.versions_current(&cp.chunk_versions));
+    }
+
+    #[test]
+    fn test_shared_goal_uses_flow_field() {
+        use crate::components::WanderTarget;
+        use crate::flow_field::FLOW_MIN_DEMAND;
+
+        let mut world = World::new_with_seed(42);
+        world.tiles = crate::tile_map::TileMap::new(30, 30);
+        let goal = (25, 15);
+        let walkers: Vec<Entity> = (0..FLOW_MIN_DEMAND as i32 + 2)
+            .map(|i| {
+                let e = world.spawn();
+                world.body.positions.insert(e, Position { x: 2, y: 2 + i });
+                world.body.gait_profiles.insert(e, GaitProfile::biped());
+                world.mind.wander_targets.insert(
+                    e,
+                    WanderTarget {
+                        goal_x: goal.0,
+                        goal_y: goal.1,
+                    },
+                );
+                e
+            })
+            .collect();
+        world.rebuild_spatial_index();
+
+        run_wander(&mut world, Tick(0));
+        assert!(world.flow_fields.contains(goal));
+        // Walkers after the field was built follow it without caching a path.
+        let last = walkers[walkers.len() - 1];
+        assert!(!world.mind.cached_paths.contains_key(&last));
+        assert_eq!(world.body.positions[&last].x, 3);
+    }
+
+    #[test]
+    fn test_flow_field_skipped_only_near_danger() {
+        use crate::components::{Intention, WanderTarget};
+        use crate::flow_field::FLOW_MIN_DEMAND;
+        use crate::systems::danger::run_danger;
+
+        // Whether the last of a crowd headed for one goal follows a flow
+        // field, with a fight going on at `fight`.
+        let follows_field = |fight: (i32, i32)| {
+            let mut world = World::new_with_seed(42);
+            world.tiles = crate::tile_map::TileMap::new(300, 30);
+            let fighter = world.spawn();
+            world.body.positions.insert(
+                fighter,
+                Position {
+                    x: fight.0,
+                    y: fight.1,
+                },
+            );
+            world.mind.intentions.insert(
+                fighter,
+                Intention {
+                    action: ActionId::Attack,
+                    target: None,
+                },
+            );
+            run_danger(&mut world, Tick(0));
+            let mut last = fighter;
+            for i in 0..FLOW_MIN_DEMAND as i32 + 2 {
+                last = world.spawn();
+                world
+                    .body
+                    .positions
+                    .insert(last, Position { x: 2, y: 2 + i });
+                world.body.gait_profiles.insert(last, GaitProfile::biped());
+                world.mind.wander_targets.insert(
+                    last,
+                    WanderTarget {
+                        goal_x: 25,
+                        goal_y: 15,
+                    },
+                );
+            }
+            world.rebuild_spatial_index();
+            run_wander(&mut world, Tick(0));
+            !world.mind.cached_paths.contains_key(&last)
+        };
+
+        assert!(follows_field((250, 15)), "a fight far off leaves the field");
+        assert!(!follows_field((20, 25)), "a fight inside it does not");
+    }
+
+    #[test]
+    fn test_full_door_blocks_until_free() {
+        use crate::components::WanderTarget;
+        use crate::tile_map::Terrain;
+
+        // A wall along x = 5 with a single door at (5, 5).
+        let mut world = World::new_with_seed(42);
+        world.tiles = crate::tile_map::TileMap::new(10, 10);
+        for y in 0..10 {
+            world.tiles.set_terrain(5, y, Terrain::Wall);
+        }
+        world.tiles.set_terrain(5, 5, Terrain::Door);
+        world.tiles.set_capacity(Terrain::Door, 1);
+
+        let blocker = world.spawn();
+        world
+            .body
+            .positions
+            .insert(blocker, Position { x: 5, y: 5 });
+        world
+            .body
+            .gait_profiles
+            .insert(blocker, GaitProfile::biped());
+        world.mind.intentions.insert(
+            blocker,
+            crate::components::Intention {
+                action: ActionId::Idle,
+                target: None,
+            },
+        );
+        // A loaf on the door takes up no room.
+        let loaf = world.spawn();
+        world.body.positions.insert(loaf, Position { x: 5, y: 5 });
+        let walker = world.spawn();
+        world.body.positions.insert(walker, Position { x: 4, y: 5 });
+        world
+            .body
+            .gait_profiles
+            .insert(walker, GaitProfile::biped());
+        world.mind.wander_targets.insert(
+            walker,
+            WanderTarget {
+                goal_x: 8,
+                goal_y: 5,
+            },
+        );
+        world.rebuild_spatial_index();
+
+        run_wander(&mut world, Tick(0));
+        let pos = world.body.positions[&walker];
+        assert!(pos.x < 5, "door is full, walker stays on its side");
+        assert_eq!(world.occupants(5, 5, 0), 1);
+
+        world
+            .body
+            .positions
+            .insert(blocker, Position { x: 6, y: 5 });
+        world.body.move_cooldowns.clear();
+        world.rebuild_spatial_index();
+        run_wander(&mut world, Tick(1));
+        let pos = world.body.positions[&walker];
+        assert_eq!((pos.x, pos.y), (5, 5));
+        assert_eq!(world.occupants(4, 5, 0), 0);
+        assert_eq!(world.occupants(5, 5, 0), 1);
+    }
+
+    #[test]
+    fn test_private_door_admits_only_residents() {
+        use crate::components::{HomeBuilding, WanderTarget};
+        use crate::registry::BuildingData;
+        use crate::tile_map::Terrain;
+
+        // A house east of x = 5 whose only door is (5, 5).
+        let mut world = World::new_with_seed(42);
+        world.tiles = crate::tile_map::TileMap::new(10, 10);
+        let bid = world.gis.buildings.next_id();
+        let mut tiles = Vec::new();
+        for y in 0..10 {
+            for x in 5..10 {
+                world.tiles.set_building_id(x, y, bid);
+                let t = if x == 5 {
+                    Terrain::Wall
+                } else {
+                    Terrain::Floor
+                };
+                world.tiles.set_terrain(x, y, t);
+                tiles.push((x as i32, y as i32));
+            }
+        }
+        world.tiles.set_terrain(5, 5, Terrain::Door);
+        world
+            .gis
+            .buildings
+            .insert(BuildingData::test_default(bid, tiles));
+        world
+            .gis
+            .buildings
+            .assign_doors(&world.tiles, 1845, world.gis.year_tolerance);
+
+        let walker = world.spawn();
+        world.body.positions.insert(walker, Position { x: 4, y: 5 });
+        world
+            .body
+            .gait_profiles
+            .insert(walker, GaitProfile::biped());
+        world.mind.wander_targets.insert(
+            walker,
+            WanderTarget {
+                goal_x: 7,
+                goal_y: 5,
+            },
+        );
+        world.rebuild_spatial_index();
+        run_wander(&mut world, Tick(0));
+        let pos = world.body.positions[&walker];
+        assert!(pos.x < 5, "stranger can't enter");
+
+        world.body.positions.insert(walker, Position { x: 4, y: 5 });
+        world.gis.home_buildings.insert(walker, HomeBuilding(bid));
+        world.body.move_cooldowns.clear();
+        world.rebuild_spatial_index();
+        run_wander(&mut world, Tick(1));
+        let pos = world.body.positions[&walker];
+        assert_eq!((pos.x, pos.y), (5, 5), "resident walks in");
+    }
+
+    /// A three-storey house east of x = 5 with its door at (5, 5) and a
+    /// resident living on storey 2, standing in the street.
+    fn storey_world() -> (World, Entity) {
+        use crate::components::{HomeBuilding, Storey};
+        use crate::registry::BuildingData;
+
+        let mut world = World::new_with_seed(42);
+        world.tiles = TileMap::new(12, 12);
+        let bid = world.gis.buildings.next_id();
+        let mut tiles = Vec::new();
+        for y in 3..=8 {
+            for x in 5..=10 {
+                world.tiles.set_building_id(x, y, bid);
+                let edge = x == 5 || x == 10 || y == 3 || y == 8;
+                let t = if edge { Terrain::Wall } else { Terrain::Floor };
+                world.tiles.set_terrain(x, y, t);
+                tiles.push((x as i32, y as i32));
+            }
+        }
+        world.tiles.set_terrain(5, 5, Terrain::Door);
+        world.gis.buildings.insert(BuildingData {
+            floor_count: 3,
+            ..BuildingData::test_default(bid, tiles)
+        });
+        world
+            .gis
+            .buildings
+            .assign_doors(&world.tiles, 1845, world.gis.year_tolerance);
+        world.gis.buildings.assign_stairs(&world.tiles);
+
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x: 2, y: 5 });
+        world.body.gait_profiles.insert(e, GaitProfile::biped());
+        world.gis.home_buildings.insert(e, HomeBuilding(bid));
+        world.gis.home_storeys.insert(e, Storey(2));
+        (world, e)
+    }
+
+    fn walk_to(world: &mut World, e: Entity, goal: (i32, i32), ticks: u64) {
+        world.mind.wander_targets.insert(
+            e,
+            WanderTarget {
+                goal_x: goal.0,
+                goal_y: goal.1,
+            },
+        );
+        for t in 0..ticks {
+            let pos = world.body.positions[&e];
+            if !world.mind.wander_targets.contains_key(&e) && (pos.x, pos.y) == goal {
+                break;
+            }
+            world.body.move_cooldowns.clear();
+            world.rebuild_spatial_index();
+            run_wander(world, Tick(t));
+        }
+    }
+
+    #[test]
+    fn test_resident_climbs_to_apartment() {
+        let (mut world, e) = storey_world();
+        assert_eq!(
+            world.gis.buildings.buildings[0].stairs,
+            Some((6, 5)),
+            "stairs next to the door"
+        );
+
+        walk_to(&mut world, e, (8, 6), 40);
+        let pos = world.body.positions[&e];
+        assert_eq!((pos.x, pos.y), (8, 6));
+        assert_eq!(world.storey_of(e), 2);
+        assert_eq!(world.occupants(8, 6, 2), 1);
+        assert_eq!(world.occupants(8, 6, 0), 0);
+    }
+
+    #[test]
+    fn test_upstairs_resident_comes_down_to_street() {
+        use crate::components::Storey;
+
+        let (mut world, e) = storey_world();
+        world.body.positions.insert(e, Position { x: 8, y: 6 });
+        world.body.storeys.insert(e, Storey(2));
+
+        walk_to(&mut world, e, (2, 5), 40);
+        let pos = world.body.positions[&e];
+        assert_eq!((pos.x, pos.y), (2, 5));
+        assert_eq!(world.storey_of(e), 0);
+        assert!(!world.body.storeys.contains_key(&e));
+    }
+
+    /// A river across rows 4..8, bridged at x = 3 and x = 15, with a
+    /// walker north of the western bridge.
+    fn river_world() -> (World, Entity) {
+        use crate::registry::BridgeRegistry;
+
+        let mut world = World::new_with_seed(42);
+        world.tiles = TileMap::new(20, 12);
+        for y in 4..8 {
+            for x in 0..20 {
+                let t = if x == 3 || x == 15 {
+                    Terrain::Bridge
+                } else {
+                    Terrain::Water
+                };
+                world.tiles.set_terrain(x, y, t);
+            }
+        }
+        world.gis.bridges = BridgeRegistry::build_from_tiles(&world.tiles);
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x: 3, y: 1 });
+        world.body.gait_profiles.insert(e, GaitProfile::biped());
+        (world, e)
+    }
+
+    /// Walk to `goal` and return the x of every river tile crossed.
+    fn crossing_columns(world: &mut World, e: Entity, goal: (i32, i32)) -> Vec<i32> {
+        world.mind.wander_targets.insert(
+            e,
+            WanderTarget {
+                goal_x: goal.0,
+                goal_y: goal.1,
+            },
+        );
+        let mut columns = Vec::new();
+        for t in 0..80 {
+            world.body.move_cooldowns.clear();
+            world.rebuild_spatial_index();
+            run_wander(world, Tick(t));
+            let pos = world.body.positions[&e];
+            if (4..8).contains(&pos.y) {
+                columns.push(pos.x);
+            }
+            if (pos.x, pos.y) == goal {
+                break;
+            }
+        }
+        let pos = world.body.positions[&e];
+        assert_eq!((pos.x, pos.y), goal);
+        columns
+    }
+
+    #[test]
+    fn test_closed_bridge_forces_detour() {
+        use crate::registry::BridgeId;
+
+        let (mut world, e) = river_world();
+        let stranded = world.spawn();
+        world
+            .body
+            .positions
+            .insert(stranded, Position { x: 3, y: 6 });
+        world
+            .body
+            .gait_profiles
+            .insert(stranded, GaitProfile::biped());
+        assert!(world.close_bridge(BridgeId(1)));
+        assert!(!world.close_bridge(BridgeId(1)), "already closed");
+        assert_eq!(world.tiles.get_terrain(3, 4), Some(Terrain::Bridge));
+        let p = world.body.positions[&stranded];
+        assert_eq!((p.x, p.y), (3, 8), "sent to the nearer bank");
+        world.body.positions.remove(&stranded);
+        let columns = crossing_columns(&mut world, e, (3, 10));
+        assert!(columns.iter().all(|&x| x == 15), "crossed at {columns:?}");
+
+        assert!(world.reopen_bridge(BridgeId(1)));
+        let columns = crossing_columns(&mut world, e, (15, 1));
+        assert!(columns.iter().all(|&x| x == 3), "crossed at {columns:?}");
+        let crossings = |w: &World, id| w.gis.bridges.get(id).map_or(0, |b| b.crossings);
+        assert_eq!(crossings(&world, BridgeId(1)), 1);
+        assert_eq!(crossings(&world, BridgeId(2)), 1);
+    }
+
+    #[test]
+    fn test_toll_bridge_avoided_when_detour_is_cheaper() {
+        use crate::registry::BridgeId;
+
+        let (mut world, e) = river_world();
+        if let Some(b) = world.gis.bridges.get_mut(BridgeId(1)) {
+            b.toll = Some(200);
+        }
+        let columns = crossing_columns(&mut world, e, (3, 10));
+        assert!(columns.iter().all(|&x| x == 15), "crossed at {columns:?}");
+    }
+
+    #[test]
+    fn test_debug_entity_search_is_traced() {
+        let (mut world, e) = river_world();
+        let other = world.spawn();
+        world.body.positions.insert(other, Position { x: 10, y: 1 });
+        world.body.gait_profiles.insert(other, GaitProfile::biped());
+        for walker in [e, other] {
+            world.mind.wander_targets.insert(
+                walker,
+                WanderTarget {
+                    goal_x: 15,
+                    goal_y: 10,
+                },
+            );
+        }
+        // `e` searches first but only `other` is traced.
+        world.path_debug_entity = Some(other);
+        world.rebuild_spatial_index();
+        run_wander(&mut world, Tick(0));
+
+        let stats = world.path_workspace.take_stats();
+        assert_eq!(stats.calls, 2);
+        assert_eq!(stats.failures, 0);
+        assert_eq!(world.path_debug_trace.first(), Some(&(10, 1)));
+        assert!(
+            world.path_debug_trace.contains(&(15, 5)),
+            "crossed the bridge"
+        );
+    }
+
+    #[test]
+    fn test_unconscious_does_not_move() {
+        use crate::components::Fatigue;
+
+        let mut world = World::new_with_seed(42);
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x: 10, y: 10 });
+        world.body.gait_profiles.insert(e, GaitProfile::biped());
+        world.body.fatigues.insert(e, Fatigue { current: 120.0 });
+        run_wander(&mut world, Tick(0));
+        let pos = world.body.positions[&e];
+        assert_eq!((pos.x, pos.y), (10, 10));
+    }
+
+    /// A walker heading 30 tiles east along a clear row, in a quartier in
+    /// `zone`. Returns its x after each of `ticks` ticks.
+    fn walk_east(zone: crate::lod::LodZone, ticks: u64) -> Vec<i32> {
+        use crate::registry::QuartierId;
+
+        let mut world = World::new_with_seed(42);
+        world.tiles = crate::tile_map::TileMap::new(40, 5);
+        for y in 0..5 {
+            for x in 0..40 {
+                world.tiles.set_quartier_id(x, y, 1);
+            }
+        }
+        world.gis.lod_zones.insert(QuartierId(1), zone);
+        let e = world.spawn();
+        world.body.positions.insert(e, Position { x: 2, y: 2 });
+        world.body.gait_profiles.insert(e, GaitProfile::biped());
+        world.mind.wander_targets.insert(
+            e,
+            WanderTarget {
+                goal_x: 32,
+                goal_y: 2,
+            },
+        );
+        (0..ticks)
+            .map(|t| {
+                run_wander(&mut world, Tick(t));
+                world.body.positions[&e].x
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_nearby_walker_jumps_along_path() {
+        use crate::lod::{LodZone, NEARBY_TICK_INTERVAL};
+
+        let n = NEARBY_TICK_INTERVAL as usize;
+        let active = walk_east(LodZone::Active, 2 * NEARBY_TICK_INTERVAL);
+        let nearby = walk_east(LodZone::Nearby, 2 * NEARBY_TICK_INTERVAL);
+        // Entity 1 updates on ticks 29 and 59: one jump per interval.
+        let moves: Vec<usize> = (0..nearby.len())
+            .filter(|&t| nearby[t] != if t == 0 { 2 } else { nearby[t - 1] })
+            .collect();
+        assert_eq!(moves, vec![n - 1, 2 * n - 1]);
+        assert!(nearby[n - 1] - 2 > 1, "jumped several tiles");
+        // Over whole intervals it covers the same ground as an Active walker.
+        assert_eq!(nearby[2 * n - 1], active[2 * n - 1]);
+    }
 }
@@ -0,0 +1,303 @@
+use crate::components::{BodyPart, Entity, Tick, Weapon, Wound, WoundKind};
+use crate::events::Event;
+use crate::world::World;
+use rand::RngExt;
+
+/// Hit location weights, in `BodyPart` order: head, torso, arms, legs.
+const HIT_LOCATIONS: [(BodyPart, f32); 6] = [
+    (BodyPart::Head, 0.10),
+    (BodyPart::Torso, 0.40),
+    (BodyPart::LeftArm, 0.125),
+    (BodyPart::RightArm, 0.125),
+    (BodyPart::LeftLeg, 0.125),
+    (BodyPart::RightLeg, 0.125),
+];
+/// Blunt damage at or above this breaks the bone it lands on.
+const FRACTURE_DAMAGE: f32 = 12.0;
+/// Chance a musket ball breaks the limb it hits.
+const MUSKET_FRACTURE_CHANCE: f32 = 0.5;
+/// Bleeding per tick per point of damage from a blade.
+const BLADE_BLEEDING: f32 = 0.01;
+/// Bleeding per tick per point of damage from a musket ball.
+const MUSKET_BLEEDING: f32 = 0.015;
+/// Fraction of bleeding left after each tick (half-life ~23 ticks).
+const CLOTTING: f32 = 0.97;
+/// Bleeding below this per tick has stopped.
+const CLOTTED: f32 = 0.001;
+/// Health regained per tick by a wounded entity that has stopped bleeding.
+const HEALTH_REGEN: f32 = 0.01;
+
+/// Ticks a wound takes to heal (1 tick = 1 game minute).
+fn healing_ticks(kind: WoundKind) -> u32 {
+    const DAY: u32 = 1440;
+    match kind {
+        WoundKind::Bruise => 3 * DAY,
+        WoundKind::Cut => 14 * DAY,
+        WoundKind::Fracture => 42 * DAY,
+    }
+}
+
+/// Record the wound a landed blow of `damage` leaves on `defender`: roll the
+/// hit location, then the wound the weapon (bare hands when `None`) makes
+/// there. Pushes `Event::Wounded`. Called by `run_combat`.
+pub fn inflict(
+    world: &mut World,
+    defender: Entity,
+    weapon: Option<Weapon>,
+    damage: f32,
+    tick: Tick,
+) {
+    let roll: f32 = world.rng.random();
+    let mut acc = 0.0;
+    let part = HIT_LOCATIONS
+        .iter()
+        .find(|&&(_, w)| {
+            acc += w;
+            roll < acc
+        })
+        .map_or(BodyPart::Torso, |&(p, _)| p);
+    let limb_or_head = part != BodyPart::Torso;
+
+    let (kind, bleeding) = match weapon {
+        None | Some(Weapon::Cane) if damage >= FRACTURE_DAMAGE && limb_or_head => {
+            (WoundKind::Fracture, 0.0)
+        }
+        None | Some(Weapon::Cane) => (WoundKind::Bruise, 0.0),
+        Some(Weapon::Knife | Weapon::Sabre) => (WoundKind::Cut, damage * BLADE_BLEEDING),
+        Some(Weapon::Musket) => {
+            let breaks = limb_or_head && world.rng.random::<f32>() < MUSKET_FRACTURE_CHANCE;
+            let kind = if breaks {
+                WoundKind::Fracture
+            } else {
+                WoundKind::Cut
+            };
+            (kind, damage * MUSKET_BLEEDING)
+        }
+    };
+
+    world
+        .body
+        .wounds
+        .entry(defender)
+        .or_default()
+        .0
+        .push(Wound {
+            part,
+            kind,
+            bleeding,
+            healing: healing_ticks(kind),
+        });
+    world.events.push(Event::Wounded {
+        entity: defender,
+        part,
+        kind,
+        tick,
+    });
+}
+
+/// Phase 2 (Needs): Bleeding and healing.
+///
+/// Each wound bleeds `bleeding` health per tick, clotting by `CLOTTING`, and
+/// heals after its `healing` ticks run out (`Event::WoundHealed`). While no
+/// wound bleeds, health regenerates by `HEALTH_REGEN` per tick. Bleeding out
+/// pushes a death event. Nearby entities update once per
+/// `NEARBY_TICK_INTERVAL` for the whole interval; frozen ones not at all.
+pub fn run_wounds(world: &mut World, tick: Tick) {
+    let mut wounded: Vec<(Entity, u64)> = world
+        .body
+        .wounds
+        .keys()
+        .filter(|e| !world.pending_deaths.contains(e))
+        .filter(|e| !world.frozen.contains(e))
+        .map(|&e| (e, world.lod_step(e, tick)))
+        .filter(|&(_, step)| step > 0)
+        .collect();
+    wounded.sort_by_key(|(e, _)| e.0);
+
+    for (e, step) in wounded {
+        let Some(wounds) = world.body.wounds.get_mut(&e) else {
+            continue;
+        };
+        let steps = step as u32;
+        let mut blood_lost = 0.0;
+        for w in &mut wounds.0 {
+            if w.bleeding > 0.0 {
+                let left = CLOTTING.powi(steps as i32);
+                // Sum of the geometric series bleeding·(1 + c + … + c^(n-1)).
+                blood_lost += w.bleeding * (1.0 - left) / (1.0 - CLOTTING);
+                w.bleeding *= left;
+                if w.bleeding < CLOTTED {
+                    w.bleeding = 0.0;
+                }
+            }
+            w.healing = w.healing.saturating_sub(steps);
+        }
+        let healed: Vec<Wound> = wounds
+            .0
+            .iter()
+            .filter(|w| w.healing == 0)
+            .copied()
+            .collect();
+        wounds.0.retain(|w| w.healing > 0);
+        let regen = if wounds.is_bleeding() {
+            0.0
+        } else {
+            HEALTH_REGEN * step as f32
+        };
+        if wounds.0.is_empty() {
+            world.body.wounds.remove(&e);
+        }
+        for w in healed {
+            world.events.push(Event::WoundHealed {
+                entity: e,
+                part: w.part,
+                kind: w.kind,
+                tick,
+            });
+        }
+
+        if let Some(health) = world.body.healths.get_mut(&e) {
+            health.current = (health.current - blood_lost + regen).clamp(0.0, health.max);
+            if health.current <= 0.0 {
+                world.events.push(Event::Died { entity: e, tick });
+                world.pending_deaths.insert(e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Health, Wounds};
+
+    fn wounded(world: &mut World, wound: Wound, health: f32) -> Entity {
+        let e = world.spawn();
+        world.body.wounds.insert(e, Wounds(vec![wound]));
+        world.body.healths.insert(
+            e,
+            Health {
+                current: health,
+                max: 100.0,
+            },
+        );
+        e
+    }
+
+    #[test]
+    fn test_bleeding_drains_health_and_clots() {
+        let mut world = World::new_with_seed(42);
+        let wound = Wound {
+            part: BodyPart::Torso,
+            kind: WoundKind::Cut,
+            bleeding: 0.5,
+            healing: 1000,
+        };
+        let e = wounded(&mut world, wound, 50.0);
+
+        run_wounds(&mut world, Tick(0));
+        assert!((world.body.healths[&e].current - 49.5).abs() < 0.001);
+        let bleeding = world.body.wounds[&e].0[0].bleeding;
+        assert!((bleeding - 0.5 * CLOTTING).abs() < 0.001);
+
+        for t in 1..400 {
+            run_wounds(&mut world, Tick(t));
+        }
+        assert!(!world.body.wounds[&e].is_bleeding());
+        // Total loss converges on bleeding / (1 - CLOTTING) ≈ 16.7.
+        let health = world.body.healths[&e].current;
+        assert!(health > 33.0 && health < 37.0, "health {health}");
+    }
+
+    #[test]
+    fn test_bleeding_out_kills() {
+        let mut world = World::new_with_seed(42);
+        let wound = Wound {
+            part: BodyPart::Torso,
+            kind: WoundKind::Cut,
+            bleeding: 2.0,
+            healing: 1000,
+        };
+        let e = wounded(&mut world, wound, 1.0);
+
+        run_wounds(&mut world, Tick(0));
+        assert!(world.pending_deaths.contains(&e));
+        assert!(
+            world
+                .events
+                .iter()
+                .any(|ev| matches!(ev, Event::Died { entity, .. } if *entity == e))
+        );
+    }
+
+    #[test]
+    fn test_wounds_heal_and_health_regenerates() {
+        let mut world = World::new_with_seed(42);
+        let wound = Wound {
+            part: BodyPart::LeftLeg,
+            kind: WoundKind::Fracture,
+            bleeding: 0.0,
+            healing: 2,
+        };
+        let e = wounded(&mut world, wound, 50.0);
+        assert_eq!(world.body.wounds[&e].limp(), 3);
+
+        run_wounds(&mut world, Tick(0));
+        assert!((world.body.healths[&e].current - 50.01).abs() < 0.001);
+        run_wounds(&mut world, Tick(1));
+        assert!(!world.body.wounds.contains_key(&e));
+        assert!(world.events.iter().any(|ev| matches!(
+            ev,
+            Event::WoundHealed {
+                entity,
+                kind: WoundKind::Fracture,
+                ..
+            } if *entity == e
+        )));
+    }
+
+    #[test]
+    fn test_inflict_wound_by_weapon() {
+        let mut world = World::new_with_seed(42);
+        let e = world.spawn();
+
+        inflict(&mut world, e, Some(Weapon::Knife), 10.0, Tick(0));
+        let w = world.body.wounds[&e].0[0];
+        assert_eq!(w.kind, WoundKind::Cut);
+        assert!((w.bleeding - 0.1).abs() < 0.001);
+
+        inflict(&mut world, e, None, 3.0, Tick(0));
+        let w = world.body.wounds[&e].0[1];
+        assert_eq!(w.kind, WoundKind::Bruise);
+        assert_eq!(w.bleeding, 0.0);
+        assert_eq!(
+            world
+                .events
+                .iter()
+                .filter(|ev| matches!(ev, Event::Wounded { .. }))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_wounds_reduce_work_ability() {
+        let arm = |kind, bleeding| Wound {
+            part: BodyPart::RightArm,
+            kind,
+            bleeding,
+            healing: 100,
+        };
+        assert_eq!(Wounds::default().work_ability(), 1.0);
+        assert_eq!(Wounds(vec![arm(WoundKind::Cut, 0.1)]).work_ability(), 0.5);
+        assert_eq!(
+            Wounds(vec![
+                arm(WoundKind::Fracture, 0.0),
+                arm(WoundKind::Fracture, 0.0)
+            ])
+            .work_ability(),
+            0.0
+        );
+        assert_eq!(Wounds(vec![arm(WoundKind::Bruise, 0.0)]).limp(), 1);
+    }
+}
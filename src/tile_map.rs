@@ -22,6 +22,8 @@ pub enum Terrain {
     Water = 6,     // river — blocked
     Bridge = 7,    // river crossing — walkable
     Fixture = 8,   // fountain, well, kiosk (BATI=3 minor feature) — walkable
+    Barricade = 9, // paving stones and carts piled up by rioters — blocked
+    Rubble = 10,   // burnt-out building — walkable
 }
 
 impl Terrain {
@@ -35,9 +37,15 @@ impl Terrain {
                 | Terrain::Garden
                 | Terrain::Bridge
                 | Terrain::Fixture
+                | Terrain::Rubble
         )
     }
 
+    /// Whether this terrain blocks line of sight.
+    pub fn is_opaque(self) -> bool {
+        matches!(self, Terrain::Wall)
+    }
+
     /// Target equilibrium temperature for this terrain type (°C).
     pub fn target_temperature(self) -> f32 {
         match self {
@@ -50,6 +58,8 @@ impl Terrain {
             Terrain::Door => 17.0,      // building entrance
             Terrain::Floor => 18.0,     // building interior
             Terrain::Fixture => 16.0,   // outdoor minor feature
+            Terrain::Barricade => 16.0, // on the street
+            Terrain::Rubble => 16.0,    // open to the sky
         }
     }
 
@@ -69,6 +79,8 @@ impl Terrain {
             6 => Some(Terrain::Water),
             7 => Some(Terrain::Bridge),
             8 => Some(Terrain::Fixture),
+            9 => Some(Terrain::Barricade),
+            10 => Some(Terrain::Rubble),
             _ => None,
         }
     }
@@ -106,6 +118,9 @@ pub struct Chunk {
     /// True when all tiles are at their terrain's target temperature.
     /// Set by initialize_temperatures / run_temperature. Cleared by set_terrain.
     pub at_equilibrium: bool,
+    /// Bumped whenever `set_terrain` changes a tile, so anything derived
+    /// from this chunk's terrain (cached paths) can tell it has gone stale.
+    pub version: u32,
 }
 
 impl fmt::Debug for Chunk {
@@ -129,6 +144,7 @@ impl Chunk {
             dirty: false,
             last_tick: Tick(0),
             at_equilibrium: false,
+            version: 0,
         }
     }
 
@@ -142,7 +158,11 @@ impl Chunk {
     }
 
     pub fn set_terrain(&mut self, lx: usize, ly: usize, t: Terrain) {
-        self.terrain[Self::local_index(lx, ly)] = t;
+        let i = Self::local_index(lx, ly);
+        if self.terrain[i] != t {
+            self.version = self.version.wrapping_add(1);
+        }
+        self.terrain[i] = t;
         self.dirty = true;
         self.at_equilibrium = false;
     }
@@ -267,11 +287,25 @@ impl ChunkRange {
     }
 }
 
+/// Number of `Terrain` variants (size of per-terrain tables).
+pub const TERRAIN_COUNT: usize = 11;
+
+/// Path cost of a cardinal step onto plain terrain, in A* fixed-point units.
+pub const BASE_MOVE_COST: u32 = 100;
+
+/// Tile capacity before `terrain.kdl` sets one: no limit.
+pub const UNLIMITED_CAPACITY: u16 = u16::MAX;
+
 pub struct TileMap {
     chunks: Vec<Chunk>,
     chunks_x: usize,
     width: usize,  // total tiles
     height: usize, // total tiles
+    /// Cost of a cardinal step onto each terrain, indexed by `Terrain as usize`.
+    /// Never below `BASE_MOVE_COST`, so the octile heuristic stays admissible.
+    move_costs: [u32; TERRAIN_COUNT],
+    /// How many entities may stand on one tile of each terrain.
+    capacities: [u16; TERRAIN_COUNT],
 }
 
 impl fmt::Debug for TileMap {
@@ -294,6 +328,8 @@ impl TileMap {
             chunks_x,
             width,
             height,
+            move_costs: [BASE_MOVE_COST; TERRAIN_COUNT],
+            capacities: [UNLIMITED_CAPACITY; TERRAIN_COUNT],
         }
     }
 
@@ -339,16 +375,45 @@ impl TileMap {
         }
     }
 
+    /// `(chunk index, version)` for each chunk `steps` passes through, in
+    /// order of first visit. Stored with a cached path to detect terrain
+    /// edits along it; see `versions_current`.
+    pub fn chunk_versions(&self, steps: &[(i32, i32)]) -> Vec<(usize, u32)> {
+        let mut out: Vec<(usize, u32)> = Vec::new();
+        for &(x, y) in steps {
+            if x < 0 || y < 0 {
+                continue;
+            }
+            let Some((idx, _, _)) = self.chunk_and_local(x as usize, y as usize) else {
+                continue;
+            };
+            if !out.iter().any(|&(i, _)| i == idx) {
+                out.push((idx, self.chunks[idx].version));
+            }
+        }
+        out
+    }
+
+    /// Whether none of the chunks in `stamps` (from `chunk_versions`) has
+    /// changed terrain since.
+    pub fn versions_current(&self, stamps: &[(usize, u32)]) -> bool {
+        stamps
+            .iter()
+            .all(|&(idx, v)| self.chunks.get(idx).is_some_and(|c| c.version == v))
+    }
+
     #[allow(dead_code)]
     pub fn get_temperature(&self, x: usize, y: usize) -> Option<f32> {
         let (idx, lx, ly) = self.chunk_and_local(x, y)?;
         Some(self.chunks[idx].get_temperature(lx, ly))
     }
 
-    #[allow(dead_code)]
+    /// Set a tile's temperature and wake its chunk so `run_temperature`
+    /// drifts it back toward the terrain's target.
     pub fn set_temperature(&mut self, x: usize, y: usize, temp: f32) {
         if let Some((idx, lx, ly)) = self.chunk_and_local(x, y) {
             self.chunks[idx].set_temperature(lx, ly, temp);
+            self.chunks[idx].at_equilibrium = false;
         }
     }
 
@@ -385,6 +450,67 @@ impl TileMap {
         }
     }
 
+    /// Cost of a cardinal step onto `t` (loaded from `terrain.kdl`).
+    pub fn move_cost(&self, t: Terrain) -> u32 {
+        self.move_costs[t as usize]
+    }
+
+    /// Set the cost of a cardinal step onto `t`. Clamped to at least
+    /// `BASE_MOVE_COST`.
+    pub fn set_move_cost(&mut self, t: Terrain, cost: u32) {
+        self.move_costs[t as usize] = cost.max(BASE_MOVE_COST);
+    }
+
+    /// All per-terrain step costs, indexed by `Terrain as usize`.
+    pub fn move_costs(&self) -> [u32; TERRAIN_COUNT] {
+        self.move_costs
+    }
+
+    /// Cost of stepping onto `(x, y)` from an adjacent tile: the terrain's
+    /// move cost, scaled by √2 for diagonal steps.
+    pub fn step_cost(&self, x: i32, y: i32, diagonal: bool) -> u32 {
+        let terrain = if x < 0 || y < 0 {
+            None
+        } else {
+            self.get_terrain(x as usize, y as usize)
+        };
+        let cost = terrain.map_or(BASE_MOVE_COST, |t| self.move_cost(t));
+        if diagonal { cost * 141 / 100 } else { cost }
+    }
+
+    /// How many entities may stand on one tile of `t`.
+    pub fn capacity(&self, t: Terrain) -> u16 {
+        self.capacities[t as usize]
+    }
+
+    /// Set the per-tile capacity of `t`. Clamped to at least 1.
+    pub fn set_capacity(&mut self, t: Terrain, n: u16) {
+        self.capacities[t as usize] = n.max(1);
+    }
+
+    /// Capacity of the tile at `(x, y)`: its terrain's capacity, halved
+    /// (at least 1) where the tile is a narrow passage, i.e. blocked on
+    /// both sides east-west or north-south. 0 if out of bounds.
+    pub fn capacity_at(&self, x: i32, y: i32) -> u16 {
+        if x < 0 || y < 0 {
+            return 0;
+        }
+        let Some(t) = self.get_terrain(x as usize, y as usize) else {
+            return 0;
+        };
+        let open = |dx: i32, dy: i32| {
+            let (nx, ny) = (x + dx, y + dy);
+            nx >= 0 && ny >= 0 && self.is_walkable(nx as usize, ny as usize)
+        };
+        let narrow = (!open(-1, 0) && !open(1, 0)) || (!open(0, -1) && !open(0, 1));
+        let cap = self.capacity(t);
+        if narrow && cap != UNLIMITED_CAPACITY {
+            (cap / 2).max(1)
+        } else {
+            cap
+        }
+    }
+
     /// Check if a tile is walkable (in-bounds and terrain allows passage).
     pub fn is_walkable(&self, x: usize, y: usize) -> bool {
         self.get_terrain(x, y).is_some_and(|t| t.is_walkable())
@@ -398,9 +524,10 @@ impl TileMap {
     /// convention. Out-of-bounds or negative coordinates count as
     /// non-walkable.
     ///
-    /// Any system that performs diagonal movement or ray-casting MUST call
-    /// this method. See also: `find_path` neighbor loop, `run_wander`
-    /// random fallback, player movement validation.
+    /// Any system that performs diagonal movement MUST call this method.
+    /// See also: `find_path` neighbor loop, `run_wander` random fallback,
+    /// player movement validation. Ray-casting uses `line_of_sight`, which
+    /// applies the same seam rule to opaque tiles.
     pub fn diagonal_clear(&self, x1: i32, y1: i32, x2: i32, y2: i32) -> bool {
         // Negative coordinates are out-of-bounds (non-walkable).
         if x1 < 0 || y1 < 0 || x2 < 0 || y2 < 0 {
@@ -409,6 +536,46 @@ impl TileMap {
         self.is_walkable(x2 as usize, y1 as usize) || self.is_walkable(x1 as usize, y2 as usize)
     }
 
+    /// Check if `to` is visible from `from` along a Bresenham line.
+    ///
+    /// Tiles strictly between the endpoints must not be opaque. A diagonal
+    /// step is blocked when both shoulder tiles are opaque (a wall seam, as
+    /// in `diagonal_clear`). Out-of-bounds tiles count as opaque.
+    pub fn line_of_sight(&self, from: (i32, i32), to: (i32, i32)) -> bool {
+        let opaque = |x: i32, y: i32| {
+            x < 0
+                || y < 0
+                || self
+                    .get_terrain(x as usize, y as usize)
+                    .is_none_or(|t| t.is_opaque())
+        };
+        let (mut x, mut y) = from;
+        let dx = (to.0 - x).abs();
+        let dy = -(to.1 - y).abs();
+        let sx = if x < to.0 { 1 } else { -1 };
+        let sy = if y < to.1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        while (x, y) != to {
+            let (px, py) = (x, y);
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+            if px != x && py != y && opaque(x, py) && opaque(px, y) {
+                return false;
+            }
+            if (x, y) != to && opaque(x, y) {
+                return false;
+            }
+        }
+        true
+    }
+
     /// Get an immutable reference to a chunk by coordinate.
     #[allow(dead_code)]
     pub fn get_chunk(&self, coord: ChunkCoord) -> Option<&Chunk> {
@@ -639,6 +806,8 @@ impl TileMap {
                 chunks_x,
                 width,
                 height,
+                move_costs: [BASE_MOVE_COST; TERRAIN_COUNT],
+                capacities: [UNLIMITED_CAPACITY; TERRAIN_COUNT],
             },
             uuid,
         ))
@@ -662,6 +831,15 @@ pub fn is_diagonal_step(a: (i32, i32), b: (i32, i32)) -> bool {
     a.0 != b.0 && a.1 != b.1
 }
 
+/// A* counters accumulated by a `PathWorkspace` since the last `take_stats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PathStats {
+    pub calls: u32,
+    /// Searches that found no path (unreachable goal or node limit hit).
+    pub failures: u32,
+    pub expanded: u32,
+}
+
 /// Reusable A* workspace. Allocates flat arrays once, reuses across calls with
 /// generation-counter clearing: bumping `generation` each call makes all cells
 /// "unvisited" in O(1) instead of zeroing 30M entries.
@@ -672,6 +850,9 @@ pub struct PathWorkspace {
     generations: Vec<u64>,
     generation: u64,
     size: usize,
+    stats: PathStats,
+    /// Tiles expanded since `start_trace`, if tracing.
+    trace: Option<Vec<(i32, i32)>>,
 }
 
 impl Default for PathWorkspace {
@@ -690,9 +871,27 @@ impl PathWorkspace {
             generations: Vec::new(),
             generation: 0,
             size: 0,
+            stats: PathStats::default(),
+            trace: None,
         }
     }
 
+    /// Counters since the last call, which resets them.
+    pub fn take_stats(&mut self) -> PathStats {
+        std::mem::take(&mut self.stats)
+    }
+
+    /// Record every tile expanded by following searches until
+    /// `finish_trace`.
+    pub fn start_trace(&mut self) {
+        self.trace = Some(Vec::new());
+    }
+
+    /// Stop tracing and return the tiles expanded since `start_trace`.
+    pub fn finish_trace(&mut self) -> Vec<(i32, i32)> {
+        self.trace.take().unwrap_or_default()
+    }
+
     /// Ensure workspace is sized for `total` cells. Reallocates only if map
     /// size changed (should happen at most once after loading).
     fn ensure_size(&mut self, total: usize) {
@@ -727,6 +926,10 @@ impl PathWorkspace {
 
     #[inline]
     fn set_g(&mut self, i: usize, val: u32) {
+        if self.generations[i] != self.generation {
+            // First touch this generation: drop a stale closed flag.
+            self.closed[i] = false;
+        }
         self.g_score[i] = val;
         self.generations[i] = self.generation;
     }
@@ -761,6 +964,39 @@ pub fn find_path(
     start: (i32, i32),
     goal: (i32, i32),
     ws: &mut PathWorkspace,
+) -> Option<Vec<(i32, i32)>> {
+    find_path_weighted(map, start, goal, ws, |_, _| 0)
+}
+
+/// Extra cost that makes `find_path_weighted` treat a tile as a wall.
+pub const IMPASSABLE: u32 = u32::MAX;
+
+/// `find_path` with an extra cost for entering each tile, in the same
+/// fixed-point units as a cardinal step (100). Step costs come from
+/// `TileMap::step_cost`; both they and the extra costs only ever add to the
+/// plain octile distance, so the heuristic stays admissible. An extra cost
+/// of `IMPASSABLE` closes the tile, goal included.
+pub fn find_path_weighted(
+    map: &TileMap,
+    start: (i32, i32),
+    goal: (i32, i32),
+    ws: &mut PathWorkspace,
+    extra_cost: impl Fn(i32, i32) -> u32,
+) -> Option<Vec<(i32, i32)>> {
+    let path = astar(map, start, goal, ws, extra_cost);
+    ws.stats.calls += 1;
+    if path.is_none() {
+        ws.stats.failures += 1;
+    }
+    path
+}
+
+fn astar(
+    map: &TileMap,
+    start: (i32, i32),
+    goal: (i32, i32),
+    ws: &mut PathWorkspace,
+    extra_cost: impl Fn(i32, i32) -> u32,
 ) -> Option<Vec<(i32, i32)>> {
     if start == goal {
         return Some(Vec::new());
@@ -834,6 +1070,10 @@ pub fn find_path(
             continue;
         }
         ws.set_closed(ci);
+        ws.stats.expanded += 1;
+        if let Some(trace) = &mut ws.trace {
+            trace.push((cx, cy));
+        }
 
         expanded += 1;
         if expanded > MAX_EXPANDED {
@@ -864,12 +1104,12 @@ pub fn find_path(
             if is_diagonal && !map.diagonal_clear(cx, cy, nx, ny) {
                 continue;
             }
-            let step_cost = if is_diagonal {
-                DIAGONAL_COST
-            } else {
-                CARDINAL_COST
-            };
-            let new_g = current_g + step_cost;
+            let extra = extra_cost(nx, ny);
+            if extra == IMPASSABLE {
+                continue;
+            }
+            let step_cost = map.step_cost(nx, ny, is_diagonal);
+            let new_g = current_g + step_cost + extra;
 
             if new_g < ws.get_g(ni) {
                 ws.set_g(ni, new_g);
@@ -1087,6 +1327,7 @@ mod tests {
         assert!(Terrain::Garden.is_walkable());
         assert!(Terrain::Bridge.is_walkable());
         assert!(Terrain::Fixture.is_walkable());
+        assert!(Terrain::Rubble.is_walkable());
         assert!(!Terrain::Wall.is_walkable());
         assert!(!Terrain::Water.is_walkable());
     }
@@ -1116,6 +1357,8 @@ mod tests {
             Terrain::Water,
             Terrain::Bridge,
             Terrain::Fixture,
+            Terrain::Barricade,
+            Terrain::Rubble,
         ];
         for t in variants {
             let u = t.to_u8();
@@ -1123,7 +1366,7 @@ mod tests {
             assert_eq!(t, back, "roundtrip failed for {t:?} (u8={u})");
         }
         // Invalid values return None
-        assert!(Terrain::from_u8(9).is_none());
+        assert!(Terrain::from_u8(11).is_none());
         assert!(Terrain::from_u8(255).is_none());
     }
 
@@ -1425,6 +1668,126 @@ mod tests {
         assert_eq!(path.len(), 3); // 3 diagonal steps
     }
 
+    #[test]
+    fn test_find_path_reused_workspace() {
+        // Cells closed by an earlier search must not read as closed later.
+        let map = TileMap::new(20, 20);
+        let mut ws = PathWorkspace::new();
+        let first = find_path(&map, (2, 5), (12, 5), &mut ws).expect("first path");
+        let second = find_path(&map, (3, 5), (12, 5), &mut ws).expect("second path");
+        assert_eq!(first.len(), 10);
+        assert_eq!(second.len(), 9);
+    }
+
+    #[test]
+    fn test_find_path_weighted_detours_around_cost() {
+        // Straight line along y=5 is cheapest unweighted; a costly band
+        // across the middle of it pushes the path off the row.
+        let map = TileMap::new(20, 20);
+        let mut ws = PathWorkspace::new();
+        let path = find_path_weighted(&map, (2, 5), (12, 5), &mut ws, |x, y| {
+            if (6..=8).contains(&x) && (4..=6).contains(&y) {
+                1000
+            } else {
+                0
+            }
+        })
+        .expect("path exists");
+        assert_eq!(path.last(), Some(&(12, 5)));
+        assert!(
+            path.iter()
+                .all(|&(x, y)| !((6..=8).contains(&x) && (4..=6).contains(&y)))
+        );
+    }
+
+    #[test]
+    fn test_find_path_prefers_cheap_terrain() {
+        // A garden patch straddles the straight row; once gardens cost
+        // more than roads the path goes around it.
+        let mut map = TileMap::new(20, 20);
+        for y in 3..=7 {
+            for x in 6..=8 {
+                map.set_terrain(x, y, Terrain::Garden);
+            }
+        }
+        let crosses = |map: &TileMap, path: &[(i32, i32)]| {
+            path.iter()
+                .any(|&(x, y)| map.get_terrain(x as usize, y as usize) == Some(Terrain::Garden))
+        };
+        let mut ws = PathWorkspace::new();
+        let flat = find_path(&map, (2, 5), (12, 5), &mut ws).expect("path exists");
+        assert!(crosses(&map, &flat));
+
+        map.set_move_cost(Terrain::Garden, 300);
+        map.set_move_cost(Terrain::Door, 50);
+        assert_eq!(map.move_cost(Terrain::Door), BASE_MOVE_COST, "clamped");
+        let path = find_path(&map, (2, 5), (12, 5), &mut ws).expect("path exists");
+        assert_eq!(path.last(), Some(&(12, 5)));
+        assert!(!crosses(&map, &path));
+    }
+
+    #[test]
+    fn test_impassable_extra_cost_closes_tile() {
+        let mut map = TileMap::new(10, 10);
+        for y in 0..10 {
+            map.set_terrain(5, y, Terrain::Wall);
+        }
+        map.set_terrain(5, 5, Terrain::Door);
+        let mut ws = PathWorkspace::new();
+        assert!(find_path(&map, (2, 5), (8, 5), &mut ws).is_some());
+        let closed = |x, y| if (x, y) == (5, 5) { IMPASSABLE } else { 0 };
+        assert!(find_path_weighted(&map, (2, 5), (8, 5), &mut ws, closed).is_none());
+    }
+
+    #[test]
+    fn test_workspace_counts_and_traces_searches() {
+        let mut map = TileMap::new(10, 10);
+        for y in 0..10 {
+            map.set_terrain(5, y, Terrain::Wall);
+        }
+        let mut ws = PathWorkspace::new();
+        assert!(find_path(&map, (1, 1), (3, 3), &mut ws).is_some());
+        ws.start_trace();
+        assert!(find_path(&map, (2, 5), (8, 5), &mut ws).is_none());
+        let trace = ws.finish_trace();
+
+        let stats = ws.take_stats();
+        assert_eq!(stats.calls, 2);
+        assert_eq!(stats.failures, 1);
+        // The failed search floods the whole west half.
+        assert_eq!(trace.len(), 50);
+        assert!(stats.expanded as usize > trace.len());
+        assert_eq!(ws.take_stats(), PathStats::default());
+        assert!(ws.finish_trace().is_empty(), "trace ended");
+    }
+
+    #[test]
+    fn test_set_terrain_bumps_chunk_version() {
+        let mut map = TileMap::new(128, 64);
+        let stamps = map.chunk_versions(&[(10, 10), (70, 10)]);
+        assert_eq!(stamps.len(), 2);
+        map.set_terrain(10, 10, Terrain::Road);
+        assert!(map.versions_current(&stamps), "no-op write keeps version");
+        map.set_terrain(100, 20, Terrain::Wall);
+        assert!(!map.versions_current(&stamps));
+        assert!(map.versions_current(&stamps[..1]));
+    }
+
+    #[test]
+    fn test_capacity_narrows_in_alleys() {
+        let mut map = TileMap::new(10, 10);
+        assert_eq!(map.capacity_at(5, 5), UNLIMITED_CAPACITY);
+        map.set_capacity(Terrain::Road, 6);
+        map.set_capacity(Terrain::Door, 0);
+        assert_eq!(map.capacity(Terrain::Door), 1, "clamped");
+        assert_eq!(map.capacity_at(5, 5), 6);
+        // Walls east and west make (5, 5) a one-tile-wide alley.
+        map.set_terrain(4, 5, Terrain::Wall);
+        map.set_terrain(6, 5, Terrain::Wall);
+        assert_eq!(map.capacity_at(5, 5), 3);
+        assert_eq!(map.capacity_at(-1, 5), 0);
+    }
+
     // --- target_temperature ---
 
     #[test]
@@ -1550,4 +1913,13 @@ mod tests {
         // Other chunks should still be at equilibrium
         assert!(map.chunk_at(1, 0).at_equilibrium);
     }
+
+    #[test]
+    fn test_set_temperature_resets_equilibrium() {
+        let mut map = TileMap::new(130, 70);
+        map.initialize_temperatures();
+        map.set_temperature(70, 5, 800.0);
+        assert!(!map.chunk_at(1, 0).at_equilibrium);
+        assert!(map.chunk_at(0, 0).at_equilibrium);
+    }
 }
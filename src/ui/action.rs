@@ -18,6 +18,9 @@ pub enum UiAction {
 
     // Main menu (src/ui/main_menu.rs)
     MenuNewGame,
+    MenuQuartiers,
+    MenuYearTolerance,
+    MenuSample,
     MenuContinue,
     MenuLoad,
     MenuSettings,
@@ -4,6 +4,7 @@
 //! Registered with PanelManager as `"character:<entity_id>"`.
 
 use super::draw::TextSpan;
+use super::opinion_view::{OpinionViewInfo, collect_opinion_info};
 use super::theme::Theme;
 use super::widget::CrossAlign;
 use super::window::build_window_frame;
@@ -21,11 +22,16 @@ pub struct CharacterPanelInfo {
     pub position: (i32, i32),
     pub gait: Option<String>,
     pub action: Option<String>,
+    /// Strongest opinions this character holds, strongest first.
+    pub relations: Vec<OpinionViewInfo>,
 }
 
 /// Character panel width in pixels.
 const PANEL_WIDTH: f32 = 280.0;
 
+/// Most relations listed in the Relations tab.
+const MAX_RELATIONS: usize = 8;
+
 /// Build the full character panel (UI-400).
 ///
 /// Returns `(panel_root_id, close_button_id)`.
@@ -240,7 +246,7 @@ pub fn build_character_panel(
         },
     );
 
-    // === Relations tab content (tab child 2) -- placeholder ===
+    // === Relations tab content (tab child 2) ===
     let relations_col = tree.insert(
         tabs,
         Widget::Column {
@@ -249,16 +255,51 @@ pub fn build_character_panel(
         },
     );
     tree.set_sizing(relations_col, Sizing::Fixed(content_w), Sizing::Fit);
-    tree.insert(
-        relations_col,
-        Widget::Label {
-            text: "No relationships yet.".to_string(),
-            color: theme.disabled,
-            font_size: theme.font_body_size,
-            font_family: FontFamily::Serif,
-            wrap: true,
-        },
-    );
+    if info.relations.is_empty() {
+        tree.insert(
+            relations_col,
+            Widget::Label {
+                text: "No relationships yet.".to_string(),
+                color: theme.disabled,
+                font_size: theme.font_body_size,
+                font_family: FontFamily::Serif,
+                wrap: true,
+            },
+        );
+    }
+    for relation in &info.relations {
+        let opinion: i32 = relation.modifiers.iter().map(|m| m.value).sum();
+        let mut spans = vec![
+            TextSpan {
+                text: format!("{:+4} ", opinion),
+                color: if opinion >= 0 {
+                    theme.text_positive
+                } else {
+                    theme.text_negative
+                },
+                font_family: FontFamily::Mono,
+            },
+            TextSpan {
+                text: relation.target_name.clone(),
+                color: theme.text_medium,
+                font_family: FontFamily::Serif,
+            },
+        ];
+        if let Some(sentiment) = relation.sentiment {
+            spans.push(TextSpan {
+                text: format!(" ({})", sentiment.label()),
+                color: sentiment.color(theme),
+                font_family: FontFamily::Serif,
+            });
+        }
+        tree.insert(
+            relations_col,
+            Widget::RichText {
+                spans,
+                font_size: theme.font_data_size,
+            },
+        );
+    }
 
     // === Traits tab content (tab child 3) -- placeholder ===
     let traits_col = tree.insert(
@@ -325,6 +366,19 @@ pub fn collect_character_info(
         .action_states
         .get(&entity)
         .and_then(|a| a.current_action.as_ref().map(|id| format!("{:?}", id)));
+    let mut relations: Vec<OpinionViewInfo> = world
+        .mind
+        .relationships
+        .known_by(entity)
+        .into_iter()
+        .filter_map(|other| collect_opinion_info(entity, other, world))
+        .filter(|r| !r.modifiers.is_empty())
+        .collect();
+    relations.sort_by_key(|r| {
+        let opinion: i32 = r.modifiers.iter().map(|m| m.value).sum();
+        (std::cmp::Reverse(opinion.abs()), r.target_id)
+    });
+    relations.truncate(MAX_RELATIONS);
 
     Some(CharacterPanelInfo {
         entity_id: entity.0,
@@ -337,6 +391,7 @@ pub fn collect_character_info(
         position: pos,
         gait,
         action,
+        relations,
     })
 }
 
@@ -367,6 +422,7 @@ mod tests {
             position: (12, 34),
             gait: Some("Walk".to_string()),
             action: Some("Wander".to_string()),
+            relations: Vec::new(),
         }
     }
 
@@ -441,4 +497,38 @@ mod tests {
             panic!("Expected Button widget for close");
         }
     }
+
+    #[test]
+    fn relations_tab_lists_collected_opinions() {
+        use crate::components::{Position as Pos, Tick};
+        use crate::relationships::ModifierKind;
+        use crate::world::World;
+
+        let mut world = World::new_with_seed(42);
+        let a = world.spawn();
+        world.body.positions.insert(a, Pos { x: 1, y: 1 });
+        let b = world.spawn();
+        let c = world.spawn();
+        let rel = &mut world.mind.relationships;
+        rel.apply(a, b, ModifierKind::Neighbor, Tick(0));
+        rel.apply(a, c, ModifierKind::AttackedMe, Tick(0));
+
+        let info = collect_character_info(a, &world).expect("alive with position");
+        assert_eq!(info.relations.len(), 2);
+        assert_eq!(info.relations[0].target_id, c.0, "strongest opinion first");
+
+        let theme = Theme::default();
+        let mut tree = WidgetTree::new();
+        let (root, _close) = build_character_panel(&mut tree, &theme, &info);
+        let frame_col = tree.get(root).expect("root").children[0];
+        let content_id = tree.get(frame_col).expect("frame").children[2];
+        let tab_id = tree.get(content_id).expect("content").children[0];
+        let relations_id = tree.get(tab_id).expect("tabs").children[2];
+        let rows = &tree.get(relations_id).expect("relations").children;
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|&r| matches!(
+            tree.get(r).map(|n| &n.widget),
+            Some(Widget::RichText { .. })
+        )));
+    }
 }
@@ -1,9 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::time::Instant;
 
 use super::WidgetId;
 use super::action::PanelKind;
 use super::animation::Animator;
+use super::event_popup::NarrativeEvent;
 use super::input::UiState;
 use super::modal::{ModalPop, ModalStack};
 use super::panel_manager::PanelManager;
@@ -19,6 +20,9 @@ pub struct UiContext {
     pub animator: Animator,
     /// Modal dialog stack with dim layers and focus scoping.
     pub modals: ModalStack,
+    /// Narrative events awaiting the player, oldest first. The front one is
+    /// shown as a modal popup until a choice is made.
+    pub event_popups: VecDeque<NarrativeEvent>,
     /// Open panel tracking, draw order, animated close.
     pub panels: PanelManager,
     /// Scroll offsets for panels, keyed by PanelKind.
@@ -127,6 +131,7 @@ mod tests {
             input: UiState::new(),
             animator: Animator::new(),
             modals: ModalStack::new(),
+            event_popups: VecDeque::new(),
             panels: PanelManager::new(),
             scroll: HashMap::new(),
             sidebar: SidebarState::new(),
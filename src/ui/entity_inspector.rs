@@ -16,7 +16,11 @@ pub struct EntityInspectorInfo {
     pub hunger: Option<(f32, f32)>, // (current, max)
     pub fatigue: Option<f32>,
     pub combat: Option<(f32, f32, f32)>, // (atk, def, aggression)
+    pub weapon: Option<String>,          // "sabre"
+    pub wounds: Option<String>,          // "cut left arm, fractured right leg"
     pub action: Option<String>,          // "Idle", "Wandering", etc.
+    pub plan: Option<String>,            // "EatAtHome 2/4: PickUp"
+    pub directions: Option<String>,      // "Walk 120 m along Rue du Temple"
     pub gait: Option<String>,            // "Walk", "Run", etc.
     pub occupation: Option<String>,      // "activity [naics]"
     pub home_building: Option<u32>,      // BuildingId for display
@@ -51,12 +55,43 @@ pub fn collect_inspector_info(
         .get(&entity)
         .map(|c| (c.attack, c.defense, c.aggression));
 
+    let weapon = world
+        .body
+        .weapons
+        .get(&entity)
+        .map(|w| w.label().to_string());
+    let wounds = world.body.wounds.get(&entity).map(|w| {
+        w.0.iter()
+            .map(|w| format!("{} {}", w.kind.label(), w.part.label()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    });
+
     let action = world
         .mind
         .action_states
         .get(&entity)
         .and_then(|a| a.current_action.as_ref().map(|id| format!("{:?}", id)));
 
+    let plan = world.mind.plans.get(&entity).map(|p| {
+        let step = match p.current_step() {
+            Some(crate::components::PlanStep::MoveTo { x, y }) => format!("MoveTo ({}, {})", x, y),
+            Some(crate::components::PlanStep::PickUp { .. }) => "PickUp".to_string(),
+            Some(crate::components::PlanStep::Consume { .. }) => "Consume".to_string(),
+            None => "done".to_string(),
+        };
+        format!(
+            "{:?} {}/{}: {}",
+            p.task,
+            (p.current + 1).min(p.steps.len()),
+            p.steps.len(),
+            step
+        )
+    });
+
+    let directions =
+        goal_of(world, entity).and_then(|goal| next_directions(world, (pos.x, pos.y), goal));
+
     let gait = world
         .body
         .current_gaits
@@ -81,7 +116,11 @@ pub fn collect_inspector_info(
         hunger,
         fatigue,
         combat,
+        weapon,
+        wounds,
         action,
+        plan,
+        directions,
         gait,
         occupation,
         home_building,
@@ -89,6 +128,38 @@ pub fn collect_inspector_info(
     })
 }
 
+/// Where `entity` is heading: its plan's current `MoveTo`, else its
+/// wander target.
+fn goal_of(world: &crate::world::World, entity: crate::components::Entity) -> Option<(i32, i32)> {
+    let plan_step = world.mind.plans.get(&entity).and_then(|p| p.current_step());
+    if let Some(crate::components::PlanStep::MoveTo { x, y }) = plan_step {
+        return Some((x, y));
+    }
+    world
+        .mind
+        .wander_targets
+        .get(&entity)
+        .map(|t| (t.goal_x, t.goal_y))
+}
+
+/// First leg of the street route from `from` to `goal`, with the number of
+/// legs after it. `None` when both are nearest the same street node or no
+/// open route joins them.
+fn next_directions(
+    world: &crate::world::World,
+    from: (i32, i32),
+    goal: (i32, i32),
+) -> Option<String> {
+    let graph = &world.gis.street_graph;
+    let route = graph.route(graph.nearest_node(from)?, graph.nearest_node(goal)?)?;
+    let dirs = graph.directions(&route, &world.gis.streets);
+    let first = dirs.first()?;
+    Some(match dirs.len() - 1 {
+        0 => first.clone(),
+        n => format!("{first}, then {n} more"),
+    })
+}
+
 /// Inspector panel width in pixels.
 const INSPECTOR_WIDTH: f32 = 220.0;
 
@@ -331,6 +402,54 @@ pub fn build_entity_inspector(
         y += data_h + gap;
     }
 
+    // Weapon
+    if let Some(ref weapon) = info.weapon {
+        let weapon_label = tree.insert(
+            panel,
+            Widget::RichText {
+                spans: vec![
+                    TextSpan {
+                        text: "Armed ".to_string(),
+                        color: theme.disabled,
+                        font_family: FontFamily::Mono,
+                    },
+                    TextSpan {
+                        text: weapon.clone(),
+                        color: theme.text_light,
+                        font_family: FontFamily::Mono,
+                    },
+                ],
+                font_size: theme.font_data_size,
+            },
+        );
+        tree.set_position(weapon_label, Position::Fixed { x: 0.0, y });
+        y += data_h + gap;
+    }
+
+    // Wounds
+    if let Some(ref wounds) = info.wounds {
+        let wounds_label = tree.insert(
+            panel,
+            Widget::RichText {
+                spans: vec![
+                    TextSpan {
+                        text: "Wounds ".to_string(),
+                        color: theme.disabled,
+                        font_family: FontFamily::Mono,
+                    },
+                    TextSpan {
+                        text: wounds.clone(),
+                        color: theme.danger,
+                        font_family: FontFamily::Mono,
+                    },
+                ],
+                font_size: theme.font_data_size,
+            },
+        );
+        tree.set_position(wounds_label, Position::Fixed { x: 0.0, y });
+        y += data_h + gap;
+    }
+
     // Action
     if let Some(ref action) = info.action {
         let act = tree.insert(
@@ -355,6 +474,54 @@ pub fn build_entity_inspector(
         y += body_h + gap;
     }
 
+    // Plan (HTN step progress)
+    if let Some(ref plan) = info.plan {
+        let plan_label = tree.insert(
+            panel,
+            Widget::RichText {
+                spans: vec![
+                    TextSpan {
+                        text: "Plan ".to_string(),
+                        color: theme.gold,
+                        font_family: FontFamily::Mono,
+                    },
+                    TextSpan {
+                        text: plan.clone(),
+                        color: theme.text_light,
+                        font_family: FontFamily::Mono,
+                    },
+                ],
+                font_size: theme.font_body_size,
+            },
+        );
+        tree.set_position(plan_label, Position::Fixed { x: 0.0, y });
+        y += body_h + gap;
+    }
+
+    // Directions to the current goal along the street graph
+    if let Some(ref directions) = info.directions {
+        let dir_label = tree.insert(
+            panel,
+            Widget::RichText {
+                spans: vec![
+                    TextSpan {
+                        text: "Route ".to_string(),
+                        color: theme.gold,
+                        font_family: FontFamily::Mono,
+                    },
+                    TextSpan {
+                        text: directions.clone(),
+                        color: theme.text_light,
+                        font_family: FontFamily::Mono,
+                    },
+                ],
+                font_size: theme.font_data_size,
+            },
+        );
+        tree.set_position(dir_label, Position::Fixed { x: 0.0, y });
+        y += data_h + gap;
+    }
+
     // Gait
     if let Some(ref gait) = info.gait {
         let gait_label = tree.insert(
@@ -492,9 +659,80 @@ mod tests {
         assert!(info.fatigue.is_none());
         assert!(info.combat.is_none());
         assert!(info.action.is_none());
+        assert!(info.plan.is_none());
+        assert!(info.directions.is_none());
         assert!(info.gait.is_none());
     }
 
+    #[test]
+    fn collect_inspector_info_directions_to_goal() {
+        use crate::street_graph::StreetGraph;
+        use crate::tile_map::{Terrain, TileMap};
+        let mut world = crate::world::World::new_with_seed(42);
+        // A 3-wide street along row 20 turning north up column 20.
+        world.tiles = TileMap::new(41, 41);
+        for y in 0..41 {
+            for x in 0..41 {
+                let street =
+                    ((19..=21).contains(&y) && x <= 21) || ((19..=21).contains(&x) && y <= 21);
+                if !street {
+                    world.tiles.set_terrain(x, y, Terrain::Wall);
+                }
+            }
+        }
+        world.gis.street_graph =
+            StreetGraph::build(&world.tiles, &world.gis.buildings, &world.gis.streets);
+        let e = world.spawn();
+        world
+            .body
+            .positions
+            .insert(e, crate::components::Position { x: 0, y: 20 });
+        world.mind.wander_targets.insert(
+            e,
+            crate::components::WanderTarget {
+                goal_x: 20,
+                goal_y: 0,
+            },
+        );
+
+        let info = collect_inspector_info(e, &world).expect("alive with position");
+        let directions = info.directions.expect("route to the goal");
+        assert!(directions.starts_with("Walk "), "{directions}");
+
+        // A barricade across the street closes the route.
+        for x in 19..=21 {
+            world.set_terrain(x, 10, Terrain::Barricade);
+        }
+        let info = collect_inspector_info(e, &world).expect("alive with position");
+        assert!(info.directions.is_none());
+    }
+
+    #[test]
+    fn collect_inspector_info_plan_progress() {
+        use crate::components::{ActionId, Plan, PlanStep, TaskId};
+        let mut world = crate::world::World::new_with_seed(42);
+        let e = spawn_full_entity(&mut world);
+        let food = world.spawn();
+        world.mind.plans.insert(
+            e,
+            Plan {
+                task: TaskId::EatAtHome,
+                action: ActionId::Eat,
+                steps: vec![
+                    PlanStep::MoveTo { x: 3, y: 4 },
+                    PlanStep::PickUp { item: food },
+                    PlanStep::MoveTo { x: 9, y: 9 },
+                    PlanStep::Consume { food },
+                ],
+                current: 1,
+                replans: 0,
+            },
+        );
+
+        let info = collect_inspector_info(e, &world).expect("alive entity should return Some");
+        assert_eq!(info.plan.as_deref(), Some("EatAtHome 2/4: PickUp"));
+    }
+
     #[test]
     fn build_inspector_creates_panel() {
         let theme = Theme::default();
@@ -507,7 +745,11 @@ mod tests {
             hunger: Some((30.0, 100.0)),
             fatigue: None,
             combat: None,
+            weapon: None,
+            wounds: None,
             action: None,
+            plan: None,
+            directions: None,
             gait: None,
             occupation: None,
             home_building: None,
@@ -531,7 +773,11 @@ mod tests {
             hunger: None,
             fatigue: None,
             combat: None,
+            weapon: None,
+            wounds: None,
             action: None,
+            plan: None,
+            directions: None,
             gait: None,
             occupation: None,
             home_building: None,
@@ -563,7 +809,11 @@ mod tests {
             hunger: None,
             fatigue: None,
             combat: None,
+            weapon: None,
+            wounds: None,
             action: None,
+            plan: None,
+            directions: None,
             gait: None,
             occupation: None,
             home_building: None,
@@ -596,7 +846,11 @@ mod tests {
             hunger: None,
             fatigue: None,
             combat: None,
+            weapon: None,
+            wounds: None,
             action: None,
+            plan: None,
+            directions: None,
             gait: None,
             occupation: None,
             home_building: None,
@@ -630,7 +884,11 @@ mod tests {
             hunger: Some((30.0, 100.0)),
             fatigue: Some(5.0),
             combat: Some((12.0, 8.0, 0.7)),
+            weapon: None,
+            wounds: None,
             action: Some("Idle".into()),
+            plan: None,
+            directions: None,
             gait: Some("Walk".into()),
             occupation: None,
             home_building: None,
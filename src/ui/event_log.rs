@@ -23,6 +23,36 @@ pub enum EventLogEntry {
         defender: String,
         damage: f32,
     },
+    /// `wound` reads like "cut left arm".
+    Wounded {
+        name: String,
+        wound: String,
+    },
+    WoundHealed {
+        name: String,
+        wound: String,
+    },
+    /// `loot` is what the thief got away with, if anything.
+    Theft {
+        thief: String,
+        victim: String,
+        success: bool,
+        loot: Option<String>,
+    },
+    Arrested {
+        officer: String,
+        offender: String,
+    },
+    /// `place` is the building's first address or name.
+    FireStarted {
+        place: String,
+        cause: String,
+    },
+    FireOut {
+        place: String,
+        destroyed: bool,
+        displaced: u32,
+    },
 }
 
 /// Maximum significant events kept in the ScrollList.
@@ -144,6 +174,125 @@ pub fn build_event_log(
                     font_family: FontFamily::Mono,
                 },
             ],
+            EventLogEntry::Wounded { name, wound } => vec![
+                TextSpan {
+                    text: name.clone(),
+                    color: theme.text_light,
+                    font_family: FontFamily::Mono,
+                },
+                TextSpan {
+                    text: " wounded: ".to_string(),
+                    color: theme.disabled,
+                    font_family: FontFamily::Mono,
+                },
+                TextSpan {
+                    text: wound.clone(),
+                    color: theme.danger,
+                    font_family: FontFamily::Mono,
+                },
+            ],
+            EventLogEntry::WoundHealed { name, wound } => vec![
+                TextSpan {
+                    text: name.clone(),
+                    color: theme.text_light,
+                    font_family: FontFamily::Mono,
+                },
+                TextSpan {
+                    text: " healed: ".to_string(),
+                    color: theme.disabled,
+                    font_family: FontFamily::Mono,
+                },
+                TextSpan {
+                    text: wound.clone(),
+                    color: theme.gold,
+                    font_family: FontFamily::Mono,
+                },
+            ],
+            EventLogEntry::Theft {
+                thief,
+                victim,
+                success,
+                loot,
+            } => {
+                let mut spans = vec![
+                    TextSpan {
+                        text: thief.clone(),
+                        color: theme.text_light,
+                        font_family: FontFamily::Mono,
+                    },
+                    TextSpan {
+                        text: if *success { " robs " } else { " fails to rob " }.to_string(),
+                        color: theme.danger,
+                        font_family: FontFamily::Mono,
+                    },
+                    TextSpan {
+                        text: victim.clone(),
+                        color: theme.text_light,
+                        font_family: FontFamily::Mono,
+                    },
+                ];
+                if let Some(loot) = loot {
+                    spans.push(TextSpan {
+                        text: format!(" of {loot}"),
+                        color: theme.danger,
+                        font_family: FontFamily::Mono,
+                    });
+                }
+                spans
+            }
+            EventLogEntry::Arrested { officer, offender } => vec![
+                TextSpan {
+                    text: officer.clone(),
+                    color: theme.text_light,
+                    font_family: FontFamily::Mono,
+                },
+                TextSpan {
+                    text: " arrests ".to_string(),
+                    color: theme.disabled,
+                    font_family: FontFamily::Mono,
+                },
+                TextSpan {
+                    text: offender.clone(),
+                    color: theme.gold,
+                    font_family: FontFamily::Mono,
+                },
+            ],
+            EventLogEntry::FireStarted { place, cause } => vec![
+                TextSpan {
+                    text: format!("Fire ({cause}) at "),
+                    color: theme.danger,
+                    font_family: FontFamily::Mono,
+                },
+                TextSpan {
+                    text: place.clone(),
+                    color: theme.text_light,
+                    font_family: FontFamily::Mono,
+                },
+            ],
+            EventLogEntry::FireOut {
+                place,
+                destroyed,
+                displaced,
+            } => vec![
+                TextSpan {
+                    text: place.clone(),
+                    color: theme.text_light,
+                    font_family: FontFamily::Mono,
+                },
+                TextSpan {
+                    text: if *destroyed {
+                        format!(" burnt down, {displaced} displaced")
+                    } else {
+                        " fire put out".to_string()
+                    },
+                    color: if *destroyed {
+                        theme.danger
+                    } else {
+                        theme.disabled
+                    },
+                    font_family: FontFamily::Mono,
+                },
+            ],
         };
         tree.insert(
             list,
@@ -159,11 +308,14 @@ pub fn build_event_log(
 
 /// Collect significant events from World into `EventLogEntry` structs.
 ///
-/// Filters to Spawned/Died/Ate/Attacked (skips Moved, HungerChanged).
+/// Filters to Spawned/Died/Ate/Attacked/Wounded/WoundHealed/Theft/Arrested
+/// and fires, placed by `buildings` (skips Moved, HungerChanged, and riots,
+/// which get a popup).
 /// Returns up to `EVENT_LOG_MAX_ENTRIES` entries, newest last.
 pub fn collect_event_entries(
     events: &crate::events::EventLog,
     names: &std::collections::HashMap<crate::components::Entity, crate::components::Name>,
+    buildings: &crate::registry::BuildingRegistry,
 ) -> Vec<EventLogEntry> {
     use crate::events::Event;
 
@@ -173,6 +325,13 @@ pub fn collect_event_entries(
             .map(|n| n.value.clone())
             .unwrap_or_else(|| format!("E{}", e.0))
     };
+    let place = |b: &crate::registry::BuildingId| -> String {
+        let data = buildings.get(*b);
+        data.and_then(|d| d.addresses.first())
+            .map(|a| format!("{} {}", a.house_number, a.street_name))
+            .or_else(|| data.and_then(|d| d.nom_bati.clone()))
+            .unwrap_or_else(|| format!("building {}", b.0))
+    };
 
     let raw = events.recent(EVENT_LOG_MAX_ENTRIES * 10);
     let mut entries = Vec::new();
@@ -199,7 +358,56 @@ pub fn collect_event_entries(
                 defender: resolve(defender),
                 damage: *damage,
             },
-            Event::Moved { .. } | Event::HungerChanged { .. } => continue,
+            Event::Wounded {
+                entity, part, kind, ..
+            } => EventLogEntry::Wounded {
+                name: resolve(entity),
+                wound: format!("{} {}", kind.label(), part.label()),
+            },
+            Event::WoundHealed {
+                entity, part, kind, ..
+            } => EventLogEntry::WoundHealed {
+                name: resolve(entity),
+                wound: format!("{} {}", kind.label(), part.label()),
+            },
+            Event::Theft {
+                thief,
+                victim,
+                success,
+                loot,
+                ..
+            } => EventLogEntry::Theft {
+                thief: resolve(thief),
+                victim: resolve(victim),
+                success: *success,
+                loot: loot.map(|l| l.label().to_string()),
+            },
+            Event::Arrested {
+                officer, offender, ..
+            } => EventLogEntry::Arrested {
+                officer: resolve(officer),
+                offender: resolve(offender),
+            },
+            Event::FireStarted {
+                building, cause, ..
+            } => EventLogEntry::FireStarted {
+                place: place(building),
+                cause: cause.label().to_string(),
+            },
+            Event::FireOut {
+                building,
+                destroyed,
+                displaced,
+                ..
+            } => EventLogEntry::FireOut {
+                place: place(building),
+                destroyed: *destroyed,
+                displaced: *displaced,
+            },
+            Event::Moved { .. }
+            | Event::HungerChanged { .. }
+            | Event::RiotBegan { .. }
+            | Event::RiotDispersed { .. } => continue,
         };
         entries.push(entry);
         if entries.len() >= EVENT_LOG_MAX_ENTRIES {
@@ -607,10 +607,8 @@ impl UiState {
             let node = tree.get(id)?;
             match node.widget {
                 Widget::ScrollView { .. } => return Some(id),
-                Widget::ScrollList { .. } => {
-                    if found_scroll_list.is_none() {
-                        found_scroll_list = Some(id);
-                    }
+                Widget::ScrollList { .. } if found_scroll_list.is_none() => {
+                    found_scroll_list = Some(id);
                 }
                 _ => {}
             }
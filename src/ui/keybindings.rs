@@ -60,6 +60,18 @@ pub enum Action {
     ScaleUp,
     /// Decrease UI scale (UI-504).
     ScaleDown,
+    /// Show the storey above on the map.
+    StoreyUp,
+    /// Show the storey below on the map.
+    StoreyDown,
+    /// Toggle the pathfinding debug overlay for the selected entity.
+    TogglePathDebug,
+    /// Switch to the previous SoDUCo directory year.
+    YearEarlier,
+    /// Switch to the next SoDUCo directory year.
+    YearLater,
+    /// Show or hide the main menu.
+    ToggleMainMenu,
 }
 
 /// Configurable keyboard shortcut map.
@@ -124,6 +136,20 @@ impl KeyBindings {
             Action::ScaleDown,
         );
 
+        // PgUp/PgDn switch the displayed storey.
+        map.insert(KeyCombo::plain(KeyCode::PageUp), Action::StoreyUp);
+        map.insert(KeyCombo::plain(KeyCode::PageDown), Action::StoreyDown);
+
+        // F3 = pathfinding debug overlay.
+        map.insert(KeyCombo::plain(KeyCode::F3), Action::TogglePathDebug);
+
+        // [ / ] step through the directory years.
+        map.insert(KeyCombo::plain(KeyCode::BracketLeft), Action::YearEarlier);
+        map.insert(KeyCombo::plain(KeyCode::BracketRight), Action::YearLater);
+
+        // F10 = main menu (new game options).
+        map.insert(KeyCombo::plain(KeyCode::F10), Action::ToggleMainMenu);
+
         let reverse = Self::build_reverse(&map);
         Self { map, reverse }
     }
@@ -229,6 +255,8 @@ fn key_name(key: KeyCode) -> &'static str {
         KeyCode::PageDown => "PgDn",
         KeyCode::Equal => "=",
         KeyCode::Minus => "-",
+        KeyCode::BracketLeft => "[",
+        KeyCode::BracketRight => "]",
         _ => "?",
     }
 }
@@ -349,4 +377,45 @@ mod tests {
         assert_eq!(kb.label_for(Action::ScaleUp).as_deref(), Some("Ctrl+="));
         assert_eq!(kb.label_for(Action::ScaleDown).as_deref(), Some("Ctrl+-"));
     }
+
+    #[test]
+    fn page_keys_switch_storey() {
+        let kb = KeyBindings::defaults();
+        assert_eq!(
+            kb.lookup(KeyCombo::plain(KeyCode::PageUp)),
+            Some(Action::StoreyUp)
+        );
+        assert_eq!(
+            kb.lookup(KeyCombo::plain(KeyCode::PageDown)),
+            Some(Action::StoreyDown)
+        );
+    }
+
+    #[test]
+    fn f3_toggles_path_debug() {
+        let kb = KeyBindings::defaults();
+        assert_eq!(
+            kb.lookup(KeyCombo::plain(KeyCode::F3)),
+            Some(Action::TogglePathDebug)
+        );
+    }
+
+    #[test]
+    fn brackets_step_directory_year() {
+        let kb = KeyBindings::defaults();
+        assert_eq!(
+            kb.lookup(KeyCombo::plain(KeyCode::BracketLeft)),
+            Some(Action::YearEarlier)
+        );
+        assert_eq!(kb.label_for(Action::YearLater).as_deref(), Some("]"));
+    }
+
+    #[test]
+    fn f10_toggles_main_menu() {
+        let kb = KeyBindings::defaults();
+        assert_eq!(
+            kb.lookup(KeyCombo::plain(KeyCode::F10)),
+            Some(Action::ToggleMainMenu)
+        );
+    }
 }
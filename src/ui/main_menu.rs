@@ -20,12 +20,19 @@ pub struct MainMenuInfo {
     pub has_saves: bool,
     pub screen_width: f32,
     pub screen_height: f32,
+    /// New-game options: quartier label, SoDUCo year tolerance and share
+    /// of residents spawned (see `loading_gis::SpawnOptions`).
+    pub quartiers: String,
+    pub year_tolerance: u16,
+    pub sample_ratio: f64,
 }
 
 /// Build the main menu (UI-415).
 ///
 /// Returns the panel root ID. Buttons dispatch `UiAction` variants:
 /// `MenuNewGame`, `MenuContinue`, `MenuLoad`, `MenuSettings`, `MenuQuit`.
+/// A second panel beside the menu holds the new-game options; each button
+/// steps one option (`MenuQuartiers`, `MenuYearTolerance`, `MenuSample`).
 pub fn build_main_menu(tree: &mut WidgetTree, theme: &Theme, info: &MainMenuInfo) -> WidgetId {
     // Full-screen background
     let bg = tree.insert_root(Widget::Panel {
@@ -130,6 +137,51 @@ pub fn build_main_menu(tree: &mut WidgetTree, theme: &Theme, info: &MainMenuInfo
     let quit = make_menu_button(tree, theme, col, "Quit", button_w);
     tree.set_on_click(quit, super::UiAction::MenuQuit);
 
+    // New-game options, right of the menu.
+    let options = tree.insert(
+        bg,
+        Widget::Column {
+            gap: theme.label_gap * 3.0,
+            align: CrossAlign::Center,
+        },
+    );
+    tree.set_position(
+        options,
+        Position::Fixed {
+            x: menu_x + menu_w,
+            y: menu_y,
+        },
+    );
+    tree.set_sizing(options, Sizing::Fixed(menu_w), Sizing::Fit);
+    tree.insert(
+        options,
+        Widget::Label {
+            text: "New game".to_string(),
+            color: theme.gold,
+            font_size: theme.font_header_size,
+            font_family: FontFamily::Serif,
+            wrap: false,
+        },
+    );
+    let option_buttons = [
+        (
+            format!("Quartiers: {}", info.quartiers),
+            super::UiAction::MenuQuartiers,
+        ),
+        (
+            format!("Year tolerance: {}", info.year_tolerance),
+            super::UiAction::MenuYearTolerance,
+        ),
+        (
+            format!("Residents: {:.0}%", info.sample_ratio * 100.0),
+            super::UiAction::MenuSample,
+        ),
+    ];
+    for (text, action) in option_buttons {
+        let btn = make_menu_button(tree, theme, options, &text, button_w);
+        tree.set_on_click(btn, action);
+    }
+
     bg
 }
 
@@ -177,6 +229,9 @@ mod tests {
             has_saves: true,
             screen_width: 800.0,
             screen_height: 600.0,
+            quartiers: "Arcis".to_string(),
+            year_tolerance: 20,
+            sample_ratio: 1.0,
         };
         let root = build_main_menu(&mut tree, &theme, &info);
         let bg_node = tree.get(root).unwrap();
@@ -206,6 +261,9 @@ mod tests {
             has_saves: false,
             screen_width: 800.0,
             screen_height: 600.0,
+            quartiers: "Arcis".to_string(),
+            year_tolerance: 20,
+            sample_ratio: 1.0,
         };
         let root = build_main_menu(&mut tree, &theme, &info);
         let bg_node = tree.get(root).unwrap();
@@ -228,6 +286,9 @@ mod tests {
             has_saves: false,
             screen_width: 800.0,
             screen_height: 600.0,
+            quartiers: "Arcis".to_string(),
+            year_tolerance: 20,
+            sample_ratio: 1.0,
         };
         let root = build_main_menu(&mut tree, &theme, &info);
         let bg_node = tree.get(root).unwrap();
@@ -244,6 +305,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn new_game_options_step_each_option() {
+        let theme = Theme::default();
+        let mut tree = WidgetTree::new();
+        let info = MainMenuInfo {
+            has_saves: false,
+            screen_width: 800.0,
+            screen_height: 600.0,
+            quartiers: "All quartiers".to_string(),
+            year_tolerance: 10,
+            sample_ratio: 0.25,
+        };
+        let root = build_main_menu(&mut tree, &theme, &info);
+        let options_id = tree.get(root).expect("menu root").children[1];
+        let buttons: Vec<_> = tree
+            .get(options_id)
+            .expect("options panel")
+            .children
+            .iter()
+            .filter_map(|&id| tree.get(id))
+            .filter_map(|n| match &n.widget {
+                Widget::Button { text, .. } => Some((text.clone(), n.on_click.clone())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(buttons.len(), 3);
+        assert_eq!(buttons[0].0, "Quartiers: All quartiers");
+        assert!(matches!(
+            buttons[0].1,
+            Some(crate::ui::UiAction::MenuQuartiers)
+        ));
+        assert_eq!(buttons[1].0, "Year tolerance: 10");
+        assert!(matches!(
+            buttons[1].1,
+            Some(crate::ui::UiAction::MenuYearTolerance)
+        ));
+        assert_eq!(buttons[2].0, "Residents: 25%");
+        assert!(matches!(
+            buttons[2].1,
+            Some(crate::ui::UiAction::MenuSample)
+        ));
+    }
+
     #[test]
     fn app_state_variants() {
         assert_ne!(AppState::MainMenu, AppState::Loading);
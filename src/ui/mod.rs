@@ -62,7 +62,7 @@ pub use animation::{Anim, Animator, Easing};
 // Infrastructure
 pub use context::{DismissResult, SidebarState, UiContext};
 pub use keybindings::{Action, KeyBindings, KeyCombo, ModifierFlags};
-pub use modal::ModalStack;
+pub use modal::{ModalOptions, ModalStack};
 pub use panel_manager::PanelManager;
 pub use theme::Theme;
 
@@ -89,7 +89,9 @@ pub use loading_screen::{LoadingScreenInfo, LoadingStage, build_loading_screen};
 pub use main_menu::{AppState, MainMenuInfo, build_main_menu};
 pub use map_mode::{MapMode, MapModeInfo, build_map_mode_selector};
 pub use notification::{NotificationManager, NotificationPriority};
-pub use opinion_view::{OpinionModifier, OpinionViewInfo, Sentiment, build_opinion_view};
+pub use opinion_view::{
+    OpinionModifier, OpinionViewInfo, Sentiment, build_opinion_view, collect_opinion_info,
+};
 pub use outliner::{
     ActiveEvent, AlertEntry, AlertPriority, OutlinerInfo, PinnedCharacter, build_outliner,
 };
@@ -107,6 +107,28 @@ impl ModalStack {
         })
     }
 
+    /// Re-insert every modal into a freshly rebuilt `tree` (DD-5), bottom
+    /// first, keeping its callbacks. `build` inserts the content root for
+    /// the modal at each stack index.
+    pub fn rebuild(
+        &mut self,
+        tree: &mut WidgetTree,
+        mut build: impl FnMut(&mut WidgetTree, usize) -> WidgetId,
+    ) {
+        let entries = std::mem::take(&mut self.modals);
+        for (i, entry) in entries.into_iter().enumerate() {
+            let content = build(tree, i);
+            self.push(
+                tree,
+                content,
+                ModalOptions {
+                    on_dismiss: entry.on_dismiss,
+                    on_confirm: entry.on_confirm,
+                },
+            );
+        }
+    }
+
     /// Get the confirm action of the topmost modal (for Enter key dispatch).
     pub fn confirm_action(&self) -> Option<&UiAction> {
         self.modals.last().and_then(|e| e.on_confirm.as_ref())
@@ -165,6 +187,43 @@ mod tests {
         assert!(tree.get(modal_b).is_none(), "top modal removed");
     }
 
+    #[test]
+    fn rebuild_reinserts_into_new_tree_with_callbacks() {
+        let mut tree = WidgetTree::new();
+        let mut stack = ModalStack::new();
+        let modal = make_modal_panel(&mut tree);
+        stack.push(
+            &mut tree,
+            modal,
+            ModalOptions {
+                on_dismiss: None,
+                on_confirm: Some(UiAction::DialogAccept),
+            },
+        );
+
+        // Next frame: a fresh tree.
+        let mut tree = WidgetTree::new();
+        let mut built = Vec::new();
+        stack.rebuild(&mut tree, |tree, i| {
+            built.push(i);
+            make_modal_panel(tree)
+        });
+        assert_eq!(built, vec![0]);
+        assert_eq!(stack.len(), 1);
+        assert!(matches!(
+            stack.confirm_action(),
+            Some(UiAction::DialogAccept)
+        ));
+        let roots = tree.roots();
+        assert_eq!(roots.len(), 2, "dim + modal");
+        assert!(roots.iter().all(|&r| tree.z_tier(r) == Some(ZTier::Modal)));
+
+        // Popping removes them from the new tree.
+        let popped = stack.pop(&mut tree).expect("modal");
+        assert!(tree.get(popped.content).is_none());
+        assert!(stack.is_empty());
+    }
+
     #[test]
     fn modal_roots_at_modal_z_tier() {
         let mut tree = WidgetTree::new();
@@ -30,6 +30,12 @@ pub struct UiPerfMetrics {
     pub text_cmds: usize,
     /// Number of sprite draw commands.
     pub sprite_cmds: usize,
+    /// A* searches run by the simulation this frame.
+    pub astar_calls: u32,
+    /// A* searches that found no path this frame.
+    pub astar_failures: u32,
+    /// Nodes expanded by A* this frame.
+    pub astar_expanded: u32,
 }
 
 // ---------------------------------------------------------------------------
@@ -120,7 +120,7 @@ impl NotificationManager {
 
         // Sort: Critical first, then Important, then Info.
         self.notifications
-            .sort_by(|a, b| b.priority.cmp(&a.priority));
+            .sort_by_key(|n| std::cmp::Reverse(n.priority));
 
         let visible_count = self.notifications.len().min(self.max_visible);
         let notif_w = 250.0;
@@ -1,7 +1,7 @@
 //! Relationship/opinion view (UI-406).
 //!
 //! Sub-panel within the character panel's Relations tab.
-//! Stubbed with mock data until SIM-008 (relationships) is implemented.
+//! Filled from `world.mind.relationships` by `collect_opinion_info`.
 
 use super::theme::Theme;
 use super::widget::CrossAlign;
@@ -54,6 +54,71 @@ impl Sentiment {
 /// Opinion panel width.
 const OPINION_WIDTH: f32 = 250.0;
 
+/// Collect `observer`'s opinion of `target` from the world.
+/// Returns None if either entity is dead.
+pub fn collect_opinion_info(
+    observer: crate::components::Entity,
+    target: crate::components::Entity,
+    world: &crate::world::World,
+) -> Option<OpinionViewInfo> {
+    use crate::faction::{self, Stance};
+    use crate::relationships::{FRIEND_OPINION, RIVAL_OPINION};
+
+    if !world.alive.contains(&observer) || !world.alive.contains(&target) {
+        return None;
+    }
+    let tick = world.tick;
+    let target_name = world
+        .body
+        .names
+        .get(&target)
+        .map(|n| n.value.clone())
+        .unwrap_or_else(|| format!("Entity #{}", target.0));
+    let modifiers = world
+        .mind
+        .relationships
+        .modifiers(observer, target, tick)
+        .into_iter()
+        .map(|m| OpinionModifier {
+            label: m.kind.label().to_string(),
+            value: m.value_at(tick),
+            icon: None,
+            duration: Some(format_remaining(m.expires.0.saturating_sub(tick.0))),
+        })
+        .collect();
+
+    let opinion = world.mind.relationships.opinion(observer, target, tick);
+    let sentiment = if opinion <= RIVAL_OPINION {
+        Some(Sentiment::Rival)
+    } else if opinion >= FRIEND_OPINION {
+        Some(Sentiment::Friend)
+    } else if world.mind.faction_members.contains_key(&observer)
+        && faction::stance_between(world, observer, target) == Stance::Allied
+    {
+        Some(Sentiment::Ally)
+    } else {
+        None
+    };
+
+    Some(OpinionViewInfo {
+        target_name,
+        target_id: target.0,
+        modifiers,
+        sentiment,
+    })
+}
+
+/// Remaining game time (1 tick = 1 minute) as "3d remaining" / "5h remaining".
+fn format_remaining(ticks: u64) -> String {
+    if ticks >= 1440 {
+        format!("{}d remaining", ticks / 1440)
+    } else if ticks >= 60 {
+        format!("{}h remaining", ticks / 60)
+    } else {
+        format!("{}m remaining", ticks)
+    }
+}
+
 /// Build the opinion view sub-panel (UI-406).
 ///
 /// Returns the panel root ID. Designed to be inserted into the character panel's Relations tab.
@@ -370,4 +435,35 @@ mod tests {
         }
         assert!(found_modifiers, "Should have a modifiers section");
     }
+
+    #[test]
+    fn collect_opinion_info_from_relationships() {
+        use crate::components::{Name, Tick};
+        use crate::relationships::ModifierKind;
+        use crate::world::World;
+
+        let mut world = World::new_with_seed(42);
+        let a = world.spawn();
+        let b = world.spawn();
+        world.body.names.insert(
+            b,
+            Name {
+                value: "Jean Valjean".to_string(),
+            },
+        );
+        let rel = &mut world.mind.relationships;
+        rel.apply(a, b, ModifierKind::Neighbor, Tick(0));
+        rel.apply(a, b, ModifierKind::Coworker, Tick(0));
+
+        let info = collect_opinion_info(a, b, &world).expect("both alive");
+        assert_eq!(info.target_name, "Jean Valjean");
+        assert_eq!(info.target_id, b.0);
+        assert_eq!(info.modifiers.len(), 2);
+        assert_eq!(info.modifiers[0].label, "Coworker");
+        assert_eq!(info.modifiers[0].duration.as_deref(), Some("7d remaining"));
+        assert_eq!(info.sentiment, Some(Sentiment::Friend));
+
+        world.despawn(b);
+        assert!(collect_opinion_info(a, b, &world).is_none());
+    }
 }
@@ -923,7 +923,11 @@ mod tests {
             hunger: Some((30.0, 80.0)),
             fatigue: None,
             combat: Some((5.0, 3.0, 0.7)),
+            weapon: None,
+            wounds: None,
             action: Some("Wandering".into()),
+            plan: None,
+            directions: None,
             gait: Some("Walk".into()),
             occupation: None,
             home_building: None,
@@ -1001,7 +1005,11 @@ mod tests {
             hunger: Some((25.0, 100.0)),
             fatigue: None,
             combat: None,
+            weapon: None,
+            wounds: None,
             action: Some("Idle".into()),
+            plan: None,
+            directions: None,
             gait: None,
             occupation: None,
             home_building: None,
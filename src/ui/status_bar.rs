@@ -120,7 +120,7 @@ pub fn build_status_bar(tree: &mut WidgetTree, theme: &Theme, info: &StatusBarIn
         spans.push(sep());
         spans.push(TextSpan {
             text: format!(
-                "sim {:.1}ms/{}t | build {:.1} | layout {:.1} | draw {:.1} | render {:.1}ms | {}w",
+                "sim {:.1}ms/{}t | build {:.1} | layout {:.1} | draw {:.1} | render {:.1}ms | {}w | A* {} ({} failed, {} nodes)",
                 perf.sim_us as f64 / 1000.0,
                 perf.sim_ticks,
                 perf.build_us as f64 / 1000.0,
@@ -128,6 +128,9 @@ pub fn build_status_bar(tree: &mut WidgetTree, theme: &Theme, info: &StatusBarIn
                 perf.draw_us as f64 / 1000.0,
                 perf.render_us as f64 / 1000.0,
                 perf.widget_count,
+                perf.astar_calls,
+                perf.astar_failures,
+                perf.astar_expanded,
             ),
             color: theme.disabled,
             font_family: FontFamily::Mono,
@@ -193,6 +196,9 @@ mod tests {
             panel_cmds: 10,
             text_cmds: 20,
             sprite_cmds: 0,
+            astar_calls: 12,
+            astar_failures: 1,
+            astar_expanded: 3400,
         };
         let info = StatusBarInfo {
             tick: 0,
@@ -217,6 +223,7 @@ mod tests {
             assert!(perf_span.text.contains("build 0.3"));
             assert!(perf_span.text.contains("layout 0.1"));
             assert!(perf_span.text.contains("42w"));
+            assert!(perf_span.text.contains("A* 12 (1 failed, 3400 nodes)"));
         } else {
             panic!("expected RichText");
         }
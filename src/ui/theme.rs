@@ -122,6 +122,8 @@ pub struct Theme {
     pub overlay_selection: [f32; 4],
     /// Wander target tile highlight color (semi-transparent).
     pub overlay_path: [f32; 4],
+    /// Tiles expanded by the last A* search (path debug overlay).
+    pub overlay_search: [f32; 4],
 
     // -- Progress bar defaults (UI-200) --
     /// Default progress bar height in pixels.
@@ -273,6 +275,7 @@ impl Default for Theme {
             overlay_hover: hex_a(0xF0, 0xE6, 0xD2, 0.15), // light parchment, subtle
             overlay_selection: hex_a(0xC8, 0xA8, 0x50, 0.35), // gold, prominent
             overlay_path: hex_a(0x60, 0xA0, 0x60, 0.25),  // muted green
+            overlay_search: hex_a(0x50, 0x70, 0xB0, 0.15), // faint blue
 
             // Progress bar defaults (UI-200)
             progress_bar_height: 8.0,
@@ -366,6 +369,7 @@ mod tests {
         assert!(t.overlay_hover[3] > 0.0 && t.overlay_hover[3] < 1.0);
         assert!(t.overlay_selection[3] > 0.0 && t.overlay_selection[3] < 1.0);
         assert!(t.overlay_path[3] > 0.0 && t.overlay_path[3] < 1.0);
+        assert!(t.overlay_search[3] > 0.0 && t.overlay_search[3] < 1.0);
         // Selection should be more visible than hover.
         assert!(t.overlay_selection[3] > t.overlay_hover[3]);
     }
@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use rand::rngs::StdRng;
 
+use crate::aggregate::{Folded, QuartierAggregate};
 use crate::components::*;
 use crate::lod::{self, LodTransition, LodZone};
 
@@ -11,21 +12,38 @@ const SPATIAL_CELL_SHIFT: i32 = 4; // 2^4 = 16m cells
 /// Coarse spatial grid: cell coords → list of (entity, x, y) in that cell.
 pub type SpatialGrid = HashMap<(i32, i32), Vec<(Entity, i32, i32)>>;
 
-use crate::events::EventLog;
+use crate::events::{Event, EventLog};
+use crate::faction::FactionRegistry;
+use crate::flow_field::FlowFieldCache;
+use crate::hpa::HpaGraph;
 use crate::registry::{
-    BlockRegistry, BuildingRegistry, QuartierId, QuartierRegistry, StreetRegistry,
+    BlockRegistry, BridgeId, BridgeRegistry, BuildingId, BuildingRegistry, DEFAULT_YEAR_TOLERANCE,
+    QuartierId, QuartierRegistry, StreetRegistry,
 };
+use crate::relationships::Relationships;
 use crate::rng::create_rng;
+use crate::street_graph::StreetGraph;
+use crate::systems::danger::DangerMap;
 use crate::systems::decisions::UtilityConfig;
-use crate::tile_map::{PathWorkspace, TileMap};
+use crate::systems::fire::Fire;
+use crate::systems::unrest::Riot;
+use crate::tile_map::{PathWorkspace, Terrain, TileMap};
 
 pub struct BodyTables {
     pub positions: HashMap<Entity, Position>,
     pub healths: HashMap<Entity, Health>,
     pub fatigues: HashMap<Entity, Fatigue>,
     pub combat_stats: HashMap<Entity, CombatStats>,
+    /// Wielded weapon; absent means bare hands.
+    pub weapons: HashMap<Entity, Weapon>,
+    /// Open wounds per body part. Written by `run_combat` (new wounds) and
+    /// `run_wounds` (bleeding, healing).
+    pub wounds: HashMap<Entity, Wounds>,
     pub gait_profiles: HashMap<Entity, GaitProfile>,
     pub current_gaits: HashMap<Entity, Gait>,
+    /// Storey of entities above street level; absent means storey 0.
+    /// Written only by `run_wander` (and spawning).
+    pub storeys: HashMap<Entity, Storey>,
     pub move_cooldowns: HashMap<Entity, MoveCooldown>,
     pub icons: HashMap<Entity, Icon>,
     pub names: HashMap<Entity, Name>,
@@ -38,8 +56,11 @@ impl BodyTables {
             healths: HashMap::new(),
             fatigues: HashMap::new(),
             combat_stats: HashMap::new(),
+            weapons: HashMap::new(),
+            wounds: HashMap::new(),
             gait_profiles: HashMap::new(),
             current_gaits: HashMap::new(),
+            storeys: HashMap::new(),
             move_cooldowns: HashMap::new(),
             icons: HashMap::new(),
             names: HashMap::new(),
@@ -51,8 +72,11 @@ impl BodyTables {
         self.healths.remove(entity);
         self.fatigues.remove(entity);
         self.combat_stats.remove(entity);
+        self.weapons.remove(entity);
+        self.wounds.remove(entity);
         self.gait_profiles.remove(entity);
         self.current_gaits.remove(entity);
+        self.storeys.remove(entity);
         self.move_cooldowns.remove(entity);
         self.icons.remove(entity);
         self.names.remove(entity);
@@ -67,7 +91,29 @@ pub struct MindTables {
     pub wander_targets: HashMap<Entity, WanderTarget>,
     pub cached_paths: HashMap<Entity, CachedPath>,
     pub occupations: HashMap<Entity, Occupation>,
+    /// Faction membership. Unaffiliated entities are hostile to all.
+    pub faction_members: HashMap<Entity, FactionMember>,
+    /// Grudges and fears. Written only by `run_reactions`.
+    pub grievances: HashMap<Entity, Grievances>,
+    /// Short-term perception memory. Written only by `run_perception`.
+    pub memories: HashMap<Entity, Memory>,
+    /// Active HTN plans. Written only by `run_decisions`.
+    pub plans: HashMap<Entity, Plan>,
+    /// Carried items: item → carrier. Written only by `run_plans`.
+    pub carried_by: HashMap<Entity, Entity>,
+    /// Unconscious allies being dragged: patient → rescuer. Written only by
+    /// `run_rescue`.
+    pub dragged_by: HashMap<Entity, Entity>,
+    /// Criminal dispositions. Set at spawn; `last_offence` written only by
+    /// `run_crime`.
+    pub criminals: HashMap<Entity, Criminal>,
+    /// Arrested offenders serving a sentence. Written only by `run_crime`.
+    pub imprisoned: HashMap<Entity, Imprisoned>,
+    /// Directed opinion modifiers between entities. Written only by
+    /// `run_social`.
+    pub relationships: Relationships,
     pub utility_config: UtilityConfig,
+    pub factions: FactionRegistry,
 }
 
 impl MindTables {
@@ -80,7 +126,17 @@ impl MindTables {
             wander_targets: HashMap::new(),
             cached_paths: HashMap::new(),
             occupations: HashMap::new(),
+            faction_members: HashMap::new(),
+            grievances: HashMap::new(),
+            memories: HashMap::new(),
+            plans: HashMap::new(),
+            carried_by: HashMap::new(),
+            dragged_by: HashMap::new(),
+            criminals: HashMap::new(),
+            imprisoned: HashMap::new(),
+            relationships: Relationships::new(),
             utility_config: UtilityConfig::default(),
+            factions: FactionRegistry::new(),
         }
     }
 
@@ -92,6 +148,18 @@ impl MindTables {
         self.wander_targets.remove(entity);
         self.cached_paths.remove(entity);
         self.occupations.remove(entity);
+        self.faction_members.remove(entity);
+        self.grievances.remove(entity);
+        self.memories.remove(entity);
+        self.plans.remove(entity);
+        self.carried_by.remove(entity);
+        // A dead carrier drops everything it held.
+        self.carried_by.retain(|_, carrier| carrier != entity);
+        self.dragged_by.remove(entity);
+        self.dragged_by.retain(|_, rescuer| rescuer != entity);
+        self.criminals.remove(entity);
+        self.imprisoned.remove(entity);
+        self.relationships.remove_entity(entity);
     }
 }
 
@@ -102,18 +170,46 @@ pub struct GisTables {
     pub quartier_names: Vec<String>,
     /// Street registry, reconstructed from building address data.
     pub streets: StreetRegistry,
+    /// Street centerline graph, extracted from Road/Bridge tiles at load time.
+    pub street_graph: StreetGraph,
+    /// Seine crossings, rebuilt from Bridge tiles at load time.
+    pub bridges: BridgeRegistry,
     /// Quartier aggregate data, rebuilt from buildings/blocks at load time.
     pub quartiers: QuartierRegistry,
     /// Active SoDUCo snapshot year for occupant display.
     pub active_year: u16,
+    /// Years from `active_year` a building's nearest directory listing may
+    /// be and still count (spawning, aggregates, hover info).
+    pub year_tolerance: u16,
+    /// Chance each listed resident is spawned as an entity; the rest stay
+    /// unseen in the quartier aggregate. Applies to later arrivals too.
+    pub sample_ratio: f64,
+    /// Game-date year `follow_game_date` last synced `active_year` for;
+    /// 0 before the first sync.
+    pub calendar_year: i32,
     // LOD zone classification (keyed by quartier, not entity)
     /// Current LOD zone per quartier. Updated once per frame.
     pub lod_zones: HashMap<QuartierId, LodZone>,
     /// Zone transitions detected during the last recompute.
     pub lod_transitions: Vec<LodTransition>,
+    /// Aggregate model per quartier, advanced by `run_statistical` while
+    /// the quartier is Statistical. Seeded at load time.
+    pub aggregates: HashMap<QuartierId, QuartierAggregate>,
+    /// Riots under way, started and ended by `unrest::update_riots`.
+    pub riots: HashMap<QuartierId, Riot>,
+    /// Burning buildings, advanced by `run_fire`.
+    pub fires: HashMap<BuildingId, Fire>,
     // Per-entity GIS links
+    /// Entities folded into a Statistical quartier's aggregate. They have
+    /// no position until `apply_lod_transitions` unfolds them.
+    pub folded: HashMap<Entity, Folded>,
     pub home_buildings: HashMap<Entity, HomeBuilding>,
     pub workplaces: HashMap<Entity, Workplace>,
+    /// Apartment storey within `home_buildings`; absent means storey 0.
+    pub home_storeys: HashMap<Entity, Storey>,
+    /// Building the directory lists a resident in, for those a fire left
+    /// homeless or rehoused elsewhere (see `fire::rehouse`).
+    pub listed_homes: HashMap<Entity, BuildingId>,
 }
 
 impl GisTables {
@@ -123,18 +219,47 @@ impl GisTables {
             blocks: BlockRegistry::new(),
             quartier_names: Vec::new(),
             streets: StreetRegistry::new(),
+            street_graph: StreetGraph::new(),
+            bridges: BridgeRegistry::new(),
             quartiers: QuartierRegistry::new(),
             active_year: 1845,
+            year_tolerance: DEFAULT_YEAR_TOLERANCE,
+            sample_ratio: 1.0,
+            calendar_year: 0,
             lod_zones: HashMap::new(),
             lod_transitions: Vec::new(),
+            aggregates: HashMap::new(),
+            riots: HashMap::new(),
+            fires: HashMap::new(),
+            folded: HashMap::new(),
             home_buildings: HashMap::new(),
             workplaces: HashMap::new(),
+            home_storeys: HashMap::new(),
+            listed_homes: HashMap::new(),
         }
     }
 
     fn remove(&mut self, entity: &Entity) {
+        self.folded.remove(entity);
         self.home_buildings.remove(entity);
         self.workplaces.remove(entity);
+        self.home_storeys.remove(entity);
+        self.listed_homes.remove(entity);
+    }
+
+    /// Every resident with a directory listing and the building it lists
+    /// them in: `listed_homes` for those a fire displaced, else their home.
+    /// Sorted by entity.
+    pub fn listed_residents(&self) -> Vec<(Entity, BuildingId)> {
+        let mut residents: Vec<(Entity, BuildingId)> = self
+            .home_buildings
+            .iter()
+            .filter(|(e, _)| !self.listed_homes.contains_key(e))
+            .map(|(&e, h)| (e, h.0))
+            .chain(self.listed_homes.iter().map(|(&e, &b)| (e, b)))
+            .collect();
+        residents.sort_by_key(|(e, _)| e.0);
+        residents
     }
 
     /// Recompute LOD zones for all quartiers based on camera position.
@@ -164,6 +289,9 @@ pub struct World {
     // Entity tracking
     pub alive: HashSet<Entity>,
     pub pending_deaths: HashSet<Entity>,
+    /// Entities standing in Statistical quartiers. Per-entity systems skip
+    /// them. Rebuilt each tick by `run_statistical`.
+    pub frozen: HashSet<Entity>,
     next_entity_id: u64,
 
     // Sub-struct property tables
@@ -174,10 +302,26 @@ pub struct World {
     // Spatial acceleration
     /// Coarse spatial index, rebuilt from positions each tick.
     pub spatial_index: SpatialGrid,
+    /// People standing on each occupied tile, per storey. Rebuilt
+    /// alongside `spatial_index`; `run_wander` keeps it current as it moves
+    /// entities.
+    pub occupancy: HashMap<(i32, i32, u8), u16>,
+    /// Extra path cost near fighting, rebuilt each tick by `run_danger`.
+    pub danger: DangerMap,
 
     // Infrastructure
     pub tiles: TileMap,
     pub path_workspace: PathWorkspace,
+    /// Chunk-level routing graph for long paths, built lazily from `tiles`.
+    pub hpa: HpaGraph,
+    /// Dijkstra maps toward popular destinations, shared by every mover
+    /// headed there. LRU-evicted; see `flow_field`.
+    pub flow_fields: FlowFieldCache,
+    /// Entity whose A* searches `run_wander` traces for the path debug
+    /// overlay. Set by the UI.
+    pub path_debug_entity: Option<Entity>,
+    /// Tiles expanded by `path_debug_entity`'s last A* search.
+    pub path_debug_trace: Vec<(i32, i32)>,
     pub events: EventLog,
     pub rng: StdRng,
     pub tick: Tick,
@@ -193,6 +337,7 @@ impl World {
         Self {
             alive: HashSet::new(),
             pending_deaths: HashSet::new(),
+            frozen: HashSet::new(),
             next_entity_id: 1, // 0 is reserved/unused
 
             body: BodyTables::new(),
@@ -200,9 +345,15 @@ impl World {
             gis: GisTables::new(),
 
             spatial_index: HashMap::new(),
+            occupancy: HashMap::new(),
+            danger: DangerMap::new(),
 
             tiles: TileMap::new(64, 64), // 64m × 64m
             path_workspace: PathWorkspace::new(),
+            hpa: HpaGraph::new(),
+            flow_fields: FlowFieldCache::new(),
+            path_debug_entity: None,
+            path_debug_trace: Vec::new(),
             events: EventLog::default_capacity(),
             rng: create_rng(seed),
             tick: Tick(0),
@@ -222,8 +373,11 @@ impl World {
 
     /// Rebuild the spatial index from current positions.
     /// Call at the start of each tick, after run_death has cleared pending_deaths.
+    /// Also recounts per-tile `occupancy` (people only, see `is_person`).
     pub fn rebuild_spatial_index(&mut self) {
         self.spatial_index.clear();
+        self.occupancy.clear();
+        self.gis.bridges.clear_loads();
         for (&entity, pos) in &self.body.positions {
             if self.alive.contains(&entity) {
                 let key = (pos.x >> SPATIAL_CELL_SHIFT, pos.y >> SPATIAL_CELL_SHIFT);
@@ -231,7 +385,167 @@ impl World {
                     .entry(key)
                     .or_default()
                     .push((entity, pos.x, pos.y));
+                if self.is_person(entity) {
+                    let storey = self.storey_of(entity);
+                    *self.occupancy.entry((pos.x, pos.y, storey)).or_default() += 1;
+                    if storey == 0 {
+                        self.gis.bridges.add_load(pos.x, pos.y);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether `entity` takes up room on a tile: anything that walks
+    /// (`GaitProfile`) or decides (`ActionState`). Food and items don't.
+    pub fn is_person(&self, entity: Entity) -> bool {
+        self.body.gait_profiles.contains_key(&entity)
+            || self.mind.action_states.contains_key(&entity)
+    }
+
+    /// Number of people on tile `(x, y)` of `storey` as of the last
+    /// spatial rebuild (plus `run_wander`'s moves since).
+    pub fn occupants(&self, x: i32, y: i32, storey: u8) -> u16 {
+        self.occupancy.get(&(x, y, storey)).copied().unwrap_or(0)
+    }
+
+    /// Whether another entity can step onto `(x, y)` of `storey` without
+    /// exceeding the tile's capacity.
+    pub fn has_room(&self, x: i32, y: i32, storey: u8) -> bool {
+        self.occupants(x, y, storey) < self.tiles.capacity_at(x, y)
+    }
+
+    /// Storey `entity` stands on (0 = street level).
+    pub fn storey_of(&self, entity: Entity) -> u8 {
+        self.body.storeys.get(&entity).map_or(0, |s| s.0)
+    }
+
+    /// Change a tile's terrain at runtime, dropping any routing data built
+    /// from the old terrain (HPA* chunk graph, flow fields) and closing or
+    /// reopening street graph segments through it.
+    pub fn set_terrain(&mut self, x: i32, y: i32, t: Terrain) {
+        if x < 0 || y < 0 {
+            return;
+        }
+        if let Some(was) = self.tiles.get_terrain(x as usize, y as usize) {
+            self.gis.street_graph.update_tile((x, y), was, t);
+        }
+        self.tiles.set_terrain(x as usize, y as usize, t);
+        self.hpa.invalidate_tile(x, y);
+        self.flow_fields.invalidate_tile(x, y);
+    }
+
+    /// Close a bridge: `DoorKeys::may_pass` refuses its deck, so routes
+    /// across it are dropped and movers re-path around. Anyone on the deck
+    /// is sent back to the nearer bank. Returns false for an unknown or
+    /// already closed bridge.
+    pub fn close_bridge(&mut self, id: BridgeId) -> bool {
+        let Some(bridge) = self.gis.bridges.get_mut(id) else {
+            return false;
+        };
+        if bridge.closed {
+            return false;
+        }
+        bridge.closed = true;
+        let ends = bridge.ends;
+
+        let landings: Vec<(i32, i32)> = ends
+            .iter()
+            .filter_map(|&(x, y)| {
+                [(0, -1), (1, 0), (0, 1), (-1, 0)]
+                    .iter()
+                    .map(|&(dx, dy)| (x + dx, y + dy))
+                    .find(|&(nx, ny)| {
+                        nx >= 0
+                            && ny >= 0
+                            && self.tiles.is_walkable(nx as usize, ny as usize)
+                            && self.gis.bridges.bridge_at(nx, ny).is_none()
+                    })
+            })
+            .collect();
+        let mut on_deck: Vec<Entity> = self
+            .body
+            .positions
+            .iter()
+            .filter(|(e, p)| {
+                self.is_person(**e)
+                    && self.storey_of(**e) == 0
+                    && self.gis.bridges.bridge_at(p.x, p.y) == Some(id)
+            })
+            .map(|(&e, _)| e)
+            .collect();
+        on_deck.sort_by_key(|e| e.0);
+        for e in on_deck {
+            let Some(pos) = self.body.positions.get_mut(&e) else {
+                continue;
+            };
+            let Some(&(x, y)) = landings
+                .iter()
+                .min_by_key(|&&(x, y)| (x - pos.x).abs().max((y - pos.y).abs()))
+            else {
+                continue;
+            };
+            *pos = Position { x, y };
+            self.mind.cached_paths.remove(&e);
+            self.events.push(Event::Moved {
+                entity: e,
+                x,
+                y,
+                tick: self.tick,
+            });
+        }
+        self.rebuild_spatial_index();
+        true
+    }
+
+    /// Reopen a closed bridge. Returns false for an unknown or open bridge.
+    pub fn reopen_bridge(&mut self, id: BridgeId) -> bool {
+        match self.gis.bridges.get_mut(id) {
+            Some(bridge) if bridge.closed => {
+                bridge.closed = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// LOD zone of the quartier containing `(x, y)`. Tiles outside any
+    /// classified quartier count as Active.
+    pub fn lod_zone_at(&self, x: i32, y: i32) -> LodZone {
+        if x < 0 || y < 0 {
+            return LodZone::Active;
+        }
+        self.tiles
+            .get_quartier_id(x as usize, y as usize)
+            .filter(|&q| q > 0)
+            .and_then(|q| self.gis.lod_zones.get(&QuartierId(q)))
+            .copied()
+            .unwrap_or(LodZone::Active)
+    }
+
+    /// LOD zone `e` stands in; Active if it has no position.
+    pub fn lod_zone_of(&self, e: Entity) -> LodZone {
+        self.body
+            .positions
+            .get(&e)
+            .map_or(LodZone::Active, |p| self.lod_zone_at(p.x, p.y))
+    }
+
+    /// Ticks of simulation `e` catches up on at `tick`: 1 every tick in
+    /// Active zones, `NEARBY_TICK_INTERVAL` once per interval in Nearby ones
+    /// (staggered by entity id so they don't all update together), 0 when
+    /// it skips this tick or is Statistical.
+    pub fn lod_step(&self, e: Entity, tick: Tick) -> u64 {
+        match self.lod_zone_of(e) {
+            LodZone::Active => 1,
+            LodZone::Nearby => {
+                if (tick.0 + e.0).is_multiple_of(lod::NEARBY_TICK_INTERVAL) {
+                    lod::NEARBY_TICK_INTERVAL
+                } else {
+                    0
+                }
             }
+            LodZone::Statistical => 0,
         }
     }
 
@@ -275,6 +589,7 @@ impl World {
     /// If you add a new property table to World, add a corresponding remove here.
     pub fn despawn(&mut self, entity: Entity) {
         self.alive.remove(&entity);
+        self.frozen.remove(&entity);
         if self.player == Some(entity) {
             self.player = None;
         }
@@ -288,6 +603,14 @@ impl World {
 /// Checks that no entity exists in any property table without being in alive.
 #[cfg(any(debug_assertions, test))]
 pub fn validate_world(world: &World) {
+    for entity in &world.frozen {
+        assert!(
+            world.alive.contains(entity),
+            "zombie entity {:?} in frozen but not in alive",
+            entity
+        );
+    }
+
     // Body tables
     for entity in world.body.positions.keys() {
         assert!(
@@ -321,6 +644,22 @@ pub fn validate_world(world: &World) {
         );
     }
 
+    for entity in world.body.weapons.keys() {
+        assert!(
+            world.alive.contains(entity),
+            "zombie entity {:?} in weapons but not in alive",
+            entity
+        );
+    }
+
+    for entity in world.body.wounds.keys() {
+        assert!(
+            world.alive.contains(entity),
+            "zombie entity {:?} in wounds but not in alive",
+            entity
+        );
+    }
+
     for entity in world.body.gait_profiles.keys() {
         assert!(
             world.alive.contains(entity),
@@ -337,6 +676,14 @@ pub fn validate_world(world: &World) {
         );
     }
 
+    for entity in world.body.storeys.keys() {
+        assert!(
+            world.alive.contains(entity),
+            "zombie entity {:?} in storeys but not in alive",
+            entity
+        );
+    }
+
     for entity in world.body.move_cooldowns.keys() {
         assert!(
             world.alive.contains(entity),
@@ -418,6 +765,88 @@ pub fn validate_world(world: &World) {
         );
     }
 
+    for entity in world.mind.faction_members.keys() {
+        assert!(
+            world.alive.contains(entity),
+            "zombie entity {:?} in faction_members but not in alive",
+            entity
+        );
+    }
+
+    for entity in world.mind.grievances.keys() {
+        assert!(
+            world.alive.contains(entity),
+            "zombie entity {:?} in grievances but not in alive",
+            entity
+        );
+    }
+
+    for entity in world.mind.memories.keys() {
+        assert!(
+            world.alive.contains(entity),
+            "zombie entity {:?} in memories but not in alive",
+            entity
+        );
+    }
+
+    for entity in world.mind.plans.keys() {
+        assert!(
+            world.alive.contains(entity),
+            "zombie entity {:?} in plans but not in alive",
+            entity
+        );
+    }
+
+    for (item, carrier) in &world.mind.carried_by {
+        assert!(
+            world.alive.contains(item),
+            "zombie entity {:?} in carried_by but not in alive",
+            item
+        );
+        assert!(
+            world.alive.contains(carrier),
+            "zombie carrier {:?} in carried_by but not in alive",
+            carrier
+        );
+    }
+
+    for (patient, rescuer) in &world.mind.dragged_by {
+        assert!(
+            world.alive.contains(patient),
+            "zombie entity {:?} in dragged_by but not in alive",
+            patient
+        );
+        assert!(
+            world.alive.contains(rescuer),
+            "zombie rescuer {:?} in dragged_by but not in alive",
+            rescuer
+        );
+    }
+
+    for entity in world.mind.criminals.keys() {
+        assert!(
+            world.alive.contains(entity),
+            "zombie entity {:?} in criminals but not in alive",
+            entity
+        );
+    }
+
+    for entity in world.mind.imprisoned.keys() {
+        assert!(
+            world.alive.contains(entity),
+            "zombie entity {:?} in imprisoned but not in alive",
+            entity
+        );
+    }
+
+    for entity in world.mind.relationships.entities() {
+        assert!(
+            world.alive.contains(entity),
+            "zombie entity {:?} in relationships but not in alive",
+            entity
+        );
+    }
+
     // GIS per-entity tables
     for entity in world.gis.home_buildings.keys() {
         assert!(
@@ -434,6 +863,30 @@ pub fn validate_world(world: &World) {
             entity
         );
     }
+
+    for entity in world.gis.home_storeys.keys() {
+        assert!(
+            world.alive.contains(entity),
+            "zombie entity {:?} in home_storeys but not in alive",
+            entity
+        );
+    }
+
+    for entity in world.gis.listed_homes.keys() {
+        assert!(
+            world.alive.contains(entity),
+            "zombie entity {:?} in listed_homes but not in alive",
+            entity
+        );
+    }
+
+    for entity in world.gis.folded.keys() {
+        assert!(
+            world.alive.contains(entity),
+            "zombie entity {:?} in folded but not in alive",
+            entity
+        );
+    }
 }
 
 #[cfg(test)]
@@ -481,6 +934,16 @@ mod tests {
                 aggression: 0.8,
             },
         );
+        world.body.weapons.insert(e, Weapon::Knife);
+        world.body.wounds.insert(
+            e,
+            Wounds(vec![Wound {
+                part: BodyPart::LeftArm,
+                kind: WoundKind::Cut,
+                bleeding: 0.1,
+                healing: 100,
+            }]),
+        );
         world.body.gait_profiles.insert(e, GaitProfile::biped());
         world.body.current_gaits.insert(e, Gait::Walk);
         world
@@ -523,6 +986,7 @@ mod tests {
                 steps: vec![(4, 7), (5, 7)],
                 goal: (5, 7),
                 next_step: 0,
+                chunk_versions: Vec::new(),
             },
         );
         world.mind.occupations.insert(
@@ -532,6 +996,48 @@ mod tests {
                 naics: "311".to_string(),
             },
         );
+        world.mind.faction_members.insert(
+            e,
+            FactionMember {
+                faction: crate::faction::FactionId(0),
+                quartier: None,
+            },
+        );
+        world.mind.grievances.insert(e, Grievances::default());
+        world.mind.memories.insert(e, Memory::default());
+        let item = world.spawn();
+        world.mind.plans.insert(
+            e,
+            Plan {
+                task: TaskId::EatNearby,
+                action: ActionId::Eat,
+                steps: vec![PlanStep::Consume { food: item }],
+                current: 0,
+                replans: 0,
+            },
+        );
+        world.mind.carried_by.insert(item, e);
+        world.mind.dragged_by.insert(item, e);
+        world.mind.criminals.insert(
+            e,
+            Criminal {
+                crime: Crime::Theft,
+                last_offence: None,
+            },
+        );
+        world.mind.imprisoned.insert(
+            e,
+            Imprisoned {
+                prison: crate::registry::BuildingId(1),
+                release: Tick(100),
+            },
+        );
+        world.mind.relationships.apply(
+            item,
+            e,
+            crate::relationships::ModifierKind::Neighbor,
+            Tick(0),
+        );
         world
             .gis
             .home_buildings
@@ -540,6 +1046,22 @@ mod tests {
             .gis
             .workplaces
             .insert(e, Workplace(crate::registry::BuildingId(1)));
+        world.body.storeys.insert(e, Storey(2));
+        world.gis.home_storeys.insert(e, Storey(2));
+        world
+            .gis
+            .listed_homes
+            .insert(e, crate::registry::BuildingId(2));
+        world.gis.folded.insert(
+            e,
+            Folded {
+                quartier: QuartierId(1),
+                pos: Position { x: 0, y: 0 },
+                resident: true,
+                survival: 1.0,
+            },
+        );
+        world.frozen.insert(e);
 
         world.despawn(e);
 
@@ -549,6 +1071,8 @@ mod tests {
         assert!(!world.body.healths.contains_key(&e));
         assert!(!world.body.fatigues.contains_key(&e));
         assert!(!world.body.combat_stats.contains_key(&e));
+        assert!(!world.body.weapons.contains_key(&e));
+        assert!(!world.body.wounds.contains_key(&e));
         assert!(!world.body.gait_profiles.contains_key(&e));
         assert!(!world.body.current_gaits.contains_key(&e));
         assert!(!world.body.move_cooldowns.contains_key(&e));
@@ -560,8 +1084,22 @@ mod tests {
         assert!(!world.mind.wander_targets.contains_key(&e));
         assert!(!world.mind.cached_paths.contains_key(&e));
         assert!(!world.mind.occupations.contains_key(&e));
+        assert!(!world.mind.faction_members.contains_key(&e));
+        assert!(!world.mind.grievances.contains_key(&e));
+        assert!(!world.mind.memories.contains_key(&e));
+        assert!(!world.mind.plans.contains_key(&e));
+        assert!(world.mind.carried_by.is_empty());
+        assert!(world.mind.dragged_by.is_empty());
+        assert!(!world.mind.criminals.contains_key(&e));
+        assert!(!world.mind.imprisoned.contains_key(&e));
+        assert!(world.mind.relationships.is_empty());
         assert!(!world.gis.home_buildings.contains_key(&e));
         assert!(!world.gis.workplaces.contains_key(&e));
+        assert!(!world.body.storeys.contains_key(&e));
+        assert!(!world.gis.home_storeys.contains_key(&e));
+        assert!(!world.gis.listed_homes.contains_key(&e));
+        assert!(!world.gis.folded.contains_key(&e));
+        assert!(!world.frozen.contains(&e));
     }
 
     #[test]
@@ -687,4 +1225,17 @@ mod tests {
         assert_eq!(world.entities_at(8, 9).count(), 1);
         assert!(world.entities_at(8, 9).any(|x| x == e));
     }
+
+    #[test]
+    fn set_terrain_drops_flow_fields() {
+        use crate::flow_field::FlowField;
+
+        let mut world = World::new_with_seed(42);
+        world
+            .flow_fields
+            .insert(FlowField::build(&world.tiles, (10, 10)));
+        world.set_terrain(12, 10, Terrain::Wall);
+        assert_eq!(world.tiles.get_terrain(12, 10), Some(Terrain::Wall));
+        assert!(!world.flow_fields.contains((10, 10)));
+    }
 }
@@ -17,12 +17,19 @@
 
 use wulfaz::components::*;
 use wulfaz::systems::combat::run_combat;
+use wulfaz::systems::crime::run_crime;
 use wulfaz::systems::death::run_death;
 use wulfaz::systems::eating::run_eating;
 use wulfaz::systems::fatigue::run_fatigue;
+use wulfaz::systems::fire::run_fire;
 use wulfaz::systems::hunger::run_hunger;
+use wulfaz::systems::plans::run_plans;
+use wulfaz::systems::rescue::run_rescue;
+use wulfaz::systems::statistical::run_statistical;
 use wulfaz::systems::temperature::run_temperature;
+use wulfaz::systems::unrest::run_unrest;
 use wulfaz::systems::wander::run_wander;
+use wulfaz::systems::wounds::run_wounds;
 use wulfaz::tile_map::TileMap;
 use wulfaz::world::{World, validate_world};
 
@@ -93,14 +100,21 @@ fn spawn_food(world: &mut World, x: i32, y: i32) -> Entity {
 fn run_full_tick(world: &mut World, tick: Tick) {
     // Phase 1: Environment
     run_temperature(world, tick);
+    run_fire(world, tick);
+    run_statistical(world, tick);
     // Phase 2: Needs
     run_hunger(world, tick);
     run_fatigue(world, tick);
+    run_wounds(world, tick);
     // Phase 3: Decisions (no systems yet)
     // Phase 4: Actions
     run_wander(world, tick);
+    run_plans(world, tick);
+    run_rescue(world, tick);
     run_eating(world, tick);
+    run_crime(world, tick);
     run_combat(world, tick);
+    run_unrest(world, tick);
     // Phase 5: Consequences — run_death ALWAYS last
     run_death(world, tick);
 }